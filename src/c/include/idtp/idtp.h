@@ -7,6 +7,7 @@
 #define IDTP_IDTP_H
 
 #include <idtp/idtp_header.h>
+#include <stddef.h>
 
 #ifdef __cplusplus
 extern "C" {
@@ -24,6 +25,42 @@ extern "C" {
     IDTP_PACKET_MAX_SIZE - IDTP_HEADER_SIZE - IDTP_TRAILER_SIZE \
 )
 
+/// IDTP operation result codes.
+typedef enum {
+    /// Operation succeeded.
+    IDTP_OK = 0,
+    /// Given buffer is too small.
+    IDTP_ERROR_BUFFER_TOO_SMALL = 1,
+    /// Frame is shorter than header and trailer.
+    IDTP_ERROR_FRAME_TOO_SHORT = 2,
+    /// Frame exceeds maximum packet size.
+    IDTP_ERROR_FRAME_TOO_LARGE = 3,
+    /// Frame does not start with IDTP preamble.
+    IDTP_ERROR_INVALID_PREAMBLE = 4,
+    /// Frame does not end with IDTP trailer.
+    IDTP_ERROR_INVALID_TRAILER = 5,
+    /// Header payload size does not match frame size.
+    IDTP_ERROR_PAYLOAD_SIZE_MISMATCH = 6,
+    /// Payload can not be decoded.
+    IDTP_ERROR_INVALID_PAYLOAD = 7,
+    /// Required pointer argument is null (reported by Rust implementation
+    /// only, see src/rust/idtp-ffi).
+    IDTP_ERROR_NULL_POINTER = 8,
+    /// Frame authentication failed (reported by Rust implementation only).
+    IDTP_ERROR_AUTHENTICATION_FAILED = 9,
+    /// Session key is not established or must be rotated (reported by Rust
+    /// implementation only).
+    IDTP_ERROR_KEY_UNAVAILABLE = 10,
+    /// Major protocol version of frame is not supported.
+    IDTP_ERROR_INVALID_VERSION = 11,
+    /// Frame has more errors than forward error correction can correct
+    /// (reported by Rust implementation only).
+    IDTP_ERROR_UNCORRECTABLE = 12,
+    /// Operating mode is unknown or not representable (reported by Rust
+    /// implementation only).
+    IDTP_ERROR_INVALID_MODE = 13,
+} IdtpResult;
+
 /// Inertial Measurement Unit Data Transfer Protocol frame struct.
 typedef struct {
     /// IDTP network packet header.
@@ -60,7 +97,9 @@ void idtp_frame_set_payload(IdtpFrame *self, const uint8_t *payload, size_t size
 /// @warning Buffer size should be enough to store whole IDTP packet.
 void idtp_frame_pack(const IdtpFrame *self, uint8_t *buffer);
 
-/// @brief Convert byte slice to IDTP frame.
+/// @brief Convert byte slice to IDTP frame. Structure is not validated, use
+/// idtp_frame_parse for that. Missing header bytes are treated as zeros and
+/// payload is truncated to IDTP_PAYLOAD_MAX_SIZE.
 ///
 /// @param [in] bytes given byte array to convert (big-endian byte order).
 /// @param [in] size given number of bytes.
@@ -68,6 +107,17 @@ void idtp_frame_pack(const IdtpFrame *self, uint8_t *buffer);
 /// @return IDTP frame struct from byte array.
 IdtpFrame idtp_frame_from_bytes(const uint8_t *bytes, size_t size);
 
+/// @brief Parse raw IDTP network packet validating its structure.
+/// Integrity is not checked. Frames with major version other than
+/// IDTP_VERSION_MAJOR are rejected.
+///
+/// @param [in] bytes given raw IDTP packet (big-endian byte order).
+/// @param [in] size given number of bytes.
+/// @param [out] frame given IDTP frame to store result in.
+///
+/// @return IDTP_OK in case of success, error code otherwise.
+IdtpResult idtp_frame_parse(const uint8_t *bytes, size_t size, IdtpFrame *frame);
+
 #ifdef __cplusplus
     }
 #endif
@@ -62,6 +62,19 @@ typedef enum {
     ///
     /// Both `checksum` and `crc` fields of IDTP header are used.
     IDTP_MODE_SAFETY = 0x01,
+    /// IDTP-E (Secure mode) - operating mode with payload encryption and
+    /// authentication provided by AES-128-GCM.
+    ///
+    /// Payload holds ciphertext followed by 16 bytes authentication tag.
+    /// Only `checksum` field of IDTP header is used.
+    IDTP_MODE_SECURE = 0x02,
+    /// IDTP-F (FEC mode) - operating mode with forward error correction.
+    /// Header and payload are protected by Reed–Solomon parity.
+    ///
+    /// Payload holds payload data followed by 16 bytes parity per 239 bytes
+    /// of header and payload data. Only `checksum` field of IDTP header is
+    /// used.
+    IDTP_MODE_FEC = 0x03,
     /// Unknown mode value. No special handling required.
     IDTP_MODE_UNKNOWN = 0xff,
 } IdtpMode;
@@ -44,9 +44,45 @@ void idtp_frame_pack(const IdtpFrame *self, uint8_t *buffer) {
 
 IdtpFrame idtp_frame_from_bytes(const uint8_t *bytes, const size_t size) {
     IdtpFrame frame = {0};
-    frame.header = idtp_header_from_bytes(bytes);
-    frame.payload_size = size - IDTP_PACKET_MIN_SIZE;
+    uint8_t header[IDTP_HEADER_SIZE] = {0};
+
+    // Missing header bytes are treated as zeros.
+    memcpy(header, bytes, size < IDTP_HEADER_SIZE ? size : IDTP_HEADER_SIZE);
+    frame.header = idtp_header_from_bytes(header);
+
+    if (size > IDTP_PACKET_MIN_SIZE)
+        frame.payload_size = size - IDTP_PACKET_MIN_SIZE;
+
+    if (frame.payload_size > IDTP_PAYLOAD_MAX_SIZE)
+        frame.payload_size = IDTP_PAYLOAD_MAX_SIZE;
+
     memcpy(frame.payload, &bytes[IDTP_HEADER_SIZE], frame.payload_size);
 
     return frame;
 }
+
+IdtpResult idtp_frame_parse(const uint8_t *bytes, const size_t size, IdtpFrame *frame) {
+    if (size < IDTP_PACKET_MIN_SIZE)
+        return IDTP_ERROR_FRAME_TOO_SHORT;
+
+    if (size > IDTP_PACKET_MAX_SIZE)
+        return IDTP_ERROR_FRAME_TOO_LARGE;
+
+    if (memcmp(bytes, IDTP_PREAMBLE, IDTP_PREAMBLE_SIZE) != 0)
+        return IDTP_ERROR_INVALID_PREAMBLE;
+
+    const IdtpHeader header = idtp_header_from_bytes(bytes);
+
+    if (header.version.major != IDTP_VERSION_MAJOR)
+        return IDTP_ERROR_INVALID_VERSION;
+
+    if ((uint64_t)header.payload_size + IDTP_PACKET_MIN_SIZE != size)
+        return IDTP_ERROR_PAYLOAD_SIZE_MISMATCH;
+
+    if (memcmp(&bytes[size - IDTP_TRAILER_SIZE], IDTP_TRAILER, IDTP_TRAILER_SIZE) != 0)
+        return IDTP_ERROR_INVALID_TRAILER;
+
+    *frame = idtp_frame_from_bytes(bytes, size);
+
+    return IDTP_OK;
+}
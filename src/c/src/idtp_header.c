@@ -7,7 +7,7 @@
 #include <string.h>
 
 const uint8_t IDTP_PREAMBLE[IDTP_PREAMBLE_SIZE] = { 'I', 'D', 'T', 'P' };
-const uint8_t IDTP_TRAILER[IDTP_TRAILER_SIZE]   = { 'P', 'D', 'T', 'I' };
+const uint8_t IDTP_TRAILER[IDTP_TRAILER_SIZE]   = { 'P', 'T', 'D', 'I' };
 
 void idtp_header_convert_endian(IdtpHeader *header) {
     header->device_id    = swap_uint16(header->device_id);
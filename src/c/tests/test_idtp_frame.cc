@@ -98,3 +98,50 @@ TEST(IdtpFrameTest, FromBytesCreatesFrameCorrectly) {
     EXPECT_EQ(frame.payload_size, sizeof(payload));
     EXPECT_EQ(memcmp(frame.payload, payload, frame.payload_size), 0);
 }
+
+TEST(IdtpFrameTest, TrailerMatchesSpecification) {
+    const uint8_t trailer[IDTP_TRAILER_SIZE] = { 'P', 'T', 'D', 'I' };
+    EXPECT_EQ(memcmp(IDTP_TRAILER, trailer, IDTP_TRAILER_SIZE), 0);
+}
+
+TEST(IdtpFrameTest, ParseValidatesStructure) {
+    IdtpFrame frame = idtp_frame_create();
+    const uint8_t payload_data[5] = { 1, 2, 3, 4, 5 };
+    idtp_frame_set_payload(&frame, payload_data, 5);
+    frame.header.payload_size = 5;
+
+    uint8_t buffer[IDTP_PACKET_MIN_SIZE + 5] {};
+    idtp_frame_pack(&frame, buffer);
+
+    IdtpFrame parsed = idtp_frame_create();
+    EXPECT_EQ(idtp_frame_parse(buffer, sizeof(buffer), &parsed), IDTP_OK);
+    EXPECT_EQ(parsed.payload_size, 5);
+    EXPECT_EQ(memcmp(parsed.payload, payload_data, 5), 0);
+
+    EXPECT_EQ(idtp_frame_parse(buffer, 10, &parsed), IDTP_ERROR_FRAME_TOO_SHORT);
+    EXPECT_EQ(idtp_frame_parse(buffer, sizeof(buffer) - 1, &parsed), IDTP_ERROR_PAYLOAD_SIZE_MISMATCH);
+
+    buffer[sizeof(buffer) - 1] = 'X';
+    EXPECT_EQ(idtp_frame_parse(buffer, sizeof(buffer), &parsed), IDTP_ERROR_INVALID_TRAILER);
+
+    buffer[4] = IDTP_VERSION_MAJOR + 1;
+    EXPECT_EQ(idtp_frame_parse(buffer, sizeof(buffer), &parsed), IDTP_ERROR_INVALID_VERSION);
+
+    buffer[0] = 'X';
+    EXPECT_EQ(idtp_frame_parse(buffer, sizeof(buffer), &parsed), IDTP_ERROR_INVALID_PREAMBLE);
+}
+
+TEST(IdtpFrameTest, FromBytesHandlesMalformedSize) {
+    // Regression: sizes less than IDTP_PACKET_MIN_SIZE used to underflow
+    // payload size.
+    const uint8_t short_bytes[IDTP_PACKET_MIN_SIZE - 1] = { 'I', 'D', 'T', 'P' };
+    IdtpFrame frame = idtp_frame_from_bytes(short_bytes, sizeof(short_bytes));
+    EXPECT_EQ(frame.payload_size, 0);
+
+    frame = idtp_frame_from_bytes(short_bytes, 4);
+    EXPECT_EQ(memcmp(frame.header.preamble, IDTP_PREAMBLE, IDTP_PREAMBLE_SIZE), 0);
+
+    const uint8_t long_bytes[IDTP_PACKET_MAX_SIZE + 100] {};
+    frame = idtp_frame_from_bytes(long_bytes, sizeof(long_bytes));
+    EXPECT_EQ(frame.payload_size, IDTP_PAYLOAD_MAX_SIZE);
+}
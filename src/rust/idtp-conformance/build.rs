@@ -0,0 +1,21 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Build C implementation of IDTP to test it against Rust implementation.
+
+fn main() {
+    const C_DIR: &str = "../../c";
+
+    let sources = ["idtp.c", "idtp_header.c", "utils.c"]
+        .map(|file| format!("{C_DIR}/src/{file}"));
+
+    cc::Build::new()
+        .files(&sources)
+        .include(format!("{C_DIR}/include"))
+        .std("c11")
+        .warnings(true)
+        .compile("idtp_c");
+
+    println!("cargo:rerun-if-changed={C_DIR}/src");
+    println!("cargo:rerun-if-changed={C_DIR}/include");
+}
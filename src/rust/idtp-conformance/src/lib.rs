@@ -0,0 +1,281 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Bindings to the C implementation of IDTP (`src/c`) used to check it
+//! against the Rust implementation.
+
+use core::ffi::c_int;
+use idtp::{
+    IDTP_HEADER_SIZE, IDTP_PACKET_MIN_SIZE, IDTP_PAYLOAD_MAX_SIZE, IdtpError,
+    IdtpHeader,
+};
+
+/// C `IdtpVersion` struct.
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CVersion {
+    /// Major version number.
+    pub major: u8,
+    /// Minor version number.
+    pub minor: u8,
+    /// Patch version number.
+    pub patch: u8,
+}
+
+/// C `IdtpHeader` struct (host byte order).
+#[repr(C, packed)]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CHeader {
+    /// Value to signal the start of a new IDTP packet.
+    pub preamble: [u8; 4],
+    /// Protocol version in format MAJOR.MINOR.PATCH.
+    pub version: CVersion,
+    /// Raw protocol operating mode.
+    pub mode: u8,
+    /// IMU device identifier.
+    pub device_id: u16,
+    /// Value used for simple error detection.
+    pub checksum: u16,
+    /// Timestamp from the IMU's MCU internal clock.
+    pub timestamp: u32,
+    /// Sequence number of IDTP packet sent.
+    pub sequence: u32,
+    /// Cyclic Redundancy Check.
+    pub crc: u32,
+    /// Size of packet payload in bytes.
+    pub payload_size: u32,
+    /// Packet payload type.
+    pub payload_type: u8,
+    /// Reserved field.
+    pub reserved: [u8; 3],
+}
+
+/// C `IdtpFrame` struct.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct CFrame {
+    /// IDTP network packet header.
+    pub header: CHeader,
+    /// Value that containing IMU data.
+    pub payload: [u8; IDTP_PAYLOAD_MAX_SIZE],
+    /// IDTP payload size in bytes.
+    pub payload_size: usize,
+}
+
+impl CFrame {
+    /// Get IDTP payload.
+    ///
+    /// # Returns
+    /// - IDTP payload in bytes representation.
+    pub fn payload(&self) -> &[u8] {
+        &self.payload[..self.payload_size.min(IDTP_PAYLOAD_MAX_SIZE)]
+    }
+}
+
+impl From<&IdtpHeader> for CHeader {
+    /// Convert Rust IDTP header to C IDTP header.
+    ///
+    /// # Parameters
+    /// - `header` - given Rust IDTP header to convert.
+    ///
+    /// # Returns
+    /// - C IDTP header.
+    fn from(header: &IdtpHeader) -> Self {
+        let version = header.version;
+
+        Self {
+            preamble: header.preamble,
+            version: CVersion {
+                major: version.major,
+                minor: version.minor,
+                patch: version.patch,
+            },
+            mode: header.mode as u8,
+            device_id: header.device_id,
+            checksum: header.checksum,
+            timestamp: header.timestamp,
+            sequence: header.sequence,
+            crc: header.crc,
+            payload_size: header.payload_size,
+            payload_type: header.payload_type,
+            reserved: header.reserved,
+        }
+    }
+}
+
+/// Raw C functions and constants.
+mod ffi {
+    use super::{CFrame, CHeader, c_int};
+
+    unsafe extern "C" {
+        pub static IDTP_PREAMBLE: [u8; 4];
+        pub static IDTP_TRAILER: [u8; 4];
+
+        pub fn idtp_header_create() -> CHeader;
+        pub fn idtp_header_convert_endian(header: *mut CHeader);
+        pub fn idtp_header_from_bytes(bytes: *const u8) -> CHeader;
+        pub fn idtp_frame_create() -> CFrame;
+        pub fn idtp_frame_from_bytes(bytes: *const u8, size: usize) -> CFrame;
+        pub fn idtp_frame_set_header(
+            frame: *mut CFrame,
+            header: *const CHeader,
+        );
+        pub fn idtp_frame_set_payload(
+            frame: *mut CFrame,
+            payload: *const u8,
+            size: usize,
+        );
+        pub fn idtp_frame_pack(frame: *const CFrame, buffer: *mut u8);
+        pub fn idtp_frame_parse(
+            bytes: *const u8,
+            size: usize,
+            frame: *mut CFrame,
+        ) -> c_int;
+    }
+}
+
+/// Map of C `IdtpResult` error codes to Rust errors.
+pub const ERROR_CODES: [(c_int, IdtpError); 8] = [
+    (1, IdtpError::BufferTooSmall),
+    (2, IdtpError::FrameTooShort),
+    (3, IdtpError::FrameTooLarge),
+    (4, IdtpError::InvalidPreamble),
+    (5, IdtpError::InvalidTrailer),
+    (6, IdtpError::PayloadSizeMismatch),
+    (7, IdtpError::InvalidPayload),
+    (11, IdtpError::InvalidVersion),
+];
+
+/// Get C preamble constant.
+///
+/// # Returns
+/// - C `IDTP_PREAMBLE` value.
+pub fn preamble() -> [u8; 4] {
+    // SAFETY: immutable C constant.
+    unsafe { ffi::IDTP_PREAMBLE }
+}
+
+/// Get C trailer constant.
+///
+/// # Returns
+/// - C `IDTP_TRAILER` value.
+pub fn trailer() -> [u8; 4] {
+    // SAFETY: immutable C constant.
+    unsafe { ffi::IDTP_TRAILER }
+}
+
+/// Create header with C `idtp_header_create`.
+///
+/// # Returns
+/// - New C IDTP header.
+pub fn header_create() -> CHeader {
+    // SAFETY: function has no preconditions.
+    unsafe { ffi::idtp_header_create() }
+}
+
+/// Convert big-endian header bytes with C `idtp_header_from_bytes`.
+///
+/// # Parameters
+/// - `bytes` - given raw header bytes.
+///
+/// # Returns
+/// - C IDTP header in host byte order.
+pub fn header_from_bytes(bytes: &[u8; IDTP_HEADER_SIZE]) -> CHeader {
+    // SAFETY: `bytes` holds the whole header.
+    unsafe { ffi::idtp_header_from_bytes(bytes.as_ptr()) }
+}
+
+/// Convert header to big-endian bytes with C `idtp_header_convert_endian`.
+///
+/// # Parameters
+/// - `header` - given C IDTP header in host byte order.
+///
+/// # Returns
+/// - Raw header bytes.
+pub fn header_to_bytes(header: &CHeader) -> [u8; IDTP_HEADER_SIZE] {
+    let mut header = *header;
+
+    // SAFETY: `header` is a valid exclusive pointer and `CHeader` is a
+    // packed struct of `IDTP_HEADER_SIZE` bytes without padding.
+    unsafe {
+        ffi::idtp_header_convert_endian(&raw mut header);
+        core::mem::transmute::<CHeader, [u8; IDTP_HEADER_SIZE]>(header)
+    }
+}
+
+/// Pack frame with C `idtp_frame_pack`.
+///
+/// # Parameters
+/// - `header` - given C IDTP header in host byte order.
+/// - `payload` - given payload bytes.
+///
+/// # Returns
+/// - Raw frame bytes.
+pub fn pack(header: &CHeader, payload: &[u8]) -> Vec<u8> {
+    // SAFETY: pointers are valid for the duration of calls and buffer is
+    // large enough for header, payload (not longer than frame stores) and
+    // trailer.
+    unsafe {
+        let mut frame = ffi::idtp_frame_create();
+        ffi::idtp_frame_set_header(&raw mut frame, header);
+        ffi::idtp_frame_set_payload(
+            &raw mut frame,
+            payload.as_ptr(),
+            payload.len(),
+        );
+
+        let mut buffer = vec![0u8; frame.payload_size + IDTP_PACKET_MIN_SIZE];
+        ffi::idtp_frame_pack(&raw const frame, buffer.as_mut_ptr());
+        buffer
+    }
+}
+
+/// Convert raw bytes to frame with C `idtp_frame_from_bytes` (without
+/// validation).
+///
+/// # Parameters
+/// - `bytes` - given raw frame bytes.
+///
+/// # Returns
+/// - C IDTP frame.
+pub fn frame_from_bytes(bytes: &[u8]) -> Box<CFrame> {
+    // SAFETY: `bytes` is valid for `bytes.len()` bytes, the function reads
+    // no more than that.
+    Box::new(unsafe { ffi::idtp_frame_from_bytes(bytes.as_ptr(), bytes.len()) })
+}
+
+/// Parse frame with C `idtp_frame_parse`.
+///
+/// # Parameters
+/// - `bytes` - given raw frame bytes.
+///
+/// # Returns
+/// - C IDTP frame - in case of success.
+/// - `Err` - otherwise.
+///
+/// # Errors
+/// - Will return `Err` with error mapped from C result code.
+///
+/// # Panics
+/// - Will panic if C function returns unknown result code.
+pub fn parse(bytes: &[u8]) -> Result<Box<CFrame>, IdtpError> {
+    // SAFETY: function has no preconditions.
+    let mut frame = Box::new(unsafe { ffi::idtp_frame_create() });
+
+    // SAFETY: `bytes` is valid for `bytes.len()` bytes and `frame` is a
+    // valid exclusive pointer.
+    let code = unsafe {
+        ffi::idtp_frame_parse(bytes.as_ptr(), bytes.len(), &raw mut *frame)
+    };
+
+    if code == 0 {
+        return Ok(frame);
+    }
+
+    let (_, error) = ERROR_CODES
+        .iter()
+        .find(|(value, _)| *value == code)
+        .unwrap_or_else(|| panic!("Unknown C result code {code}"));
+
+    Err(*error)
+}
@@ -0,0 +1,189 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Rust and C implementations conformance tests on golden test vectors
+//! (`res/vectors/frames.json`).
+
+extern crate idtp_conformance;
+
+#[cfg(test)]
+mod tests {
+    use idtp::*;
+    use idtp_conformance as c;
+    use serde_json::Value;
+
+    /// Golden test vector.
+    struct Vector {
+        name: String,
+        bytes: Vec<u8>,
+        parse: String,
+        json: Value,
+    }
+
+    fn decode_hex(string: &str) -> Vec<u8> {
+        (0..string.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&string[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn load_vectors() -> Vec<Vector> {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../../res/vectors/frames.json"
+        );
+        let corpus: Value =
+            serde_json::from_str(&std::fs::read_to_string(path).unwrap())
+                .unwrap();
+
+        assert_eq!(corpus["format"], 1);
+
+        corpus["vectors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|json| Vector {
+                name: json["name"].as_str().unwrap().into(),
+                bytes: decode_hex(json["bytes"].as_str().unwrap()),
+                parse: json["parse"].as_str().unwrap().into(),
+                json: json.clone(),
+            })
+            .collect()
+    }
+
+    fn parse_result<T>(result: &Result<T, IdtpError>) -> String {
+        match result {
+            Ok(_) => "Ok".into(),
+            Err(error) => format!("{error:?}"),
+        }
+    }
+
+    /// Build Rust header from expected header fields of test vector.
+    fn expected_header(json: &Value) -> IdtpHeader {
+        let field = |name: &str| json[name].as_u64().unwrap();
+        let version = &json["version"];
+        let reserved = decode_hex(json["reserved"].as_str().unwrap());
+
+        let mut header = IdtpHeader::new();
+        header.version = Version::new(
+            version[0].as_u64().unwrap() as u8,
+            version[1].as_u64().unwrap() as u8,
+            version[2].as_u64().unwrap() as u8,
+        );
+        header.mode = Mode::from(field("mode") as u8);
+        header.device_id = field("device_id") as u16;
+        header.checksum = field("checksum") as u16;
+        header.timestamp = field("timestamp") as u32;
+        header.sequence = field("sequence") as u32;
+        header.crc = field("crc") as u32;
+        header.payload_size = field("payload_size") as u32;
+        header.payload_type = field("payload_type") as u8;
+        header.reserved.copy_from_slice(&reserved);
+        header
+    }
+
+    #[test]
+    fn test_constants() {
+        assert_eq!(c::preamble(), *IDTP_PREAMBLE);
+        assert_eq!(c::trailer(), *IDTP_TRAILER);
+        assert_eq!(
+            c::header_to_bytes(&c::header_create()),
+            IdtpHeader::new().as_bytes_be()
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        for vector in load_vectors() {
+            let name = &vector.name;
+            let rust = IdtpFrame::parse(&vector.bytes);
+            let c = c::parse(&vector.bytes);
+
+            assert_eq!(parse_result(&rust), vector.parse, "Rust: {name}");
+            assert_eq!(parse_result(&c), vector.parse, "C: {name}");
+
+            let (Ok(rust), Ok(c)) = (rust, c) else {
+                continue;
+            };
+
+            let payload = decode_hex(vector.json["payload"].as_str().unwrap());
+            let expected = expected_header(&vector.json["header"]);
+            let header = expected.as_bytes_be();
+
+            assert_eq!(rust.header().as_bytes_be(), header, "Rust: {name}");
+            assert_eq!(rust.payload(), payload, "Rust: {name}");
+            assert_eq!(c::header_to_bytes(&c.header), header, "C: {name}");
+            assert_eq!(c.payload(), payload, "C: {name}");
+            assert_eq!(
+                rust.verify().is_ok(),
+                vector.json["integrity"].as_bool().unwrap(),
+                "Rust integrity: {name}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_pack() {
+        for vector in load_vectors().iter().filter(|v| v.parse == "Ok") {
+            let name = &vector.name;
+            let payload = decode_hex(vector.json["payload"].as_str().unwrap());
+            let header = expected_header(&vector.json["header"]);
+
+            let mut frame = IdtpFrame::new();
+            frame.set_header(&header);
+            frame.set_payload(&payload);
+
+            let mut rust = vec![0u8; frame.frame_size()];
+            frame.pack(&mut rust).unwrap();
+
+            let c = c::pack(&c::CHeader::from(&header), &payload);
+
+            assert_eq!(rust, vector.bytes, "Rust: {name}");
+            assert_eq!(c, vector.bytes, "C: {name}");
+        }
+    }
+
+    #[test]
+    fn test_header_endian_conversion() {
+        for vector in load_vectors() {
+            let Some(bytes) = vector.bytes.first_chunk::<IDTP_HEADER_SIZE>()
+            else {
+                continue;
+            };
+
+            let name = &vector.name;
+            let rust = IdtpHeader::from(&bytes[..]);
+            let c = c::header_from_bytes(bytes);
+
+            assert_eq!({ c.device_id }, { rust.device_id }, "{name}");
+            assert_eq!({ c.checksum }, { rust.checksum }, "{name}");
+            assert_eq!({ c.timestamp }, { rust.timestamp }, "{name}");
+            assert_eq!({ c.sequence }, { rust.sequence }, "{name}");
+            assert_eq!({ c.crc }, { rust.crc }, "{name}");
+            assert_eq!({ c.payload_size }, { rust.payload_size }, "{name}");
+            assert_eq!(c.mode, bytes[7], "{name}");
+            assert_eq!(c::header_to_bytes(&c), *bytes, "C: {name}");
+
+            // Unknown modes are normalized by Rust header.
+            if Mode::from(bytes[7]) != Mode::Unknown {
+                assert_eq!(rust.as_bytes_be(), *bytes, "Rust: {name}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_frame_from_bytes() {
+        for vector in load_vectors() {
+            let name = &vector.name;
+            let rust = IdtpFrame::from(&vector.bytes[..]);
+            let c = c::frame_from_bytes(&vector.bytes);
+
+            let mut header = [0u8; IDTP_HEADER_SIZE];
+            let size = vector.bytes.len().min(IDTP_HEADER_SIZE);
+            header[..size].copy_from_slice(&vector.bytes[..size]);
+
+            assert_eq!(c::header_to_bytes(&c.header), header, "{name}");
+            assert_eq!(rust.payload(), c.payload(), "{name}");
+        }
+    }
+}
@@ -0,0 +1,310 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Inertial Measurement Unit Data Transfer Protocol C ABI of the Rust
+//! implementation.
+
+#ifndef IDTP_IDTP_H
+#define IDTP_IDTP_H
+
+// Generated by cbindgen from src/rust/idtp-ffi. Do not edit.
+
+#include <stddef.h>
+#include <stdint.h>
+
+/// IDTP protocol version number that increments after incompatible API
+/// changes were made.
+#define IDTP_VERSION_MAJOR 1
+
+/// IDTP protocol version number that increments after adding functionality
+/// in a backwards-compatible manner.
+#define IDTP_VERSION_MINOR 0
+
+/// IDTP protocol version number that increments after backwards-compatible
+/// bug fixes were made.
+#define IDTP_VERSION_PATCH 0
+
+/// Size of IDTP preamble in bytes.
+#define IDTP_PREAMBLE_SIZE 4
+
+/// Size of IDTP trailer in bytes.
+#define IDTP_TRAILER_SIZE 4
+
+/// Size of IDTP header in bytes.
+#define IDTP_HEADER_SIZE 32
+
+/// IDTP network packet max size in bytes. It includes size of IDTP header,
+/// payload and packet trailer.
+#define IDTP_PACKET_MAX_SIZE 1024
+
+/// IDTP network packet min size in bytes.
+#define IDTP_PACKET_MIN_SIZE (IDTP_HEADER_SIZE + IDTP_TRAILER_SIZE)
+
+/// IDTP network packet payload max size in bytes.
+#define IDTP_PAYLOAD_MAX_SIZE ((IDTP_PACKET_MAX_SIZE - IDTP_HEADER_SIZE) - IDTP_TRAILER_SIZE)
+
+/// IDTP operation result codes.
+typedef enum {
+  /// Operation succeeded.
+  IDTP_OK = 0,
+  /// Given buffer is too small.
+  IDTP_ERROR_BUFFER_TOO_SMALL = 1,
+  /// Frame is shorter than header and trailer.
+  IDTP_ERROR_FRAME_TOO_SHORT = 2,
+  /// Frame exceeds maximum packet size.
+  IDTP_ERROR_FRAME_TOO_LARGE = 3,
+  /// Frame does not start with IDTP preamble.
+  IDTP_ERROR_INVALID_PREAMBLE = 4,
+  /// Frame does not end with IDTP trailer.
+  IDTP_ERROR_INVALID_TRAILER = 5,
+  /// Header payload size does not match frame size.
+  IDTP_ERROR_PAYLOAD_SIZE_MISMATCH = 6,
+  /// Payload can not be decoded.
+  IDTP_ERROR_INVALID_PAYLOAD = 7,
+  /// Required pointer argument is null.
+  IDTP_ERROR_NULL_POINTER = 8,
+  /// Frame authentication failed.
+  IDTP_ERROR_AUTHENTICATION_FAILED = 9,
+  /// Session key is not established or must be rotated.
+  IDTP_ERROR_KEY_UNAVAILABLE = 10,
+  /// Major protocol version of frame is not supported.
+  IDTP_ERROR_INVALID_VERSION = 11,
+  /// Frame has more errors than forward error correction can correct.
+  IDTP_ERROR_UNCORRECTABLE = 12,
+  /// Operating mode is unknown or not representable.
+  IDTP_ERROR_INVALID_MODE = 13,
+} IdtpResult;
+
+/// IDTP operating mode.
+enum IdtpMode
+#if defined(__cplusplus) || __STDC_VERSION__ >= 202311L
+  : uint8_t
+#endif // defined(__cplusplus) || __STDC_VERSION__ >= 202311L
+ {
+  /// IDTP-N (Normal mode) - error detection provided by checksum only.
+  IDTP_MODE_NORMAL = 0,
+  /// IDTP-S (Safety mode) - error detection provided by checksum and CRC.
+  IDTP_MODE_SAFETY = 1,
+  /// IDTP-E (Secure mode) - payload encrypted and authenticated with
+  /// AES-128-GCM.
+  IDTP_MODE_SECURE = 2,
+  /// IDTP-F (FEC mode) - header and payload protected by Reed–Solomon
+  /// parity.
+  IDTP_MODE_FEC = 3,
+  /// Unknown mode value. No special handling required.
+  IDTP_MODE_UNKNOWN = 255,
+};
+#ifndef __cplusplus
+#if __STDC_VERSION__ >= 202311L
+typedef enum IdtpMode IdtpMode;
+#else
+typedef uint8_t IdtpMode;
+#endif // __STDC_VERSION__ >= 202311L
+#endif // __cplusplus
+
+/// IDTP version information struct.
+typedef struct {
+  /// Increments after incompatible API changes were made.
+  uint8_t major;
+  /// Increments after adding functionality in a backwards-compatible
+  /// manner.
+  uint8_t minor;
+  /// Increments after backwards-compatible bug fixes were made.
+  uint8_t patch;
+} IdtpVersion;
+
+/// IDTP header struct (host byte order).
+typedef struct __attribute__((packed)) {
+  /// Value to signal the start of a new IDTP packet.
+  uint8_t preamble[IDTP_PREAMBLE_SIZE];
+  /// Protocol version in format MAJOR.MINOR.PATCH.
+  IdtpVersion version;
+  /// Protocol operating mode.
+  uint8_t mode;
+  /// IMU device identifier.
+  uint16_t device_id;
+  /// Value used for simple error detection.
+  uint16_t checksum;
+  /// Timestamp from the IMU's MCU internal clock.
+  uint32_t timestamp;
+  /// Sequence number of IDTP packet sent.
+  uint32_t sequence;
+  /// Cyclic Redundancy Check - value used for complex error detection.
+  uint32_t crc;
+  /// Size of packet payload in bytes.
+  uint32_t payload_size;
+  /// Packet payload type.
+  uint8_t payload_type;
+  /// Reserved field.
+  uint8_t reserved[3];
+} IdtpHeader;
+
+/// Inertial Measurement Unit Data Transfer Protocol frame struct.
+typedef struct {
+  /// IDTP network packet header.
+  IdtpHeader header;
+  /// Value that containing IMU data.
+  uint8_t payload[IDTP_PAYLOAD_MAX_SIZE];
+  /// IDTP payload size in bytes.
+  size_t payload_size;
+} IdtpFrame;
+
+/// Current IDTP version.
+#define IDTP_VERSION (IdtpVersion){ .major = IDTP_VERSION_MAJOR, .minor = IDTP_VERSION_MINOR, .patch = IDTP_VERSION_PATCH }
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/// Value to signal the start of a new IDTP packet.
+extern const uint8_t IDTP_PREAMBLE[IDTP_PREAMBLE_SIZE];
+
+/// Value to signal the end of a new IDTP packet.
+extern const uint8_t IDTP_TRAILER[IDTP_TRAILER_SIZE];
+
+/// Change byte order in uint16 value.
+///
+/// # Parameters
+/// - `value` - given value to swap bytes.
+///
+/// # Returns
+/// - New uint16 value with changed byte order.
+uint16_t swap_uint16(uint16_t value);
+
+/// Change byte order in uint32 value.
+///
+/// # Parameters
+/// - `value` - given value to swap bytes.
+///
+/// # Returns
+/// - New uint32 value with changed byte order.
+uint32_t swap_uint32(uint32_t value);
+
+/// Create new IDTP header object.
+///
+/// # Returns
+/// - New IDTP header.
+IdtpHeader idtp_header_create(void);
+
+/// Convert IDTP header byte order.
+///
+/// # Parameters
+/// - `header` - given IDTP header object to change.
+///
+/// # Returns
+/// - `IDTP_OK` - in case of success.
+/// - `IDTP_ERROR_NULL_POINTER` - if `header` is null.
+///
+/// # Safety
+/// - `header` must be null or valid for reads and writes.
+IdtpResult idtp_header_convert_endian(IdtpHeader *header);
+
+/// Set IDTP header from raw bytes.
+///
+/// # Parameters
+/// - `bytes` - given IDTP header bytes array pointer (big-endian byte order).
+///
+/// # Returns
+/// - IDTP header from raw bytes - if `bytes` is not null.
+/// - New IDTP header - otherwise.
+///
+/// # Safety
+/// - `bytes` must be null or valid for reads of `IDTP_HEADER_SIZE` bytes.
+IdtpHeader idtp_header_from_bytes(const uint8_t *bytes);
+
+/// Create new IDTP frame object.
+///
+/// # Returns
+/// - New IDTP frame.
+IdtpFrame idtp_frame_create(void);
+
+/// Set IDTP header.
+///
+/// # Parameters
+/// - `frame` - given IDTP frame to change.
+/// - `header` - given IDTP header to set.
+///
+/// # Returns
+/// - `IDTP_OK` - in case of success.
+/// - `IDTP_ERROR_NULL_POINTER` - if any of pointers is null.
+///
+/// # Safety
+/// - `frame` must be null or valid for writes.
+/// - `header` must be null or valid for reads.
+IdtpResult idtp_frame_set_header(IdtpFrame *frame, const IdtpHeader *header);
+
+/// Set IDTP payload. Frame is left unchanged in case of error.
+///
+/// # Parameters
+/// - `frame` - given IDTP frame to change.
+/// - `payload` - given IDTP payload bytes to set.
+/// - `size` - given IDTP payload size in bytes.
+///
+/// # Returns
+/// - `IDTP_OK` - in case of success.
+/// - `IDTP_ERROR_NULL_POINTER` - if `frame` is null or `payload` is null
+///   while `size` is not zero.
+/// - `IDTP_ERROR_FRAME_TOO_LARGE` - if `size` exceeds
+///   `IDTP_PAYLOAD_MAX_SIZE`.
+///
+/// # Safety
+/// - `frame` must be null or valid for writes.
+/// - `payload` must be null or valid for reads of `size` bytes.
+IdtpResult idtp_frame_set_payload(IdtpFrame *frame, const uint8_t *payload, size_t size);
+
+/// Pack into raw IDTP network packet.
+///
+/// # Parameters
+/// - `frame` - given IDTP frame to pack.
+/// - `buffer` - given buffer to store raw IDTP packet.
+///
+/// # Returns
+/// - `IDTP_OK` - in case of success.
+/// - `IDTP_ERROR_NULL_POINTER` - if any of pointers is null.
+/// - `IDTP_ERROR_FRAME_TOO_LARGE` - if frame `payload_size` exceeds
+///   `IDTP_PAYLOAD_MAX_SIZE`.
+///
+/// # Safety
+/// - `frame` must be null or valid for reads.
+/// - `buffer` must be null or valid for writes of
+///   `IDTP_PACKET_MIN_SIZE + frame->payload_size` bytes.
+IdtpResult idtp_frame_pack(const IdtpFrame *frame, uint8_t *buffer);
+
+/// Convert byte array to IDTP frame. Structure is not validated, use
+/// `idtp_frame_parse` for that. Missing header bytes are treated as zeros
+/// and payload is truncated to `IDTP_PAYLOAD_MAX_SIZE`.
+///
+/// # Parameters
+/// - `bytes` - given byte array to convert (big-endian byte order).
+/// - `size` - given number of bytes.
+///
+/// # Returns
+/// - IDTP frame from byte array - if `bytes` is not null.
+/// - New IDTP frame - otherwise.
+///
+/// # Safety
+/// - `bytes` must be null or valid for reads of `size` bytes.
+IdtpFrame idtp_frame_from_bytes(const uint8_t *bytes, size_t size);
+
+/// Parse raw IDTP network packet validating its structure.
+/// Integrity is not checked.
+///
+/// # Parameters
+/// - `bytes` - given raw IDTP packet (big-endian byte order).
+/// - `size` - given number of bytes.
+/// - `frame` - given IDTP frame to store result in.
+///
+/// # Returns
+/// - `IDTP_OK` - in case of success.
+/// - Error code - otherwise, `frame` is left unchanged.
+///
+/// # Safety
+/// - `bytes` must be null or valid for reads of `size` bytes.
+/// - `frame` must be null or valid for writes.
+IdtpResult idtp_frame_parse(const uint8_t *bytes, size_t size, IdtpFrame *frame);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* IDTP_IDTP_H */
@@ -0,0 +1,587 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! C ABI of the Rust implementation of IDTP.
+//!
+//! Functions mirror the C implementation API (`src/c`), so the static
+//! library together with generated `include/idtp/idtp.h` header is a drop-in
+//! replacement of it. Unlike C implementation, functions which can fail
+//! return `IdtpResult` codes instead of silently ignoring invalid input.
+
+// C naming is kept for exported items.
+#![allow(non_camel_case_types)]
+
+use idtp::{IdtpError, Mode};
+
+/// IDTP protocol version number that increments after incompatible API
+/// changes were made.
+pub const IDTP_VERSION_MAJOR: u8 = 1;
+
+/// IDTP protocol version number that increments after adding functionality
+/// in a backwards-compatible manner.
+pub const IDTP_VERSION_MINOR: u8 = 0;
+
+/// IDTP protocol version number that increments after backwards-compatible
+/// bug fixes were made.
+pub const IDTP_VERSION_PATCH: u8 = 0;
+
+/// Size of IDTP preamble in bytes.
+pub const IDTP_PREAMBLE_SIZE: usize = 4;
+
+/// Size of IDTP trailer in bytes.
+pub const IDTP_TRAILER_SIZE: usize = 4;
+
+/// Size of IDTP header in bytes.
+pub const IDTP_HEADER_SIZE: usize = 32;
+
+/// IDTP network packet max size in bytes. It includes size of IDTP header,
+/// payload and packet trailer.
+pub const IDTP_PACKET_MAX_SIZE: usize = 1024;
+
+/// IDTP network packet min size in bytes.
+pub const IDTP_PACKET_MIN_SIZE: usize = IDTP_HEADER_SIZE + IDTP_TRAILER_SIZE;
+
+/// IDTP network packet payload max size in bytes.
+pub const IDTP_PAYLOAD_MAX_SIZE: usize =
+    IDTP_PACKET_MAX_SIZE - IDTP_HEADER_SIZE - IDTP_TRAILER_SIZE;
+
+// Constants are duplicated as literals for header generation.
+const _: () = {
+    assert!(IDTP_VERSION_MAJOR == idtp::IDTP_VERSION.major);
+    assert!(IDTP_VERSION_MINOR == idtp::IDTP_VERSION.minor);
+    assert!(IDTP_VERSION_PATCH == idtp::IDTP_VERSION.patch);
+    assert!(IDTP_PREAMBLE_SIZE == idtp::IDTP_PREAMBLE_SIZE);
+    assert!(IDTP_TRAILER_SIZE == idtp::IDTP_TRAILER_SIZE);
+    assert!(IDTP_HEADER_SIZE == idtp::IDTP_HEADER_SIZE);
+    assert!(IDTP_HEADER_SIZE == size_of::<IdtpHeader>());
+    assert!(IDTP_PACKET_MAX_SIZE == idtp::IDTP_PACKET_MAX_SIZE);
+};
+
+/// Value to signal the start of a new IDTP packet.
+#[unsafe(no_mangle)]
+pub static IDTP_PREAMBLE: [u8; IDTP_PREAMBLE_SIZE] = *b"IDTP";
+
+/// Value to signal the end of a new IDTP packet.
+#[unsafe(no_mangle)]
+pub static IDTP_TRAILER: [u8; IDTP_TRAILER_SIZE] = *b"PTDI";
+
+/// IDTP version information struct.
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct IdtpVersion {
+    /// Increments after incompatible API changes were made.
+    pub major: u8,
+    /// Increments after adding functionality in a backwards-compatible
+    /// manner.
+    pub minor: u8,
+    /// Increments after backwards-compatible bug fixes were made.
+    pub patch: u8,
+}
+
+/// Current IDTP version.
+pub const IDTP_VERSION: IdtpVersion = IdtpVersion {
+    major: IDTP_VERSION_MAJOR,
+    minor: IDTP_VERSION_MINOR,
+    patch: IDTP_VERSION_PATCH,
+};
+
+/// IDTP operating mode.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdtpMode {
+    /// IDTP-N (Normal mode) - error detection provided by checksum only.
+    IDTP_MODE_NORMAL = 0x00,
+    /// IDTP-S (Safety mode) - error detection provided by checksum and CRC.
+    IDTP_MODE_SAFETY = 0x01,
+    /// IDTP-E (Secure mode) - payload encrypted and authenticated with
+    /// AES-128-GCM.
+    IDTP_MODE_SECURE = 0x02,
+    /// IDTP-F (FEC mode) - header and payload protected by Reed–Solomon
+    /// parity.
+    IDTP_MODE_FEC = 0x03,
+    /// Unknown mode value. No special handling required.
+    IDTP_MODE_UNKNOWN = 0xff,
+}
+
+/// IDTP operation result codes.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdtpResult {
+    /// Operation succeeded.
+    IDTP_OK = 0,
+    /// Given buffer is too small.
+    IDTP_ERROR_BUFFER_TOO_SMALL = 1,
+    /// Frame is shorter than header and trailer.
+    IDTP_ERROR_FRAME_TOO_SHORT = 2,
+    /// Frame exceeds maximum packet size.
+    IDTP_ERROR_FRAME_TOO_LARGE = 3,
+    /// Frame does not start with IDTP preamble.
+    IDTP_ERROR_INVALID_PREAMBLE = 4,
+    /// Frame does not end with IDTP trailer.
+    IDTP_ERROR_INVALID_TRAILER = 5,
+    /// Header payload size does not match frame size.
+    IDTP_ERROR_PAYLOAD_SIZE_MISMATCH = 6,
+    /// Payload can not be decoded.
+    IDTP_ERROR_INVALID_PAYLOAD = 7,
+    /// Required pointer argument is null.
+    IDTP_ERROR_NULL_POINTER = 8,
+    /// Frame authentication failed.
+    IDTP_ERROR_AUTHENTICATION_FAILED = 9,
+    /// Session key is not established or must be rotated.
+    IDTP_ERROR_KEY_UNAVAILABLE = 10,
+    /// Major protocol version of frame is not supported.
+    IDTP_ERROR_INVALID_VERSION = 11,
+    /// Frame has more errors than forward error correction can correct.
+    IDTP_ERROR_UNCORRECTABLE = 12,
+    /// Operating mode is unknown or not representable.
+    IDTP_ERROR_INVALID_MODE = 13,
+}
+
+impl From<IdtpError> for IdtpResult {
+    /// Convert Rust IDTP error to C result code.
+    ///
+    /// # Parameters
+    /// - `error` - given Rust IDTP error to convert.
+    ///
+    /// # Returns
+    /// - C result code.
+    fn from(error: IdtpError) -> Self {
+        match error {
+            IdtpError::BufferTooSmall => Self::IDTP_ERROR_BUFFER_TOO_SMALL,
+            IdtpError::FrameTooShort => Self::IDTP_ERROR_FRAME_TOO_SHORT,
+            IdtpError::FrameTooLarge => Self::IDTP_ERROR_FRAME_TOO_LARGE,
+            IdtpError::InvalidPreamble => Self::IDTP_ERROR_INVALID_PREAMBLE,
+            IdtpError::InvalidTrailer => Self::IDTP_ERROR_INVALID_TRAILER,
+            IdtpError::PayloadSizeMismatch => {
+                Self::IDTP_ERROR_PAYLOAD_SIZE_MISMATCH
+            }
+            IdtpError::InvalidPayload => Self::IDTP_ERROR_INVALID_PAYLOAD,
+            IdtpError::AuthenticationFailed => {
+                Self::IDTP_ERROR_AUTHENTICATION_FAILED
+            }
+            IdtpError::KeyUnavailable => Self::IDTP_ERROR_KEY_UNAVAILABLE,
+            IdtpError::InvalidVersion => Self::IDTP_ERROR_INVALID_VERSION,
+            IdtpError::Uncorrectable => Self::IDTP_ERROR_UNCORRECTABLE,
+            IdtpError::InvalidMode => Self::IDTP_ERROR_INVALID_MODE,
+        }
+    }
+}
+
+/// IDTP header struct (host byte order).
+#[repr(C, packed)]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct IdtpHeader {
+    /// Value to signal the start of a new IDTP packet.
+    pub preamble: [u8; IDTP_PREAMBLE_SIZE],
+    /// Protocol version in format MAJOR.MINOR.PATCH.
+    pub version: IdtpVersion,
+    /// Protocol operating mode.
+    pub mode: u8,
+    /// IMU device identifier.
+    pub device_id: u16,
+    /// Value used for simple error detection.
+    pub checksum: u16,
+    /// Timestamp from the IMU's MCU internal clock.
+    pub timestamp: u32,
+    /// Sequence number of IDTP packet sent.
+    pub sequence: u32,
+    /// Cyclic Redundancy Check - value used for complex error detection.
+    pub crc: u32,
+    /// Size of packet payload in bytes.
+    pub payload_size: u32,
+    /// Packet payload type.
+    pub payload_type: u8,
+    /// Reserved field.
+    pub reserved: [u8; 3],
+}
+
+impl IdtpHeader {
+    /// Convert from Rust IDTP header.
+    ///
+    /// # Parameters
+    /// - `header` - given Rust IDTP header to convert.
+    /// - `mode` - given raw operating mode byte (Rust header keeps only
+    ///   known modes).
+    ///
+    /// # Returns
+    /// - C IDTP header.
+    fn from_rust(header: &idtp::IdtpHeader, mode: u8) -> Self {
+        let version = header.version;
+
+        Self {
+            preamble: header.preamble,
+            version: IdtpVersion {
+                major: version.major,
+                minor: version.minor,
+                patch: version.patch,
+            },
+            mode,
+            device_id: header.device_id,
+            checksum: header.checksum,
+            timestamp: header.timestamp,
+            sequence: header.sequence,
+            crc: header.crc,
+            payload_size: header.payload_size,
+            payload_type: header.payload_type,
+            reserved: header.reserved,
+        }
+    }
+
+    /// Convert to Rust IDTP header.
+    ///
+    /// # Returns
+    /// - Rust IDTP header.
+    fn to_rust(self) -> idtp::IdtpHeader {
+        let version = self.version;
+
+        let mut header = idtp::IdtpHeader::new();
+        header.preamble = self.preamble;
+        header.version =
+            idtp::Version::new(version.major, version.minor, version.patch);
+        header.mode = Mode::from(self.mode);
+        header.device_id = self.device_id;
+        header.checksum = self.checksum;
+        header.timestamp = self.timestamp;
+        header.sequence = self.sequence;
+        header.crc = self.crc;
+        header.payload_size = self.payload_size;
+        header.payload_type = self.payload_type;
+        header.reserved = self.reserved;
+        header
+    }
+}
+
+/// Inertial Measurement Unit Data Transfer Protocol frame struct.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct IdtpFrame {
+    /// IDTP network packet header.
+    pub header: IdtpHeader,
+    /// Value that containing IMU data.
+    pub payload: [u8; IDTP_PAYLOAD_MAX_SIZE],
+    /// IDTP payload size in bytes.
+    pub payload_size: usize,
+}
+
+impl IdtpFrame {
+    /// Convert from Rust IDTP frame.
+    ///
+    /// # Parameters
+    /// - `frame` - given Rust IDTP frame to convert.
+    /// - `mode` - given raw operating mode byte.
+    ///
+    /// # Returns
+    /// - C IDTP frame.
+    fn from_rust(frame: &idtp::IdtpFrame, mode: u8) -> Self {
+        let mut result = idtp_frame_create();
+        result.header = IdtpHeader::from_rust(&frame.header(), mode);
+        result.payload[..frame.payload_size()].copy_from_slice(frame.payload());
+        result.payload_size = frame.payload_size();
+        result
+    }
+}
+
+/// Change byte order in uint16 value.
+///
+/// # Parameters
+/// - `value` - given value to swap bytes.
+///
+/// # Returns
+/// - New uint16 value with changed byte order.
+#[unsafe(no_mangle)]
+pub extern "C" fn swap_uint16(value: u16) -> u16 {
+    value.swap_bytes()
+}
+
+/// Change byte order in uint32 value.
+///
+/// # Parameters
+/// - `value` - given value to swap bytes.
+///
+/// # Returns
+/// - New uint32 value with changed byte order.
+#[unsafe(no_mangle)]
+pub extern "C" fn swap_uint32(value: u32) -> u32 {
+    value.swap_bytes()
+}
+
+/// Create new IDTP header object.
+///
+/// # Returns
+/// - New IDTP header.
+#[unsafe(no_mangle)]
+pub extern "C" fn idtp_header_create() -> IdtpHeader {
+    IdtpHeader::from_rust(&idtp::IdtpHeader::new(), Mode::Normal as u8)
+}
+
+/// Convert IDTP header byte order.
+///
+/// # Parameters
+/// - `header` - given IDTP header object to change.
+///
+/// # Returns
+/// - `IDTP_OK` - in case of success.
+/// - `IDTP_ERROR_NULL_POINTER` - if `header` is null.
+///
+/// # Safety
+/// - `header` must be null or valid for reads and writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn idtp_header_convert_endian(
+    header: *mut IdtpHeader,
+) -> IdtpResult {
+    // SAFETY: guaranteed by caller.
+    let Some(header) = (unsafe { header.as_mut() }) else {
+        return IdtpResult::IDTP_ERROR_NULL_POINTER;
+    };
+
+    header.device_id = header.device_id.swap_bytes();
+    header.checksum = header.checksum.swap_bytes();
+    header.timestamp = header.timestamp.swap_bytes();
+    header.sequence = header.sequence.swap_bytes();
+    header.crc = header.crc.swap_bytes();
+    header.payload_size = header.payload_size.swap_bytes();
+
+    IdtpResult::IDTP_OK
+}
+
+/// Set IDTP header from raw bytes.
+///
+/// # Parameters
+/// - `bytes` - given IDTP header bytes array pointer (big-endian byte order).
+///
+/// # Returns
+/// - IDTP header from raw bytes - if `bytes` is not null.
+/// - New IDTP header - otherwise.
+///
+/// # Safety
+/// - `bytes` must be null or valid for reads of `IDTP_HEADER_SIZE` bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn idtp_header_from_bytes(
+    bytes: *const u8,
+) -> IdtpHeader {
+    if bytes.is_null() {
+        return idtp_header_create();
+    }
+
+    // SAFETY: guaranteed by caller.
+    let bytes = unsafe { core::slice::from_raw_parts(bytes, IDTP_HEADER_SIZE) };
+
+    IdtpHeader::from_rust(&idtp::IdtpHeader::from(bytes), bytes[7])
+}
+
+/// Create new IDTP frame object.
+///
+/// # Returns
+/// - New IDTP frame.
+#[unsafe(no_mangle)]
+pub extern "C" fn idtp_frame_create() -> IdtpFrame {
+    IdtpFrame {
+        header: idtp_header_create(),
+        payload: [0u8; IDTP_PAYLOAD_MAX_SIZE],
+        payload_size: 0,
+    }
+}
+
+/// Set IDTP header.
+///
+/// # Parameters
+/// - `frame` - given IDTP frame to change.
+/// - `header` - given IDTP header to set.
+///
+/// # Returns
+/// - `IDTP_OK` - in case of success.
+/// - `IDTP_ERROR_NULL_POINTER` - if any of pointers is null.
+///
+/// # Safety
+/// - `frame` must be null or valid for writes.
+/// - `header` must be null or valid for reads.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn idtp_frame_set_header(
+    frame: *mut IdtpFrame,
+    header: *const IdtpHeader,
+) -> IdtpResult {
+    // SAFETY: guaranteed by caller.
+    let (Some(frame), Some(header)) =
+        (unsafe { frame.as_mut() }, unsafe { header.as_ref() })
+    else {
+        return IdtpResult::IDTP_ERROR_NULL_POINTER;
+    };
+
+    frame.header = *header;
+    IdtpResult::IDTP_OK
+}
+
+/// Set IDTP payload. Frame is left unchanged in case of error.
+///
+/// # Parameters
+/// - `frame` - given IDTP frame to change.
+/// - `payload` - given IDTP payload bytes to set.
+/// - `size` - given IDTP payload size in bytes.
+///
+/// # Returns
+/// - `IDTP_OK` - in case of success.
+/// - `IDTP_ERROR_NULL_POINTER` - if `frame` is null or `payload` is null
+///   while `size` is not zero.
+/// - `IDTP_ERROR_FRAME_TOO_LARGE` - if `size` exceeds
+///   `IDTP_PAYLOAD_MAX_SIZE`.
+///
+/// # Safety
+/// - `frame` must be null or valid for writes.
+/// - `payload` must be null or valid for reads of `size` bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn idtp_frame_set_payload(
+    frame: *mut IdtpFrame,
+    payload: *const u8,
+    size: usize,
+) -> IdtpResult {
+    // SAFETY: guaranteed by caller.
+    let Some(frame) = (unsafe { frame.as_mut() }) else {
+        return IdtpResult::IDTP_ERROR_NULL_POINTER;
+    };
+
+    // SAFETY: guaranteed by caller.
+    let Some(payload) = (unsafe { slice(payload, size) }) else {
+        return IdtpResult::IDTP_ERROR_NULL_POINTER;
+    };
+
+    if size > IDTP_PAYLOAD_MAX_SIZE {
+        return IdtpResult::IDTP_ERROR_FRAME_TOO_LARGE;
+    }
+
+    frame.payload[..size].copy_from_slice(payload);
+    frame.payload_size = size;
+    IdtpResult::IDTP_OK
+}
+
+/// Pack into raw IDTP network packet.
+///
+/// # Parameters
+/// - `frame` - given IDTP frame to pack.
+/// - `buffer` - given buffer to store raw IDTP packet.
+///
+/// # Returns
+/// - `IDTP_OK` - in case of success.
+/// - `IDTP_ERROR_NULL_POINTER` - if any of pointers is null.
+/// - `IDTP_ERROR_FRAME_TOO_LARGE` - if frame `payload_size` exceeds
+///   `IDTP_PAYLOAD_MAX_SIZE`.
+///
+/// # Safety
+/// - `frame` must be null or valid for reads.
+/// - `buffer` must be null or valid for writes of
+///   `IDTP_PACKET_MIN_SIZE + frame->payload_size` bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn idtp_frame_pack(
+    frame: *const IdtpFrame,
+    buffer: *mut u8,
+) -> IdtpResult {
+    // SAFETY: guaranteed by caller.
+    let Some(frame) = (unsafe { frame.as_ref() }) else {
+        return IdtpResult::IDTP_ERROR_NULL_POINTER;
+    };
+
+    if buffer.is_null() {
+        return IdtpResult::IDTP_ERROR_NULL_POINTER;
+    }
+
+    if frame.payload_size > IDTP_PAYLOAD_MAX_SIZE {
+        return IdtpResult::IDTP_ERROR_FRAME_TOO_LARGE;
+    }
+
+    let mut rust = idtp::IdtpFrame::new();
+    rust.set_header(&frame.header.to_rust());
+    rust.set_payload(&frame.payload[..frame.payload_size]);
+
+    // SAFETY: guaranteed by caller.
+    let buffer =
+        unsafe { core::slice::from_raw_parts_mut(buffer, rust.frame_size()) };
+
+    if rust.pack(buffer).is_err() {
+        return IdtpResult::IDTP_ERROR_BUFFER_TOO_SMALL;
+    }
+
+    // Rust header keeps only known modes.
+    buffer[7] = frame.header.mode;
+    IdtpResult::IDTP_OK
+}
+
+/// Convert byte array to IDTP frame. Structure is not validated, use
+/// `idtp_frame_parse` for that. Missing header bytes are treated as zeros
+/// and payload is truncated to `IDTP_PAYLOAD_MAX_SIZE`.
+///
+/// # Parameters
+/// - `bytes` - given byte array to convert (big-endian byte order).
+/// - `size` - given number of bytes.
+///
+/// # Returns
+/// - IDTP frame from byte array - if `bytes` is not null.
+/// - New IDTP frame - otherwise.
+///
+/// # Safety
+/// - `bytes` must be null or valid for reads of `size` bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn idtp_frame_from_bytes(
+    bytes: *const u8,
+    size: usize,
+) -> IdtpFrame {
+    // SAFETY: guaranteed by caller.
+    let Some(bytes) = (unsafe { slice(bytes, size) }) else {
+        return idtp_frame_create();
+    };
+
+    let mode = bytes.get(7).copied().unwrap_or_default();
+    IdtpFrame::from_rust(&idtp::IdtpFrame::from(bytes), mode)
+}
+
+/// Parse raw IDTP network packet validating its structure.
+/// Integrity is not checked.
+///
+/// # Parameters
+/// - `bytes` - given raw IDTP packet (big-endian byte order).
+/// - `size` - given number of bytes.
+/// - `frame` - given IDTP frame to store result in.
+///
+/// # Returns
+/// - `IDTP_OK` - in case of success.
+/// - Error code - otherwise, `frame` is left unchanged.
+///
+/// # Safety
+/// - `bytes` must be null or valid for reads of `size` bytes.
+/// - `frame` must be null or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn idtp_frame_parse(
+    bytes: *const u8,
+    size: usize,
+    frame: *mut IdtpFrame,
+) -> IdtpResult {
+    // SAFETY: guaranteed by caller.
+    let (Some(bytes), Some(frame)) =
+        (unsafe { slice(bytes, size) }, unsafe { frame.as_mut() })
+    else {
+        return IdtpResult::IDTP_ERROR_NULL_POINTER;
+    };
+
+    match idtp::IdtpFrame::parse(bytes) {
+        Ok(parsed) => {
+            *frame = IdtpFrame::from_rust(&parsed, bytes[7]);
+            IdtpResult::IDTP_OK
+        }
+        Err(error) => error.into(),
+    }
+}
+
+/// Convert raw pointer and size to byte slice.
+///
+/// # Returns
+/// - Byte slice - if `bytes` is not null or `size` is zero.
+/// - `None` - otherwise.
+///
+/// # Safety
+/// - `bytes` must be null or valid for reads of `size` bytes.
+unsafe fn slice<'a>(bytes: *const u8, size: usize) -> Option<&'a [u8]> {
+    match (bytes.is_null(), size) {
+        (_, 0) => Some(&[]),
+        (true, _) => None,
+        // SAFETY: guaranteed by caller.
+        (false, _) => Some(unsafe { core::slice::from_raw_parts(bytes, size) }),
+    }
+}
@@ -0,0 +1,170 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! C ABI tests.
+
+extern crate idtp_ffi;
+
+#[cfg(test)]
+mod tests {
+    use core::ptr;
+    use idtp_ffi::{IdtpResult::*, *};
+    use serde_json::Value;
+
+    fn decode_hex(string: &str) -> Vec<u8> {
+        (0..string.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&string[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn result_name(result: IdtpResult) -> &'static str {
+        match result {
+            IDTP_OK => "Ok",
+            IDTP_ERROR_BUFFER_TOO_SMALL => "BufferTooSmall",
+            IDTP_ERROR_FRAME_TOO_SHORT => "FrameTooShort",
+            IDTP_ERROR_FRAME_TOO_LARGE => "FrameTooLarge",
+            IDTP_ERROR_INVALID_PREAMBLE => "InvalidPreamble",
+            IDTP_ERROR_INVALID_TRAILER => "InvalidTrailer",
+            IDTP_ERROR_PAYLOAD_SIZE_MISMATCH => "PayloadSizeMismatch",
+            IDTP_ERROR_INVALID_PAYLOAD => "InvalidPayload",
+            IDTP_ERROR_NULL_POINTER => "NullPointer",
+            IDTP_ERROR_AUTHENTICATION_FAILED => "AuthenticationFailed",
+            IDTP_ERROR_KEY_UNAVAILABLE => "KeyUnavailable",
+            IDTP_ERROR_INVALID_VERSION => "InvalidVersion",
+            IDTP_ERROR_UNCORRECTABLE => "Uncorrectable",
+            IDTP_ERROR_INVALID_MODE => "InvalidMode",
+        }
+    }
+
+    #[test]
+    fn test_golden_vectors() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../../res/vectors/frames.json"
+        );
+        let corpus: Value =
+            serde_json::from_str(&std::fs::read_to_string(path).unwrap())
+                .unwrap();
+
+        for vector in corpus["vectors"].as_array().unwrap() {
+            let name = vector["name"].as_str().unwrap();
+            let bytes = decode_hex(vector["bytes"].as_str().unwrap());
+            let mut frame = idtp_frame_create();
+
+            // SAFETY: pointers are valid for the duration of calls.
+            let result = unsafe {
+                idtp_frame_parse(bytes.as_ptr(), bytes.len(), &raw mut frame)
+            };
+
+            assert_eq!(result_name(result), vector["parse"], "{name}");
+
+            if result != IDTP_OK {
+                continue;
+            }
+
+            let payload = decode_hex(vector["payload"].as_str().unwrap());
+            assert_eq!(&frame.payload[..frame.payload_size], payload, "{name}");
+            assert_eq!(frame.header.mode, bytes[7], "{name}");
+
+            // Packing parsed frame gives back original bytes.
+            let mut buffer = vec![0u8; bytes.len()];
+
+            // SAFETY: buffer holds the whole frame.
+            let result = unsafe {
+                idtp_frame_pack(&raw const frame, buffer.as_mut_ptr())
+            };
+
+            assert_eq!(result, IDTP_OK, "{name}");
+            assert_eq!(buffer, bytes, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_error_codes() {
+        let mut frame = idtp_frame_create();
+        let payload = [0u8; IDTP_PAYLOAD_MAX_SIZE + 1];
+
+        // SAFETY: pointers are either null or valid for given sizes.
+        unsafe {
+            assert_eq!(
+                idtp_frame_set_payload(&raw mut frame, payload.as_ptr(), 5),
+                IDTP_OK
+            );
+            assert_eq!(
+                idtp_frame_set_payload(
+                    &raw mut frame,
+                    payload.as_ptr(),
+                    payload.len()
+                ),
+                IDTP_ERROR_FRAME_TOO_LARGE
+            );
+            assert_eq!(frame.payload_size, 5);
+
+            assert_eq!(
+                idtp_frame_set_payload(&raw mut frame, ptr::null(), 0),
+                IDTP_OK
+            );
+            assert_eq!(
+                idtp_frame_set_payload(&raw mut frame, ptr::null(), 1),
+                IDTP_ERROR_NULL_POINTER
+            );
+            assert_eq!(
+                idtp_frame_set_header(ptr::null_mut(), &raw const frame.header),
+                IDTP_ERROR_NULL_POINTER
+            );
+            assert_eq!(
+                idtp_frame_pack(&raw const frame, ptr::null_mut()),
+                IDTP_ERROR_NULL_POINTER
+            );
+            assert_eq!(
+                idtp_frame_parse(ptr::null(), 64, &raw mut frame),
+                IDTP_ERROR_NULL_POINTER
+            );
+            assert_eq!(
+                idtp_header_convert_endian(ptr::null_mut()),
+                IDTP_ERROR_NULL_POINTER
+            );
+
+            frame.payload_size = IDTP_PAYLOAD_MAX_SIZE + 1;
+            let mut buffer = [0u8; IDTP_PACKET_MAX_SIZE + 1];
+
+            assert_eq!(
+                idtp_frame_pack(&raw const frame, buffer.as_mut_ptr()),
+                IDTP_ERROR_FRAME_TOO_LARGE
+            );
+
+            let frame = idtp_frame_from_bytes(ptr::null(), 10);
+            assert_eq!(frame.payload_size, 0);
+            assert_eq!(frame.header.preamble, IDTP_PREAMBLE);
+        }
+    }
+
+    #[test]
+    fn test_header_conversion() {
+        let mut header = idtp_header_create();
+        header.mode = 0x7f;
+        header.device_id = 0x1234;
+        header.payload_size = 0x0102_0304;
+
+        let mut frame = idtp_frame_create();
+        let mut buffer = [0u8; IDTP_PACKET_MIN_SIZE];
+
+        // SAFETY: pointers are valid and buffer holds the whole frame.
+        unsafe {
+            idtp_frame_set_header(&raw mut frame, &raw const header);
+            idtp_frame_pack(&raw const frame, buffer.as_mut_ptr());
+
+            let parsed = idtp_header_from_bytes(buffer.as_ptr());
+            assert_eq!(parsed.mode, 0x7f);
+            assert_eq!({ parsed.device_id }, 0x1234);
+            assert_eq!({ parsed.payload_size }, 0x0102_0304);
+            assert_eq!(parsed.version, IDTP_VERSION);
+
+            idtp_header_convert_endian(&raw mut header);
+            assert_eq!({ header.device_id }, 0x3412);
+            assert_eq!(swap_uint16(0x1234), 0x3412);
+            assert_eq!(swap_uint32(0x0102_0304), 0x0403_0201);
+        }
+    }
+}
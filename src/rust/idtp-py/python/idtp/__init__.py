@@ -0,0 +1,44 @@
+# SPDX-License-Identifier: Apache-2.0.
+# Copyright (C) 2025-present idtp project and contributors.
+
+"""Inertial Measurement Unit Data Transfer Protocol (IDTP) host-side analysis.
+
+Bindings of the Rust implementation: header and frame parsing, streaming
+deframer, recording reader & writer. Standard payloads are decoded to NumPy
+arrays, e.g. a whole recording is loaded as column arrays with
+``Recording(path).columns()``.
+"""
+
+from ._idtp import (
+    HEADER_SIZE,
+    PACKET_MAX_SIZE,
+    PACKET_MIN_SIZE,
+    PAYLOAD_MAX_SIZE,
+    PAYLOAD_TYPES,
+    PREAMBLE,
+    TRAILER,
+    Deframer,
+    Frame,
+    Header,
+    IdtpError,
+    Recorder,
+    Recording,
+    payload_fields,
+)
+
+__all__ = [
+    "HEADER_SIZE",
+    "PACKET_MAX_SIZE",
+    "PACKET_MIN_SIZE",
+    "PAYLOAD_MAX_SIZE",
+    "PAYLOAD_TYPES",
+    "PREAMBLE",
+    "TRAILER",
+    "Deframer",
+    "Frame",
+    "Header",
+    "IdtpError",
+    "Recorder",
+    "Recording",
+    "payload_fields",
+]
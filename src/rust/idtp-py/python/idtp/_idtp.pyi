@@ -0,0 +1,112 @@
+# SPDX-License-Identifier: Apache-2.0.
+# Copyright (C) 2025-present idtp project and contributors.
+
+import os
+from collections.abc import Iterator
+from types import TracebackType
+from typing import Optional
+
+import numpy as np
+import numpy.typing as npt
+
+HEADER_SIZE: int
+PACKET_MIN_SIZE: int
+PACKET_MAX_SIZE: int
+PAYLOAD_MAX_SIZE: int
+PREAMBLE: bytes
+TRAILER: bytes
+PAYLOAD_TYPES: dict[str, int]
+
+class IdtpError(ValueError): ...
+
+class Header:
+    @staticmethod
+    def from_bytes(data: bytes) -> Header: ...
+    def to_bytes(self) -> bytes: ...
+    @property
+    def preamble(self) -> bytes: ...
+    @property
+    def version(self) -> tuple[int, int, int]: ...
+    @property
+    def mode(self) -> int: ...
+    @property
+    def device_id(self) -> int: ...
+    @property
+    def checksum(self) -> int: ...
+    @property
+    def timestamp(self) -> int: ...
+    @property
+    def sequence(self) -> int: ...
+    @property
+    def crc(self) -> int: ...
+    @property
+    def payload_size(self) -> int: ...
+    @property
+    def payload_type(self) -> int: ...
+    @property
+    def reserved(self) -> bytes: ...
+
+class Frame:
+    @staticmethod
+    def parse(data: bytes) -> Frame: ...
+    @staticmethod
+    def from_bytes(data: bytes) -> Frame: ...
+    def to_bytes(self) -> bytes: ...
+    @property
+    def header(self) -> Header: ...
+    @property
+    def payload(self) -> bytes: ...
+    def verify(self) -> bool: ...
+    def decode(self) -> Optional[npt.NDArray[np.float32]]: ...
+    def __len__(self) -> int: ...
+
+class Deframer:
+    def __init__(self) -> None: ...
+    def feed(self, data: bytes) -> list[bytes]: ...
+    def reset(self) -> None: ...
+    @property
+    def resyncs(self) -> int: ...
+    @property
+    def discarded(self) -> int: ...
+    @property
+    def buffered(self) -> int: ...
+
+class Recording(Iterator[tuple[int, bytes]]):
+    def __init__(self, path: str | os.PathLike[str]) -> None: ...
+    @property
+    def session(self) -> dict[str, object]: ...
+    @property
+    def is_truncated(self) -> bool: ...
+    @property
+    def is_index_rebuilt(self) -> bool: ...
+    def rewind(self) -> None: ...
+    def seek(self, recv_time_us: int) -> None: ...
+    def columns(
+        self,
+        payload_type: Optional[int] = None,
+        device_id: Optional[int] = None,
+    ) -> dict[str, npt.NDArray[np.generic]]: ...
+    def __iter__(self) -> Recording: ...
+    def __next__(self) -> tuple[int, bytes]: ...
+
+class Recorder:
+    def __init__(
+        self,
+        path: str | os.PathLike[str],
+        host: str = "",
+        devices: Optional[dict[int, str]] = None,
+        properties: Optional[dict[str, str]] = None,
+    ) -> None: ...
+    def record(self, data: bytes, recv_time_us: Optional[int] = None) -> None: ...
+    @property
+    def count(self) -> int: ...
+    def close(self) -> None: ...
+    def __enter__(self) -> Recorder: ...
+    def __exit__(
+        self,
+        type: Optional[type[BaseException]],
+        value: Optional[BaseException],
+        traceback: Optional[TracebackType],
+    ) -> None: ...
+
+def payload_fields(payload_type: int) -> list[str]: ...
@@ -0,0 +1,622 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Python bindings of IDTP for host-side analysis: header and frame parsing,
+//! streaming deframer, recording reader & writer. Standard payloads are
+//! decoded to NumPy arrays.
+
+use idtp::{
+    IDTP_HEADER_SIZE, IDTP_PACKET_MAX_SIZE, IDTP_PACKET_MIN_SIZE,
+    IDTP_PAYLOAD_MAX_SIZE, IDTP_PREAMBLE, IDTP_TRAILER, IdtpFrame, IdtpHeader,
+    payload::{PAYLOAD_REGISTRY, StandardPayload, payload_info},
+    recording::{Player, Record, Recorder as RustRecorder, Session},
+};
+use numpy::{IntoPyArray, PyArray1};
+use pyo3::{
+    create_exception,
+    exceptions::{PyIOError, PyValueError},
+    prelude::*,
+    types::{IntoPyDict, PyDict},
+};
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::PathBuf,
+};
+
+create_exception!(
+    idtp,
+    IdtpError,
+    PyValueError,
+    "IDTP frame or payload is malformed."
+);
+
+/// Convert Rust IDTP error to Python exception.
+fn idtp_error(error: idtp::IdtpError) -> PyErr {
+    IdtpError::new_err(error.to_string())
+}
+
+/// IDTP header.
+#[pyclass(frozen, module = "idtp")]
+#[derive(Debug, Clone)]
+struct Header {
+    /// Rust IDTP header.
+    header: IdtpHeader,
+    /// Raw operating mode byte (Rust header keeps only known modes).
+    mode: u8,
+}
+
+impl Header {
+    /// Construct header from raw bytes.
+    ///
+    /// # Parameters
+    /// - `bytes` - given at least `HEADER_SIZE` bytes.
+    ///
+    /// # Returns
+    /// - New `Header` struct.
+    fn from_raw(bytes: &[u8]) -> Self {
+        Self {
+            header: IdtpHeader::from(bytes),
+            mode: bytes[7],
+        }
+    }
+}
+
+#[pymethods]
+impl Header {
+    /// Convert big-endian bytes to IDTP header. Structure is not validated.
+    #[staticmethod]
+    fn from_bytes(data: &[u8]) -> PyResult<Self> {
+        if data.len() < IDTP_HEADER_SIZE {
+            return Err(idtp_error(idtp::IdtpError::FrameTooShort));
+        }
+
+        Ok(Self::from_raw(data))
+    }
+
+    /// Convert IDTP header to big-endian bytes.
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.header.as_bytes_be();
+        bytes[7] = self.mode;
+        bytes.to_vec()
+    }
+
+    #[getter]
+    fn preamble(&self) -> Vec<u8> {
+        self.header.preamble.to_vec()
+    }
+
+    #[getter]
+    fn version(&self) -> (u8, u8, u8) {
+        let version = self.header.version;
+        (version.major, version.minor, version.patch)
+    }
+
+    #[getter]
+    fn mode(&self) -> u8 {
+        self.mode
+    }
+
+    #[getter]
+    fn device_id(&self) -> u16 {
+        self.header.device_id
+    }
+
+    #[getter]
+    fn checksum(&self) -> u16 {
+        self.header.checksum
+    }
+
+    #[getter]
+    fn timestamp(&self) -> u32 {
+        self.header.timestamp
+    }
+
+    #[getter]
+    fn sequence(&self) -> u32 {
+        self.header.sequence
+    }
+
+    #[getter]
+    fn crc(&self) -> u32 {
+        self.header.crc
+    }
+
+    #[getter]
+    fn payload_size(&self) -> u32 {
+        self.header.payload_size
+    }
+
+    #[getter]
+    fn payload_type(&self) -> u8 {
+        self.header.payload_type
+    }
+
+    #[getter]
+    fn reserved(&self) -> Vec<u8> {
+        self.header.reserved.to_vec()
+    }
+
+    fn __repr__(&self) -> String {
+        let (major, minor, patch) = self.version();
+
+        format!(
+            "Header(version={major}.{minor}.{patch}, mode={:#04x}, \
+             device_id={:#06x}, timestamp={}, sequence={}, payload_size={}, \
+             payload_type={:#04x})",
+            self.mode,
+            self.device_id(),
+            self.timestamp(),
+            self.sequence(),
+            self.payload_size(),
+            self.payload_type()
+        )
+    }
+}
+
+/// IDTP frame.
+#[pyclass(frozen, module = "idtp")]
+#[derive(Debug, Clone)]
+struct Frame {
+    /// Rust IDTP frame.
+    frame: IdtpFrame,
+    /// Raw operating mode byte.
+    mode: u8,
+}
+
+impl Frame {
+    /// Construct frame from raw bytes.
+    ///
+    /// # Parameters
+    /// - `frame` - given Rust IDTP frame.
+    /// - `bytes` - given raw frame bytes.
+    ///
+    /// # Returns
+    /// - New `Frame` struct.
+    fn new(frame: IdtpFrame, bytes: &[u8]) -> Self {
+        Self {
+            frame,
+            mode: bytes.get(7).copied().unwrap_or_default(),
+        }
+    }
+}
+
+#[pymethods]
+impl Frame {
+    /// Parse raw IDTP frame validating its structure. Integrity is not
+    /// checked, use `verify` for that.
+    #[staticmethod]
+    fn parse(data: &[u8]) -> PyResult<Self> {
+        let frame = IdtpFrame::parse(data).map_err(idtp_error)?;
+        Ok(Self::new(frame, data))
+    }
+
+    /// Convert raw bytes to IDTP frame without structure validation.
+    #[staticmethod]
+    fn from_bytes(data: &[u8]) -> Self {
+        Self::new(IdtpFrame::from(data), data)
+    }
+
+    /// Convert IDTP frame to raw bytes.
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![0u8; self.frame.frame_size()];
+        let _ = self.frame.pack(&mut bytes);
+        bytes[7] = self.mode;
+        bytes
+    }
+
+    #[getter]
+    fn header(&self) -> Header {
+        Header {
+            header: self.frame.header(),
+            mode: self.mode,
+        }
+    }
+
+    #[getter]
+    fn payload(&self) -> Vec<u8> {
+        self.frame.payload().to_vec()
+    }
+
+    /// Verify frame integrity according to its operating mode.
+    fn verify(&self) -> bool {
+        self.frame.verify().is_ok()
+    }
+
+    /// Decode standard payload into array of its fields (see
+    /// `payload_fields`). Returns `None` for non-standard payload types.
+    fn decode<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> PyResult<Option<Bound<'py, PyArray1<f32>>>> {
+        let payload_type = self.frame.header().payload_type;
+
+        let Some(payload) =
+            StandardPayload::decode(payload_type, self.frame.payload())
+        else {
+            return Ok(None);
+        };
+
+        let mut values = Vec::new();
+        payload
+            .map_err(idtp_error)?
+            .for_each_field(|_, value| values.push(value));
+
+        Ok(Some(values.into_pyarray(py)))
+    }
+
+    fn __len__(&self) -> usize {
+        self.frame.frame_size()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Frame({}, payload={} bytes)",
+            self.header().__repr__(),
+            self.frame.payload_size()
+        )
+    }
+}
+
+/// Streaming deframer extracting IDTP frames from raw byte stream.
+#[pyclass(module = "idtp")]
+#[derive(Debug, Default)]
+struct Deframer {
+    /// Rust deframer.
+    deframer: idtp::Deframer,
+}
+
+#[pymethods]
+impl Deframer {
+    #[new]
+    fn py_new() -> Self {
+        Self::default()
+    }
+
+    /// Feed stream bytes and return complete frames found so far.
+    fn feed(&mut self, data: &[u8]) -> Vec<Vec<u8>> {
+        let mut frames = Vec::new();
+        self.deframer
+            .feed(data, |frame| frames.push(frame.to_vec()));
+        frames
+    }
+
+    /// Drop buffered bytes and reset statistics.
+    fn reset(&mut self) {
+        self.deframer.reset();
+    }
+
+    /// Number of resynchronizations after invalid data.
+    #[getter]
+    fn resyncs(&self) -> u64 {
+        self.deframer.resyncs()
+    }
+
+    /// Number of discarded bytes.
+    #[getter]
+    fn discarded(&self) -> u64 {
+        self.deframer.discarded()
+    }
+
+    /// Number of buffered bytes of incomplete frame.
+    #[getter]
+    fn buffered(&self) -> usize {
+        self.deframer.buffered()
+    }
+}
+
+/// Column arrays builder.
+#[derive(Debug, Default)]
+struct Columns {
+    /// Host receive timestamps.
+    recv_time_us: Vec<u64>,
+    /// IMU device identifiers.
+    device_id: Vec<u16>,
+    /// IMU timestamps.
+    timestamp: Vec<u32>,
+    /// Sequence numbers.
+    sequence: Vec<u32>,
+    /// Payload types.
+    payload_type: Vec<u8>,
+    /// Whether frame structure and integrity are valid.
+    valid: Vec<bool>,
+    /// Decoded standard payload fields in order of appearance.
+    fields: Vec<(&'static str, Vec<f32>)>,
+}
+
+impl Columns {
+    /// Append record row. Missing payload fields are filled with NaN.
+    ///
+    /// # Parameters
+    /// - `record` - given recording record.
+    fn push(&mut self, record: &Record) {
+        let row = self.recv_time_us.len();
+        let parsed = IdtpFrame::parse(&record.data);
+        let frame = parsed.unwrap_or_else(|_| record.frame());
+        let header = frame.header();
+
+        self.recv_time_us.push(record.recv_time_us);
+        self.device_id.push(header.device_id);
+        self.timestamp.push(header.timestamp);
+        self.sequence.push(header.sequence);
+        self.payload_type.push(header.payload_type);
+        self.valid.push(parsed.is_ok() && frame.verify().is_ok());
+
+        if parsed.is_ok()
+            && let Some(Ok(payload)) =
+                StandardPayload::decode(header.payload_type, frame.payload())
+        {
+            payload.for_each_field(|name, value| {
+                let index = match self.fields.iter().position(|f| f.0 == name) {
+                    Some(index) => index,
+                    None => {
+                        self.fields.push((name, vec![f32::NAN; row]));
+                        self.fields.len() - 1
+                    }
+                };
+
+                self.fields[index].1.push(value);
+            });
+        }
+
+        for (_, column) in &mut self.fields {
+            column.resize(row + 1, f32::NAN);
+        }
+    }
+
+    /// Convert to dictionary of NumPy arrays.
+    fn into_dict(self, py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("recv_time_us", self.recv_time_us.into_pyarray(py))?;
+        dict.set_item("device_id", self.device_id.into_pyarray(py))?;
+        dict.set_item("timestamp", self.timestamp.into_pyarray(py))?;
+        dict.set_item("sequence", self.sequence.into_pyarray(py))?;
+        dict.set_item("payload_type", self.payload_type.into_pyarray(py))?;
+        dict.set_item("valid", self.valid.into_pyarray(py))?;
+
+        for (name, column) in self.fields {
+            dict.set_item(name, column.into_pyarray(py))?;
+        }
+
+        Ok(dict)
+    }
+}
+
+/// IDTP recording (`.idtp` file) reader.
+#[pyclass(module = "idtp")]
+struct Recording {
+    /// Rust recording player.
+    player: Player<BufReader<File>>,
+}
+
+#[pymethods]
+impl Recording {
+    #[new]
+    fn py_new(path: PathBuf) -> PyResult<Self> {
+        Ok(Self {
+            player: Player::open(path)?,
+        })
+    }
+
+    /// Session metadata: host, start time, devices and properties.
+    #[getter]
+    fn session<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let session = self.player.session();
+        let devices: BTreeMap<u16, &str> = session
+            .devices
+            .iter()
+            .map(|device| (device.device_id, device.name.as_str()))
+            .collect();
+        let properties: Vec<(&str, &str)> = session
+            .properties
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect();
+
+        let dict = PyDict::new(py);
+        dict.set_item("host", &session.host)?;
+        dict.set_item("start_time_us", session.start_time_us)?;
+        dict.set_item("devices", devices)?;
+        dict.set_item("properties", properties.into_py_dict(py)?)?;
+        Ok(dict)
+    }
+
+    /// Whether recording ends with incomplete record.
+    #[getter]
+    fn is_truncated(&self) -> bool {
+        self.player.is_truncated()
+    }
+
+    /// Whether footer index was missing or damaged and had to be rebuilt.
+    #[getter]
+    fn is_index_rebuilt(&self) -> bool {
+        self.player.is_index_rebuilt()
+    }
+
+    /// Move to the first record.
+    fn rewind(&mut self) -> PyResult<()> {
+        Ok(self.player.rewind()?)
+    }
+
+    /// Move to the first record received at or after given time.
+    fn seek(&mut self, recv_time_us: u64) -> PyResult<()> {
+        Ok(self.player.seek(recv_time_us)?)
+    }
+
+    /// Read records from current position up to the end into column
+    /// arrays, optionally keeping only given payload type or device.
+    #[pyo3(signature = (payload_type=None, device_id=None))]
+    fn columns<'py>(
+        &mut self,
+        py: Python<'py>,
+        payload_type: Option<u8>,
+        device_id: Option<u16>,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let player = &mut self.player;
+
+        let columns = py.detach(|| {
+            let mut columns = Columns::default();
+
+            for record in player {
+                let record = record?;
+                let header = record.frame().header();
+
+                if payload_type.is_some_and(|t| t != header.payload_type)
+                    || device_id.is_some_and(|id| id != header.device_id)
+                {
+                    continue;
+                }
+
+                columns.push(&record);
+            }
+
+            Ok::<_, std::io::Error>(columns)
+        })?;
+
+        columns.into_dict(py)
+    }
+
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Next record as `(recv_time_us, frame_bytes)` tuple.
+    fn __next__(&mut self) -> PyResult<Option<(u64, Vec<u8>)>> {
+        Ok(self
+            .player
+            .next_record()?
+            .map(|record| (record.recv_time_us, record.data)))
+    }
+}
+
+/// IDTP recording (`.idtp` file) writer.
+#[pyclass(module = "idtp")]
+struct Recorder {
+    /// Rust recorder, `None` after recording is closed.
+    recorder: Option<RustRecorder<BufWriter<File>>>,
+}
+
+impl Recorder {
+    /// Get open recorder.
+    fn recorder(&mut self) -> PyResult<&mut RustRecorder<BufWriter<File>>> {
+        self.recorder
+            .as_mut()
+            .ok_or_else(|| PyIOError::new_err("Recording is closed"))
+    }
+}
+
+#[pymethods]
+impl Recorder {
+    #[new]
+    #[pyo3(signature = (path, host="", devices=None, properties=None))]
+    fn py_new(
+        path: PathBuf,
+        host: &str,
+        devices: Option<BTreeMap<u16, String>>,
+        properties: Option<BTreeMap<String, String>>,
+    ) -> PyResult<Self> {
+        let mut session = Session::new(host);
+
+        for (device_id, name) in devices.unwrap_or_default() {
+            session.add_device(device_id, &name);
+        }
+
+        for (key, value) in properties.unwrap_or_default() {
+            session.set_property(&key, &value);
+        }
+
+        Ok(Self {
+            recorder: Some(RustRecorder::create(path, &session)?),
+        })
+    }
+
+    /// Record raw frame bytes, receive time defaults to current host time.
+    #[pyo3(signature = (data, recv_time_us=None))]
+    fn record(
+        &mut self,
+        data: &[u8],
+        recv_time_us: Option<u64>,
+    ) -> PyResult<()> {
+        let recorder = self.recorder()?;
+
+        match recv_time_us {
+            Some(time) => recorder.record(time, data)?,
+            None => recorder.record_now(data)?,
+        }
+
+        Ok(())
+    }
+
+    /// Number of records written.
+    #[getter]
+    fn count(&self) -> usize {
+        self.recorder.as_ref().map_or(0, RustRecorder::count)
+    }
+
+    /// Write footer index and close recording.
+    fn close(&mut self) -> PyResult<()> {
+        if let Some(recorder) = self.recorder.take() {
+            recorder.finish()?;
+        }
+
+        Ok(())
+    }
+
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __exit__(
+        &mut self,
+        _type: Py<PyAny>,
+        _value: Py<PyAny>,
+        _traceback: Py<PyAny>,
+    ) -> PyResult<()> {
+        self.close()
+    }
+}
+
+/// Get names of fields of standard payload type in `Frame.decode` order.
+#[pyfunction]
+fn payload_fields(payload_type: u8) -> PyResult<Vec<&'static str>> {
+    let info = payload_info(payload_type)
+        .ok_or_else(|| PyValueError::new_err("Unknown payload type"))?;
+    let zeros = vec![0u8; info.size.unwrap_or_default()];
+
+    let mut names = Vec::new();
+
+    if let Some(Ok(payload)) = StandardPayload::decode(payload_type, &zeros) {
+        payload.for_each_field(|name, _| names.push(name));
+    }
+
+    Ok(names)
+}
+
+/// Python module.
+#[pymodule]
+fn _idtp(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+
+    m.add("IdtpError", py.get_type::<IdtpError>())?;
+    m.add("PREAMBLE", IDTP_PREAMBLE.to_vec())?;
+    m.add("TRAILER", IDTP_TRAILER.to_vec())?;
+    m.add("HEADER_SIZE", IDTP_HEADER_SIZE)?;
+    m.add("PACKET_MIN_SIZE", IDTP_PACKET_MIN_SIZE)?;
+    m.add("PACKET_MAX_SIZE", IDTP_PACKET_MAX_SIZE)?;
+    m.add("PAYLOAD_MAX_SIZE", IDTP_PAYLOAD_MAX_SIZE)?;
+
+    let payload_types: BTreeMap<&str, u8> = PAYLOAD_REGISTRY
+        .iter()
+        .map(|info| (info.name, info.payload_type))
+        .collect();
+    m.add("PAYLOAD_TYPES", payload_types)?;
+
+    m.add_class::<Header>()?;
+    m.add_class::<Frame>()?;
+    m.add_class::<Deframer>()?;
+    m.add_class::<Recording>()?;
+    m.add_class::<Recorder>()?;
+    m.add_function(wrap_pyfunction!(payload_fields, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,148 @@
+# SPDX-License-Identifier: Apache-2.0.
+# Copyright (C) 2025-present idtp project and contributors.
+
+"""Python bindings tests (run with pytest against built wheel)."""
+
+import json
+import math
+import struct
+from pathlib import Path
+
+import numpy as np
+import pytest
+
+import idtp
+
+VECTORS = Path(__file__).parents[4] / "res" / "vectors" / "frames.json"
+
+
+def load_vectors():
+    corpus = json.loads(VECTORS.read_text())
+    assert corpus["format"] == 1
+
+    for vector in corpus["vectors"]:
+        yield vector, bytes.fromhex(vector["bytes"])
+
+
+def build_frame(payload_type, values, device_id=1, sequence=0, timestamp=0):
+    """Build Normal mode frame with big-endian float payload."""
+    payload = struct.pack(f">{len(values)}f", *values)
+    header = bytearray(
+        struct.pack(
+            ">4s3BBHHIIIIB3s",
+            idtp.PREAMBLE,
+            1, 0, 0,
+            0x00,
+            device_id,
+            0,
+            timestamp,
+            sequence,
+            0,
+            len(payload),
+            payload_type,
+            b"\0\0\0",
+        )
+    )
+    checksum = sum(header + payload) & 0xFFFF
+    header[10:12] = struct.pack(">H", checksum)
+    return bytes(header) + payload + idtp.TRAILER
+
+
+def test_parse_golden_vectors():
+    for vector, data in load_vectors():
+        if vector["parse"] != "Ok":
+            with pytest.raises(idtp.IdtpError):
+                idtp.Frame.parse(data)
+            continue
+
+        frame = idtp.Frame.parse(data)
+        header = vector["header"]
+
+        assert frame.header.version == tuple(header["version"])
+        assert frame.header.mode == header["mode"]
+        assert frame.header.device_id == header["device_id"]
+        assert frame.header.sequence == header["sequence"]
+        assert frame.header.payload_type == header["payload_type"]
+        assert frame.header.reserved == bytes.fromhex(header["reserved"])
+        assert frame.payload == bytes.fromhex(vector["payload"])
+        assert frame.verify() == vector["integrity"]
+        assert frame.to_bytes() == data
+        assert len(frame) == len(data)
+        assert idtp.Header.from_bytes(data).to_bytes() == data[:32]
+
+
+def test_decode_payload():
+    imu6 = idtp.PAYLOAD_TYPES["imu6"]
+    frame = idtp.Frame.parse(build_frame(imu6, [1, 2, 3, 4, 5, 6]))
+
+    assert frame.verify()
+    assert idtp.payload_fields(imu6) == [
+        "acc_x", "acc_y", "acc_z", "gyr_x", "gyr_y", "gyr_z",
+    ]
+
+    values = frame.decode()
+    assert values.dtype == np.float32
+    np.testing.assert_array_equal(values, [1, 2, 3, 4, 5, 6])
+
+    assert idtp.Frame.parse(build_frame(0x00, [1.0])).decode() is None
+
+    with pytest.raises(idtp.IdtpError):
+        idtp.Frame.parse(build_frame(imu6, [1.0])).decode()
+
+    with pytest.raises(ValueError):
+        idtp.payload_fields(0x00)
+
+
+def test_deframer():
+    frames = [
+        build_frame(idtp.PAYLOAD_TYPES["gyroscope"], [i, 0, 0], sequence=i)
+        for i in range(10)
+    ]
+    stream = b"garbage" + b"".join(frames)
+    deframer = idtp.Deframer()
+
+    found = []
+    for offset in range(0, len(stream), 7):
+        found += deframer.feed(stream[offset:offset + 7])
+
+    assert found == frames
+    assert deframer.discarded == len(b"garbage")
+    assert deframer.buffered == 0
+
+
+def test_recording_columns(tmp_path):
+    path = tmp_path / "session.idtp"
+    acc = idtp.PAYLOAD_TYPES["accelerometer"]
+    quaternion = idtp.PAYLOAD_TYPES["quaternion"]
+
+    with idtp.Recorder(path, "host", {1: "left", 2: "right"}) as recorder:
+        for i in range(1000):
+            recorder.record(build_frame(acc, [i, 0, 0], 1, i), 1000 + i)
+            recorder.record(build_frame(quaternion, [1, 0, 0, 0], 2, i), 1000 + i)
+
+        recorder.record(b"IDTP broken", 5000)
+        assert recorder.count == 2001
+
+    recording = idtp.Recording(path)
+    session = recording.session
+    assert session["host"] == "host"
+    assert session["devices"] == {1: "left", 2: "right"}
+    assert not recording.is_truncated
+
+    columns = recording.columns()
+    assert len(columns["recv_time_us"]) == 2001
+    assert columns["recv_time_us"].dtype == np.uint64
+    assert columns["valid"].sum() == 2000
+    assert not columns["valid"][-1]
+    assert math.isnan(columns["acc_x"][1]) and math.isnan(columns["w"][0])
+    np.testing.assert_array_equal(columns["acc_x"][0:2000:2], np.arange(1000))
+
+    recording.seek(1500)
+    columns = recording.columns(payload_type=acc, device_id=1)
+    np.testing.assert_array_equal(columns["sequence"], np.arange(500, 1000))
+    assert "w" not in columns
+
+    recording.rewind()
+    recv_time_us, data = next(iter(recording))
+    assert recv_time_us == 1000
+    assert idtp.Frame.parse(data).header.device_id == 1
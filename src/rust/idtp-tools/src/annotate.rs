@@ -0,0 +1,424 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Field-by-field annotation of raw IDTP frames.
+
+use idtp::{
+    IDTP_FLAG_AUTH, IDTP_FLAG_EXTENSIONS, IDTP_FLAG_RELIABLE,
+    IDTP_FLAG_TIMESTAMP_EXT, IDTP_HEADER_SIZE, IDTP_PREAMBLE,
+    IDTP_TIMESTAMP_EXT_SIZE, IDTP_TRAILER, IDTP_TRAILER_SIZE, IdtpError,
+    IdtpFrame, IdtpHeader, Integrity, Mode, TimestampExtension, TimestampUnit,
+    extension::ExtensionBlock,
+    fec,
+    payload::{StandardPayload, payload_info},
+};
+
+/// Size of frame authentication tag and Secure mode tag in bytes (the same
+/// as `IDTP_AUTH_TAG_SIZE` and `IDTP_TAG_SIZE` of optional `idtp` features).
+const TAG_SIZE: usize = 16;
+
+/// Layout of IDTP header fields: name, offset and size in bytes. Timestamp
+/// unit is annotated separately from the rest of flags byte.
+pub const HEADER_FIELDS: [(&str, usize, usize); 14] = [
+    ("preamble", 0, 4),
+    ("version", 4, 3),
+    ("mode", 7, 1),
+    ("device_id", 8, 2),
+    ("checksum", 10, 2),
+    ("timestamp", 12, 4),
+    ("sequence", 16, 4),
+    ("crc", 20, 4),
+    ("payload_size", 24, 4),
+    ("payload_type", 28, 1),
+    ("flags", 29, 1),
+    ("timestamp_unit", 29, 1),
+    ("key_id", 30, 1),
+    ("reserved", 31, 1),
+];
+
+/// Names of header flags.
+const FLAG_NAMES: [(u8, &str); 4] = [
+    (IDTP_FLAG_AUTH, "auth"),
+    (IDTP_FLAG_EXTENSIONS, "extensions"),
+    (IDTP_FLAG_TIMESTAMP_EXT, "timestamp_ext"),
+    (IDTP_FLAG_RELIABLE, "reliable"),
+];
+
+/// Annotated frame field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    /// Offset of the field from the start of the frame.
+    pub offset: usize,
+    /// Field name.
+    pub name: &'static str,
+    /// Raw field bytes.
+    pub raw: Vec<u8>,
+    /// Human-readable decoded value.
+    pub value: String,
+}
+
+/// Annotated raw IDTP frame.
+#[derive(Debug, Clone)]
+pub struct Annotation {
+    /// Raw frame size in bytes.
+    pub size: usize,
+    /// Annotated fields in order of appearance.
+    pub fields: Vec<Field>,
+    /// Structural parse error (`None` if frame is well-formed).
+    pub error: Option<IdtpError>,
+    /// Decoded header (`None` if frame is shorter than header).
+    pub header: Option<IdtpHeader>,
+    /// Integrity verification result (`None` if header is missing).
+    pub integrity: Option<Integrity>,
+    /// Payload section bytes (including extension block, timestamp
+    /// extension, tags and parity).
+    pub payload: Vec<u8>,
+    /// Decoded standard payload data (`None` if payload type is not standard
+    /// or payload is encrypted).
+    pub decoded: Option<Result<StandardPayload, IdtpError>>,
+}
+
+impl Annotation {
+    /// Check whether frame is well-formed and its integrity is confirmed.
+    ///
+    /// # Returns
+    /// - `true`  - if frame is valid.
+    /// - `false` - otherwise.
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none() && self.integrity.is_some_and(|i| i.is_ok())
+    }
+
+    /// Append annotated field.
+    ///
+    /// # Parameters
+    /// - `offset` - given offset of the field from the start of the frame.
+    /// - `name` - given field name.
+    /// - `raw` - given raw field bytes.
+    /// - `value` - given human-readable decoded value.
+    fn push(
+        &mut self,
+        offset: usize,
+        name: &'static str,
+        raw: &[u8],
+        value: String,
+    ) {
+        self.fields.push(Field {
+            offset,
+            name,
+            raw: raw.to_vec(),
+            value,
+        });
+    }
+}
+
+/// Annotate raw IDTP frame. Malformed frames are annotated as far as
+/// possible.
+///
+/// # Parameters
+/// - `data` - given raw frame bytes.
+///
+/// # Returns
+/// - Frame annotation.
+pub fn annotate(data: &[u8]) -> Annotation {
+    let error = IdtpFrame::parse(data).err();
+    let mut annotation = Annotation {
+        size: data.len(),
+        fields: Vec::new(),
+        error,
+        header: None,
+        integrity: None,
+        payload: Vec::new(),
+        decoded: None,
+    };
+
+    if data.len() < IDTP_HEADER_SIZE {
+        annotation.fields.push(Field {
+            offset: 0,
+            name: "truncated",
+            raw: data.to_vec(),
+            value: format!("{} bytes", data.len()),
+        });
+        return annotation;
+    }
+
+    let header = IdtpHeader::from(&data[..IDTP_HEADER_SIZE]);
+
+    for (name, offset, size) in HEADER_FIELDS {
+        let raw = &data[offset..offset + size];
+        annotation.fields.push(Field {
+            offset,
+            name,
+            raw: raw.to_vec(),
+            value: header_value(name, raw, &header),
+        });
+    }
+
+    let payload_end = if annotation.error.is_none() {
+        data.len() - IDTP_TRAILER_SIZE
+    } else {
+        let declared =
+            IDTP_HEADER_SIZE.saturating_add(header.payload_size as usize);
+        let available = data.len().saturating_sub(IDTP_TRAILER_SIZE);
+        declared.min(available).max(IDTP_HEADER_SIZE)
+    };
+
+    let payload = &data[IDTP_HEADER_SIZE..payload_end];
+    annotate_payload(&mut annotation, &header, payload);
+
+    if payload_end < data.len() {
+        let trailer = &data[payload_end..];
+        let status = if trailer == IDTP_TRAILER { "ok" } else { "bad" };
+        annotation.fields.push(Field {
+            offset: payload_end,
+            name: "trailer",
+            raw: trailer.to_vec(),
+            value: format!("{} {status}", quoted(trailer)),
+        });
+    }
+
+    // Raw header bytes keep values lost by `IdtpHeader` (e.g. unknown mode).
+    let raw_header = data[..IDTP_HEADER_SIZE].try_into().unwrap();
+    annotation.integrity = Some(Integrity::verify_raw(raw_header, payload));
+    annotation.header = Some(header);
+    annotation.payload = payload.to_vec();
+    annotation
+}
+
+/// Annotate payload section: extension block, timestamp extension, payload
+/// data, authentication or Secure mode tag and FEC parity. Only payload data
+/// is decoded.
+///
+/// # Parameters
+/// - `annotation` - given frame annotation to append fields to.
+/// - `header` - given frame header.
+/// - `payload` - given payload section bytes.
+fn annotate_payload(
+    annotation: &mut Annotation,
+    header: &IdtpHeader,
+    payload: &[u8],
+) {
+    let flags = header.flags();
+    let secure = header.mode == Mode::Secure;
+    let mut end = payload.len();
+    let mut tag = None;
+    let mut parity = None;
+
+    if header.mode == Mode::Fec {
+        match fec::message_size(IDTP_HEADER_SIZE + payload.len()) {
+            Some(size) => {
+                end = size - IDTP_HEADER_SIZE;
+                parity = Some(end);
+            }
+            None => annotation.decoded = Some(Err(IdtpError::InvalidPayload)),
+        }
+    }
+
+    if (secure || flags & IDTP_FLAG_AUTH != 0) && end >= TAG_SIZE {
+        end -= TAG_SIZE;
+        tag = Some(if secure { "tag" } else { "auth_tag" });
+    }
+
+    let mut offset = 0;
+
+    if secure {
+        let ciphertext = &payload[..end];
+        let value = format!("{} bytes (encrypted)", ciphertext.len());
+        annotation.push(IDTP_HEADER_SIZE, "ciphertext", ciphertext, value);
+    } else {
+        if flags & IDTP_FLAG_EXTENSIONS != 0 {
+            match ExtensionBlock::parse(&payload[..end]) {
+                Ok(block) => {
+                    let raw = &payload[..block.size()];
+                    let value = extensions_value(&block);
+                    annotation.push(IDTP_HEADER_SIZE, "extensions", raw, value);
+                    offset = block.size();
+                }
+                Err(error) => annotation.decoded = Some(Err(error)),
+            }
+        }
+
+        if flags & IDTP_FLAG_TIMESTAMP_EXT != 0 && annotation.decoded.is_none()
+        {
+            match payload[offset..end].first_chunk::<IDTP_TIMESTAMP_EXT_SIZE>()
+            {
+                Some(raw) => {
+                    let extension = TimestampExtension::from_bytes(raw);
+                    let value = timestamp_ext_value(&extension, header);
+                    annotation.push(
+                        IDTP_HEADER_SIZE + offset,
+                        "timestamp_ext",
+                        raw,
+                        value,
+                    );
+                    offset += IDTP_TIMESTAMP_EXT_SIZE;
+                }
+                None => {
+                    annotation.decoded = Some(Err(IdtpError::InvalidPayload));
+                }
+            }
+        }
+
+        let data = &payload[offset..end];
+
+        if annotation.decoded.is_none() {
+            annotation.decoded =
+                StandardPayload::decode(header.payload_type, data);
+        }
+
+        let value = payload_value(data, annotation.decoded.as_ref());
+        annotation.push(IDTP_HEADER_SIZE + offset, "payload", data, value);
+    }
+
+    if let Some(name) = tag {
+        let raw = &payload[end..end + TAG_SIZE];
+        let value = format!("{TAG_SIZE} bytes");
+        annotation.push(IDTP_HEADER_SIZE + end, name, raw, value);
+    }
+
+    if let Some(start) = parity {
+        let raw = &payload[start..];
+        let value = format!("{} bytes (Reed-Solomon)", raw.len());
+        annotation.push(IDTP_HEADER_SIZE + start, "parity", raw, value);
+    }
+}
+
+/// Get name of operating mode.
+///
+/// # Parameters
+/// - `mode` - given raw operating mode byte.
+///
+/// # Returns
+/// - Operating mode name.
+pub fn mode_name(mode: u8) -> &'static str {
+    match mode {
+        0x00 => "Normal",
+        0x01 => "Safety",
+        0x02 => "Secure",
+        0x03 => "Fec",
+        _ => "Unknown",
+    }
+}
+
+/// Format header field value.
+fn header_value(name: &str, raw: &[u8], header: &IdtpHeader) -> String {
+    match name {
+        "preamble" => {
+            let status = if raw == IDTP_PREAMBLE { "ok" } else { "bad" };
+            format!("{} {status}", quoted(raw))
+        }
+        "version" => format!("{}.{}.{}", raw[0], raw[1], raw[2]),
+        "mode" => match mode_name(raw[0]) {
+            "Unknown" => format!("Unknown (0x{:02x})", raw[0]),
+            name => name.into(),
+        },
+        "device_id" => {
+            let device_id = header.device_id;
+            format!("{device_id} (0x{device_id:04x})")
+        }
+        "checksum" => format!("0x{:04x}", { header.checksum }),
+        "timestamp" => {
+            let unit = unit_name(header.timestamp_unit());
+            format!("{} {unit}", { header.timestamp })
+        }
+        "sequence" => format!("{}", { header.sequence }),
+        "crc" => format!("0x{:08x}", { header.crc }),
+        "payload_size" => format!("{}", { header.payload_size }),
+        "payload_type" => {
+            let payload_type = header.payload_type;
+            let name = payload_info(payload_type).map_or("vendor", |i| i.name);
+            format!("0x{payload_type:02x} ({name})")
+        }
+        "flags" => {
+            let names: Vec<&str> = FLAG_NAMES
+                .iter()
+                .filter(|(flag, _)| raw[0] & flag != 0)
+                .map(|(_, name)| *name)
+                .collect();
+
+            if names.is_empty() {
+                format!("0x{:02x}", raw[0])
+            } else {
+                format!("0x{:02x} ({})", raw[0], names.join(" "))
+            }
+        }
+        "timestamp_unit" => unit_name(header.timestamp_unit()).into(),
+        "key_id" => format!("{}", raw[0]),
+        _ => {
+            if raw.iter().all(|byte| *byte == 0) {
+                "zero".into()
+            } else {
+                "non-zero".into()
+            }
+        }
+    }
+}
+
+/// Get short name of timestamp unit.
+///
+/// # Parameters
+/// - `unit` - given timestamp unit.
+///
+/// # Returns
+/// - Timestamp unit name.
+fn unit_name(unit: TimestampUnit) -> &'static str {
+    match unit {
+        TimestampUnit::Milliseconds => "ms",
+        TimestampUnit::Microseconds => "us",
+        TimestampUnit::Nanoseconds => "ns",
+        TimestampUnit::Ticks => "ticks",
+    }
+}
+
+/// Format timestamp extension value with extended 64-bit timestamp.
+fn timestamp_ext_value(
+    extension: &TimestampExtension,
+    header: &IdtpHeader,
+) -> String {
+    let timestamp = (u64::from(extension.timestamp_high) << 32)
+        | u64::from(header.timestamp);
+    let unit = unit_name(header.timestamp_unit());
+    let mut value = format!("timestamp={timestamp} {unit}");
+
+    if header.timestamp_unit() == TimestampUnit::Ticks {
+        value += &format!(" tick_rate={} Hz", extension.tick_rate);
+    }
+
+    value
+}
+
+/// Format extension block value.
+fn extensions_value(block: &ExtensionBlock) -> String {
+    let tlvs: Vec<String> = block
+        .iter()
+        .map(|tlv| format!("0x{:02x}[{}]", tlv.tlv_type, tlv.value.len()))
+        .collect();
+
+    if tlvs.is_empty() {
+        "empty".into()
+    } else {
+        tlvs.join(" ")
+    }
+}
+
+/// Format payload value.
+fn payload_value(
+    payload: &[u8],
+    decoded: Option<&Result<StandardPayload, IdtpError>>,
+) -> String {
+    match decoded {
+        Some(Ok(decoded)) => {
+            let mut fields = Vec::new();
+            decoded.for_each_field(|name, value| {
+                fields.push(format!("{name}={value}"));
+            });
+            fields.join(" ")
+        }
+        Some(Err(error)) => format!("{} bytes ({error})", payload.len()),
+        None => format!("{} bytes", payload.len()),
+    }
+}
+
+/// Format bytes as quoted ASCII string with escapes.
+fn quoted(bytes: &[u8]) -> String {
+    format!("\"{}\"", bytes.escape_ascii())
+}
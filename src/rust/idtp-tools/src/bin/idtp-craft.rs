@@ -0,0 +1,156 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! `idtp-craft` - builder of arbitrary (including malformed) IDTP frames.
+
+use clap::Parser;
+use idtp_tools::{
+    craft::{Corruption, FrameSpec, parse_int},
+    hex,
+};
+use std::{
+    fs,
+    io::{self, Write},
+    net::UdpSocket,
+    path::Path,
+    process,
+};
+
+/// Build IDTP frame from flags and/or TOML/JSON description.
+///
+/// Flags override values from the description file. Integrity fields are
+/// calculated unless overridden, corruptions are applied after packing.
+#[derive(Debug, Parser)]
+#[command(name = "idtp-craft", version)]
+struct Args {
+    /// Frame description file (`.toml` or `.json`).
+    #[arg(long, short = 'f', value_name = "FILE")]
+    spec: Option<String>,
+    /// Preamble bytes (hex).
+    #[arg(long, value_name = "HEX")]
+    preamble: Option<String>,
+    /// Protocol version (MAJOR.MINOR.PATCH).
+    #[arg(long, value_name = "VERSION")]
+    protocol_version: Option<String>,
+    /// Operating mode: normal, safety, secure, fec or raw byte value.
+    #[arg(long)]
+    mode: Option<String>,
+    /// IMU device identifier.
+    #[arg(long, value_parser = parse_int::<u16>)]
+    device_id: Option<u16>,
+    /// Checksum override.
+    #[arg(long, value_parser = parse_int::<u16>)]
+    checksum: Option<u16>,
+    /// Timestamp.
+    #[arg(long, value_parser = parse_int::<u32>)]
+    timestamp: Option<u32>,
+    /// Sequence number.
+    #[arg(long, value_parser = parse_int::<u32>)]
+    sequence: Option<u32>,
+    /// CRC override.
+    #[arg(long, value_parser = parse_int::<u32>)]
+    crc: Option<u32>,
+    /// Payload size override.
+    #[arg(long, value_parser = parse_int::<u32>)]
+    payload_size: Option<u32>,
+    /// Payload type.
+    #[arg(long, value_parser = parse_int::<u8>)]
+    payload_type: Option<u8>,
+    /// Reserved bytes (hex, 3 bytes).
+    #[arg(long, value_name = "HEX")]
+    reserved: Option<String>,
+    /// Payload bytes (hex).
+    #[arg(long, value_name = "HEX")]
+    payload: Option<String>,
+    /// Trailer bytes (hex, any length).
+    #[arg(long, value_name = "HEX")]
+    trailer: Option<String>,
+    /// Bytes to append after the trailer (hex).
+    #[arg(long, value_name = "HEX")]
+    append: Option<String>,
+    /// Corrupt byte: OFFSET (invert), OFFSET^MASK or OFFSET=VALUE.
+    #[arg(long, value_name = "SPEC")]
+    corrupt: Vec<Corruption>,
+    /// Keep only given number of first bytes.
+    #[arg(long, value_parser = parse_int::<usize>)]
+    truncate: Option<usize>,
+    /// Output file (`-` for standard output).
+    #[arg(long, short, default_value = "-", conflicts_with = "udp")]
+    output: String,
+    /// Send frame as UDP datagram to given address.
+    #[arg(long, value_name = "ADDRESS")]
+    udp: Option<String>,
+    /// Write frame as hex text instead of raw bytes.
+    #[arg(long)]
+    hex: bool,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    if let Err(error) = run(args) {
+        eprintln!("idtp-craft: {error}");
+        process::exit(1);
+    }
+}
+
+/// Build frame and write it to the selected destination.
+fn run(args: Args) -> Result<(), String> {
+    let spec = load_spec(args.spec.as_deref())?;
+    let spec = FrameSpec {
+        preamble: args.preamble.or(spec.preamble),
+        version: args.protocol_version.or(spec.version),
+        mode: args.mode.or(spec.mode),
+        device_id: args.device_id.or(spec.device_id),
+        checksum: args.checksum.or(spec.checksum),
+        timestamp: args.timestamp.or(spec.timestamp),
+        sequence: args.sequence.or(spec.sequence),
+        crc: args.crc.or(spec.crc),
+        payload_size: args.payload_size.or(spec.payload_size),
+        payload_type: args.payload_type.or(spec.payload_type),
+        reserved: args.reserved.or(spec.reserved),
+        payload: args.payload.or(spec.payload),
+        trailer: args.trailer.or(spec.trailer),
+        append: args.append.or(spec.append),
+        corrupt: [spec.corrupt, args.corrupt].concat(),
+        truncate: args.truncate.or(spec.truncate),
+    };
+
+    let mut bytes = spec.build()?;
+
+    if args.hex {
+        bytes = format!("{}\n", hex::encode(&bytes, "")).into_bytes();
+    }
+
+    let result = if let Some(address) = &args.udp {
+        UdpSocket::bind("0.0.0.0:0")
+            .and_then(|socket| socket.send_to(&bytes, address))
+            .map(|_| ())
+    } else if args.output == "-" {
+        io::stdout().write_all(&bytes)
+    } else {
+        fs::write(&args.output, &bytes)
+    };
+
+    result.map_err(|e| e.to_string())
+}
+
+/// Load frame description file.
+fn load_spec(path: Option<&str>) -> Result<FrameSpec, String> {
+    let Some(path) = path else {
+        return Ok(FrameSpec::default());
+    };
+
+    let string =
+        fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+    let is_json = Path::new(path)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+
+    if is_json {
+        FrameSpec::from_json(&string)
+    } else {
+        FrameSpec::from_toml(&string)
+    }
+    .map_err(|e| format!("{path}: {e}"))
+}
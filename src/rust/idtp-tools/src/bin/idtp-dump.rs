@@ -0,0 +1,309 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! `idtp-dump` - annotated hex dump and decoded view of IDTP frames.
+
+use clap::Parser;
+use idtp::{IdtpHeader, stats::StreamStats};
+use idtp_tools::{
+    annotate::{Annotation, annotate},
+    hex,
+    source::Source,
+};
+use serde_json::{Value, json};
+use std::{
+    collections::BTreeMap,
+    io::{self, BufWriter, Write},
+    process,
+};
+
+/// Print IDTP frames with field-by-field annotation.
+///
+/// Input can be a raw byte stream, an `.idtp` recording or a pcap/pcapng
+/// capture - the format is detected automatically.
+#[derive(Debug, Parser)]
+#[command(name = "idtp-dump", version)]
+struct Args {
+    /// Input file path (`-` for standard input).
+    #[arg(default_value = "-", conflicts_with = "udp")]
+    input: String,
+    /// Listen for UDP datagrams on given address (e.g. 0.0.0.0:5005).
+    #[arg(long, value_name = "ADDRESS")]
+    udp: Option<String>,
+    /// Extract frames only from given UDP port (pcap/pcapng input).
+    #[arg(long)]
+    port: Option<u16>,
+    /// Print JSON Lines instead of annotated text.
+    #[arg(long)]
+    json: bool,
+    /// Stop after given number of frames.
+    #[arg(long, value_name = "N")]
+    count: Option<usize>,
+    /// Print link statistics per device to standard error at the end.
+    #[arg(long)]
+    stats: bool,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    if let Err(error) = run(&args) {
+        eprintln!("idtp-dump: {error}");
+        process::exit(1);
+    }
+}
+
+/// Dump frames from the selected source.
+fn run(args: &Args) -> io::Result<()> {
+    let mut source = match &args.udp {
+        Some(address) => Source::udp(address)?,
+        None => Source::open(&args.input, args.port)?,
+    };
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let mut index = 0usize;
+    let mut stats = BTreeMap::<u16, StreamStats>::new();
+    let mut last_device = None;
+
+    while args.count.is_none_or(|count| index < count) {
+        let Some(frame) = source.next_frame()? else {
+            break;
+        };
+
+        let resyncs = source.take_resyncs();
+
+        if resyncs > 0 && !args.json {
+            writeln!(out, "-- stream resynchronized {resyncs} time(s)")?;
+        }
+
+        let annotation = annotate(&frame.data);
+
+        // Resyncs are attributed to device of the frame found after them.
+        if args.stats
+            && let (Some(header), Some(integrity)) =
+                (annotation.header, annotation.integrity)
+        {
+            let device = stats.entry(header.device_id).or_default();
+            device.record_resyncs(resyncs);
+            device.record(&header, &integrity, frame.time_us);
+            last_device = Some(header.device_id);
+        }
+
+        let result = if args.json {
+            let line = to_json(index, frame.time_us, &frame.data, &annotation);
+            writeln!(out, "{line}")
+        } else {
+            print_text(&mut out, index, frame.time_us, &annotation)
+        };
+
+        // Stop quietly if output is closed (e.g. piped into `head`).
+        match result {
+            Err(error) if error.kind() == io::ErrorKind::BrokenPipe => {
+                return Ok(());
+            }
+            result => result?,
+        }
+
+        out.flush()?;
+        index += 1;
+    }
+
+    // Resyncs after the last frame are attributed to its device.
+    let resyncs = source.take_resyncs();
+
+    if resyncs > 0 && !args.json {
+        writeln!(out, "-- stream resynchronized {resyncs} time(s)")?;
+        out.flush()?;
+    }
+
+    if let Some(device) = last_device.and_then(|id| stats.get_mut(&id)) {
+        device.record_resyncs(resyncs);
+    }
+
+    if args.stats {
+        for (device_id, device) in &stats {
+            eprintln!("device 0x{device_id:04x}: {device}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Print annotated frame as text.
+fn print_text<W: Write>(
+    out: &mut W,
+    index: usize,
+    time_us: Option<u64>,
+    annotation: &Annotation,
+) -> io::Result<()> {
+    let status = match annotation.error {
+        Some(error) => format!("MALFORMED: {error}"),
+        None if annotation.is_ok() => "ok".into(),
+        None => "INTEGRITY FAILURE".into(),
+    };
+
+    write!(out, "frame #{index}")?;
+
+    if let Some(time_us) = time_us {
+        write!(out, " @ {}.{:06}", time_us / 1_000_000, time_us % 1_000_000)?;
+    }
+
+    writeln!(out, " ({} bytes) [{status}]", annotation.size)?;
+    writeln!(out, "  {:<8}{:<14}{:<26}value", "offset", "field", "raw")?;
+
+    for field in &annotation.fields {
+        let raw = if field.raw.len() <= 8 {
+            hex::encode(&field.raw, " ")
+        } else {
+            format!("({} bytes)", field.raw.len())
+        };
+
+        writeln!(
+            out,
+            "  0x{:04x}  {:<14}{:<26}{}",
+            field.offset, field.name, raw, field.value
+        )?;
+
+        if field.raw.len() > 8 {
+            for (i, line) in field.raw.chunks(16).enumerate() {
+                writeln!(
+                    out,
+                    "          {:04x}: {}",
+                    field.offset + i * 16,
+                    hex::encode(line, " ")
+                )?;
+            }
+        }
+    }
+
+    if let Some(integrity) = annotation.integrity {
+        let checksum = integrity.checksum;
+        write!(
+            out,
+            "  integrity: checksum {}",
+            check_text(checksum.is_ok(), checksum.expected, checksum.actual)
+        )?;
+
+        match integrity.crc {
+            Some(crc) => writeln!(
+                out,
+                ", crc {}",
+                check_text(crc.is_ok(), crc.expected, crc.actual)
+            )?,
+            None => writeln!(out, ", crc n/a")?,
+        }
+    }
+
+    writeln!(out)
+}
+
+/// Format result of single integrity check.
+fn check_text<T: std::fmt::LowerHex>(
+    ok: bool,
+    expected: T,
+    actual: T,
+) -> String {
+    if ok {
+        format!("ok (0x{actual:x})")
+    } else {
+        format!("BAD (expected 0x{expected:x}, actual 0x{actual:x})")
+    }
+}
+
+/// Convert annotated frame to JSON object.
+fn to_json(
+    index: usize,
+    time_us: Option<u64>,
+    data: &[u8],
+    annotation: &Annotation,
+) -> Value {
+    let fields: Vec<Value> = annotation
+        .fields
+        .iter()
+        .map(|field| {
+            json!({
+                "offset": field.offset,
+                "name": field.name,
+                "raw": hex::encode(&field.raw, ""),
+                "value": field.value,
+            })
+        })
+        .collect();
+
+    let integrity = annotation.integrity.map(|integrity| {
+        let crc = integrity.crc.map(|crc| {
+            json!({
+                "ok": crc.is_ok(),
+                "expected": crc.expected,
+                "actual": crc.actual,
+            })
+        });
+
+        json!({
+            "ok": integrity.is_ok(),
+            "checksum": {
+                "ok": integrity.checksum.is_ok(),
+                "expected": integrity.checksum.expected,
+                "actual": integrity.checksum.actual,
+            },
+            "crc": crc,
+        })
+    });
+
+    let decoded = match &annotation.decoded {
+        Some(Ok(payload)) => {
+            let mut object = serde_json::Map::new();
+            payload.for_each_field(|name, value| {
+                object.insert(name.into(), json!(value));
+            });
+            Value::Object(object)
+        }
+        Some(Err(error)) => json!({ "error": error.to_string() }),
+        None => Value::Null,
+    };
+
+    let header = annotation.header.map(|header| {
+        let IdtpHeader {
+            version,
+            device_id,
+            checksum,
+            timestamp,
+            sequence,
+            crc,
+            payload_size,
+            payload_type,
+            reserved,
+            ..
+        } = header;
+
+        json!({
+            "version": [version.major, version.minor, version.patch],
+            "mode": data[7],
+            "device_id": device_id,
+            "checksum": checksum,
+            "timestamp": timestamp,
+            "sequence": sequence,
+            "crc": crc,
+            "payload_size": payload_size,
+            "payload_type": payload_type,
+            "reserved": hex::encode(&reserved, ""),
+        })
+    });
+
+    json!({
+        "index": index,
+        "time_us": time_us,
+        "size": annotation.size,
+        "ok": annotation.is_ok(),
+        "error": annotation.error.map(|error| error.to_string()),
+        "header": header,
+        "fields": fields,
+        "integrity": integrity,
+        "payload": {
+            "raw": hex::encode(&annotation.payload, ""),
+            "decoded": decoded,
+        },
+        "raw": hex::encode(data, ""),
+    })
+}
@@ -0,0 +1,324 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! `idtp-netem` - impairment proxy for IDTP traffic.
+
+use clap::{Args as ClapArgs, Parser, Subcommand};
+use idtp::{
+    Deframer, IDTP_PAYLOAD_MAX_SIZE, IdtpFrame, IdtpHeader, Mode,
+    recording::unix_time_us,
+};
+use idtp_tools::netem::{Delivery, Impairment, Netem, Rng};
+use std::{
+    io::{self, Read, Write},
+    net::UdpSocket,
+    process,
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+/// Apply reproducible channel impairments to IDTP traffic and report how
+/// many corrupted frames each integrity mode caught, corrected or let
+/// through.
+#[derive(Debug, Parser)]
+#[command(name = "idtp-netem", version)]
+struct Args {
+    #[command(flatten)]
+    impairment: ImpairmentArgs,
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// Impairment options.
+#[derive(Debug, ClapArgs)]
+struct ImpairmentArgs {
+    /// Random generator seed.
+    #[arg(long, default_value_t = 1)]
+    seed: u64,
+    /// Probability of every single bit to be flipped.
+    #[arg(long, default_value_t = 0.0)]
+    ber: f64,
+    /// Probability of a packet to be hit by a burst error.
+    #[arg(long, default_value_t = 0.0)]
+    burst_rate: f64,
+    /// Length of burst error in bits.
+    #[arg(long, default_value_t = 16)]
+    burst_length: usize,
+    /// Probability of a packet to be dropped.
+    #[arg(long, default_value_t = 0.0)]
+    drop: f64,
+    /// Probability of a packet to be duplicated.
+    #[arg(long, default_value_t = 0.0)]
+    duplicate: f64,
+    /// Probability of a packet to be delivered after the next one.
+    #[arg(long, default_value_t = 0.0)]
+    reorder: f64,
+    /// Constant latency in milliseconds.
+    #[arg(long, default_value_t = 0.0)]
+    delay_ms: f64,
+    /// Maximum random latency added to delay in milliseconds.
+    #[arg(long, default_value_t = 0.0)]
+    jitter_ms: f64,
+}
+
+impl ImpairmentArgs {
+    /// Build impairment simulator.
+    fn netem(&self) -> Netem {
+        let config = Impairment {
+            bit_error_rate: self.ber,
+            burst_rate: self.burst_rate,
+            burst_length: self.burst_length,
+            drop_rate: self.drop,
+            duplicate_rate: self.duplicate,
+            reorder_rate: self.reorder,
+            delay_us: (self.delay_ms * 1000.0) as u64,
+            jitter_us: (self.jitter_ms * 1000.0) as u64,
+        };
+
+        Netem::new(config, self.seed)
+    }
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Forward UDP datagrams (one frame each) through impaired channel.
+    Udp {
+        /// Local address to receive datagrams on.
+        #[arg(long)]
+        listen: String,
+        /// Address to forward datagrams to.
+        #[arg(long)]
+        forward: String,
+        /// Stop after given number of received datagrams.
+        #[arg(long)]
+        count: Option<u64>,
+    },
+    /// Forward raw byte stream from standard input to standard output.
+    Stream,
+    /// Send generated frames of every integrity mode through identical
+    /// impairments and compare detection and correction.
+    Simulate {
+        /// Number of frames per mode.
+        #[arg(long, default_value_t = 10000)]
+        frames: u32,
+        /// Payload size in bytes (at most 988).
+        #[arg(long, default_value_t = 24)]
+        payload_size: usize,
+    },
+}
+
+/// Input event of proxy loop.
+enum Input {
+    /// Received packet.
+    Packet(Vec<u8>),
+    /// No packet received before timeout.
+    Timeout,
+    /// Input ended.
+    End,
+}
+
+fn main() {
+    let args = Args::parse();
+    let netem = args.impairment.netem();
+
+    let result = match args.command {
+        Command::Udp {
+            listen,
+            forward,
+            count,
+        } => udp(netem, &listen, &forward, count),
+        Command::Stream => stream(netem),
+        Command::Simulate {
+            frames,
+            payload_size,
+        } => simulate(&args.impairment, frames, payload_size),
+    };
+
+    if let Err(error) = result {
+        eprintln!("idtp-netem: {error}");
+        process::exit(1);
+    }
+}
+
+/// Run UDP proxy.
+fn udp(
+    netem: Netem,
+    listen: &str,
+    forward: &str,
+    count: Option<u64>,
+) -> io::Result<()> {
+    let socket = UdpSocket::bind(listen)?;
+    let output = socket.try_clone()?;
+    let mut buffer = [0u8; 65536];
+    let mut received = 0u64;
+
+    proxy(
+        netem,
+        |timeout| {
+            if count.is_some_and(|count| received >= count) {
+                return Ok(Input::End);
+            }
+
+            socket.set_read_timeout(Some(timeout))?;
+
+            match socket.recv_from(&mut buffer) {
+                Ok((size, _)) => {
+                    received += 1;
+                    Ok(Input::Packet(buffer[..size].to_vec()))
+                }
+                Err(e)
+                    if matches!(
+                        e.kind(),
+                        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                    ) =>
+                {
+                    Ok(Input::Timeout)
+                }
+                Err(e) => Err(e),
+            }
+        },
+        |delivery| output.send_to(&delivery.data, forward).map(|_| ()),
+    )
+}
+
+/// Run standard input to standard output stream proxy.
+fn stream(netem: Netem) -> io::Result<()> {
+    let (sender, receiver) = mpsc::channel();
+
+    // Frames are extracted from blocking input in a separate thread to
+    // keep delivery timing independent from input.
+    thread::spawn(move || {
+        let mut stdin = io::stdin().lock();
+        let mut deframer = Deframer::new();
+        let mut chunk = [0u8; 4096];
+
+        while let Ok(size @ 1..) = stdin.read(&mut chunk) {
+            deframer.feed(&chunk[..size], |frame| {
+                let _ = sender.send(frame.to_vec());
+            });
+        }
+    });
+
+    let mut stdout = io::stdout().lock();
+
+    proxy(
+        netem,
+        |timeout| match receiver.recv_timeout(timeout) {
+            Ok(packet) => Ok(Input::Packet(packet)),
+            Err(RecvTimeoutError::Timeout) => Ok(Input::Timeout),
+            Err(RecvTimeoutError::Disconnected) => Ok(Input::End),
+        },
+        |delivery| {
+            stdout.write_all(&delivery.data)?;
+            stdout.flush()
+        },
+    )
+}
+
+/// Run proxy loop until input ends and print report to standard error.
+fn proxy<I, O>(mut netem: Netem, mut input: I, mut output: O) -> io::Result<()>
+where
+    I: FnMut(Duration) -> io::Result<Input>,
+    O: FnMut(&Delivery) -> io::Result<()>,
+{
+    const REPORT_INTERVAL_US: u64 = 10_000_000;
+    const MAX_TIMEOUT: Duration = Duration::from_millis(100);
+
+    let mut next_report = unix_time_us() + REPORT_INTERVAL_US;
+
+    loop {
+        let now = unix_time_us();
+
+        while let Some(delivery) = netem.receive(now) {
+            output(&delivery)?;
+        }
+
+        if now >= next_report {
+            eprint!("{}", netem.report());
+            next_report = now + REPORT_INTERVAL_US;
+        }
+
+        let timeout = netem.next_due().map_or(MAX_TIMEOUT, |due| {
+            Duration::from_micros(due.saturating_sub(now).max(1))
+                .min(MAX_TIMEOUT)
+        });
+
+        match input(timeout)? {
+            Input::Packet(packet) => netem.send(unix_time_us(), &packet),
+            Input::Timeout => {}
+            Input::End => break,
+        }
+    }
+
+    while let Some(due) = netem.next_due() {
+        thread::sleep(Duration::from_micros(
+            due.saturating_sub(unix_time_us()),
+        ));
+
+        while let Some(delivery) = netem.receive(unix_time_us()) {
+            output(&delivery)?;
+        }
+    }
+
+    for delivery in netem.flush() {
+        output(&delivery)?;
+    }
+
+    eprint!("{}", netem.report());
+    Ok(())
+}
+
+/// Compare integrity modes on generated traffic.
+fn simulate(
+    impairment: &ImpairmentArgs,
+    frames: u32,
+    payload_size: usize,
+) -> io::Result<()> {
+    if payload_size > IDTP_PAYLOAD_MAX_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "payload size {payload_size} exceeds maximum of \
+                 {IDTP_PAYLOAD_MAX_SIZE} bytes"
+            ),
+        ));
+    }
+
+    for mode in [Mode::Normal, Mode::Safety, Mode::Fec] {
+        // Same seed gives identical impairment patterns for frames of the
+        // same size (FEC mode frames are longer by parity).
+        let mut netem = impairment.netem();
+        let mut payload_rng = Rng::new(impairment.seed);
+        let mut payload = vec![0u8; payload_size];
+
+        for sequence in 0..frames {
+            payload.fill_with(|| payload_rng.next_u64() as u8);
+
+            let mut header = IdtpHeader::new();
+            header.mode = mode;
+            header.sequence = sequence;
+
+            let mut frame = IdtpFrame::new();
+            frame.set_header(&header);
+            frame.set_payload(&payload);
+            frame.update_integrity();
+
+            if mode == Mode::Fec
+                && let Err(error) = frame.protect()
+            {
+                eprintln!("FEC mode skipped: {error}");
+                break;
+            }
+
+            let mut bytes = vec![0u8; frame.frame_size()];
+            let _ = frame.pack(&mut bytes);
+            netem.send(u64::from(sequence), &bytes);
+        }
+
+        netem.flush();
+        print!("{}", netem.report());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,333 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Construction of arbitrary (including malformed) IDTP frames.
+
+use crate::hex;
+use idtp::{
+    IDTP_HEADER_SIZE, IDTP_PAYLOAD_MAX_SIZE, IdtpFrame, IdtpHeader, Mode,
+    Version, raw_checksum, raw_frame_crc,
+};
+use serde::Deserialize;
+use std::str::FromStr;
+
+/// Byte-level corruption applied to the packed frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Corruption {
+    /// Offset of the byte from the start of the frame.
+    pub offset: usize,
+    /// Mask to XOR the byte with (`0xff` if neither `xor` nor `value` is
+    /// set).
+    pub xor: Option<u8>,
+    /// Value to replace the byte with.
+    pub value: Option<u8>,
+}
+
+impl Corruption {
+    /// Apply corruption to the frame bytes.
+    ///
+    /// # Parameters
+    /// - `bytes` - given packed frame bytes.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if offset is out of frame bounds.
+    pub fn apply(&self, bytes: &mut [u8]) -> Result<(), String> {
+        let size = bytes.len();
+        let byte = bytes.get_mut(self.offset).ok_or_else(|| {
+            format!(
+                "Corruption offset {} is out of frame ({size})",
+                self.offset
+            )
+        })?;
+
+        *byte = match (self.value, self.xor) {
+            (Some(value), _) => value,
+            (None, Some(mask)) => *byte ^ mask,
+            (None, None) => *byte ^ 0xff,
+        };
+
+        Ok(())
+    }
+}
+
+impl FromStr for Corruption {
+    type Err = String;
+
+    /// Parse corruption from `OFFSET`, `OFFSET^MASK` or `OFFSET=VALUE`.
+    ///
+    /// # Parameters
+    /// - `string` - given corruption description.
+    ///
+    /// # Returns
+    /// - Parsed corruption - in case of success.
+    /// - `Err` - otherwise.
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let mut corruption = Self {
+            offset: 0,
+            xor: None,
+            value: None,
+        };
+
+        let offset = if let Some((offset, mask)) = string.split_once('^') {
+            corruption.xor = Some(parse_int(mask)?);
+            offset
+        } else if let Some((offset, value)) = string.split_once('=') {
+            corruption.value = Some(parse_int(value)?);
+            offset
+        } else {
+            string
+        };
+
+        corruption.offset = parse_int(offset)?;
+        Ok(corruption)
+    }
+}
+
+/// Description of the frame to build.
+///
+/// Every field is optional. Unset header fields get the same values as in
+/// `IdtpHeader::new`, `payload_size`, `checksum` and `crc` are calculated
+/// unless overridden. Byte strings (`preamble`, `reserved`, `payload`,
+/// `trailer` and `append`) are hexadecimal.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FrameSpec {
+    /// Preamble bytes override.
+    pub preamble: Option<String>,
+    /// Protocol version in `MAJOR.MINOR.PATCH` format.
+    pub version: Option<String>,
+    /// Operating mode: `normal`, `safety`, `secure`, `fec` or raw byte value.
+    pub mode: Option<String>,
+    /// IMU device identifier.
+    pub device_id: Option<u16>,
+    /// Checksum override.
+    pub checksum: Option<u16>,
+    /// Timestamp.
+    pub timestamp: Option<u32>,
+    /// Sequence number.
+    pub sequence: Option<u32>,
+    /// CRC override.
+    pub crc: Option<u32>,
+    /// Payload size override.
+    pub payload_size: Option<u32>,
+    /// Payload type.
+    pub payload_type: Option<u8>,
+    /// Reserved bytes.
+    pub reserved: Option<String>,
+    /// Payload bytes.
+    pub payload: Option<String>,
+    /// Trailer bytes override (may have any length).
+    pub trailer: Option<String>,
+    /// Bytes to append after the trailer.
+    pub append: Option<String>,
+    /// Byte-level corruptions applied after packing.
+    pub corrupt: Vec<Corruption>,
+    /// Number of bytes to keep (applied last).
+    pub truncate: Option<usize>,
+}
+
+impl FrameSpec {
+    /// Parse frame description from TOML.
+    ///
+    /// # Parameters
+    /// - `string` - given TOML document.
+    ///
+    /// # Returns
+    /// - Parsed frame description - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if document is invalid.
+    pub fn from_toml(string: &str) -> Result<Self, String> {
+        toml::from_str(string).map_err(|e| e.to_string())
+    }
+
+    /// Parse frame description from JSON.
+    ///
+    /// # Parameters
+    /// - `string` - given JSON document.
+    ///
+    /// # Returns
+    /// - Parsed frame description - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if document is invalid.
+    pub fn from_json(string: &str) -> Result<Self, String> {
+        serde_json::from_str(string).map_err(|e| e.to_string())
+    }
+
+    /// Build raw frame bytes.
+    ///
+    /// Frame is built and packed with `IdtpFrame`, then mode byte which
+    /// `IdtpHeader` can not represent (unknown mode) is written. Integrity
+    /// fields are calculated over the final packed header bytes (including
+    /// raw mode byte and overridden `payload_size`). Then trailer,
+    /// corruptions, appended bytes and truncation are applied.
+    ///
+    /// # Returns
+    /// - Raw frame bytes - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if description contains invalid values.
+    pub fn build(&self) -> Result<Vec<u8>, String> {
+        let payload = decode_opt(self.payload.as_deref())?;
+
+        if payload.len() > IDTP_PAYLOAD_MAX_SIZE {
+            return Err(format!(
+                "Payload of {} bytes exceeds maximum of {IDTP_PAYLOAD_MAX_SIZE} \
+                 bytes (use `append` for oversized frames)",
+                payload.len()
+            ));
+        }
+
+        let mode = self.mode.as_deref().map(parse_mode).transpose()?;
+        let mut header = IdtpHeader::new();
+
+        if let Some(preamble) = &self.preamble {
+            header.preamble = fixed_bytes("preamble", preamble)?;
+        }
+
+        if let Some(version) = &self.version {
+            header.version = parse_version(version)?;
+        }
+
+        if let Some(mode) = mode {
+            header.mode = Mode::from(mode);
+        }
+
+        if let Some(reserved) = &self.reserved {
+            header.reserved = fixed_bytes("reserved", reserved)?;
+        }
+
+        header.device_id = self.device_id.unwrap_or(header.device_id);
+        header.timestamp = self.timestamp.unwrap_or(header.timestamp);
+        header.sequence = self.sequence.unwrap_or(header.sequence);
+        header.payload_type = self.payload_type.unwrap_or(header.payload_type);
+
+        header.payload_size = self.payload_size.unwrap_or(payload.len() as u32);
+
+        let mut frame = IdtpFrame::new();
+        frame.set_header(&header);
+        frame.set_payload(&payload);
+
+        let mut bytes = vec![0u8; frame.frame_size()];
+        frame.pack(&mut bytes)?;
+
+        if let Some(mode) = mode {
+            bytes[7] = mode;
+        }
+
+        let raw_header: [u8; IDTP_HEADER_SIZE] =
+            bytes[..IDTP_HEADER_SIZE].try_into().unwrap();
+
+        let crc = match header.mode {
+            Mode::Safety => raw_frame_crc(&raw_header, &payload),
+            _ => 0,
+        };
+        let crc = self.crc.unwrap_or(crc);
+        let checksum = raw_checksum(&raw_header, &payload);
+        let checksum = self.checksum.unwrap_or(checksum);
+
+        bytes[10..12].copy_from_slice(&checksum.to_be_bytes());
+        bytes[20..24].copy_from_slice(&crc.to_be_bytes());
+
+        if let Some(trailer) = &self.trailer {
+            bytes.truncate(IDTP_HEADER_SIZE + payload.len());
+            bytes.extend_from_slice(&hex::decode(trailer)?);
+        }
+
+        for corruption in &self.corrupt {
+            corruption.apply(&mut bytes)?;
+        }
+
+        bytes.extend_from_slice(&decode_opt(self.append.as_deref())?);
+
+        if let Some(size) = self.truncate {
+            bytes.truncate(size);
+        }
+
+        Ok(bytes)
+    }
+}
+
+/// Parse unsigned integer in decimal or `0x`-prefixed hexadecimal notation.
+///
+/// # Parameters
+/// - `string` - given number string.
+///
+/// # Returns
+/// - Parsed number - in case of success.
+/// - `Err` - otherwise.
+///
+/// # Errors
+/// - Will return `Err` if string is not a number or it is out of range.
+pub fn parse_int<T: TryFrom<u64>>(string: &str) -> Result<T, String> {
+    let string = string.trim();
+    let value = match string.strip_prefix("0x") {
+        Some(digits) => u64::from_str_radix(digits, 16),
+        None => string.parse(),
+    }
+    .map_err(|_| format!("Invalid number \"{string}\""))?;
+
+    T::try_from(value).map_err(|_| format!("Number {value} is out of range"))
+}
+
+/// Parse operating mode name or raw byte value.
+///
+/// # Parameters
+/// - `string` - given mode string.
+///
+/// # Returns
+/// - Raw mode byte - in case of success.
+/// - `Err` - otherwise.
+fn parse_mode(string: &str) -> Result<u8, String> {
+    match string.to_ascii_lowercase().as_str() {
+        "normal" => Ok(Mode::Normal as u8),
+        "safety" => Ok(Mode::Safety as u8),
+        "secure" => Ok(Mode::Secure as u8),
+        "fec" => Ok(Mode::Fec as u8),
+        _ => parse_int(string),
+    }
+}
+
+/// Parse protocol version in `MAJOR.MINOR.PATCH` format.
+///
+/// # Parameters
+/// - `string` - given version string.
+///
+/// # Returns
+/// - Parsed version - in case of success.
+/// - `Err` - otherwise.
+fn parse_version(string: &str) -> Result<Version, String> {
+    let parts = string
+        .split('.')
+        .map(parse_int)
+        .collect::<Result<Vec<u8>, _>>()?;
+
+    match parts[..] {
+        [major, minor, patch] => Ok(Version::new(major, minor, patch)),
+        _ => Err(format!("Invalid version \"{string}\"")),
+    }
+}
+
+/// Decode optional hexadecimal string.
+fn decode_opt(string: Option<&str>) -> Result<Vec<u8>, String> {
+    string.map_or(Ok(Vec::new()), hex::decode)
+}
+
+/// Decode hexadecimal string of fixed length.
+fn fixed_bytes<const N: usize>(
+    name: &str,
+    string: &str,
+) -> Result<[u8; N], String> {
+    hex::decode(string)?
+        .try_into()
+        .map_err(|_| format!("Field `{name}` must be exactly {N} bytes long"))
+}
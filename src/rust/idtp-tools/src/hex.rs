@@ -0,0 +1,61 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Hexadecimal encoding helpers.
+
+use std::fmt::Write;
+
+/// Encode bytes as hexadecimal string.
+///
+/// # Parameters
+/// - `bytes` - given bytes to encode.
+/// - `separator` - given string to put between bytes.
+///
+/// # Returns
+/// - Lowercase hexadecimal string.
+pub fn encode(bytes: &[u8], separator: &str) -> String {
+    let mut string = String::with_capacity(bytes.len() * (2 + separator.len()));
+
+    for (i, byte) in bytes.iter().enumerate() {
+        if i > 0 {
+            string.push_str(separator);
+        }
+        let _ = write!(string, "{byte:02x}");
+    }
+
+    string
+}
+
+/// Decode hexadecimal string. Whitespace, `:` and `_` separators as well as
+/// `0x` prefix are ignored.
+///
+/// # Parameters
+/// - `string` - given hexadecimal string.
+///
+/// # Returns
+/// - Decoded bytes - in case of success.
+/// - `Err` - otherwise.
+///
+/// # Errors
+/// - Will return `Err` if string contains odd number of digits or
+///   non-hexadecimal characters.
+pub fn decode(string: &str) -> Result<Vec<u8>, String> {
+    let string = string.strip_prefix("0x").unwrap_or(string);
+    let digits: Vec<u8> = string
+        .bytes()
+        .filter(|c| !c.is_ascii_whitespace() && *c != b':' && *c != b'_')
+        .collect();
+
+    if !digits.len().is_multiple_of(2) {
+        return Err(format!("Odd number of hex digits in \"{string}\""));
+    }
+
+    digits
+        .chunks_exact(2)
+        .map(|pair| {
+            let pair = std::str::from_utf8(pair).unwrap_or("??");
+            u8::from_str_radix(pair, 16)
+                .map_err(|_| format!("Invalid hex byte \"{pair}\""))
+        })
+        .collect()
+}
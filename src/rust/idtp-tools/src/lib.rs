@@ -0,0 +1,10 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Shared functionality of IDTP command-line tools.
+
+pub mod annotate;
+pub mod craft;
+pub mod hex;
+pub mod netem;
+pub mod source;
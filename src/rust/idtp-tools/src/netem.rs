@@ -0,0 +1,454 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Network impairment simulator: random bit flips, burst errors, drops,
+//! duplication, reordering and latency jitter with reproducible seed.
+
+use crate::annotate::mode_name;
+use idtp::{IdtpFrame, Mode, fec};
+use std::{cmp::Reverse, collections::BTreeMap, collections::BinaryHeap, fmt};
+
+/// Small deterministic pseudo-random number generator (SplitMix64).
+///
+/// Used instead of external crates to keep impairment patterns reproducible
+/// for a given seed across platforms and dependency versions.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    /// Generator state.
+    state: u64,
+}
+
+impl Rng {
+    /// Construct new `Rng` struct.
+    ///
+    /// # Parameters
+    /// - `seed` - given seed.
+    ///
+    /// # Returns
+    /// - New `Rng` struct.
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Get next random 64-bit number.
+    ///
+    /// # Returns
+    /// - Random number.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Get random number uniformly distributed in `[0, 1)`.
+    ///
+    /// # Returns
+    /// - Random number.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Get random number uniformly distributed in `[0, bound)`.
+    ///
+    /// # Parameters
+    /// - `bound` - given exclusive upper bound (must be non-zero).
+    ///
+    /// # Returns
+    /// - Random number.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        ((u128::from(self.next_u64()) * u128::from(bound)) >> 64) as u64
+    }
+
+    /// Get random event outcome.
+    ///
+    /// # Parameters
+    /// - `probability` - given event probability.
+    ///
+    /// # Returns
+    /// - `true`  - if event happened.
+    /// - `false` - otherwise.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        probability > 0.0 && self.next_f64() < probability
+    }
+}
+
+/// Impairment configuration.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Impairment {
+    /// Probability of every single bit to be flipped.
+    pub bit_error_rate: f64,
+    /// Probability of a packet to be hit by a burst error.
+    pub burst_rate: f64,
+    /// Length of burst error in bits.
+    pub burst_length: usize,
+    /// Probability of a packet to be dropped.
+    pub drop_rate: f64,
+    /// Probability of a packet to be duplicated.
+    pub duplicate_rate: f64,
+    /// Probability of a packet to be delivered after the next one. Packet
+    /// is delivered in order if the next one is not sent within maximum
+    /// latency (`delay_us` + `jitter_us`).
+    pub reorder_rate: f64,
+    /// Constant latency in microseconds.
+    pub delay_us: u64,
+    /// Maximum random latency added to `delay_us` in microseconds.
+    pub jitter_us: u64,
+}
+
+/// Packet leaving the simulated channel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Delivery {
+    /// Delivery time in microseconds.
+    pub time_us: u64,
+    /// Packet bytes.
+    pub data: Vec<u8>,
+    /// Whether packet bytes were altered by the channel.
+    pub corrupted: bool,
+}
+
+/// Detection statistics of corrupted frames for single operating mode.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Detection {
+    /// Number of delivered frames.
+    pub frames: u64,
+    /// Number of delivered corrupted frames.
+    pub corrupted: u64,
+    /// Number of corrupted frames rejected by structure or integrity checks.
+    pub caught: u64,
+    /// Number of corrupted frames restored by forward error correction.
+    pub corrected: u64,
+    /// Number of bytes restored by forward error correction.
+    pub corrected_bytes: u64,
+    /// Number of corrupted frames which passed all checks.
+    pub missed: u64,
+}
+
+/// Impairment simulator report.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Report {
+    /// Number of packets sent into the channel.
+    pub sent: u64,
+    /// Number of dropped packets.
+    pub dropped: u64,
+    /// Number of extra packet copies.
+    pub duplicated: u64,
+    /// Number of reordered packets.
+    pub reordered: u64,
+    /// Number of corrupted packets.
+    pub corrupted: u64,
+    /// Detection statistics by raw operating mode byte of original frames.
+    pub modes: BTreeMap<u8, Detection>,
+}
+
+impl fmt::Display for Report {
+    /// Format report as counters line followed by detection line of every
+    /// operating mode.
+    ///
+    /// # Parameters
+    /// - `f` - given formatter.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "sent {}, dropped {}, duplicated {}, reordered {}, corrupted {}",
+            self.sent,
+            self.dropped,
+            self.duplicated,
+            self.reordered,
+            self.corrupted
+        )?;
+
+        for (mode, detection) in &self.modes {
+            writeln!(
+                f,
+                "{:<8} frames {}, corrupted {}, caught {}, corrected {} \
+                 ({} bytes), missed {}",
+                mode_name(*mode),
+                detection.frames,
+                detection.corrupted,
+                detection.caught,
+                detection.corrected,
+                detection.corrected_bytes,
+                detection.missed
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Packet in flight: delivery time, arrival order, bytes and corruption flag.
+type InFlight = Reverse<(u64, u64, Vec<u8>, bool)>;
+
+/// Packet held back: latest delivery time, bytes and corruption flag.
+type Held = (u64, Vec<u8>, bool);
+
+/// Simulated lossy channel.
+///
+/// Packets are sent with `send` and taken out with `receive` once their
+/// delivery time has come. Every packet is expected to be a single IDTP
+/// frame - it is used to account detection of corrupted frames.
+#[derive(Debug)]
+pub struct Netem {
+    /// Impairment configuration.
+    config: Impairment,
+    /// Random number generator.
+    rng: Rng,
+    /// Packets in flight ordered by delivery time and arrival order.
+    queue: BinaryHeap<InFlight>,
+    /// Packet held back to be delivered after the next one.
+    held: Option<Held>,
+    /// Arrival counter used to keep order of packets with equal time.
+    counter: u64,
+    /// Simulator report.
+    report: Report,
+}
+
+impl Netem {
+    /// Construct new `Netem` struct.
+    ///
+    /// # Parameters
+    /// - `config` - given impairment configuration.
+    /// - `seed` - given random generator seed.
+    ///
+    /// # Returns
+    /// - New `Netem` struct.
+    pub fn new(config: Impairment, seed: u64) -> Self {
+        Self {
+            config,
+            rng: Rng::new(seed),
+            queue: BinaryHeap::new(),
+            held: None,
+            counter: 0,
+            report: Report::default(),
+        }
+    }
+
+    /// Get simulator report.
+    ///
+    /// # Returns
+    /// - Simulator report.
+    pub fn report(&self) -> &Report {
+        &self.report
+    }
+
+    /// Send packet into the channel.
+    ///
+    /// # Parameters
+    /// - `now_us` - given current time in microseconds.
+    /// - `data` - given packet bytes.
+    pub fn send(&mut self, now_us: u64, data: &[u8]) {
+        self.release_held(now_us);
+        self.report.sent += 1;
+
+        if self.rng.chance(self.config.drop_rate) {
+            self.report.dropped += 1;
+            return;
+        }
+
+        let copies = if self.rng.chance(self.config.duplicate_rate) {
+            self.report.duplicated += 1;
+            2
+        } else {
+            1
+        };
+
+        for _ in 0..copies {
+            let mut packet = data.to_vec();
+            let corrupted = self.corrupt(&mut packet);
+
+            if corrupted {
+                self.report.corrupted += 1;
+            }
+
+            self.account(data, &packet, corrupted);
+
+            let time_us = now_us + self.latency();
+
+            if self.held.is_none() && self.rng.chance(self.config.reorder_rate)
+            {
+                let deadline = now_us + self.max_latency();
+                self.held = Some((deadline, packet, corrupted));
+                continue;
+            }
+
+            self.schedule(time_us, packet, corrupted);
+
+            if let Some((_, packet, corrupted)) = self.held.take() {
+                self.report.reordered += 1;
+                self.schedule(time_us, packet, corrupted);
+            }
+        }
+    }
+
+    /// Get delivery time of the next packet in flight (including held
+    /// packet, which is delivered in order once its maximum latency passes).
+    ///
+    /// # Returns
+    /// - Delivery time in microseconds - if there are packets in flight.
+    /// - `None` - otherwise.
+    pub fn next_due(&self) -> Option<u64> {
+        let queued = self.queue.peek().map(|Reverse((time_us, ..))| *time_us);
+        let held = self.held.as_ref().map(|(deadline, ..)| *deadline);
+
+        match (queued, held) {
+            (Some(queued), Some(held)) => Some(queued.min(held)),
+            (queued, held) => queued.or(held),
+        }
+    }
+
+    /// Take next packet whose delivery time has come.
+    ///
+    /// # Parameters
+    /// - `now_us` - given current time in microseconds.
+    ///
+    /// # Returns
+    /// - Delivered packet - if any.
+    /// - `None` - otherwise.
+    pub fn receive(&mut self, now_us: u64) -> Option<Delivery> {
+        self.release_held(now_us);
+
+        if self.next_due()? > now_us {
+            return None;
+        }
+
+        self.queue
+            .pop()
+            .map(|Reverse((time_us, _, data, corrupted))| Delivery {
+                time_us,
+                data,
+                corrupted,
+            })
+    }
+
+    /// Release held packet and take all packets in flight regardless of
+    /// their delivery time.
+    ///
+    /// # Returns
+    /// - Packets in delivery order.
+    pub fn flush(&mut self) -> Vec<Delivery> {
+        let mut deliveries = Vec::with_capacity(self.queue.len());
+
+        while let Some(delivery) = self.receive(u64::MAX) {
+            deliveries.push(delivery);
+        }
+
+        deliveries
+    }
+
+    /// Put held packet into the delivery queue once its maximum latency
+    /// passes without the next packet.
+    fn release_held(&mut self, now_us: u64) {
+        if let Some((deadline, packet, corrupted)) =
+            self.held.take_if(|(deadline, ..)| *deadline <= now_us)
+        {
+            self.schedule(deadline, packet, corrupted);
+        }
+    }
+
+    /// Put packet into the delivery queue.
+    fn schedule(&mut self, time_us: u64, packet: Vec<u8>, corrupted: bool) {
+        self.counter += 1;
+        self.queue
+            .push(Reverse((time_us, self.counter, packet, corrupted)));
+    }
+
+    /// Get random packet latency.
+    fn latency(&mut self) -> u64 {
+        let jitter = match self.config.jitter_us {
+            0 => 0,
+            jitter => self.rng.below(jitter + 1),
+        };
+
+        self.config.delay_us + jitter
+    }
+
+    /// Get maximum packet latency.
+    fn max_latency(&self) -> u64 {
+        self.config.delay_us + self.config.jitter_us
+    }
+
+    /// Apply random bit errors and burst errors.
+    fn corrupt(&mut self, packet: &mut [u8]) -> bool {
+        let bits = packet.len() * 8;
+        let mut corrupted = false;
+
+        if self.config.bit_error_rate > 0.0 {
+            for bit in 0..bits {
+                if self.rng.chance(self.config.bit_error_rate) {
+                    packet[bit / 8] ^= 0x80 >> (bit % 8);
+                    corrupted = true;
+                }
+            }
+        }
+
+        let length = self.config.burst_length.min(bits);
+
+        if length > 0 && self.rng.chance(self.config.burst_rate) {
+            let start = self.rng.below((bits - length + 1) as u64) as usize;
+
+            // Burst starts and ends with an error, inner bits are random.
+            for bit in start..start + length {
+                let edge = bit == start || bit == start + length - 1;
+
+                if edge || self.rng.chance(0.5) {
+                    packet[bit / 8] ^= 0x80 >> (bit % 8);
+                }
+            }
+
+            corrupted = true;
+        }
+
+        corrupted
+    }
+
+    /// Account packet in detection statistics.
+    fn account(&mut self, original: &[u8], packet: &[u8], corrupted: bool) {
+        let Some(&mode) = original.get(7) else {
+            return;
+        };
+
+        let detection = self.report.modes.entry(mode).or_default();
+        detection.frames += 1;
+
+        if !corrupted {
+            return;
+        }
+
+        detection.corrupted += 1;
+
+        // Receiver of FEC link corrects frames before validating them.
+        let mut received = packet.to_vec();
+        let corrected = match Mode::from(mode) {
+            Mode::Fec => fec::correct(&mut received).unwrap_or(0),
+            _ => 0,
+        };
+
+        if !is_accepted(&received) {
+            detection.caught += 1;
+        } else if received == original {
+            detection.corrected += 1;
+            detection.corrected_bytes += corrected as u64;
+        } else {
+            detection.missed += 1;
+        }
+    }
+}
+
+/// Check whether receiver would accept frame: its structure is valid and
+/// integrity checks of its operating mode pass.
+///
+/// # Parameters
+/// - `data` - given raw frame bytes.
+///
+/// # Returns
+/// - `true`  - if frame is accepted.
+/// - `false` - otherwise.
+pub fn is_accepted(data: &[u8]) -> bool {
+    IdtpFrame::parse(data).is_ok_and(|frame| frame.verify().is_ok())
+}
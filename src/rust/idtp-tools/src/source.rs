@@ -0,0 +1,286 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Sources of raw IDTP frames: raw byte streams, `.idtp` recordings,
+//! pcap/pcapng captures and UDP sockets.
+
+use idtp::{
+    Deframer,
+    pcap::PcapReader,
+    recording::{Player, RECORDING_MAGIC, unix_time_us},
+};
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{self, BufReader, Cursor, Read},
+    net::UdpSocket,
+    path::Path,
+};
+
+/// Raw IDTP frame read from source.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RawFrame {
+    /// Receive or capture timestamp in microseconds since Unix epoch
+    /// (`None` if source does not provide timestamps).
+    pub time_us: Option<u64>,
+    /// Raw frame bytes.
+    pub data: Vec<u8>,
+}
+
+/// Detected source format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Raw byte stream of back-to-back frames.
+    Stream,
+    /// `.idtp` recording.
+    Recording,
+    /// pcap or pcapng capture.
+    Capture,
+}
+
+/// Detect source format by its first bytes.
+///
+/// # Parameters
+/// - `magic` - given first (up to 8) bytes of the source.
+///
+/// # Returns
+/// - Detected source format.
+pub fn detect(magic: &[u8]) -> Format {
+    const CAPTURE_MAGICS: [[u8; 4]; 5] = [
+        [0x0a, 0x0d, 0x0d, 0x0a],
+        [0xa1, 0xb2, 0xc3, 0xd4],
+        [0xd4, 0xc3, 0xb2, 0xa1],
+        [0xa1, 0xb2, 0x3c, 0x4d],
+        [0x4d, 0x3c, 0xb2, 0xa1],
+    ];
+
+    if magic.starts_with(RECORDING_MAGIC) {
+        Format::Recording
+    } else if CAPTURE_MAGICS.iter().any(|m| magic.starts_with(m)) {
+        Format::Capture
+    } else {
+        Format::Stream
+    }
+}
+
+/// Source of raw IDTP frames.
+pub enum Source {
+    /// Raw byte stream split into frames by deframer.
+    Stream {
+        /// Input to read bytes from.
+        reader: Box<dyn Read>,
+        /// Streaming deframer.
+        deframer: Box<Deframer>,
+        /// Frames found but not returned yet.
+        queue: VecDeque<Vec<u8>>,
+        /// Number of resyncs already reported.
+        resyncs: u64,
+    },
+    /// `.idtp` recording.
+    Recording(Box<Player<Box<dyn ReadSeek>>>),
+    /// pcap or pcapng capture.
+    Capture(PcapReader<Box<dyn Read>>),
+    /// UDP socket, every datagram is treated as single frame.
+    Udp(UdpSocket),
+}
+
+/// Readable and seekable input.
+pub trait ReadSeek: Read + io::Seek {}
+
+impl<T: Read + io::Seek> ReadSeek for T {}
+
+impl Source {
+    /// Open file (or standard input if path is `-`) and detect its format.
+    ///
+    /// # Parameters
+    /// - `path` - given file path or `-`.
+    /// - `port` - given UDP port to extract frames from (captures only).
+    ///
+    /// # Returns
+    /// - New `Source` - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if input could not be opened or is malformed.
+    pub fn open(path: &str, port: Option<u16>) -> io::Result<Self> {
+        if path == "-" {
+            return Self::from_reader(Box::new(io::stdin().lock()), port);
+        }
+
+        let mut file = BufReader::new(File::open(Path::new(path))?);
+        let magic = peek(&mut file)?;
+
+        if detect(&magic) == Format::Recording {
+            let player = Player::new(Box::new(file) as Box<dyn ReadSeek>)?;
+            return Ok(Self::Recording(Box::new(player)));
+        }
+
+        let reader = Box::new(Cursor::new(magic).chain(file));
+        Self::from_reader(reader, port)
+    }
+
+    /// Detect format of non-seekable input.
+    ///
+    /// # Parameters
+    /// - `reader` - given input.
+    /// - `port` - given UDP port to extract frames from (captures only).
+    ///
+    /// # Returns
+    /// - New `Source` - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if input could not be read or is malformed.
+    pub fn from_reader(
+        mut reader: Box<dyn Read>,
+        port: Option<u16>,
+    ) -> io::Result<Self> {
+        let magic = peek(&mut reader)?;
+        let format = detect(&magic);
+        let mut reader: Box<dyn Read> =
+            Box::new(Cursor::new(magic).chain(reader));
+
+        match format {
+            Format::Recording => {
+                let mut bytes = Vec::new();
+                reader.read_to_end(&mut bytes)?;
+                let player = Player::new(
+                    Box::new(Cursor::new(bytes)) as Box<dyn ReadSeek>
+                )?;
+                Ok(Self::Recording(Box::new(player)))
+            }
+            Format::Capture => {
+                let mut capture = PcapReader::new(reader)?;
+                capture.set_port(port);
+                Ok(Self::Capture(capture))
+            }
+            Format::Stream => Ok(Self::Stream {
+                reader,
+                deframer: Box::default(),
+                queue: VecDeque::new(),
+                resyncs: 0,
+            }),
+        }
+    }
+
+    /// Bind UDP socket.
+    ///
+    /// # Parameters
+    /// - `address` - given local address to listen on.
+    ///
+    /// # Returns
+    /// - New `Source` - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if socket could not be bound.
+    pub fn udp(address: &str) -> io::Result<Self> {
+        Ok(Self::Udp(UdpSocket::bind(address)?))
+    }
+
+    /// Get detected source format.
+    ///
+    /// # Returns
+    /// - Source format.
+    pub fn format(&self) -> Format {
+        match self {
+            Self::Stream { .. } | Self::Udp(_) => Format::Stream,
+            Self::Recording(_) => Format::Recording,
+            Self::Capture(_) => Format::Capture,
+        }
+    }
+
+    /// Get number of new resyncs (runs of discarded bytes) of raw stream
+    /// since the last call.
+    ///
+    /// # Returns
+    /// - Number of new resyncs.
+    pub fn take_resyncs(&mut self) -> u64 {
+        match self {
+            Self::Stream {
+                deframer, resyncs, ..
+            } => {
+                let new = deframer.resyncs() - *resyncs;
+                *resyncs = deframer.resyncs();
+                new
+            }
+            _ => 0,
+        }
+    }
+
+    /// Read next raw IDTP frame.
+    ///
+    /// # Returns
+    /// - Next raw frame - in case of success.
+    /// - `None` - if source ended.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if source could not be read.
+    pub fn next_frame(&mut self) -> io::Result<Option<RawFrame>> {
+        match self {
+            Self::Stream {
+                reader,
+                deframer,
+                queue,
+                ..
+            } => {
+                let mut chunk = [0u8; 4096];
+
+                while queue.is_empty() {
+                    let size = match reader.read(&mut chunk) {
+                        Ok(0) => return Ok(None),
+                        Ok(size) => size,
+                        Err(e) if e.kind() == io::ErrorKind::Interrupted => {
+                            continue;
+                        }
+                        Err(e) => return Err(e),
+                    };
+
+                    deframer.feed(&chunk[..size], |frame| {
+                        queue.push_back(frame.to_vec());
+                    });
+                }
+
+                Ok(queue.pop_front().map(|data| RawFrame {
+                    time_us: None,
+                    data,
+                }))
+            }
+            Self::Recording(player) => {
+                Ok(player.next_record()?.map(|record| RawFrame {
+                    time_us: Some(record.recv_time_us),
+                    data: record.data,
+                }))
+            }
+            Self::Capture(capture) => {
+                Ok(capture.next_frame()?.map(|frame| RawFrame {
+                    time_us: Some(frame.timestamp_us),
+                    data: frame.data,
+                }))
+            }
+            Self::Udp(socket) => {
+                let mut buffer = [0u8; 65536];
+                let (size, _) = socket.recv_from(&mut buffer)?;
+
+                Ok(Some(RawFrame {
+                    time_us: Some(unix_time_us()),
+                    data: buffer[..size].to_vec(),
+                }))
+            }
+        }
+    }
+}
+
+/// Read up to 8 first bytes of input.
+///
+/// # Parameters
+/// - `reader` - given input.
+///
+/// # Returns
+/// - First bytes of input.
+fn peek<R: Read>(reader: &mut R) -> io::Result<Vec<u8>> {
+    let mut magic = Vec::with_capacity(8);
+    reader.by_ref().take(8).read_to_end(&mut magic)?;
+    Ok(magic)
+}
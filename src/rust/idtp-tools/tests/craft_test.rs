@@ -0,0 +1,124 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Frame crafting tests.
+
+extern crate idtp_tools;
+
+#[cfg(test)]
+mod tests {
+    use idtp::*;
+    use idtp_tools::craft::*;
+
+    #[test]
+    fn test_craft_valid_frame() {
+        let spec = FrameSpec {
+            mode: Some("safety".into()),
+            device_id: Some(0x0102),
+            sequence: Some(42),
+            payload: Some("01 02 03 04".into()),
+            ..FrameSpec::default()
+        };
+
+        let bytes = spec.build().unwrap();
+        let frame = IdtpFrame::parse(&bytes).unwrap();
+        let header = frame.header();
+
+        assert_eq!(bytes.len(), IDTP_PACKET_MIN_SIZE + 4);
+        assert_eq!({ header.device_id }, 0x0102);
+        assert_eq!({ header.sequence }, 42);
+        assert_eq!(frame.payload(), [1, 2, 3, 4]);
+        assert!(frame.verify().is_ok());
+        assert!(frame.verify().crc.is_some());
+    }
+
+    #[test]
+    fn test_craft_unknown_mode() {
+        let spec = FrameSpec {
+            mode: Some("0x07".into()),
+            payload: Some("0102".into()),
+            ..FrameSpec::default()
+        };
+
+        let bytes = spec.build().unwrap();
+        let checksum = u16::from_be_bytes([bytes[10], bytes[11]]);
+
+        // Plain sum of header bytes (with raw mode byte) and payload.
+        let mut covered = bytes[..bytes.len() - IDTP_TRAILER_SIZE].to_vec();
+        covered[10..12].fill(0);
+        let sum = covered.iter().map(|byte| u16::from(*byte)).sum::<u16>();
+
+        assert_eq!(bytes[7], 0x07);
+        assert_eq!(checksum, sum);
+        assert_eq!(checksum, 0x13e);
+        assert_eq!(&bytes[20..24], [0; 4]);
+    }
+
+    #[test]
+    fn test_craft_malformed_frames() {
+        let spec = FrameSpec::from_toml(
+            r#"
+            mode = "0x42"
+            reserved = "000001"
+            payload = "aabb"
+            payload_size = 1000
+            trailer = "50544458"
+            "#,
+        )
+        .unwrap();
+
+        let bytes = spec.build().unwrap();
+        let header = IdtpHeader::from(&bytes[..IDTP_HEADER_SIZE]);
+
+        assert_eq!(bytes[7], 0x42);
+        assert_eq!(bytes[31], 0x01);
+        assert_eq!({ header.payload_size }, 1000);
+        assert_eq!(&bytes[bytes.len() - 4..], b"PTDX");
+        assert_eq!(
+            IdtpFrame::parse(&bytes).err(),
+            Some(IdtpError::PayloadSizeMismatch)
+        );
+
+        let spec = FrameSpec::from_json(
+            r#"{"checksum": 4660, "corrupt": [{"offset": 0, "value": 0}],
+                "append": "ff", "truncate": 34}"#,
+        )
+        .unwrap();
+
+        let bytes = spec.build().unwrap();
+        assert_eq!(bytes.len(), 34);
+        assert_eq!(&bytes[0..4], b"\0DTP");
+        assert_eq!(&bytes[10..12], [0x12, 0x34]);
+
+        assert!(FrameSpec::from_toml("unknown = 1").is_err());
+        assert!(
+            FrameSpec {
+                reserved: Some("00".into()),
+                ..FrameSpec::default()
+            }
+            .build()
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_corruption_parsing() {
+        let mut bytes = [0u8; 16];
+        let mut corrupt =
+            |spec: &str| spec.parse::<Corruption>().unwrap().apply(&mut bytes);
+
+        assert!(corrupt("0x0a^0x01").is_ok());
+        assert!(corrupt("3=255").is_ok());
+        assert!(corrupt("4").is_ok());
+        assert!(corrupt("16").is_err());
+        assert_eq!(bytes[10], 0x01);
+        assert_eq!(bytes[3], 0xff);
+        assert_eq!(bytes[4], 0xff);
+
+        assert!("x".parse::<Corruption>().is_err());
+        assert_eq!(
+            parse_int::<u8>("0x100"),
+            Err("Number 256 is out of range".into())
+        );
+    }
+}
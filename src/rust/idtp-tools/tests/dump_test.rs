@@ -0,0 +1,230 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Frame annotation and source detection tests.
+
+extern crate idtp_tools;
+
+#[cfg(test)]
+mod tests {
+    use idtp::{extension::*, payload::*, *};
+    use idtp_tools::{annotate::annotate, hex, source::*};
+    use std::io::Cursor;
+
+    fn build_frame(mode: Mode) -> Vec<u8> {
+        let mut payload = [0u8; Quaternion::SIZE];
+        Quaternion::default().encode(&mut payload).unwrap();
+
+        let mut header = IdtpHeader::new();
+        header.mode = mode;
+        header.device_id = 0x0102;
+        header.sequence = 7;
+        header.payload_type = PAYLOAD_TYPE_QUATERNION;
+
+        let mut frame = IdtpFrame::new();
+        frame.set_header(&header);
+        frame.set_payload(&payload);
+        frame.update_integrity();
+
+        let mut buffer = vec![0u8; frame.frame_size()];
+        frame.pack(&mut buffer).unwrap();
+        buffer
+    }
+
+    #[test]
+    fn test_hex_roundtrip() {
+        assert_eq!(hex::encode(&[0x49, 0x0a, 0xff], " "), "49 0a ff");
+        assert_eq!(hex::decode("0x49 0A:ff_00"), Ok(vec![0x49, 0x0a, 0xff, 0]));
+        assert!(hex::decode("490").is_err());
+        assert!(hex::decode("zz").is_err());
+    }
+
+    #[test]
+    fn test_annotate_valid_and_corrupted_frames() {
+        let bytes = build_frame(Mode::Safety);
+        let annotation = annotate(&bytes);
+
+        assert!(annotation.is_ok());
+        assert_eq!(annotation.fields.len(), 16);
+        assert_eq!(annotation.fields[3].value, "258 (0x0102)");
+        assert_eq!(annotation.fields[9].value, "0xc5 (quaternion)");
+        assert_eq!(annotation.fields[15].name, "trailer");
+        assert_eq!(
+            annotation.decoded,
+            Some(Ok(StandardPayload::Quaternion(Quaternion::default())))
+        );
+
+        let mut corrupted = bytes.clone();
+        corrupted[IDTP_HEADER_SIZE] ^= 0x01;
+        let annotation = annotate(&corrupted);
+        let integrity = annotation.integrity.unwrap();
+
+        assert!(annotation.error.is_none());
+        assert!(!annotation.is_ok());
+        assert!(!integrity.checksum.is_ok());
+        assert!(!integrity.crc.unwrap().is_ok());
+
+        let annotation = annotate(&bytes[..bytes.len() - 2]);
+        assert_eq!(annotation.error, Some(IdtpError::PayloadSizeMismatch));
+        assert_eq!(annotation.payload.len(), Quaternion::SIZE - 2);
+
+        let annotation = annotate(&bytes[..10]);
+        assert_eq!(annotation.error, Some(IdtpError::FrameTooShort));
+        assert_eq!(annotation.fields[0].name, "truncated");
+    }
+
+    #[test]
+    fn test_annotate_payload_sections() {
+        let mut payload = [0xAAu8; Quaternion::SIZE + 16];
+        Quaternion::default().encode(&mut payload).unwrap();
+
+        let mut block = [0u8; 8];
+        let mut builder = ExtensionBuilder::new(&mut block);
+        builder
+            .push(EXTENSION_TYPE_TEMPERATURE, &[0x01, 0x02])
+            .unwrap();
+        let size = builder.finish().unwrap();
+
+        let mut header = IdtpHeader::new();
+        header.payload_type = PAYLOAD_TYPE_QUATERNION;
+        header.set_flags(IDTP_FLAG_AUTH);
+
+        let mut frame = IdtpFrame::new();
+        frame.set_header(&header);
+        frame.set_payload(&payload);
+        frame.set_extensions(&block[..size]).unwrap();
+        frame.update_integrity();
+
+        let mut bytes = vec![0u8; frame.frame_size()];
+        frame.pack(&mut bytes).unwrap();
+
+        let annotation = annotate(&bytes);
+        let names: Vec<_> = annotation.fields.iter().map(|f| f.name).collect();
+        assert!(annotation.is_ok());
+        assert_eq!(
+            names[14..],
+            ["extensions", "payload", "auth_tag", "trailer"]
+        );
+        assert_eq!(annotation.fields[10].value, "0x03 (auth extensions)");
+        assert_eq!(annotation.fields[14].value, "0x02[2]");
+        assert_eq!(annotation.fields[15].offset, IDTP_HEADER_SIZE + 6);
+        assert_eq!(annotation.fields[16].raw, [0xAA; 16]);
+        assert_eq!(
+            annotation.decoded,
+            Some(Ok(StandardPayload::Quaternion(Quaternion::default())))
+        );
+
+        // Encrypted payload is not decoded.
+        bytes[7] = Mode::Secure as u8;
+        let annotation = annotate(&bytes);
+        let names: Vec<_> = annotation.fields.iter().map(|f| f.name).collect();
+        assert_eq!(names[14..], ["ciphertext", "tag", "trailer"]);
+        assert_eq!(annotation.decoded, None);
+    }
+
+    #[test]
+    fn test_annotate_timestamp_extension() {
+        let mut payload = [0u8; Quaternion::SIZE];
+        Quaternion::default().encode(&mut payload).unwrap();
+
+        let mut header = IdtpHeader::new();
+        header.timestamp = 5;
+        header.payload_type = PAYLOAD_TYPE_QUATERNION;
+        header.set_timestamp_unit(TimestampUnit::Ticks);
+
+        let mut frame = IdtpFrame::new();
+        frame.set_header(&header);
+        frame.set_payload(&payload);
+        let extension = TimestampExtension {
+            timestamp_high: 1,
+            tick_rate: 32_768,
+        };
+        frame.set_timestamp_extension(&extension).unwrap();
+        frame.update_integrity();
+
+        let mut bytes = vec![0u8; frame.frame_size()];
+        frame.pack(&mut bytes).unwrap();
+
+        let annotation = annotate(&bytes);
+        assert!(annotation.is_ok());
+        assert_eq!(annotation.fields[5].value, "5 ticks");
+        assert_eq!(annotation.fields[11].value, "ticks");
+        assert_eq!(annotation.fields[14].name, "timestamp_ext");
+        assert_eq!(
+            annotation.fields[14].value,
+            "timestamp=4294967301 ticks tick_rate=32768 Hz"
+        );
+        assert_eq!(annotation.fields[15].raw, payload);
+        assert_eq!(
+            annotation.decoded,
+            Some(Ok(StandardPayload::Quaternion(Quaternion::default())))
+        );
+    }
+
+    #[test]
+    fn test_annotate_fec_parity() {
+        let mut frame = IdtpFrame::parse(&build_frame(Mode::Normal)).unwrap();
+        frame.protect().unwrap();
+
+        let mut bytes = vec![0u8; frame.frame_size()];
+        frame.pack(&mut bytes).unwrap();
+
+        let annotation = annotate(&bytes);
+        let parity = &annotation.fields[15];
+        assert!(annotation.is_ok());
+        assert_eq!(annotation.fields[14].raw.len(), Quaternion::SIZE);
+        assert_eq!(parity.name, "parity");
+        assert_eq!(parity.offset, IDTP_HEADER_SIZE + Quaternion::SIZE);
+        assert_eq!(parity.raw.len(), fec::FEC_PARITY_SIZE);
+        assert_eq!(
+            annotation.decoded,
+            Some(Ok(StandardPayload::Quaternion(Quaternion::default())))
+        );
+    }
+
+    #[test]
+    fn test_annotate_unknown_mode() {
+        let mut bytes = build_frame(Mode::Normal);
+        bytes[7] = 0x07;
+        bytes[10..12].fill(0);
+
+        // Plain sum of header and payload bytes with raw mode byte.
+        let end = bytes.len() - IDTP_TRAILER_SIZE;
+        let sum = bytes[..end]
+            .iter()
+            .fold(0u16, |sum, byte| sum.wrapping_add(u16::from(*byte)));
+        bytes[10..12].copy_from_slice(&sum.to_be_bytes());
+
+        let annotation = annotate(&bytes);
+        let integrity = annotation.integrity.unwrap();
+        assert_eq!(annotation.fields[2].value, "Unknown (0x07)");
+        assert_eq!(integrity.checksum.expected, sum);
+        assert!(annotation.is_ok());
+
+        // Checksum calculated as if mode was 0xff does not match.
+        let wrong = sum.wrapping_add(0xff - 0x07);
+        bytes[10..12].copy_from_slice(&wrong.to_be_bytes());
+        assert!(!annotate(&bytes).is_ok());
+    }
+
+    #[test]
+    fn test_stream_source() {
+        let frame = build_frame(Mode::Normal);
+        let mut stream = b"noise".to_vec();
+        stream.extend_from_slice(&frame);
+        stream.extend_from_slice(&frame);
+
+        assert_eq!(detect(&stream), Format::Stream);
+        assert_eq!(detect(b"\x0a\x0d\x0d\x0a"), Format::Capture);
+
+        let mut source =
+            Source::from_reader(Box::new(Cursor::new(stream)), None).unwrap();
+
+        assert_eq!(source.format(), Format::Stream);
+        assert_eq!(source.next_frame().unwrap().unwrap().data, frame);
+        assert_eq!(source.take_resyncs(), 1);
+        assert_eq!(source.next_frame().unwrap().unwrap().data, frame);
+        assert_eq!(source.take_resyncs(), 0);
+        assert_eq!(source.next_frame().unwrap(), None);
+    }
+}
@@ -0,0 +1,344 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Impairment simulator tests.
+
+extern crate idtp_tools;
+
+#[cfg(test)]
+mod tests {
+    use idtp::payload::Payload;
+    use idtp::reliable::*;
+    use idtp::*;
+    use idtp_tools::netem::*;
+
+    fn build_frame(mode: Mode, sequence: u32) -> Vec<u8> {
+        let mut header = IdtpHeader::new();
+        header.mode = mode;
+        header.sequence = sequence;
+
+        let mut frame = IdtpFrame::new();
+        frame.set_header(&header);
+        frame.set_payload(&[0x5a; 24]);
+
+        if mode == Mode::Fec {
+            frame.protect().unwrap();
+        } else {
+            frame.update_integrity();
+        }
+
+        let mut bytes = vec![0u8; frame.frame_size()];
+        frame.pack(&mut bytes).unwrap();
+        bytes
+    }
+
+    fn run(
+        config: Impairment,
+        seed: u64,
+        mode: Mode,
+    ) -> (Vec<Delivery>, Report) {
+        let mut netem = Netem::new(config, seed);
+
+        for sequence in 0..1000 {
+            netem
+                .send(u64::from(sequence) * 1000, &build_frame(mode, sequence));
+        }
+
+        (netem.flush(), netem.report().clone())
+    }
+
+    #[test]
+    fn test_clean_channel() {
+        let config = Impairment {
+            delay_us: 500,
+            ..Impairment::default()
+        };
+
+        let (deliveries, report) = run(config, 1, Mode::Normal);
+
+        assert_eq!(deliveries.len(), 1000);
+        assert_eq!(report.corrupted, 0);
+        assert_eq!(report.modes[&0x00].frames, 1000);
+
+        for (sequence, delivery) in deliveries.iter().enumerate() {
+            assert_eq!(delivery.time_us, sequence as u64 * 1000 + 500);
+            assert_eq!(
+                delivery.data,
+                build_frame(Mode::Normal, sequence as u32)
+            );
+        }
+    }
+
+    #[test]
+    fn test_impairments_are_reproducible() {
+        let config = Impairment {
+            bit_error_rate: 1e-3,
+            burst_rate: 0.05,
+            burst_length: 16,
+            drop_rate: 0.05,
+            duplicate_rate: 0.05,
+            reorder_rate: 0.05,
+            delay_us: 1000,
+            jitter_us: 3000,
+        };
+
+        let (first, report) = run(config, 7, Mode::Safety);
+        let (second, _) = run(config, 7, Mode::Safety);
+        let (third, _) = run(config, 8, Mode::Safety);
+
+        assert_eq!(first, second);
+        assert_ne!(first, third);
+
+        assert!(report.dropped > 0 && report.duplicated > 0);
+        assert!(report.reordered > 0 && report.corrupted > 0);
+        assert_eq!(
+            first.len() as u64,
+            report.sent - report.dropped + report.duplicated
+        );
+        assert!(first.windows(2).all(|w| w[0].time_us <= w[1].time_us));
+
+        let detection = report.modes[&0x01];
+        assert_eq!(detection.corrupted, report.corrupted);
+        assert_eq!(detection.caught + detection.missed, detection.corrupted);
+        assert_eq!(
+            first.iter().filter(|d| d.corrupted).count() as u64,
+            report.corrupted
+        );
+        assert!(first.iter().all(|d| d.corrupted || is_accepted(&d.data)));
+    }
+
+    #[test]
+    fn test_reordering_and_detection() {
+        let config = Impairment {
+            reorder_rate: 1.0,
+            delay_us: 10,
+            ..Impairment::default()
+        };
+
+        let mut netem = Netem::new(config, 1);
+        netem.send(0, &build_frame(Mode::Normal, 0));
+        netem.send(1, &build_frame(Mode::Normal, 1));
+
+        assert_eq!(netem.next_due(), Some(11));
+        assert_eq!(netem.receive(10), None);
+        assert_eq!(netem.receive(11).unwrap().data[19], 1);
+        assert_eq!(netem.receive(11).unwrap().data[19], 0);
+        assert_eq!(netem.report().reordered, 1);
+
+        // Held packet is delivered in order once traffic stops.
+        netem.send(20, &build_frame(Mode::Normal, 2));
+        assert_eq!(netem.next_due(), Some(30));
+        assert_eq!(netem.receive(29), None);
+        assert_eq!(netem.receive(30).unwrap().data[19], 2);
+        assert_eq!(netem.next_due(), None);
+
+        // Next packet sent after maximum latency does not overtake it.
+        netem.send(40, &build_frame(Mode::Normal, 3));
+        netem.send(51, &build_frame(Mode::Normal, 4));
+        let deliveries = netem.flush();
+        assert_eq!(deliveries[0].data[19], 3);
+        assert_eq!(deliveries[0].time_us, 50);
+        assert_eq!(netem.report().reordered, 1);
+
+        let config = Impairment {
+            burst_rate: 1.0,
+            burst_length: 64,
+            ..Impairment::default()
+        };
+
+        let (_, normal) = run(config, 3, Mode::Normal);
+        let (_, safety) = run(config, 3, Mode::Safety);
+
+        assert_eq!(normal.modes[&0x00].corrupted, 1000);
+        assert_eq!(safety.modes[&0x01].caught, 1000);
+        assert!(normal.modes[&0x00].caught <= safety.modes[&0x01].caught);
+    }
+
+    #[test]
+    fn test_fec_correction() {
+        let config = Impairment {
+            bit_error_rate: 2e-3,
+            burst_rate: 0.02,
+            burst_length: 4,
+            ..Impairment::default()
+        };
+
+        let (_, safety) = run(config, 5, Mode::Safety);
+        let (deliveries, fec) = run(config, 5, Mode::Fec);
+
+        let safety = safety.modes[&0x01];
+        let detection = fec.modes[&0x03];
+        assert!(detection.corrupted > 0);
+        assert_eq!(
+            detection.caught + detection.corrected + detection.missed,
+            detection.corrupted
+        );
+        assert_eq!(safety.corrected, 0);
+        assert_eq!(detection.missed, 0);
+        assert!(detection.corrected > detection.caught);
+        assert!(detection.corrected_bytes >= detection.corrected);
+
+        // Corrected frames are delivered intact after correction.
+        for delivery in deliveries.iter().filter(|d| d.corrupted) {
+            let mut received = delivery.data.clone();
+
+            if fec::correct(&mut received).is_ok() {
+                assert!(is_accepted(&received));
+            }
+        }
+    }
+
+    fn pack(frame: &IdtpFrame) -> Vec<u8> {
+        let mut bytes = vec![0u8; frame.frame_size()];
+        frame.pack(&mut bytes).unwrap();
+        bytes
+    }
+
+    fn control_frame<P: Payload>(payload: &P) -> Vec<u8> {
+        let mut header = IdtpHeader::new();
+        header.mode = Mode::Safety;
+        header.payload_type = P::PAYLOAD_TYPE;
+
+        let mut buffer = [0u8; IDTP_PAYLOAD_MAX_SIZE];
+        let size = payload.encode(&mut buffer).unwrap();
+
+        let mut frame = IdtpFrame::new();
+        frame.set_header(&header);
+        frame.set_payload(&buffer[..size]);
+        frame.update_integrity();
+        pack(&frame)
+    }
+
+    #[test]
+    fn test_reliable_delivery() {
+        let config = Impairment {
+            bit_error_rate: 1e-4,
+            drop_rate: 0.2,
+            duplicate_rate: 0.05,
+            reorder_rate: 0.1,
+            delay_us: 2000,
+            jitter_us: 1000,
+            ..Impairment::default()
+        };
+        let mut uplink = Netem::new(config, 11);
+        let mut downlink = Netem::new(config, 12);
+
+        let mut sender = ReliableSender::<8>::new(ReliableConfig {
+            timeout_us: 10_000,
+            max_retries: 10,
+        });
+        let mut receiver = ReliableReceiver::new();
+        let mut received = Vec::new();
+        let mut next = 0u32;
+        let mut now_us = 0;
+
+        // Calibration dump of 200 frames.
+        while next < 200 || sender.pending() > 0 {
+            assert!(now_us < 60_000_000, "upload did not finish");
+
+            while next < 200 && !sender.is_full() {
+                let mut header = IdtpHeader::new();
+                header.mode = Mode::Safety;
+                header.sequence = next;
+                header.set_flags(IDTP_FLAG_RELIABLE);
+
+                let mut frame = IdtpFrame::new();
+                frame.set_header(&header);
+                frame.set_payload(&next.to_be_bytes());
+                frame.update_integrity();
+
+                sender.send(&frame, now_us).unwrap();
+                uplink.send(now_us, &pack(&frame));
+                next += 1;
+            }
+
+            while let Some(event) = sender.poll(now_us) {
+                match event {
+                    SenderEvent::Retransmit(frame) => {
+                        uplink.send(now_us, &pack(frame));
+                    }
+                    SenderEvent::Expired(_) => panic!("frame expired"),
+                }
+            }
+
+            while let Some(delivery) = uplink.receive(now_us) {
+                if !is_accepted(&delivery.data) {
+                    continue;
+                }
+
+                let frame = IdtpFrame::parse(&delivery.data).unwrap();
+                let ack = match receiver.receive(frame.header().sequence) {
+                    Reception::New(ack) => {
+                        received.push(frame.payload().to_vec());
+                        ack
+                    }
+                    Reception::Duplicate(ack) => ack,
+                    Reception::Stale => continue,
+                };
+
+                downlink.send(now_us, &control_frame(&ack));
+            }
+
+            while let Some(delivery) = downlink.receive(now_us) {
+                if is_accepted(&delivery.data) {
+                    let frame = IdtpFrame::parse(&delivery.data).unwrap();
+                    let ack = Ack::decode(frame.payload()).unwrap();
+                    sender.acknowledge(&ack, now_us);
+                }
+            }
+
+            now_us += 500;
+        }
+
+        // Every frame is delivered exactly once despite loss and duplicates.
+        received.sort();
+        let expected: Vec<Vec<u8>> =
+            (0..200u32).map(|i| i.to_be_bytes().to_vec()).collect();
+        assert_eq!(received, expected);
+        assert!(uplink.report().dropped > 0 && downlink.report().dropped > 0);
+        assert!(uplink.report().duplicated > 0);
+    }
+
+    #[test]
+    fn test_reliable_expiry() {
+        let config = Impairment {
+            drop_rate: 1.0,
+            ..Impairment::default()
+        };
+        let mut uplink = Netem::new(config, 1);
+        let mut sender = ReliableSender::<2>::new(ReliableConfig {
+            timeout_us: 1000,
+            max_retries: 3,
+        });
+
+        let mut header = IdtpHeader::new();
+        header.sequence = 5;
+        header.set_flags(IDTP_FLAG_RELIABLE);
+        let mut frame = IdtpFrame::new();
+        frame.set_header(&header);
+        frame.update_integrity();
+
+        sender.send(&frame, 0).unwrap();
+        uplink.send(0, &pack(&frame));
+
+        let mut expired = Vec::new();
+
+        for now_us in (0..10_000).step_by(100) {
+            while let Some(event) = sender.poll(now_us) {
+                match event {
+                    SenderEvent::Retransmit(frame) => {
+                        uplink.send(now_us, &pack(frame));
+                    }
+                    SenderEvent::Expired(header) => {
+                        expired.push((now_us, header.sequence));
+                    }
+                }
+            }
+        }
+
+        // Original transmission and 3 retransmissions are lost.
+        assert_eq!(expired, [(4000, 5)]);
+        assert_eq!(uplink.report().sent, 4);
+        assert!(uplink.flush().is_empty());
+    }
+}
@@ -0,0 +1,33 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Fuzzing of streaming IDTP deframer.
+
+#![no_main]
+
+use idtp::{Deframer, IDTP_PACKET_MAX_SIZE, IdtpFrame};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&chunk_size, stream)) = data.split_first() else {
+        return;
+    };
+
+    let mut deframer = Deframer::new();
+    let mut framed = 0usize;
+
+    // Split stream into chunks to exercise partial frames.
+    for chunk in stream.chunks(usize::from(chunk_size).max(1)) {
+        deframer.feed(chunk, |frame| {
+            assert!(frame.len() <= IDTP_PACKET_MAX_SIZE);
+            assert!(IdtpFrame::parse(frame).is_ok());
+            framed += frame.len();
+        });
+    }
+
+    // Every byte is either framed, discarded or still buffered.
+    assert_eq!(
+        framed as u64 + deframer.discarded() + deframer.buffered() as u64,
+        stream.len() as u64
+    );
+});
@@ -0,0 +1,56 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Differential fuzzing of Rust and C IDTP parsers.
+
+#![no_main]
+
+use idtp::{IDTP_HEADER_SIZE, IdtpFrame, IdtpHeader};
+use idtp_conformance as c;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // Unvalidated conversion must never panic and must match C one.
+    let rust = IdtpFrame::from(data);
+    let c = c::frame_from_bytes(data);
+    let mut rust_header = rust.header().as_bytes_be();
+
+    // Unknown modes are normalized by Rust header.
+    rust_header[7] = data.get(7).copied().unwrap_or_default();
+
+    assert_eq!(c::header_to_bytes(&c.header), rust_header);
+    assert_eq!(rust.payload(), c.payload());
+
+    match (IdtpFrame::parse(data), c::parse(data)) {
+        (Ok(rust), Ok(c)) => {
+            let header = &data[..IDTP_HEADER_SIZE];
+            let mut rust_header = rust.header().as_bytes_be();
+
+            // Unknown modes are normalized by Rust header.
+            rust_header[7] = header[7];
+
+            assert_eq!(rust_header, header);
+            assert_eq!(c::header_to_bytes(&c.header), header);
+            assert_eq!(rust.payload(), c.payload());
+        }
+        (Err(rust), Err(c)) => assert_eq!(rust, c),
+        (rust, c) => {
+            panic!("Parsers disagree: Rust {:?}, C {:?}", rust.err(), c.err())
+        }
+    }
+
+    if let Some(bytes) = data.first_chunk::<IDTP_HEADER_SIZE>() {
+        let rust = IdtpHeader::from(&bytes[..]);
+        let c = c::header_from_bytes(bytes);
+
+        assert_eq!({ rust.device_id }, { c.device_id });
+        assert_eq!({ rust.checksum }, { c.checksum });
+        assert_eq!({ rust.timestamp }, { c.timestamp });
+        assert_eq!({ rust.sequence }, { c.sequence });
+        assert_eq!({ rust.crc }, { c.crc });
+        assert_eq!({ rust.payload_size }, { c.payload_size });
+        assert_eq!(rust.payload_type, c.payload_type);
+        assert_eq!(rust.reserved, c.reserved);
+        assert_eq!(c::header_to_bytes(&c), *bytes);
+    }
+});
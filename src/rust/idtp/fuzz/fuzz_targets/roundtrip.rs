@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Fuzzing of IDTP frame pack to parse round-trip.
+
+#![no_main]
+
+use idtp::{IDTP_PAYLOAD_MAX_SIZE, IdtpFrame, IdtpHeader, Mode};
+use idtp_conformance as c;
+use libfuzzer_sys::{
+    arbitrary::{self, Arbitrary},
+    fuzz_target,
+};
+
+/// Arbitrary frame contents.
+#[derive(Debug, Arbitrary)]
+struct Input {
+    safety: bool,
+    device_id: u16,
+    timestamp: u32,
+    sequence: u32,
+    payload_type: u8,
+    reserved: [u8; 3],
+    payload: Vec<u8>,
+}
+
+fuzz_target!(|input: Input| {
+    let payload =
+        &input.payload[..input.payload.len().min(IDTP_PAYLOAD_MAX_SIZE)];
+
+    let mut header = IdtpHeader::new();
+    header.mode = if input.safety {
+        Mode::Safety
+    } else {
+        Mode::Normal
+    };
+    header.device_id = input.device_id;
+    header.timestamp = input.timestamp;
+    header.sequence = input.sequence;
+    header.payload_type = input.payload_type;
+    header.reserved = input.reserved;
+
+    let mut frame = IdtpFrame::new();
+    frame.set_header(&header);
+    frame.set_payload(payload);
+    frame.update_integrity();
+
+    let mut bytes = vec![0u8; frame.frame_size()];
+    frame.pack(&mut bytes).unwrap();
+
+    let parsed = IdtpFrame::parse(&bytes).unwrap();
+    assert_eq!(parsed.header().as_bytes_be(), frame.header().as_bytes_be());
+    assert_eq!(parsed.payload(), payload);
+    assert!(parsed.verify().is_ok());
+
+    let c = c::parse(&bytes).unwrap();
+    assert_eq!(c.payload(), payload);
+    assert_eq!(c::pack(&c.header, c.payload()), bytes);
+});
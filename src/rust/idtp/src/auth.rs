@@ -0,0 +1,107 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Frame authentication without encryption.
+//!
+//! Authenticated frame has `IDTP_FLAG_AUTH` header flag set and its payload
+//! section ends with `IDTP_AUTH_TAG_SIZE` bytes tag, which is counted in
+//! header `payload_size`. Tag is HMAC-SHA256 over header bytes (with
+//! `checksum` and `crc` fields zeroed) and payload without tag, truncated to
+//! the first `IDTP_AUTH_TAG_SIZE` bytes. Receivers which do not support
+//! authentication see valid frame with tag at the end of payload.
+
+use crate::{IdtpError, IdtpHeader, covered_header};
+use core::fmt;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Size of frame authentication tag in bytes.
+pub const IDTP_AUTH_TAG_SIZE: usize = 16;
+
+/// Pre-shared frame authentication key.
+#[derive(Clone)]
+pub struct AuthKey {
+    /// HMAC-SHA256 instance initialized with the key.
+    mac: Hmac<Sha256>,
+}
+
+impl AuthKey {
+    /// Construct new `AuthKey` struct.
+    ///
+    /// # Parameters
+    /// - `key` - given raw key bytes (32 bytes are recommended).
+    ///
+    /// # Returns
+    /// - New `AuthKey` struct.
+    pub fn new(key: &[u8]) -> Self {
+        Self {
+            // HMAC accepts keys of any size.
+            mac: Hmac::new_from_slice(key).expect("HMAC key of any size"),
+        }
+    }
+
+    /// Calculate HMAC-SHA256 over header and payload.
+    fn mac(&self, header: &IdtpHeader, payload: &[u8]) -> Hmac<Sha256> {
+        let mut mac = self.mac.clone();
+        mac.update(&covered_header(header));
+        mac.update(payload);
+        mac
+    }
+
+    /// Calculate frame authentication tag.
+    ///
+    /// # Parameters
+    /// - `header` - given frame header with final `payload_size`.
+    /// - `payload` - given payload bytes without tag.
+    ///
+    /// # Returns
+    /// - Frame authentication tag.
+    pub fn tag(
+        &self,
+        header: &IdtpHeader,
+        payload: &[u8],
+    ) -> [u8; IDTP_AUTH_TAG_SIZE] {
+        let mut tag = [0u8; IDTP_AUTH_TAG_SIZE];
+        let bytes = self.mac(header, payload).finalize().into_bytes();
+        tag.copy_from_slice(&bytes[..IDTP_AUTH_TAG_SIZE]);
+        tag
+    }
+
+    /// Verify frame authentication tag in constant time.
+    ///
+    /// # Parameters
+    /// - `header` - given frame header.
+    /// - `payload` - given payload bytes without tag.
+    /// - `tag` - given frame authentication tag.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if tag does not match.
+    pub fn verify(
+        &self,
+        header: &IdtpHeader,
+        payload: &[u8],
+        tag: &[u8; IDTP_AUTH_TAG_SIZE],
+    ) -> Result<(), IdtpError> {
+        self.mac(header, payload)
+            .verify_truncated_left(tag)
+            .map_err(|_| IdtpError::AuthenticationFailed)
+    }
+}
+
+impl fmt::Debug for AuthKey {
+    /// Format key without exposing key material.
+    ///
+    /// # Parameters
+    /// - `f` - given formatter.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuthKey").finish_non_exhaustive()
+    }
+}
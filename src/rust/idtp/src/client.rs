@@ -0,0 +1,579 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Host-side client of IDTP command channel.
+//!
+//! `Client` sends `CommandRequest` frames over `Transport`, waits for the
+//! `CommandResponse` with matching `request_id` and retransmits request if
+//! response does not arrive in time. Other frames received while waiting
+//! (e.g. IMU data) are kept in bounded queue and can be taken with
+//! `Client::take_frame`, the oldest ones are dropped when queue is full.
+
+use crate::{
+    IdtpFrame, IdtpHeader, Mode,
+    command::{
+        Command, CommandError, CommandRequest, CommandResponse, DeviceInfo,
+        Reply, Sensor,
+    },
+    payload::{PAYLOAD_TYPE_COMMAND_RESPONSE, Payload},
+    recording::unix_time_us,
+    sync::SyncSample,
+};
+use std::{
+    collections::VecDeque,
+    fmt, io,
+    net::UdpSocket,
+    time::{Duration, Instant},
+    vec,
+    vec::Vec,
+};
+
+/// Default time to wait for command response.
+pub const CLIENT_DEFAULT_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// Default number of request retransmissions.
+pub const CLIENT_DEFAULT_RETRIES: u32 = 2;
+
+/// Default maximum number of frames kept while waiting for responses.
+pub const CLIENT_DEFAULT_QUEUE_SIZE: usize = 256;
+
+/// Frame-oriented link between host and device.
+pub trait Transport {
+    /// Send raw IDTP frame.
+    ///
+    /// # Parameters
+    /// - `frame` - given packed frame.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if frame could not be sent.
+    fn send(&mut self, frame: &[u8]) -> io::Result<()>;
+
+    /// Receive raw IDTP frame.
+    ///
+    /// # Parameters
+    /// - `timeout` - given maximum time to wait.
+    ///
+    /// # Returns
+    /// - Packed frame - in case of success.
+    /// - `None` - if no frame was received in time.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if receiving failed.
+    fn receive(&mut self, timeout: Duration) -> io::Result<Option<Vec<u8>>>;
+}
+
+impl Transport for UdpSocket {
+    /// Send raw IDTP frame to connected peer.
+    ///
+    /// # Parameters
+    /// - `frame` - given packed frame.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if socket is not connected or sending failed.
+    fn send(&mut self, frame: &[u8]) -> io::Result<()> {
+        UdpSocket::send(self, frame).map(|_| ())
+    }
+
+    /// Receive raw IDTP frame from connected peer.
+    ///
+    /// # Parameters
+    /// - `timeout` - given maximum time to wait.
+    ///
+    /// # Returns
+    /// - Packed frame - in case of success.
+    /// - `None` - if no frame was received in time.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if receiving failed.
+    fn receive(&mut self, timeout: Duration) -> io::Result<Option<Vec<u8>>> {
+        // Zero duration is rejected by `set_read_timeout`.
+        self.set_read_timeout(Some(timeout.max(Duration::from_millis(1))))?;
+        let mut buffer = vec![0u8; 65_536];
+
+        match self.recv(&mut buffer) {
+            Ok(size) => {
+                buffer.truncate(size);
+                Ok(Some(buffer))
+            }
+            Err(error)
+                if matches!(
+                    error.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) =>
+            {
+                Ok(None)
+            }
+            Err(error) => Err(error),
+        }
+    }
+}
+
+/// Command client error.
+#[derive(Debug)]
+pub enum ClientError {
+    /// Transport failed.
+    Io(io::Error),
+    /// No response was received after all retransmissions.
+    Timeout,
+    /// Device rejected command (NACK).
+    Nack(CommandError),
+    /// Response does not match the requested command or its timestamp unit
+    /// is unknown.
+    InvalidResponse,
+}
+
+impl fmt::Display for ClientError {
+    /// Format client error as human-readable message.
+    ///
+    /// # Parameters
+    /// - `f` - given formatter.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Transport error: {error}"),
+            Self::Timeout => f.write_str("Command response timed out"),
+            Self::Nack(error) => write!(f, "Command rejected: {error}"),
+            Self::InvalidResponse => {
+                f.write_str("Response does not match command")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<io::Error> for ClientError {
+    /// Convert I/O error to client error.
+    ///
+    /// # Parameters
+    /// - `error` - given I/O error.
+    ///
+    /// # Returns
+    /// - Client error.
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// Host-side command client.
+#[derive(Debug)]
+pub struct Client<T: Transport> {
+    /// Link to device.
+    transport: T,
+    /// Device identifier used in request headers.
+    device_id: u16,
+    /// Identifier of the next request.
+    next_request_id: u16,
+    /// Sequence number of the next request frame.
+    sequence: u32,
+    /// Time to wait for each response.
+    timeout: Duration,
+    /// Number of request retransmissions.
+    retries: u32,
+    /// Frames received while waiting for responses.
+    frames: VecDeque<Vec<u8>>,
+    /// Maximum number of kept frames.
+    queue_size: usize,
+    /// Number of frames dropped because queue was full.
+    dropped_frames: u64,
+}
+
+impl<T: Transport> Client<T> {
+    /// Construct new `Client` struct.
+    ///
+    /// # Parameters
+    /// - `transport` - given link to device.
+    /// - `device_id` - given identifier of device.
+    ///
+    /// # Returns
+    /// - New `Client` struct.
+    pub fn new(transport: T, device_id: u16) -> Self {
+        Self {
+            transport,
+            device_id,
+            next_request_id: 0,
+            sequence: 0,
+            timeout: CLIENT_DEFAULT_TIMEOUT,
+            retries: CLIENT_DEFAULT_RETRIES,
+            frames: VecDeque::new(),
+            queue_size: CLIENT_DEFAULT_QUEUE_SIZE,
+            dropped_frames: 0,
+        }
+    }
+
+    /// Set time to wait for response before request is retransmitted.
+    ///
+    /// # Parameters
+    /// - `timeout` - given response timeout.
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    /// Set number of request retransmissions.
+    ///
+    /// # Parameters
+    /// - `retries` - given number of retransmissions.
+    pub fn set_retries(&mut self, retries: u32) {
+        self.retries = retries;
+    }
+
+    /// Set maximum number of frames kept while waiting for responses. The
+    /// oldest frames above the limit are dropped, `0` disables keeping.
+    ///
+    /// # Parameters
+    /// - `queue_size` - given maximum number of kept frames.
+    pub fn set_queue_size(&mut self, queue_size: usize) {
+        self.queue_size = queue_size;
+
+        while self.frames.len() > queue_size {
+            self.frames.pop_front();
+            self.dropped_frames += 1;
+        }
+    }
+
+    /// Get number of frames dropped because queue was full.
+    ///
+    /// # Returns
+    /// - Number of dropped frames.
+    pub fn dropped_frames(&self) -> u64 {
+        self.dropped_frames
+    }
+
+    /// Get link to device.
+    ///
+    /// # Returns
+    /// - Reference to transport.
+    pub fn transport(&mut self) -> &mut T {
+        &mut self.transport
+    }
+
+    /// Take the oldest frame which was received while waiting for response
+    /// and is not a command response.
+    ///
+    /// # Returns
+    /// - Packed frame - if any.
+    /// - `None` - otherwise.
+    pub fn take_frame(&mut self) -> Option<Vec<u8>> {
+        self.frames.pop_front()
+    }
+
+    /// Send command and wait for its response.
+    ///
+    /// # Parameters
+    /// - `command` - given command.
+    ///
+    /// # Returns
+    /// - Command result - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if transport fails.
+    /// - Will return `Err` if no response was received in time.
+    /// - Will return `Err` if device answered with NACK.
+    pub fn execute(&mut self, command: Command) -> Result<Reply, ClientError> {
+        let request = self.next_request(command);
+
+        for _ in 0..=self.retries {
+            self.send(&request)?;
+
+            if let Some((_, response)) = self.wait(request.request_id)? {
+                return Self::reply(&request, &response);
+            }
+        }
+
+        Err(ClientError::Timeout)
+    }
+
+    /// Run clock synchronization ping exchange (see `sync` module).
+    ///
+    /// # Returns
+    /// - Ping exchange result with host times from `unix_time_us` - in case
+    ///   of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` in the same cases as `execute` or if response
+    ///   timestamps are device ticks with unknown tick rate.
+    pub fn sync(&mut self) -> Result<SyncSample, ClientError> {
+        for _ in 0..=self.retries {
+            // Each attempt uses new request, so late response to previous
+            // one is not paired with the wrong send time.
+            let request = self.next_request(Command::Ping);
+            let t1 = self.send(&request)?;
+
+            if let Some((frame, response)) = self.wait(request.request_id)? {
+                let t4 = unix_time_us();
+
+                // Device timestamps are counted in unit of response frame.
+                let tick_rate =
+                    frame.timestamp_extension().map_or(0, |ext| ext.tick_rate);
+                let period_us =
+                    frame.header().timestamp_unit().period_us(tick_rate);
+
+                return match (Self::reply(&request, &response)?, period_us) {
+                    (Reply::Pong { received, sent }, Some(period_us)) => {
+                        Ok(SyncSample {
+                            t1,
+                            t2: received,
+                            t3: sent,
+                            t4,
+                            period_us,
+                        })
+                    }
+                    _ => Err(ClientError::InvalidResponse),
+                };
+            }
+        }
+
+        Err(ClientError::Timeout)
+    }
+
+    /// Get device identification.
+    ///
+    /// # Returns
+    /// - Device identification - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` in the same cases as `execute`.
+    pub fn device_info(&mut self) -> Result<DeviceInfo, ClientError> {
+        match self.execute(Command::GetDeviceInfo)? {
+            Reply::DeviceInfo(info) => Ok(info),
+            _ => Err(ClientError::InvalidResponse),
+        }
+    }
+
+    /// Get output data rate.
+    ///
+    /// # Returns
+    /// - Output data rate in Hz - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` in the same cases as `execute`.
+    pub fn output_data_rate(&mut self) -> Result<u16, ClientError> {
+        match self.execute(Command::GetOutputDataRate)? {
+            Reply::OutputDataRate(rate) => Ok(rate),
+            _ => Err(ClientError::InvalidResponse),
+        }
+    }
+
+    /// Set output data rate.
+    ///
+    /// # Parameters
+    /// - `rate` - given output data rate in Hz.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` in the same cases as `execute`.
+    pub fn set_output_data_rate(
+        &mut self,
+        rate: u16,
+    ) -> Result<(), ClientError> {
+        self.execute(Command::SetOutputDataRate(rate)).map(|_| ())
+    }
+
+    /// Get full-scale range of sensor.
+    ///
+    /// # Parameters
+    /// - `sensor` - given sensor.
+    ///
+    /// # Returns
+    /// - Sensor range - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` in the same cases as `execute`.
+    pub fn sensor_range(&mut self, sensor: Sensor) -> Result<u16, ClientError> {
+        match self.execute(Command::GetSensorRange(sensor))? {
+            Reply::SensorRange(actual, range) if actual == sensor => Ok(range),
+            _ => Err(ClientError::InvalidResponse),
+        }
+    }
+
+    /// Set full-scale range of sensor.
+    ///
+    /// # Parameters
+    /// - `sensor` - given sensor.
+    /// - `range` - given sensor range.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` in the same cases as `execute`.
+    pub fn set_sensor_range(
+        &mut self,
+        sensor: Sensor,
+        range: u16,
+    ) -> Result<(), ClientError> {
+        self.execute(Command::SetSensorRange(sensor, range))
+            .map(|_| ())
+    }
+
+    /// Get operating mode.
+    ///
+    /// # Returns
+    /// - Operating mode - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` in the same cases as `execute`.
+    pub fn mode(&mut self) -> Result<Mode, ClientError> {
+        match self.execute(Command::GetMode)? {
+            Reply::Mode(mode) => Ok(mode),
+            _ => Err(ClientError::InvalidResponse),
+        }
+    }
+
+    /// Set operating mode.
+    ///
+    /// # Parameters
+    /// - `mode` - given operating mode.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` in the same cases as `execute`.
+    pub fn set_mode(&mut self, mode: Mode) -> Result<(), ClientError> {
+        self.execute(Command::SetMode(mode)).map(|_| ())
+    }
+
+    /// Set device clock.
+    ///
+    /// # Parameters
+    /// - `timestamp` - given header timestamp value to continue from.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` in the same cases as `execute`.
+    pub fn set_time(&mut self, timestamp: u32) -> Result<(), ClientError> {
+        self.execute(Command::SetTime(timestamp)).map(|_| ())
+    }
+
+    /// Construct request with new identifier.
+    fn next_request(&mut self, command: Command) -> CommandRequest {
+        let request = CommandRequest {
+            request_id: self.next_request_id,
+            command,
+        };
+        self.next_request_id = self.next_request_id.wrapping_add(1);
+        request
+    }
+
+    /// Get command result from response.
+    fn reply(
+        request: &CommandRequest,
+        response: &CommandResponse,
+    ) -> Result<Reply, ClientError> {
+        if response.command != request.command.code() {
+            return Err(ClientError::InvalidResponse);
+        }
+
+        response.result.map_err(ClientError::Nack)
+    }
+
+    /// Send request frame and return host send time.
+    fn send(&mut self, request: &CommandRequest) -> Result<u64, ClientError> {
+        let mut payload = [0u8; CommandRequest::SIZE];
+        // Buffer is allocated with the exact payload size.
+        request
+            .encode(&mut payload)
+            .expect("buffer of payload size");
+
+        let mut header = IdtpHeader::new();
+        header.device_id = self.device_id;
+        header.sequence = self.sequence;
+        header.payload_type = CommandRequest::PAYLOAD_TYPE;
+        self.sequence = self.sequence.wrapping_add(1);
+
+        let mut frame = IdtpFrame::new();
+        frame.set_header(&header);
+        frame.set_payload(&payload);
+        frame.update_integrity();
+
+        let mut bytes = vec![0u8; frame.frame_size()];
+        // Buffer is allocated with the exact frame size.
+        frame.pack(&mut bytes).expect("buffer of frame size");
+
+        let time = unix_time_us();
+        self.transport.send(&bytes)?;
+        Ok(time)
+    }
+
+    /// Wait for response to request with given identifier.
+    fn wait(
+        &mut self,
+        request_id: u16,
+    ) -> Result<Option<(IdtpFrame, CommandResponse)>, ClientError> {
+        let deadline = Instant::now() + self.timeout;
+
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+
+            if remaining.is_zero() {
+                return Ok(None);
+            }
+
+            let Some(bytes) = self.transport.receive(remaining)? else {
+                return Ok(None);
+            };
+
+            let response = IdtpFrame::parse(&bytes).ok().filter(|frame| {
+                let header = frame.header();
+                header.payload_type == PAYLOAD_TYPE_COMMAND_RESPONSE
+                    && header.device_id == self.device_id
+                    && frame.verify().is_ok()
+            });
+
+            let Some(frame) = response else {
+                self.keep(bytes);
+                continue;
+            };
+
+            // Late responses to retransmitted or abandoned requests are
+            // dropped.
+            match CommandResponse::decode(frame.payload()) {
+                Ok(response) if response.request_id == request_id => {
+                    return Ok(Some((frame, response)));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Keep frame received while waiting for response.
+    fn keep(&mut self, bytes: Vec<u8>) {
+        if self.frames.len() >= self.queue_size {
+            self.dropped_frames += 1;
+
+            if self.frames.pop_front().is_none() {
+                return;
+            }
+        }
+
+        self.frames.push_back(bytes);
+    }
+}
@@ -0,0 +1,650 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Command channel for configuring IMU devices.
+//!
+//! Host sends `CommandRequest` control payloads and device answers each of
+//! them with `CommandResponse` carrying the same `request_id`. Response with
+//! zero status is an ACK with command result, any other status is a NACK
+//! with `CommandError` code. Device firmware implements `CommandHandler`
+//! and passes received request payloads to `CommandHandler::dispatch`.
+
+use crate::{
+    IdtpError, Mode, Version,
+    payload::{
+        PAYLOAD_TYPE_COMMAND_REQUEST, PAYLOAD_TYPE_COMMAND_RESPONSE, Payload,
+    },
+};
+
+/// Get device information command code.
+pub const COMMAND_GET_DEVICE_INFO: u8 = 0x01;
+
+/// Get output data rate command code.
+pub const COMMAND_GET_OUTPUT_DATA_RATE: u8 = 0x02;
+
+/// Set output data rate command code.
+pub const COMMAND_SET_OUTPUT_DATA_RATE: u8 = 0x03;
+
+/// Get sensor range command code.
+pub const COMMAND_GET_SENSOR_RANGE: u8 = 0x04;
+
+/// Set sensor range command code.
+pub const COMMAND_SET_SENSOR_RANGE: u8 = 0x05;
+
+/// Get operating mode command code.
+pub const COMMAND_GET_MODE: u8 = 0x06;
+
+/// Set operating mode command code.
+pub const COMMAND_SET_MODE: u8 = 0x07;
+
+/// Set device time command code.
+pub const COMMAND_SET_TIME: u8 = 0x08;
+
+/// Clock synchronization ping command code.
+pub const COMMAND_PING: u8 = 0x09;
+
+/// Size of command arguments in bytes.
+const ARGUMENTS_SIZE: usize = 4;
+
+/// Size of command result data in bytes.
+const DATA_SIZE: usize = 8;
+
+/// IMU sensor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Sensor {
+    /// Accelerometer (range in g).
+    Accelerometer = 0x00,
+    /// Gyroscope (range in degrees per second).
+    Gyroscope = 0x01,
+    /// Magnetometer (range in microtesla).
+    Magnetometer = 0x02,
+}
+
+impl TryFrom<u8> for Sensor {
+    type Error = CommandError;
+
+    /// Convert byte to IMU sensor.
+    ///
+    /// # Parameters
+    /// - `byte` - given byte to convert.
+    ///
+    /// # Returns
+    /// - IMU sensor - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if byte does not match any sensor.
+    fn try_from(byte: u8) -> Result<Self, Self::Error> {
+        match byte {
+            0x00 => Ok(Self::Accelerometer),
+            0x01 => Ok(Self::Gyroscope),
+            0x02 => Ok(Self::Magnetometer),
+            _ => Err(CommandError::InvalidArgument),
+        }
+    }
+}
+
+/// Command NACK error code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum CommandError {
+    /// Command code is not known to device.
+    UnknownCommand = 0x01,
+    /// Command argument is malformed or out of range.
+    InvalidArgument = 0x02,
+    /// Command is known but not supported by device.
+    Unsupported = 0x03,
+    /// Device can not execute command right now.
+    Busy = 0x04,
+    /// Command execution failed.
+    Failed = 0x05,
+}
+
+impl From<u8> for CommandError {
+    /// Convert non-zero response status to command error.
+    ///
+    /// # Parameters
+    /// - `byte` - given status byte.
+    ///
+    /// # Returns
+    /// - Command error (`Failed` for unknown codes).
+    fn from(byte: u8) -> Self {
+        match byte {
+            0x01 => Self::UnknownCommand,
+            0x02 => Self::InvalidArgument,
+            0x03 => Self::Unsupported,
+            0x04 => Self::Busy,
+            _ => Self::Failed,
+        }
+    }
+}
+
+impl core::fmt::Display for CommandError {
+    /// Format command error as human-readable message.
+    ///
+    /// # Parameters
+    /// - `f` - given formatter.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let message = match self {
+            Self::UnknownCommand => "Command is not known to device",
+            Self::InvalidArgument => "Command argument is invalid",
+            Self::Unsupported => "Command is not supported by device",
+            Self::Busy => "Device is busy",
+            Self::Failed => "Command execution failed",
+        };
+
+        f.write_str(message)
+    }
+}
+
+impl core::error::Error for CommandError {}
+
+/// Device identification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeviceInfo {
+    /// Firmware version.
+    pub firmware_version: Version,
+    /// Hardware revision.
+    pub hardware_revision: u8,
+    /// Serial number.
+    pub serial_number: u32,
+}
+
+/// Device command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    /// Get device identification.
+    GetDeviceInfo,
+    /// Get output data rate.
+    GetOutputDataRate,
+    /// Set output data rate in Hz.
+    SetOutputDataRate(u16),
+    /// Get full-scale range of sensor.
+    GetSensorRange(Sensor),
+    /// Set full-scale range of sensor.
+    SetSensorRange(Sensor, u16),
+    /// Get operating mode.
+    GetMode,
+    /// Set operating mode.
+    SetMode(Mode),
+    /// Set device clock to given header timestamp value.
+    SetTime(u32),
+    /// Get device clock for synchronization (see `sync` module).
+    Ping,
+}
+
+impl Command {
+    /// Get command code.
+    ///
+    /// # Returns
+    /// - Command code.
+    pub const fn code(&self) -> u8 {
+        match self {
+            Self::GetDeviceInfo => COMMAND_GET_DEVICE_INFO,
+            Self::GetOutputDataRate => COMMAND_GET_OUTPUT_DATA_RATE,
+            Self::SetOutputDataRate(_) => COMMAND_SET_OUTPUT_DATA_RATE,
+            Self::GetSensorRange(_) => COMMAND_GET_SENSOR_RANGE,
+            Self::SetSensorRange(..) => COMMAND_SET_SENSOR_RANGE,
+            Self::GetMode => COMMAND_GET_MODE,
+            Self::SetMode(_) => COMMAND_SET_MODE,
+            Self::SetTime(_) => COMMAND_SET_TIME,
+            Self::Ping => COMMAND_PING,
+        }
+    }
+
+    /// Serialize command arguments.
+    fn encode_arguments(&self) -> [u8; ARGUMENTS_SIZE] {
+        let mut arguments = [0u8; ARGUMENTS_SIZE];
+
+        match *self {
+            Self::GetDeviceInfo
+            | Self::GetOutputDataRate
+            | Self::GetMode
+            | Self::Ping => {}
+            Self::SetOutputDataRate(rate) => {
+                arguments[0..2].copy_from_slice(&rate.to_be_bytes());
+            }
+            Self::GetSensorRange(sensor) => arguments[0] = sensor as u8,
+            Self::SetSensorRange(sensor, range) => {
+                arguments[0] = sensor as u8;
+                arguments[1..3].copy_from_slice(&range.to_be_bytes());
+            }
+            Self::SetMode(mode) => arguments[0] = mode as u8,
+            Self::SetTime(timestamp) => {
+                arguments.copy_from_slice(&timestamp.to_be_bytes());
+            }
+        }
+
+        arguments
+    }
+
+    /// Deserialize command from code and arguments.
+    fn decode(code: u8, arguments: &[u8]) -> Result<Self, CommandError> {
+        let word = u16::from_be_bytes([arguments[0], arguments[1]]);
+
+        match code {
+            COMMAND_GET_DEVICE_INFO => Ok(Self::GetDeviceInfo),
+            COMMAND_GET_OUTPUT_DATA_RATE => Ok(Self::GetOutputDataRate),
+            COMMAND_SET_OUTPUT_DATA_RATE => Ok(Self::SetOutputDataRate(word)),
+            COMMAND_GET_SENSOR_RANGE => {
+                Ok(Self::GetSensorRange(Sensor::try_from(arguments[0])?))
+            }
+            COMMAND_SET_SENSOR_RANGE => Ok(Self::SetSensorRange(
+                Sensor::try_from(arguments[0])?,
+                u16::from_be_bytes([arguments[1], arguments[2]]),
+            )),
+            COMMAND_GET_MODE => Ok(Self::GetMode),
+            COMMAND_SET_MODE => match Mode::from(arguments[0]) {
+                Mode::Unknown => Err(CommandError::InvalidArgument),
+                mode => Ok(Self::SetMode(mode)),
+            },
+            COMMAND_SET_TIME => Ok(Self::SetTime(u32::from_be_bytes([
+                arguments[0],
+                arguments[1],
+                arguments[2],
+                arguments[3],
+            ]))),
+            COMMAND_PING => Ok(Self::Ping),
+            _ => Err(CommandError::UnknownCommand),
+        }
+    }
+}
+
+/// Result of successfully executed command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reply {
+    /// Command without result data was executed.
+    Ack,
+    /// Device identification.
+    DeviceInfo(DeviceInfo),
+    /// Output data rate in Hz.
+    OutputDataRate(u16),
+    /// Full-scale range of sensor.
+    SensorRange(Sensor, u16),
+    /// Operating mode.
+    Mode(Mode),
+    /// Device clock values when ping request was received and when response
+    /// was sent.
+    Pong {
+        /// Header timestamp value at request reception.
+        received: u32,
+        /// Header timestamp value at response transmission.
+        sent: u32,
+    },
+}
+
+impl Reply {
+    /// Serialize reply data.
+    fn encode_data(&self) -> [u8; DATA_SIZE] {
+        let mut data = [0u8; DATA_SIZE];
+
+        match *self {
+            Self::Ack => {}
+            Self::DeviceInfo(info) => {
+                data[0..3].copy_from_slice(&info.firmware_version.as_bytes());
+                data[3] = info.hardware_revision;
+                data[4..8].copy_from_slice(&info.serial_number.to_be_bytes());
+            }
+            Self::OutputDataRate(rate) => {
+                data[0..2].copy_from_slice(&rate.to_be_bytes());
+            }
+            Self::SensorRange(sensor, range) => {
+                data[0] = sensor as u8;
+                data[1..3].copy_from_slice(&range.to_be_bytes());
+            }
+            Self::Mode(mode) => data[0] = mode as u8,
+            Self::Pong { received, sent } => {
+                data[0..4].copy_from_slice(&received.to_be_bytes());
+                data[4..8].copy_from_slice(&sent.to_be_bytes());
+            }
+        }
+
+        data
+    }
+
+    /// Deserialize reply data of command with given code.
+    fn decode(code: u8, data: &[u8]) -> Result<Self, IdtpError> {
+        match code {
+            COMMAND_GET_DEVICE_INFO => Ok(Self::DeviceInfo(DeviceInfo {
+                firmware_version: Version::from(&data[0..3]),
+                hardware_revision: data[3],
+                serial_number: u32::from_be_bytes([
+                    data[4], data[5], data[6], data[7],
+                ]),
+            })),
+            COMMAND_GET_OUTPUT_DATA_RATE => {
+                Ok(Self::OutputDataRate(u16::from_be_bytes([data[0], data[1]])))
+            }
+            COMMAND_GET_SENSOR_RANGE => Ok(Self::SensorRange(
+                Sensor::try_from(data[0])
+                    .map_err(|_| IdtpError::InvalidPayload)?,
+                u16::from_be_bytes([data[1], data[2]]),
+            )),
+            COMMAND_GET_MODE => Ok(Self::Mode(Mode::from(data[0]))),
+            COMMAND_PING => Ok(Self::Pong {
+                received: u32::from_be_bytes([
+                    data[0], data[1], data[2], data[3],
+                ]),
+                sent: u32::from_be_bytes([data[4], data[5], data[6], data[7]]),
+            }),
+            COMMAND_SET_OUTPUT_DATA_RATE
+            | COMMAND_SET_SENSOR_RANGE
+            | COMMAND_SET_MODE
+            | COMMAND_SET_TIME => Ok(Self::Ack),
+            _ => Err(IdtpError::InvalidPayload),
+        }
+    }
+}
+
+/// Command request message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommandRequest {
+    /// Identifier which correlates request with its response.
+    pub request_id: u16,
+    /// Requested command.
+    pub command: Command,
+}
+
+/// Command response message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommandResponse {
+    /// Identifier of answered request.
+    pub request_id: u16,
+    /// Code of answered command.
+    pub command: u8,
+    /// Command result (ACK) or error code (NACK).
+    pub result: Result<Reply, CommandError>,
+}
+
+impl Payload for CommandRequest {
+    const PAYLOAD_TYPE: u8 = PAYLOAD_TYPE_COMMAND_REQUEST;
+    const SIZE: usize = 3 + ARGUMENTS_SIZE;
+
+    fn encode(&self, buffer: &mut [u8]) -> Result<usize, IdtpError> {
+        if buffer.len() < Self::SIZE {
+            return Err(IdtpError::BufferTooSmall);
+        }
+
+        buffer[0..2].copy_from_slice(&self.request_id.to_be_bytes());
+        buffer[2] = self.command.code();
+        buffer[3..Self::SIZE].copy_from_slice(&self.command.encode_arguments());
+        Ok(Self::SIZE)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, IdtpError> {
+        if bytes.len() != Self::SIZE {
+            return Err(IdtpError::InvalidPayload);
+        }
+
+        Ok(Self {
+            request_id: u16::from_be_bytes([bytes[0], bytes[1]]),
+            command: Command::decode(bytes[2], &bytes[3..])
+                .map_err(|_| IdtpError::InvalidPayload)?,
+        })
+    }
+}
+
+impl Payload for CommandResponse {
+    const PAYLOAD_TYPE: u8 = PAYLOAD_TYPE_COMMAND_RESPONSE;
+    const SIZE: usize = 4 + DATA_SIZE;
+
+    fn encode(&self, buffer: &mut [u8]) -> Result<usize, IdtpError> {
+        if buffer.len() < Self::SIZE {
+            return Err(IdtpError::BufferTooSmall);
+        }
+
+        let (status, data) = match self.result {
+            Ok(reply) => (0, reply.encode_data()),
+            Err(error) => (error as u8, [0u8; DATA_SIZE]),
+        };
+
+        buffer[0..2].copy_from_slice(&self.request_id.to_be_bytes());
+        buffer[2] = self.command;
+        buffer[3] = status;
+        buffer[4..Self::SIZE].copy_from_slice(&data);
+        Ok(Self::SIZE)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, IdtpError> {
+        if bytes.len() != Self::SIZE {
+            return Err(IdtpError::InvalidPayload);
+        }
+
+        let result = match bytes[3] {
+            0 => Ok(Reply::decode(bytes[2], &bytes[4..])?),
+            status => Err(CommandError::from(status)),
+        };
+
+        Ok(Self {
+            request_id: u16::from_be_bytes([bytes[0], bytes[1]]),
+            command: bytes[2],
+            result,
+        })
+    }
+}
+
+impl CommandResponse {
+    /// Set transmission time of ping response. Firmware should call it right
+    /// before response is sent, as `CommandHandler::dispatch` fills it with
+    /// reception time.
+    ///
+    /// # Parameters
+    /// - `timestamp` - given header timestamp value at transmission.
+    pub fn set_sent(&mut self, timestamp: u32) {
+        if let Ok(Reply::Pong { sent, .. }) = &mut self.result {
+            *sent = timestamp;
+        }
+    }
+}
+
+/// Device-side command handler implemented by firmware.
+///
+/// Every command has a handler method; commands without overridden handler
+/// are answered with `CommandError::Unsupported`.
+pub trait CommandHandler {
+    /// Get device identification.
+    ///
+    /// # Returns
+    /// - Device identification - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` with NACK error code to send to host.
+    fn device_info(&mut self) -> Result<DeviceInfo, CommandError>;
+
+    /// Get output data rate.
+    ///
+    /// # Returns
+    /// - Output data rate in Hz - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` with NACK error code to send to host.
+    fn output_data_rate(&mut self) -> Result<u16, CommandError> {
+        Err(CommandError::Unsupported)
+    }
+
+    /// Set output data rate.
+    ///
+    /// # Parameters
+    /// - `rate` - given output data rate in Hz.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` with NACK error code to send to host.
+    fn set_output_data_rate(&mut self, rate: u16) -> Result<(), CommandError> {
+        let _ = rate;
+        Err(CommandError::Unsupported)
+    }
+
+    /// Get full-scale range of sensor.
+    ///
+    /// # Parameters
+    /// - `sensor` - given sensor.
+    ///
+    /// # Returns
+    /// - Sensor range - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` with NACK error code to send to host.
+    fn sensor_range(&mut self, sensor: Sensor) -> Result<u16, CommandError> {
+        let _ = sensor;
+        Err(CommandError::Unsupported)
+    }
+
+    /// Set full-scale range of sensor.
+    ///
+    /// # Parameters
+    /// - `sensor` - given sensor.
+    /// - `range` - given sensor range.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` with NACK error code to send to host.
+    fn set_sensor_range(
+        &mut self,
+        sensor: Sensor,
+        range: u16,
+    ) -> Result<(), CommandError> {
+        let _ = (sensor, range);
+        Err(CommandError::Unsupported)
+    }
+
+    /// Get operating mode.
+    ///
+    /// # Returns
+    /// - Operating mode - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` with NACK error code to send to host.
+    fn mode(&mut self) -> Result<Mode, CommandError> {
+        Err(CommandError::Unsupported)
+    }
+
+    /// Set operating mode.
+    ///
+    /// # Parameters
+    /// - `mode` - given operating mode.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` with NACK error code to send to host.
+    fn set_mode(&mut self, mode: Mode) -> Result<(), CommandError> {
+        let _ = mode;
+        Err(CommandError::Unsupported)
+    }
+
+    /// Set device clock.
+    ///
+    /// # Parameters
+    /// - `timestamp` - given header timestamp value to continue from.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` with NACK error code to send to host.
+    fn set_time(&mut self, timestamp: u32) -> Result<(), CommandError> {
+        let _ = timestamp;
+        Err(CommandError::Unsupported)
+    }
+
+    /// Get current device clock value.
+    ///
+    /// # Returns
+    /// - Header timestamp value - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` with NACK error code to send to host.
+    fn timestamp(&mut self) -> Result<u32, CommandError> {
+        Err(CommandError::Unsupported)
+    }
+
+    /// Execute command request.
+    ///
+    /// # Parameters
+    /// - `request` - given command request.
+    ///
+    /// # Returns
+    /// - Command response to send to host.
+    fn handle(&mut self, request: &CommandRequest) -> CommandResponse {
+        let result = match request.command {
+            Command::GetDeviceInfo => self.device_info().map(Reply::DeviceInfo),
+            Command::GetOutputDataRate => {
+                self.output_data_rate().map(Reply::OutputDataRate)
+            }
+            Command::SetOutputDataRate(rate) => {
+                self.set_output_data_rate(rate).map(|()| Reply::Ack)
+            }
+            Command::GetSensorRange(sensor) => self
+                .sensor_range(sensor)
+                .map(|range| Reply::SensorRange(sensor, range)),
+            Command::SetSensorRange(sensor, range) => {
+                self.set_sensor_range(sensor, range).map(|()| Reply::Ack)
+            }
+            Command::GetMode => self.mode().map(Reply::Mode),
+            Command::SetMode(mode) => self.set_mode(mode).map(|()| Reply::Ack),
+            Command::SetTime(timestamp) => {
+                self.set_time(timestamp).map(|()| Reply::Ack)
+            }
+            Command::Ping => self.timestamp().map(|timestamp| Reply::Pong {
+                received: timestamp,
+                sent: timestamp,
+            }),
+        };
+
+        CommandResponse {
+            request_id: request.request_id,
+            command: request.command.code(),
+            result,
+        }
+    }
+
+    /// Decode and execute command request payload.
+    ///
+    /// # Parameters
+    /// - `payload` - given payload of `PAYLOAD_TYPE_COMMAND_REQUEST` frame.
+    ///
+    /// # Returns
+    /// - Command response to send to host (NACK for unknown commands and
+    ///   invalid arguments).
+    /// - `None` - if payload size does not match, so request can not be
+    ///   answered.
+    fn dispatch(&mut self, payload: &[u8]) -> Option<CommandResponse> {
+        if payload.len() != CommandRequest::SIZE {
+            return None;
+        }
+
+        let request_id = u16::from_be_bytes([payload[0], payload[1]]);
+
+        match Command::decode(payload[2], &payload[3..]) {
+            Ok(command) => Some(self.handle(&CommandRequest {
+                request_id,
+                command,
+            })),
+            Err(error) => Some(CommandResponse {
+                request_id,
+                command: payload[2],
+                result: Err(error),
+            }),
+        }
+    }
+}
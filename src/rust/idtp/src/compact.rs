@@ -0,0 +1,407 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Compact IDTP frames for bandwidth-constrained links (e.g. UART or radio).
+//!
+//! Compact frame consists of 12 bytes compact header followed by payload of
+//! at most 255 bytes, without trailer. Fields which rarely change (version,
+//! key identifier, timestamp and sequence high bits) are taken from
+//! `CompactContext` established by the last full IDTP frame of the device,
+//! so compact frame converts to full IDTP frame and back without loss.
+//! Integrity of compact frame is protected by CRC-16 over compact header and
+//! payload in all operating modes.
+
+use crate::{
+    Check, IDTP_FLAGS_INDEX, IDTP_KEY_ID_INDEX, IdtpError, IdtpFrame,
+    IdtpHeader, Mode, Version,
+};
+
+/// Compact header size in bytes.
+pub const COMPACT_HEADER_SIZE: usize = 12;
+
+/// Compact frame payload max size in bytes.
+pub const COMPACT_PAYLOAD_MAX_SIZE: usize = u8::MAX as usize;
+
+/// Compact frame max size in bytes.
+pub const COMPACT_FRAME_MAX_SIZE: usize =
+    COMPACT_HEADER_SIZE + COMPACT_PAYLOAD_MAX_SIZE;
+
+/// Value of the first byte of compact frame (differs from the first byte of
+/// IDTP preamble, so both frame kinds can share one link).
+pub const COMPACT_SYNC: u8 = 0xA5;
+
+/// Mask of header flags carried by compact header.
+pub const COMPACT_FLAGS_MASK: u8 = 0x3F;
+
+/// Offset of `crc` field in compact header.
+const COMPACT_CRC_OFFSET: usize = 10;
+
+/// CRC-16/CCITT-FALSE generator polynomial.
+const CRC16_POLYNOMIAL: u16 = 0x1021;
+
+/// CRC-16 lookup table.
+const CRC16_TABLE: [u16; 256] = crc16_table();
+
+/// Generate CRC-16 lookup table.
+///
+/// # Returns
+/// - CRC-16 lookup table.
+const fn crc16_table() -> [u16; 256] {
+    let mut table = [0u16; 256];
+    let mut i = 0;
+
+    while i < 256 {
+        let mut value = (i as u16) << 8;
+        let mut bit = 0;
+
+        while bit < 8 {
+            value = if value & 0x8000 != 0 {
+                (value << 1) ^ CRC16_POLYNOMIAL
+            } else {
+                value << 1
+            };
+            bit += 1;
+        }
+
+        table[i] = value;
+        i += 1;
+    }
+
+    table
+}
+
+/// Calculate CRC-16/CCITT-FALSE (`0x1021` polynomial, `0xFFFF` initial
+/// value).
+///
+/// # Parameters
+/// - `bytes` - given bytes to process.
+///
+/// # Returns
+/// - CRC-16 value.
+pub fn crc16(bytes: &[u8]) -> u16 {
+    crc16_update(0xFFFF, bytes)
+}
+
+/// Continue CRC-16 calculation.
+fn crc16_update(crc: u16, bytes: &[u8]) -> u16 {
+    bytes.iter().fold(crc, |crc, byte| {
+        let index = ((crc >> 8) ^ u16::from(*byte)) & 0xFF;
+        (crc << 8) ^ CRC16_TABLE[index as usize]
+    })
+}
+
+/// Get 2-bit compact encoding of operating mode.
+fn mode_bits(mode: Mode) -> Option<u8> {
+    match mode {
+        Mode::Normal => Some(0),
+        Mode::Safety => Some(1),
+        Mode::Secure => Some(2),
+        Mode::Fec => Some(3),
+        Mode::Unknown => None,
+    }
+}
+
+/// Compact IDTP header struct.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CompactHeader {
+    /// Protocol operating mode (`Mode::Unknown` is not representable).
+    pub mode: Mode,
+    /// Header flags (bits covered by `COMPACT_FLAGS_MASK`).
+    pub flags: u8,
+    /// Vendor-specific unique IMU device identifier.
+    pub device_id: u16,
+    /// Low 16 bits of sequence number.
+    pub sequence: u16,
+    /// Timestamp relative to context base timestamp.
+    pub timestamp_delta: u16,
+    /// Packet payload type.
+    pub payload_type: u8,
+    /// Size of packet payload in bytes.
+    pub payload_size: u8,
+    /// CRC-16 over compact header (without `crc` field) and payload.
+    pub crc: u16,
+}
+
+impl CompactHeader {
+    /// Convert compact header to bytes. `Mode::Unknown` must not be used,
+    /// it is encoded as `Mode::Normal`.
+    ///
+    /// # Returns
+    /// - Compact header bytes (big-endian byte order).
+    pub fn as_bytes_be(&self) -> [u8; COMPACT_HEADER_SIZE] {
+        let mode = mode_bits(self.mode);
+        debug_assert!(mode.is_some(), "Unknown mode is not representable");
+        let mode = mode.unwrap_or_default();
+
+        let mut bytes = [0u8; COMPACT_HEADER_SIZE];
+        bytes[0] = COMPACT_SYNC;
+        bytes[1] = (mode << 6) | (self.flags & COMPACT_FLAGS_MASK);
+        bytes[2..4].copy_from_slice(&self.device_id.to_be_bytes());
+        bytes[4..6].copy_from_slice(&self.sequence.to_be_bytes());
+        bytes[6..8].copy_from_slice(&self.timestamp_delta.to_be_bytes());
+        bytes[8] = self.payload_type;
+        bytes[9] = self.payload_size;
+        bytes[10..12].copy_from_slice(&self.crc.to_be_bytes());
+        bytes
+    }
+
+    /// Convert bytes to compact header.
+    ///
+    /// # Parameters
+    /// - `bytes` - given compact header bytes (big-endian byte order).
+    ///
+    /// # Returns
+    /// - Compact header.
+    pub fn from_bytes_be(bytes: &[u8; COMPACT_HEADER_SIZE]) -> Self {
+        Self {
+            mode: Mode::from(bytes[1] >> 6),
+            flags: bytes[1] & COMPACT_FLAGS_MASK,
+            device_id: u16::from_be_bytes([bytes[2], bytes[3]]),
+            sequence: u16::from_be_bytes([bytes[4], bytes[5]]),
+            timestamp_delta: u16::from_be_bytes([bytes[6], bytes[7]]),
+            payload_type: bytes[8],
+            payload_size: bytes[9],
+            crc: u16::from_be_bytes([bytes[10], bytes[11]]),
+        }
+    }
+}
+
+/// Compact IDTP frame struct.
+#[derive(Debug, Clone, Copy)]
+pub struct CompactFrame {
+    /// Compact header.
+    header: CompactHeader,
+    /// Value that containing IMU data.
+    payload: [u8; COMPACT_PAYLOAD_MAX_SIZE],
+}
+
+impl CompactFrame {
+    /// Get compact header.
+    ///
+    /// # Returns
+    /// - Compact header struct.
+    pub fn header(&self) -> CompactHeader {
+        self.header
+    }
+
+    /// Get payload.
+    ///
+    /// # Returns
+    /// - Payload in bytes representation.
+    pub fn payload(&self) -> &[u8] {
+        &self.payload[..self.header.payload_size as usize]
+    }
+
+    /// Get size of raw compact frame in bytes.
+    ///
+    /// # Returns
+    /// - Size of compact header and payload in bytes.
+    pub fn frame_size(&self) -> usize {
+        COMPACT_HEADER_SIZE + self.header.payload_size as usize
+    }
+
+    /// Calculate CRC-16 over compact header and payload.
+    fn crc(&self) -> u16 {
+        let header = self.header.as_bytes_be();
+        crc16_update(crc16(&header[..COMPACT_CRC_OFFSET]), self.payload())
+    }
+
+    /// Verify compact frame CRC-16.
+    ///
+    /// # Returns
+    /// - CRC verification result.
+    pub fn verify(&self) -> Check<u16> {
+        Check {
+            expected: self.crc(),
+            actual: self.header.crc,
+        }
+    }
+
+    /// Parse raw compact frame validating its structure (see `verify` for
+    /// integrity verification).
+    ///
+    /// # Parameters
+    /// - `bytes` - given raw compact frame.
+    ///
+    /// # Returns
+    /// - Compact frame struct - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if frame is shorter than compact header, does not
+    ///   start with `COMPACT_SYNC` or `payload_size` does not match frame
+    ///   size.
+    pub fn parse(bytes: &[u8]) -> Result<Self, IdtpError> {
+        let header = bytes
+            .first_chunk::<COMPACT_HEADER_SIZE>()
+            .ok_or(IdtpError::FrameTooShort)?;
+
+        if header[0] != COMPACT_SYNC {
+            return Err(IdtpError::InvalidPreamble);
+        }
+
+        let header = CompactHeader::from_bytes_be(header);
+        let payload = &bytes[COMPACT_HEADER_SIZE..];
+
+        if payload.len() != header.payload_size as usize {
+            return Err(IdtpError::PayloadSizeMismatch);
+        }
+
+        let mut frame = Self {
+            header,
+            payload: [0u8; COMPACT_PAYLOAD_MAX_SIZE],
+        };
+        frame.payload[..payload.len()].copy_from_slice(payload);
+        Ok(frame)
+    }
+
+    /// Pack into raw compact frame.
+    ///
+    /// # Parameters
+    /// - `buffer` - given buffer to store raw compact frame.
+    ///
+    /// # Returns
+    /// - Size of compact frame in bytes - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if buffer is too small or header mode is
+    ///   `Mode::Unknown`.
+    pub fn pack(&self, buffer: &mut [u8]) -> Result<usize, IdtpError> {
+        let size = self.frame_size();
+
+        if mode_bits(self.header.mode).is_none() {
+            return Err(IdtpError::InvalidMode);
+        }
+
+        if buffer.len() < size {
+            return Err(IdtpError::BufferTooSmall);
+        }
+
+        buffer[..COMPACT_HEADER_SIZE]
+            .copy_from_slice(&self.header.as_bytes_be());
+        buffer[COMPACT_HEADER_SIZE..size].copy_from_slice(self.payload());
+        Ok(size)
+    }
+}
+
+/// Per-device context shared by sender and receiver of compact frames.
+///
+/// Context is established by full IDTP frame: sender sends full frame when
+/// compact frame can not represent it (e.g. timestamp delta overflows) and
+/// both sides then construct new context from its header.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CompactContext {
+    /// Protocol version of device frames.
+    pub version: Version,
+    /// Identifier of the key used in Secure mode.
+    pub key_id: u8,
+    /// Base timestamp of `timestamp_delta` values.
+    pub base_timestamp: u32,
+    /// Sequence number of the last frame, used to restore sequence high
+    /// bits.
+    pub sequence: u32,
+}
+
+impl CompactContext {
+    /// Construct new `CompactContext` struct from full IDTP header.
+    ///
+    /// # Parameters
+    /// - `header` - given header of full IDTP frame.
+    ///
+    /// # Returns
+    /// - New `CompactContext` struct.
+    pub fn new(header: &IdtpHeader) -> Self {
+        Self {
+            version: header.version,
+            key_id: header.key_id(),
+            base_timestamp: header.timestamp,
+            sequence: header.sequence,
+        }
+    }
+
+    /// Convert full IDTP frame to compact frame and remember its sequence
+    /// number.
+    ///
+    /// # Parameters
+    /// - `frame` - given full IDTP frame.
+    ///
+    /// # Returns
+    /// - Compact frame - if frame is representable in this context.
+    /// - `None` - otherwise (full frame must be sent instead), context is
+    ///   left unchanged.
+    pub fn compress(&mut self, frame: &IdtpFrame) -> Option<CompactFrame> {
+        let header = frame.header();
+        let flags = header.flags();
+        let sequence_step = header.sequence.wrapping_sub(self.sequence) as i32;
+
+        let representable = mode_bits(header.mode).is_some()
+            && header.version == self.version
+            && header.key_id() == self.key_id
+            && header.reserved[2] == 0
+            && flags & !COMPACT_FLAGS_MASK == 0
+            && i16::try_from(sequence_step).is_ok()
+            && frame.payload_size() <= COMPACT_PAYLOAD_MAX_SIZE;
+
+        let delta = header.timestamp.wrapping_sub(self.base_timestamp);
+
+        if !representable || delta > u32::from(u16::MAX) {
+            return None;
+        }
+
+        let mut compact = CompactFrame {
+            header: CompactHeader {
+                mode: header.mode,
+                flags,
+                device_id: header.device_id,
+                sequence: header.sequence as u16,
+                timestamp_delta: delta as u16,
+                payload_type: header.payload_type,
+                payload_size: frame.payload_size() as u8,
+                crc: 0,
+            },
+            payload: [0u8; COMPACT_PAYLOAD_MAX_SIZE],
+        };
+        compact.payload[..frame.payload_size()]
+            .copy_from_slice(frame.payload());
+        compact.header.crc = compact.crc();
+
+        self.sequence = header.sequence;
+        Some(compact)
+    }
+
+    /// Convert compact frame to full IDTP frame with filled `checksum` and
+    /// `crc` fields and remember its sequence number. Compact frame must be
+    /// verified beforehand.
+    ///
+    /// # Parameters
+    /// - `compact` - given compact frame.
+    ///
+    /// # Returns
+    /// - Full IDTP frame.
+    pub fn expand(&mut self, compact: &CompactFrame) -> IdtpFrame {
+        let low = compact.header.sequence;
+        let step = low.wrapping_sub(self.sequence as u16) as i16;
+        let sequence = self.sequence.wrapping_add_signed(step.into());
+
+        let mut header = IdtpHeader::new();
+        header.version = self.version;
+        header.mode = compact.header.mode;
+        header.device_id = compact.header.device_id;
+        header.timestamp = self
+            .base_timestamp
+            .wrapping_add(compact.header.timestamp_delta.into());
+        header.sequence = sequence;
+        header.payload_type = compact.header.payload_type;
+        header.reserved[IDTP_FLAGS_INDEX] = compact.header.flags;
+        header.reserved[IDTP_KEY_ID_INDEX] = self.key_id;
+
+        let mut frame = IdtpFrame::new();
+        frame.set_header(&header);
+        frame.set_payload(compact.payload());
+        frame.update_integrity();
+
+        self.sequence = sequence;
+        frame
+    }
+}
@@ -0,0 +1,199 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Standard IDTP control payloads.
+//!
+//! Hello exchange lets host and device agree on protocol version, operating
+//! modes, header flags and maximum frame size: host sends `Hello` with its
+//! capabilities, device answers with `HelloReply` carrying its own ones and
+//! both sides use `Capabilities::negotiate` result. Layout of these payloads
+//! does not depend on protocol version, so peers of incompatible versions can
+//! still detect each other.
+
+use crate::{
+    IDTP_FLAG_AUTH, IDTP_FLAG_EXTENSIONS, IDTP_FLAG_RELIABLE,
+    IDTP_FLAG_TIMESTAMP_EXT, IDTP_PACKET_MAX_SIZE, IDTP_PACKET_MIN_SIZE,
+    IDTP_VERSION, IdtpError, Mode, Version,
+    payload::{PAYLOAD_TYPE_HELLO, PAYLOAD_TYPE_HELLO_REPLY, Payload},
+};
+
+/// Supported features of IDTP implementation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Protocol version.
+    pub version: Version,
+    /// Supported operating modes (bit `n` is set if mode with value `n` is
+    /// supported, see `Capabilities::mode_bit`).
+    pub modes: u8,
+    /// Supported header flags.
+    pub flags: u8,
+    /// Maximum frame size in bytes.
+    pub max_frame_size: u16,
+}
+
+impl Capabilities {
+    /// Size of encoded capabilities in bytes.
+    pub const SIZE: usize = 7;
+
+    /// Get capabilities of this implementation: current version, Normal,
+    /// Safety and FEC modes, extension block, timestamp extension and
+    /// reliable delivery flags, Secure mode and authentication flag if
+    /// corresponding features are enabled and maximum frame size.
+    ///
+    /// # Returns
+    /// - Capabilities of this implementation.
+    pub fn local() -> Self {
+        let mut modes = Self::mode_bit(Mode::Normal)
+            | Self::mode_bit(Mode::Safety)
+            | Self::mode_bit(Mode::Fec);
+        let mut flags =
+            IDTP_FLAG_EXTENSIONS | IDTP_FLAG_TIMESTAMP_EXT | IDTP_FLAG_RELIABLE;
+
+        if cfg!(feature = "secure") {
+            modes |= Self::mode_bit(Mode::Secure);
+        }
+
+        if cfg!(feature = "auth") {
+            flags |= IDTP_FLAG_AUTH;
+        }
+
+        Self {
+            version: IDTP_VERSION,
+            modes,
+            flags,
+            max_frame_size: IDTP_PACKET_MAX_SIZE as u16,
+        }
+    }
+
+    /// Get bit of operating mode in `modes` mask.
+    ///
+    /// # Parameters
+    /// - `mode` - given operating mode.
+    ///
+    /// # Returns
+    /// - Mode bit - for modes with values below 8.
+    /// - `0` - otherwise.
+    pub const fn mode_bit(mode: Mode) -> u8 {
+        match mode as u8 {
+            value @ 0..8 => 1 << value,
+            _ => 0,
+        }
+    }
+
+    /// Check whether operating mode is supported.
+    ///
+    /// # Parameters
+    /// - `mode` - given operating mode.
+    ///
+    /// # Returns
+    /// - `true`  - if mode is supported.
+    /// - `false` - otherwise.
+    pub const fn supports_mode(&self, mode: Mode) -> bool {
+        self.modes & Self::mode_bit(mode) != 0
+    }
+
+    /// Agree on capabilities with peer: the lowest of both versions, modes
+    /// and flags supported by both sides (Normal mode is always supported)
+    /// and the smallest maximum frame size.
+    ///
+    /// # Parameters
+    /// - `peer` - given capabilities of peer.
+    ///
+    /// # Returns
+    /// - Agreed capabilities - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if versions are not compatible.
+    /// - Will return `Err` if maximum frame size of any side is smaller than
+    ///   `IDTP_PACKET_MIN_SIZE`.
+    pub fn negotiate(&self, peer: &Capabilities) -> Result<Self, IdtpError> {
+        if !self.version.is_compatible(&peer.version) {
+            return Err(IdtpError::InvalidVersion);
+        }
+
+        let max_frame_size = self.max_frame_size.min(peer.max_frame_size);
+
+        if usize::from(max_frame_size) < IDTP_PACKET_MIN_SIZE {
+            return Err(IdtpError::InvalidPayload);
+        }
+
+        Ok(Self {
+            version: self.version.min(peer.version),
+            modes: self.modes & peer.modes | Self::mode_bit(Mode::Normal),
+            flags: self.flags & peer.flags,
+            max_frame_size,
+        })
+    }
+
+    /// Serialize capabilities.
+    fn encode(&self, buffer: &mut [u8]) -> Result<usize, IdtpError> {
+        if buffer.len() < Self::SIZE {
+            return Err(IdtpError::BufferTooSmall);
+        }
+
+        buffer[0..3].copy_from_slice(&self.version.as_bytes());
+        buffer[3] = self.modes;
+        buffer[4] = self.flags;
+        buffer[5..7].copy_from_slice(&self.max_frame_size.to_be_bytes());
+        Ok(Self::SIZE)
+    }
+
+    /// Deserialize capabilities.
+    fn decode(bytes: &[u8]) -> Result<Self, IdtpError> {
+        if bytes.len() != Self::SIZE {
+            return Err(IdtpError::InvalidPayload);
+        }
+
+        Ok(Self {
+            version: Version::from(&bytes[0..3]),
+            modes: bytes[3],
+            flags: bytes[4],
+            max_frame_size: u16::from_be_bytes([bytes[5], bytes[6]]),
+        })
+    }
+}
+
+impl Default for Capabilities {
+    /// Construct capabilities of this implementation (see `local`).
+    ///
+    /// # Returns
+    /// - Capabilities of this implementation.
+    fn default() -> Self {
+        Self::local()
+    }
+}
+
+/// Hello message with host capabilities.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Hello(pub Capabilities);
+
+/// Hello reply message with device capabilities.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct HelloReply(pub Capabilities);
+
+impl Payload for Hello {
+    const PAYLOAD_TYPE: u8 = PAYLOAD_TYPE_HELLO;
+    const SIZE: usize = Capabilities::SIZE;
+
+    fn encode(&self, buffer: &mut [u8]) -> Result<usize, IdtpError> {
+        self.0.encode(buffer)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, IdtpError> {
+        Capabilities::decode(bytes).map(Self)
+    }
+}
+
+impl Payload for HelloReply {
+    const PAYLOAD_TYPE: u8 = PAYLOAD_TYPE_HELLO_REPLY;
+    const SIZE: usize = Capabilities::SIZE;
+
+    fn encode(&self, buffer: &mut [u8]) -> Result<usize, IdtpError> {
+        self.0.encode(buffer)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, IdtpError> {
+        Capabilities::decode(bytes).map(Self)
+    }
+}
@@ -0,0 +1,204 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Streaming IDTP deframer - extraction of IDTP frames from byte streams
+//! (UART, TCP, files and etc).
+
+use crate::{
+    IDTP_HEADER_SIZE, IDTP_PACKET_MAX_SIZE, IDTP_PREAMBLE, IDTP_TRAILER,
+    IDTP_TRAILER_SIZE, IDTP_VERSION, IdtpHeader,
+};
+
+/// Streaming IDTP deframer.
+///
+/// Bytes are accumulated in the fixed-size internal buffer until complete
+/// frame (valid preamble, compatible version, `payload_size` and trailer) is
+/// found. Bytes which can not be the start of a frame are discarded and
+/// counted as a resync.
+#[derive(Debug, Clone)]
+pub struct Deframer {
+    /// Buffer of received bytes.
+    buffer: [u8; IDTP_PACKET_MAX_SIZE],
+    /// Number of bytes stored in buffer.
+    len: usize,
+    /// Size of frame returned by the last `next_frame` call.
+    consumed: usize,
+    /// Whether bytes are being discarded right now.
+    discarding: bool,
+    /// Number of times stream synchronization was lost.
+    resyncs: u64,
+    /// Total number of discarded bytes.
+    discarded: u64,
+}
+
+impl Deframer {
+    /// Construct new `Deframer` struct.
+    ///
+    /// # Returns
+    /// - New `Deframer` struct.
+    pub const fn new() -> Self {
+        Self {
+            buffer: [0u8; IDTP_PACKET_MAX_SIZE],
+            len: 0,
+            consumed: 0,
+            discarding: false,
+            resyncs: 0,
+            discarded: 0,
+        }
+    }
+
+    /// Get number of times stream synchronization was lost, i.e. number of
+    /// runs of bytes which had to be discarded to find the next preamble.
+    ///
+    /// # Returns
+    /// - Number of preamble resyncs.
+    pub fn resyncs(&self) -> u64 {
+        self.resyncs
+    }
+
+    /// Get total number of discarded bytes.
+    ///
+    /// # Returns
+    /// - Number of discarded bytes.
+    pub fn discarded(&self) -> u64 {
+        self.discarded
+    }
+
+    /// Get number of buffered bytes which are not part of a complete frame
+    /// yet.
+    ///
+    /// # Returns
+    /// - Number of buffered bytes.
+    pub fn buffered(&self) -> usize {
+        self.len - self.consumed
+    }
+
+    /// Drop all buffered bytes.
+    pub fn reset(&mut self) {
+        self.len = 0;
+        self.consumed = 0;
+        self.discarding = false;
+    }
+
+    /// Append bytes to the internal buffer.
+    ///
+    /// # Parameters
+    /// - `data` - given bytes received from stream.
+    ///
+    /// # Returns
+    /// - Number of bytes consumed from `data`. It is less than `data`
+    ///   length only if buffer is full and `next_frame` should be called.
+    pub fn push(&mut self, data: &[u8]) -> usize {
+        self.compact();
+
+        let size = data.len().min(IDTP_PACKET_MAX_SIZE - self.len);
+        self.buffer[self.len..self.len + size].copy_from_slice(&data[..size]);
+        self.len += size;
+        size
+    }
+
+    /// Get next complete raw IDTP frame from buffered bytes.
+    ///
+    /// # Returns
+    /// - Raw IDTP frame bytes - if complete frame is buffered.
+    /// - `None` - if more bytes are required.
+    pub fn next_frame(&mut self) -> Option<&[u8]> {
+        self.compact();
+
+        loop {
+            let prefix = self.len.min(IDTP_PREAMBLE.len());
+
+            if self.buffer[..prefix] != IDTP_PREAMBLE[..prefix] {
+                self.skip();
+                continue;
+            }
+
+            if self.len < IDTP_HEADER_SIZE {
+                return None;
+            }
+
+            let header = IdtpHeader::from(&self.buffer[..IDTP_HEADER_SIZE]);
+            let frame_size = header.frame_size();
+
+            if !IDTP_VERSION.is_compatible(&header.version)
+                || frame_size > IDTP_PACKET_MAX_SIZE
+            {
+                self.skip();
+                continue;
+            }
+
+            if self.len < frame_size {
+                return None;
+            }
+
+            if self.buffer[frame_size - IDTP_TRAILER_SIZE..frame_size]
+                != *IDTP_TRAILER
+            {
+                self.skip();
+                continue;
+            }
+
+            self.discarding = false;
+            self.consumed = frame_size;
+            return Some(&self.buffer[..frame_size]);
+        }
+    }
+
+    /// Feed bytes and handle every complete frame found.
+    ///
+    /// # Parameters
+    /// - `data` - given bytes received from stream.
+    /// - `handler` - given function to call for every complete raw frame.
+    pub fn feed<F: FnMut(&[u8])>(&mut self, mut data: &[u8], mut handler: F) {
+        loop {
+            let size = self.push(data);
+            data = &data[size..];
+
+            while let Some(frame) = self.next_frame() {
+                handler(frame);
+            }
+
+            if data.is_empty() {
+                return;
+            }
+        }
+    }
+
+    /// Remove frame returned by the last `next_frame` call.
+    fn compact(&mut self) {
+        if self.consumed > 0 {
+            self.buffer.copy_within(self.consumed..self.len, 0);
+            self.len -= self.consumed;
+            self.consumed = 0;
+        }
+    }
+
+    /// Discard bytes up to the next possible preamble start.
+    fn skip(&mut self) {
+        if !self.discarding {
+            self.discarding = true;
+            self.resyncs += 1;
+        }
+
+        let start = (1..self.len)
+            .find(|&i| {
+                let prefix = (self.len - i).min(IDTP_PREAMBLE.len());
+                self.buffer[i..i + prefix] == IDTP_PREAMBLE[..prefix]
+            })
+            .unwrap_or(self.len);
+
+        self.buffer.copy_within(start..self.len, 0);
+        self.len -= start;
+        self.discarded += start as u64;
+    }
+}
+
+impl Default for Deframer {
+    /// Construct new default `Deframer` struct.
+    ///
+    /// # Returns
+    /// - New default `Deframer` struct.
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,591 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Delta compression of standard IMU payload streams (`PAYLOAD_TYPE_DELTA`).
+//!
+//! Delta payload carries a batch of consecutive samples of one standard data
+//! payload type. Every value is converted to integer code - quantized to
+//! configured resolution of its channel group (e.g. 1 mg for accelerometer
+//! and 0.01 dps for gyroscope) or taken as raw `f32` bits for lossless
+//! compression - and transmitted as zigzag varint difference from the same
+//! value of the previous sample. Keyframes restart differences from zero and
+//! carry resolutions, so receivers can join mid-stream and recover from loss
+//! of delta frames.
+
+use crate::{
+    IdtpError,
+    payload::{
+        PAYLOAD_TYPE_ACCELEROMETER, PAYLOAD_TYPE_GYROSCOPE, PAYLOAD_TYPE_IMU6,
+        PAYLOAD_TYPE_IMU9, PAYLOAD_TYPE_MAGNETOMETER, PAYLOAD_TYPE_QUATERNION,
+        StandardPayload,
+    },
+};
+
+/// Delta payload header size in bytes (without keyframe resolutions).
+pub const DELTA_HEADER_SIZE: usize = 5;
+
+/// Keyframe flag of delta payload header.
+pub const DELTA_FLAG_KEYFRAME: u8 = 0x01;
+
+/// Maximum number of channel groups of standard data payload.
+pub const DELTA_MAX_GROUPS: usize = 3;
+
+/// Maximum number of channels (values) of standard data payload.
+pub const DELTA_MAX_CHANNELS: usize = 9;
+
+/// Maximum number of samples of single delta payload.
+pub const DELTA_MAX_SAMPLES: usize = u8::MAX as usize;
+
+/// Default number of frames between keyframes.
+pub const DELTA_KEYFRAME_INTERVAL: u16 = 50;
+
+/// Maximum size of varint encoded `u32` value in bytes.
+const VARINT_MAX_SIZE: usize = 5;
+
+/// Get sizes of channel groups sharing quantization resolution.
+///
+/// # Parameters
+/// - `payload_type` - given standard data payload type.
+///
+/// # Returns
+/// - Number of channels of every group - if payload type is supported.
+/// - `None` - otherwise.
+fn channel_groups(payload_type: u8) -> Option<&'static [usize]> {
+    match payload_type {
+        PAYLOAD_TYPE_IMU6 => Some(&[3, 3]),
+        PAYLOAD_TYPE_IMU9 => Some(&[3, 3, 3]),
+        PAYLOAD_TYPE_ACCELEROMETER
+        | PAYLOAD_TYPE_GYROSCOPE
+        | PAYLOAD_TYPE_MAGNETOMETER => Some(&[3]),
+        PAYLOAD_TYPE_QUATERNION => Some(&[4]),
+        _ => None,
+    }
+}
+
+/// Spread group resolutions over channels.
+///
+/// # Parameters
+/// - `groups` - given sizes of channel groups.
+/// - `resolutions` - given resolutions of channel groups.
+///
+/// # Returns
+/// - Resolution of every channel and number of channels.
+fn channel_resolutions(
+    groups: &[usize],
+    resolutions: &[f32; DELTA_MAX_GROUPS],
+) -> ([f32; DELTA_MAX_CHANNELS], usize) {
+    let mut channels = [0f32; DELTA_MAX_CHANNELS];
+    let mut count = 0;
+
+    for (size, resolution) in groups.iter().zip(resolutions) {
+        channels[count..count + size].fill(*resolution);
+        count += size;
+    }
+
+    (channels, count)
+}
+
+/// Check that resolution is finite and not negative.
+fn is_valid_resolution(resolution: f32) -> bool {
+    resolution.is_finite() && resolution >= 0.0
+}
+
+/// Convert value to integer code.
+///
+/// # Parameters
+/// - `value` - given sample value.
+/// - `resolution` - given quantization resolution (`0` - lossless).
+///
+/// # Returns
+/// - Value rounded to the nearest multiple of resolution (saturated to `i32`
+///   range) or raw value bits if resolution is zero.
+fn quantize(value: f32, resolution: f32) -> i32 {
+    if resolution == 0.0 {
+        return value.to_bits() as i32;
+    }
+
+    let scaled = f64::from(value) / f64::from(resolution);
+
+    if scaled < 0.0 {
+        (scaled - 0.5) as i32
+    } else {
+        (scaled + 0.5) as i32
+    }
+}
+
+/// Convert integer code back to value.
+///
+/// # Parameters
+/// - `code` - given integer code.
+/// - `resolution` - given quantization resolution (`0` - lossless).
+///
+/// # Returns
+/// - Sample value.
+fn dequantize(code: i32, resolution: f32) -> f32 {
+    if resolution == 0.0 {
+        return f32::from_bits(code as u32);
+    }
+
+    (f64::from(code) * f64::from(resolution)) as f32
+}
+
+/// Map signed value to unsigned so that small magnitudes stay small.
+const fn zigzag(value: i32) -> u32 {
+    ((value << 1) ^ (value >> 31)) as u32
+}
+
+/// Reverse `zigzag` mapping.
+const fn unzigzag(value: u32) -> i32 {
+    (value >> 1) as i32 ^ -((value & 1) as i32)
+}
+
+/// Write LEB128 varint.
+///
+/// # Parameters
+/// - `value` - given value to write.
+/// - `buffer` - given buffer to write to.
+/// - `offset` - given offset to write at, advanced past written bytes.
+///
+/// # Errors
+/// - Will return `Err` if buffer is too small.
+fn write_varint(
+    mut value: u32,
+    buffer: &mut [u8],
+    offset: &mut usize,
+) -> Result<(), IdtpError> {
+    loop {
+        let byte = buffer.get_mut(*offset).ok_or(IdtpError::BufferTooSmall)?;
+        *offset += 1;
+
+        if value < 0x80 {
+            *byte = value as u8;
+            return Ok(());
+        }
+
+        *byte = value as u8 | 0x80;
+        value >>= 7;
+    }
+}
+
+/// Read LEB128 varint.
+///
+/// # Parameters
+/// - `bytes` - given bytes to read from.
+/// - `offset` - given offset to read at, advanced past read bytes.
+///
+/// # Returns
+/// - Read value - in case of success.
+/// - `Err` - otherwise.
+///
+/// # Errors
+/// - Will return `Err` if varint is truncated or exceeds `u32` range.
+fn read_varint(bytes: &[u8], offset: &mut usize) -> Result<u32, IdtpError> {
+    let mut value = 0u32;
+
+    for i in 0..VARINT_MAX_SIZE {
+        let byte = *bytes.get(*offset).ok_or(IdtpError::InvalidPayload)?;
+        *offset += 1;
+
+        if i == VARINT_MAX_SIZE - 1 && byte > 0x0F {
+            return Err(IdtpError::InvalidPayload);
+        }
+
+        value |= u32::from(byte & 0x7F) << (7 * i);
+
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+
+    Err(IdtpError::InvalidPayload)
+}
+
+/// Get values of standard data payload in field order.
+fn sample_values(sample: &StandardPayload) -> [f32; DELTA_MAX_CHANNELS] {
+    let mut values = [0f32; DELTA_MAX_CHANNELS];
+    let mut count = 0;
+
+    sample.for_each_field(|_, value| {
+        values[count] = value;
+        count += 1;
+    });
+
+    values
+}
+
+/// Construct standard data payload from values in field order.
+fn sample_from_values(
+    payload_type: u8,
+    values: &[f32],
+) -> Result<StandardPayload, IdtpError> {
+    let mut bytes = [0u8; DELTA_MAX_CHANNELS * 4];
+
+    for (value, chunk) in values.iter().zip(bytes.chunks_exact_mut(4)) {
+        chunk.copy_from_slice(&value.to_be_bytes());
+    }
+
+    StandardPayload::decode(payload_type, &bytes[..values.len() * 4])
+        .ok_or(IdtpError::InvalidPayload)?
+}
+
+/// Delta payload header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DeltaHeader {
+    /// Standard data payload type of samples.
+    payload_type: u8,
+    /// Delta payload flags.
+    flags: u8,
+    /// Number of frames since the last keyframe (`0` for keyframe).
+    index: u16,
+    /// Number of samples.
+    count: u8,
+}
+
+impl DeltaHeader {
+    /// Serialize header.
+    fn to_bytes(self) -> [u8; DELTA_HEADER_SIZE] {
+        let [index_high, index_low] = self.index.to_be_bytes();
+        [
+            self.payload_type,
+            self.flags,
+            index_high,
+            index_low,
+            self.count,
+        ]
+    }
+
+    /// Deserialize header.
+    fn from_bytes(bytes: &[u8]) -> Result<Self, IdtpError> {
+        match *bytes {
+            [payload_type, flags, index_high, index_low, count, ..] => {
+                Ok(Self {
+                    payload_type,
+                    flags,
+                    index: u16::from_be_bytes([index_high, index_low]),
+                    count,
+                })
+            }
+            _ => Err(IdtpError::InvalidPayload),
+        }
+    }
+
+    /// Check keyframe flag.
+    const fn is_keyframe(&self) -> bool {
+        self.flags & DELTA_FLAG_KEYFRAME != 0
+    }
+}
+
+/// Delta compression parameters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeltaConfig {
+    /// Standard data payload type of samples.
+    pub payload_type: u8,
+    /// Quantization resolution of every channel group in payload units
+    /// (acceleration, angular velocity, magnetic field; single group for
+    /// one-sensor payloads and quaternion). `0` keeps values lossless.
+    pub resolutions: [f32; DELTA_MAX_GROUPS],
+    /// Number of frames between keyframes (`0` - keyframes only on request).
+    pub keyframe_interval: u16,
+}
+
+impl DeltaConfig {
+    /// Construct lossless compression parameters with default keyframe
+    /// interval.
+    ///
+    /// # Parameters
+    /// - `payload_type` - given standard data payload type of samples.
+    ///
+    /// # Returns
+    /// - New `DeltaConfig` struct.
+    pub const fn lossless(payload_type: u8) -> Self {
+        Self {
+            payload_type,
+            resolutions: [0.0; DELTA_MAX_GROUPS],
+            keyframe_interval: DELTA_KEYFRAME_INTERVAL,
+        }
+    }
+}
+
+/// Delta payload encoder of single sample stream.
+#[derive(Debug, Clone)]
+pub struct DeltaEncoder {
+    /// Compression parameters.
+    config: DeltaConfig,
+    /// Resolution of every channel.
+    resolutions: [f32; DELTA_MAX_CHANNELS],
+    /// Number of channels.
+    channels: usize,
+    /// Codes of the last encoded sample.
+    reference: [i32; DELTA_MAX_CHANNELS],
+    /// Index of the last encoded frame.
+    index: u16,
+    /// Whether the next frame must be keyframe.
+    keyframe: bool,
+}
+
+impl DeltaEncoder {
+    /// Construct new `DeltaEncoder` struct. The first frame is keyframe.
+    ///
+    /// # Parameters
+    /// - `config` - given compression parameters.
+    ///
+    /// # Returns
+    /// - New `DeltaEncoder` struct - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if payload type is not a standard data payload or
+    ///   any resolution is negative or not finite.
+    pub fn new(config: DeltaConfig) -> Result<Self, IdtpError> {
+        let groups = channel_groups(config.payload_type)
+            .ok_or(IdtpError::InvalidPayload)?;
+
+        if !config.resolutions.iter().all(|r| is_valid_resolution(*r)) {
+            return Err(IdtpError::InvalidPayload);
+        }
+
+        let (resolutions, channels) =
+            channel_resolutions(groups, &config.resolutions);
+
+        Ok(Self {
+            config,
+            resolutions,
+            channels,
+            reference: [0; DELTA_MAX_CHANNELS],
+            index: 0,
+            keyframe: true,
+        })
+    }
+
+    /// Get compression parameters.
+    ///
+    /// # Returns
+    /// - Compression parameters.
+    pub const fn config(&self) -> &DeltaConfig {
+        &self.config
+    }
+
+    /// Make the next frame keyframe (e.g. when receiver joins or reports
+    /// loss).
+    pub const fn request_keyframe(&mut self) {
+        self.keyframe = true;
+    }
+
+    /// Encode batch of consecutive samples into delta payload.
+    ///
+    /// # Parameters
+    /// - `samples` - given samples of configured payload type.
+    /// - `buffer` - given buffer to store payload bytes.
+    ///
+    /// # Returns
+    /// - Number of bytes written - in case of success.
+    /// - `Err` - otherwise, encoder state is left unchanged.
+    ///
+    /// # Errors
+    /// - Will return `Err` if there are no or more than `DELTA_MAX_SAMPLES`
+    ///   samples, any sample has different payload type or buffer is too
+    ///   small.
+    pub fn encode(
+        &mut self,
+        samples: &[StandardPayload],
+        buffer: &mut [u8],
+    ) -> Result<usize, IdtpError> {
+        if samples.is_empty() || samples.len() > DELTA_MAX_SAMPLES {
+            return Err(IdtpError::InvalidPayload);
+        }
+
+        if samples
+            .iter()
+            .any(|sample| sample.payload_type() != self.config.payload_type)
+        {
+            return Err(IdtpError::InvalidPayload);
+        }
+
+        let interval = self.config.keyframe_interval;
+        let next = self.index.wrapping_add(1);
+        let keyframe = self.keyframe || (interval != 0 && next >= interval);
+
+        let header = DeltaHeader {
+            payload_type: self.config.payload_type,
+            flags: if keyframe { DELTA_FLAG_KEYFRAME } else { 0 },
+            index: if keyframe { 0 } else { next },
+            count: samples.len() as u8,
+        };
+
+        let mut offset = DELTA_HEADER_SIZE;
+        buffer
+            .get_mut(..offset)
+            .ok_or(IdtpError::BufferTooSmall)?
+            .copy_from_slice(&header.to_bytes());
+
+        let mut reference = self.reference;
+
+        if keyframe {
+            let groups = channel_groups(header.payload_type).unwrap_or(&[]);
+
+            for resolution in &self.config.resolutions[..groups.len()] {
+                buffer
+                    .get_mut(offset..offset + 4)
+                    .ok_or(IdtpError::BufferTooSmall)?
+                    .copy_from_slice(&resolution.to_be_bytes());
+                offset += 4;
+            }
+
+            reference = [0; DELTA_MAX_CHANNELS];
+        }
+
+        for sample in samples {
+            let values = sample_values(sample);
+            let channels = values.iter().zip(&self.resolutions);
+
+            for (i, (value, resolution)) in
+                channels.take(self.channels).enumerate()
+            {
+                let code = quantize(*value, *resolution);
+                let delta = code.wrapping_sub(reference[i]);
+                write_varint(zigzag(delta), buffer, &mut offset)?;
+                reference[i] = code;
+            }
+        }
+
+        self.reference = reference;
+        self.index = header.index;
+        self.keyframe = false;
+        Ok(offset)
+    }
+}
+
+/// Delta payload decoder of single sample stream.
+#[derive(Debug, Default, Clone)]
+pub struct DeltaDecoder {
+    /// Standard data payload type of the current stream.
+    payload_type: u8,
+    /// Resolution of every channel from the last keyframe.
+    resolutions: [f32; DELTA_MAX_CHANNELS],
+    /// Codes of the last decoded sample.
+    reference: [i32; DELTA_MAX_CHANNELS],
+    /// Index of the last decoded frame.
+    index: u16,
+    /// Whether decoder has reference for the next delta frame.
+    synced: bool,
+}
+
+impl DeltaDecoder {
+    /// Construct new `DeltaDecoder` struct waiting for keyframe.
+    ///
+    /// # Returns
+    /// - New `DeltaDecoder` struct.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check whether decoder can decode the next delta frame.
+    ///
+    /// # Returns
+    /// - `true` - if decoder is synchronized to stream.
+    /// - `false` - if decoder waits for keyframe.
+    pub const fn is_synced(&self) -> bool {
+        self.synced
+    }
+
+    /// Decode delta payload. Delta frames are decoded only if they directly
+    /// follow the last decoded frame, otherwise decoder waits for keyframe.
+    ///
+    /// # Parameters
+    /// - `bytes` - given delta payload bytes.
+    /// - `f` - given function to call with every decoded sample.
+    ///
+    /// # Returns
+    /// - Number of decoded samples - in case of success.
+    /// - `None` - if delta frame was skipped while waiting for keyframe.
+    /// - `Err` - otherwise, decoder state is left unchanged and no samples
+    ///   are passed to `f`.
+    ///
+    /// # Errors
+    /// - Will return `Err` if payload is malformed.
+    pub fn decode<F: FnMut(StandardPayload)>(
+        &mut self,
+        bytes: &[u8],
+        mut f: F,
+    ) -> Result<Option<usize>, IdtpError> {
+        let header = DeltaHeader::from_bytes(bytes)?;
+        let groups = channel_groups(header.payload_type)
+            .ok_or(IdtpError::InvalidPayload)?;
+
+        if header.count == 0 {
+            return Err(IdtpError::InvalidPayload);
+        }
+
+        let channels = groups.iter().sum::<usize>();
+        let mut offset = DELTA_HEADER_SIZE;
+        let resolutions = if header.is_keyframe() {
+            let mut resolutions = [0f32; DELTA_MAX_GROUPS];
+
+            for resolution in &mut resolutions[..groups.len()] {
+                let chunk = bytes
+                    .get(offset..offset + 4)
+                    .ok_or(IdtpError::InvalidPayload)?;
+                *resolution = f32::from_be_bytes([
+                    chunk[0], chunk[1], chunk[2], chunk[3],
+                ]);
+                offset += 4;
+
+                if !is_valid_resolution(*resolution) {
+                    return Err(IdtpError::InvalidPayload);
+                }
+            }
+
+            channel_resolutions(groups, &resolutions).0
+        } else {
+            self.resolutions
+        };
+
+        // Validate values before any sample is passed on.
+        let total = usize::from(header.count) * channels;
+        let mut end = offset;
+
+        for _ in 0..total {
+            read_varint(bytes, &mut end)?;
+        }
+
+        if end != bytes.len() {
+            return Err(IdtpError::InvalidPayload);
+        }
+
+        let mut reference = [0; DELTA_MAX_CHANNELS];
+
+        if !header.is_keyframe() {
+            if !self.synced
+                || header.payload_type != self.payload_type
+                || header.index != self.index.wrapping_add(1)
+            {
+                self.synced = false;
+                return Ok(None);
+            }
+
+            reference = self.reference;
+        }
+
+        for _ in 0..header.count {
+            let mut values = [0f32; DELTA_MAX_CHANNELS];
+
+            for (i, value) in values[..channels].iter_mut().enumerate() {
+                let delta = unzigzag(read_varint(bytes, &mut offset)?);
+                reference[i] = reference[i].wrapping_add(delta);
+                *value = dequantize(reference[i], resolutions[i]);
+            }
+
+            f(sample_from_values(
+                header.payload_type,
+                &values[..channels],
+            )?);
+        }
+
+        self.payload_type = header.payload_type;
+        self.resolutions = resolutions;
+        self.reference = reference;
+        self.index = header.index;
+        self.synced = true;
+        Ok(Some(usize::from(header.count)))
+    }
+}
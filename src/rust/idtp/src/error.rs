@@ -0,0 +1,66 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP error declarations.
+
+use core::fmt;
+
+/// IDTP error kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdtpError {
+    /// Given buffer is too small to store the result.
+    BufferTooSmall,
+    /// Frame is shorter than IDTP header and trailer.
+    FrameTooShort,
+    /// Frame exceeds maximum IDTP frame size.
+    FrameTooLarge,
+    /// Frame does not start with IDTP preamble.
+    InvalidPreamble,
+    /// Frame does not end with IDTP trailer.
+    InvalidTrailer,
+    /// Header `payload_size` does not match the actual frame size.
+    PayloadSizeMismatch,
+    /// Payload does not match the layout of its payload type.
+    InvalidPayload,
+    /// Frame authentication tag does not match or key is not applicable.
+    AuthenticationFailed,
+    /// Session key is not established or must be rotated.
+    KeyUnavailable,
+    /// Protocol version is malformed or incompatible.
+    InvalidVersion,
+    /// Frame has more errors than forward error correction can correct.
+    Uncorrectable,
+    /// Operating mode is unknown or not representable.
+    InvalidMode,
+}
+
+impl fmt::Display for IdtpError {
+    /// Format IDTP error as human-readable message.
+    ///
+    /// # Parameters
+    /// - `f` - given formatter.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::BufferTooSmall => "Buffer size too small",
+            Self::FrameTooShort => "Frame is shorter than header and trailer",
+            Self::FrameTooLarge => "Frame exceeds maximum frame size",
+            Self::InvalidPreamble => "Frame does not start with preamble",
+            Self::InvalidTrailer => "Frame does not end with trailer",
+            Self::PayloadSizeMismatch => "Payload size does not match frame",
+            Self::InvalidPayload => "Payload does not match payload type",
+            Self::AuthenticationFailed => "Frame authentication failed",
+            Self::KeyUnavailable => "Session key is not available",
+            Self::InvalidVersion => "Protocol version is not supported",
+            Self::Uncorrectable => "Frame has too many errors to correct",
+            Self::InvalidMode => "Operating mode is not supported",
+        };
+
+        f.write_str(message)
+    }
+}
+
+impl core::error::Error for IdtpError {}
@@ -0,0 +1,282 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP extension block with optional per-frame metadata.
+//!
+//! Frame with `IDTP_FLAG_EXTENSIONS` header flag carries extension block
+//! between header and payload. Block starts with its total size (`u16`,
+//! including the size field itself) followed by TLV (type, length, value)
+//! entries with `u8` type and `u8` length. Extension block is counted in
+//! header `payload_size` and covered by checksum, CRC, encryption and
+//! authentication tag like the rest of payload. All multi-byte values are
+//! transmitted in big-endian byte order.
+//!
+//! TLV types `0x00` - `0x7F` are reserved for standard extensions and range
+//! `0x80` - `0xFF` is vendor-specific. Receivers must skip unknown types.
+
+use crate::IdtpError;
+
+/// Size of extension block size field in bytes.
+pub const EXTENSION_BLOCK_HEADER_SIZE: usize = 2;
+
+/// Size of TLV type and length fields in bytes.
+pub const EXTENSION_TLV_HEADER_SIZE: usize = 2;
+
+/// Maximum size of TLV value in bytes.
+pub const EXTENSION_VALUE_MAX_SIZE: usize = u8::MAX as usize;
+
+/// Sensor status bits (`u16`, vendor-specific meaning of bits).
+pub const EXTENSION_TYPE_SENSOR_STATUS: u8 = 0x01;
+
+/// Sensor temperature (`i16`, hundredths of degree Celsius).
+pub const EXTENSION_TYPE_TEMPERATURE: u8 = 0x02;
+
+/// Fragment of larger message (`u16` index and `u16` count of fragments).
+pub const EXTENSION_TYPE_FRAGMENT: u8 = 0x03;
+
+/// Identifier of the key used for frame authentication (`u8`).
+pub const EXTENSION_TYPE_KEY_ID: u8 = 0x04;
+
+/// Single TLV entry of extension block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tlv<'a> {
+    /// Extension type.
+    pub tlv_type: u8,
+    /// Extension value.
+    pub value: &'a [u8],
+}
+
+/// Validated extension block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtensionBlock<'a> {
+    /// TLV entries without block size field.
+    entries: &'a [u8],
+    /// Size of extension block including block size field.
+    size: usize,
+}
+
+impl<'a> ExtensionBlock<'a> {
+    /// Empty extension block of frames without extensions.
+    pub const EMPTY: ExtensionBlock<'static> = ExtensionBlock {
+        entries: &[],
+        size: 0,
+    };
+
+    /// Parse extension block from the start of bytes.
+    ///
+    /// # Parameters
+    /// - `bytes` - given bytes starting with extension block (e.g. frame
+    ///   payload).
+    ///
+    /// # Returns
+    /// - Extension block - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if block size exceeds `bytes` or TLV entries
+    ///   do not fill the block exactly.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, IdtpError> {
+        let size = Self::block_size(bytes)?;
+        let entries = &bytes[EXTENSION_BLOCK_HEADER_SIZE..size];
+        let mut offset = 0;
+
+        while offset < entries.len() {
+            if entries.len() - offset < EXTENSION_TLV_HEADER_SIZE {
+                return Err(IdtpError::InvalidPayload);
+            }
+
+            offset += EXTENSION_TLV_HEADER_SIZE + entries[offset + 1] as usize;
+        }
+
+        if offset != entries.len() {
+            return Err(IdtpError::InvalidPayload);
+        }
+
+        Ok(Self { entries, size })
+    }
+
+    /// Read extension block size from the start of bytes.
+    ///
+    /// # Parameters
+    /// - `bytes` - given bytes starting with extension block.
+    ///
+    /// # Returns
+    /// - Extension block size in bytes - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if block size field is malformed or block size
+    ///   exceeds `bytes`.
+    pub fn block_size(bytes: &[u8]) -> Result<usize, IdtpError> {
+        let size = bytes
+            .first_chunk::<EXTENSION_BLOCK_HEADER_SIZE>()
+            .map(|size| u16::from_be_bytes(*size) as usize)
+            .ok_or(IdtpError::InvalidPayload)?;
+
+        if size < EXTENSION_BLOCK_HEADER_SIZE || size > bytes.len() {
+            return Err(IdtpError::InvalidPayload);
+        }
+
+        Ok(size)
+    }
+
+    /// Get size of extension block in bytes.
+    ///
+    /// # Returns
+    /// - Size of extension block including block size field (zero for
+    ///   `EMPTY` block).
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Check if extension block has no entries.
+    ///
+    /// # Returns
+    /// - `true` - if block has no entries.
+    /// - `false` - otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Get iterator over TLV entries.
+    ///
+    /// # Returns
+    /// - Iterator over TLV entries in block order.
+    pub fn iter(&self) -> Tlvs<'a> {
+        Tlvs {
+            entries: self.entries,
+        }
+    }
+
+    /// Find the first TLV entry of given type.
+    ///
+    /// # Parameters
+    /// - `tlv_type` - given extension type.
+    ///
+    /// # Returns
+    /// - Value of the first entry of `tlv_type` - if there is one.
+    /// - `None` - otherwise.
+    pub fn get(&self, tlv_type: u8) -> Option<&'a [u8]> {
+        self.iter()
+            .find(|tlv| tlv.tlv_type == tlv_type)
+            .map(|tlv| tlv.value)
+    }
+}
+
+impl<'a> IntoIterator for &ExtensionBlock<'a> {
+    type Item = Tlv<'a>;
+    type IntoIter = Tlvs<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over TLV entries of extension block.
+#[derive(Debug, Clone)]
+pub struct Tlvs<'a> {
+    /// Remaining TLV entries.
+    entries: &'a [u8],
+}
+
+impl<'a> Iterator for Tlvs<'a> {
+    type Item = Tlv<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let [tlv_type, length, rest @ ..] = self.entries else {
+            return None;
+        };
+
+        let (value, rest) = rest.split_at(*length as usize);
+        self.entries = rest;
+
+        Some(Tlv {
+            tlv_type: *tlv_type,
+            value,
+        })
+    }
+}
+
+/// Writer of extension block into caller-provided buffer.
+#[derive(Debug)]
+pub struct ExtensionBuilder<'a> {
+    /// Output buffer.
+    buffer: &'a mut [u8],
+    /// Number of bytes written including block size field.
+    size: usize,
+}
+
+impl<'a> ExtensionBuilder<'a> {
+    /// Construct new `ExtensionBuilder` struct.
+    ///
+    /// # Parameters
+    /// - `buffer` - given buffer to write extension block to.
+    ///
+    /// # Returns
+    /// - New `ExtensionBuilder` struct.
+    pub fn new(buffer: &'a mut [u8]) -> Self {
+        Self {
+            buffer,
+            size: EXTENSION_BLOCK_HEADER_SIZE,
+        }
+    }
+
+    /// Append TLV entry.
+    ///
+    /// # Parameters
+    /// - `tlv_type` - given extension type.
+    /// - `value` - given extension value.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise, builder is left unchanged.
+    ///
+    /// # Errors
+    /// - Will return `Err` if value is longer than
+    ///   `EXTENSION_VALUE_MAX_SIZE` or entry does not fit into buffer or
+    ///   `u16` block size.
+    pub fn push(
+        &mut self,
+        tlv_type: u8,
+        value: &[u8],
+    ) -> Result<(), IdtpError> {
+        if value.len() > EXTENSION_VALUE_MAX_SIZE {
+            return Err(IdtpError::InvalidPayload);
+        }
+
+        let end = self.size + EXTENSION_TLV_HEADER_SIZE + value.len();
+
+        if end > u16::MAX as usize {
+            return Err(IdtpError::InvalidPayload);
+        }
+
+        if end > self.buffer.len() {
+            return Err(IdtpError::BufferTooSmall);
+        }
+
+        self.buffer[self.size] = tlv_type;
+        self.buffer[self.size + 1] = value.len() as u8;
+        self.buffer[self.size + EXTENSION_TLV_HEADER_SIZE..end]
+            .copy_from_slice(value);
+        self.size = end;
+        Ok(())
+    }
+
+    /// Write block size field and finish extension block.
+    ///
+    /// # Returns
+    /// - Size of extension block in bytes - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if buffer is too small for block size field.
+    pub fn finish(self) -> Result<usize, IdtpError> {
+        if self.buffer.len() < EXTENSION_BLOCK_HEADER_SIZE {
+            return Err(IdtpError::BufferTooSmall);
+        }
+
+        self.buffer[..EXTENSION_BLOCK_HEADER_SIZE]
+            .copy_from_slice(&(self.size as u16).to_be_bytes());
+        Ok(self.size)
+    }
+}
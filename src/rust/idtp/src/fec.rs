@@ -0,0 +1,350 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Reed–Solomon forward error correction of IDTP-F (FEC mode) frames.
+//!
+//! Header (with `checksum` field filled and `crc` field zeroed) and payload
+//! data are split into blocks of at most `FEC_BLOCK_DATA_SIZE` bytes. Each
+//! block is protected by `FEC_PARITY_SIZE` bytes of RS(255, 239) parity over
+//! GF(2^8) (`0x11D` polynomial, generator roots `α^0` - `α^15`), so up to
+//! `FEC_MAX_ERRORS` corrupted bytes of every block are corrected. Parity of
+//! all blocks follows payload data in block order and is counted in header
+//! `payload_size`.
+
+use crate::{
+    IDTP_HEADER_SIZE, IDTP_PACKET_MAX_SIZE, IDTP_PACKET_MIN_SIZE, IDTP_TRAILER,
+    IDTP_TRAILER_SIZE, IdtpError, Mode,
+};
+
+/// Number of parity bytes of single block.
+pub const FEC_PARITY_SIZE: usize = 16;
+
+/// Maximum number of corrected bytes of single block.
+pub const FEC_MAX_ERRORS: usize = FEC_PARITY_SIZE / 2;
+
+/// Size of single code block (data and parity) in bytes.
+pub const FEC_BLOCK_SIZE: usize = 255;
+
+/// Maximum size of data of single block in bytes.
+pub const FEC_BLOCK_DATA_SIZE: usize = FEC_BLOCK_SIZE - FEC_PARITY_SIZE;
+
+/// GF(2^8) field generator polynomial.
+const GF_POLYNOMIAL: u16 = 0x11D;
+
+/// GF(2^8) exponent table (doubled to avoid modulo on multiplication).
+const GF_EXP: [u8; 512] = gf_tables().0;
+
+/// GF(2^8) logarithm table.
+const GF_LOG: [u8; 256] = gf_tables().1;
+
+/// RS generator polynomial coefficients (highest degree first).
+const GENERATOR: [u8; FEC_PARITY_SIZE + 1] = generator();
+
+/// Generate GF(2^8) exponent and logarithm tables.
+///
+/// # Returns
+/// - Exponent and logarithm tables.
+const fn gf_tables() -> ([u8; 512], [u8; 256]) {
+    let mut exp = [0u8; 512];
+    let mut log = [0u8; 256];
+    let mut value: u16 = 1;
+    let mut i = 0;
+
+    while i < 255 {
+        exp[i] = value as u8;
+        exp[i + 255] = value as u8;
+        log[value as usize] = i as u8;
+
+        value <<= 1;
+
+        if value & 0x100 != 0 {
+            value ^= GF_POLYNOMIAL;
+        }
+
+        i += 1;
+    }
+
+    exp[510] = exp[0];
+    exp[511] = exp[1];
+    (exp, log)
+}
+
+/// Multiply GF(2^8) elements.
+const fn mul(a: u8, b: u8) -> u8 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+
+    GF_EXP[GF_LOG[a as usize] as usize + GF_LOG[b as usize] as usize]
+}
+
+/// Divide GF(2^8) elements (`b` must not be zero).
+const fn div(a: u8, b: u8) -> u8 {
+    if a == 0 {
+        return 0;
+    }
+
+    GF_EXP[GF_LOG[a as usize] as usize + 255 - GF_LOG[b as usize] as usize]
+}
+
+/// Get power of GF(2^8) primitive element `α`.
+const fn alpha(power: usize) -> u8 {
+    GF_EXP[power % 255]
+}
+
+/// Generate RS generator polynomial `(x - α^0)(x - α^1)...(x - α^15)`.
+///
+/// # Returns
+/// - Generator polynomial coefficients (highest degree first).
+const fn generator() -> [u8; FEC_PARITY_SIZE + 1] {
+    let mut polynomial = [0u8; FEC_PARITY_SIZE + 1];
+    polynomial[0] = 1;
+    let mut degree = 0;
+
+    while degree < FEC_PARITY_SIZE {
+        let root = alpha(degree);
+        let mut i = degree + 1;
+
+        // Multiply by (x + root): coefficient i gets root * coefficient i-1.
+        while i > 0 {
+            polynomial[i] ^= mul(root, polynomial[i - 1]);
+            i -= 1;
+        }
+
+        degree += 1;
+    }
+
+    polynomial
+}
+
+/// Get number of parity bytes protecting message.
+///
+/// # Parameters
+/// - `message_size` - given size of header and payload data in bytes.
+///
+/// # Returns
+/// - Number of parity bytes.
+pub const fn parity_size(message_size: usize) -> usize {
+    message_size.div_ceil(FEC_BLOCK_DATA_SIZE) * FEC_PARITY_SIZE
+}
+
+/// Get size of message protected by parity.
+///
+/// # Parameters
+/// - `coded_size` - given size of header, payload data and parity in bytes.
+///
+/// # Returns
+/// - Size of header and payload data in bytes - if `coded_size` is valid.
+/// - `None` - otherwise.
+pub const fn message_size(coded_size: usize) -> Option<usize> {
+    let parity = coded_size.div_ceil(FEC_BLOCK_SIZE) * FEC_PARITY_SIZE;
+
+    match coded_size.checked_sub(parity) {
+        Some(size) if size > 0 && parity_size(size) == parity => Some(size),
+        _ => None,
+    }
+}
+
+/// Calculate parity of message.
+///
+/// # Parameters
+/// - `message` - given header and payload data bytes.
+/// - `parity` - given buffer of `parity_size(message.len())` bytes to store
+///   parity to.
+pub fn encode(message: &[u8], parity: &mut [u8]) {
+    let blocks = message.chunks(FEC_BLOCK_DATA_SIZE);
+
+    for (block, parity) in blocks.zip(parity.chunks_mut(FEC_PARITY_SIZE)) {
+        parity.fill(0);
+
+        for byte in block {
+            let feedback = byte ^ parity[0];
+            parity.copy_within(1.., 0);
+            parity[FEC_PARITY_SIZE - 1] = 0;
+
+            for (value, coefficient) in parity.iter_mut().zip(&GENERATOR[1..]) {
+                *value ^= mul(feedback, *coefficient);
+            }
+        }
+    }
+}
+
+/// Correct errors of single code block in place.
+///
+/// # Parameters
+/// - `block` - given block data followed by its parity.
+///
+/// # Returns
+/// - Number of corrected bytes - in case of success.
+/// - `None` - if block has more errors than can be corrected.
+fn correct_block(block: &mut [u8]) -> Option<usize> {
+    let size = block.len();
+    let mut syndromes = [0u8; FEC_PARITY_SIZE];
+
+    for (j, syndrome) in syndromes.iter_mut().enumerate() {
+        let root = alpha(j);
+        *syndrome = block.iter().fold(0, |sum, byte| mul(sum, root) ^ byte);
+    }
+
+    if syndromes.iter().all(|syndrome| *syndrome == 0) {
+        return Some(0);
+    }
+
+    // Berlekamp–Massey: error locator polynomial (lowest degree first).
+    let mut locator = [0u8; FEC_PARITY_SIZE + 1];
+    let mut previous = [0u8; FEC_PARITY_SIZE + 1];
+    locator[0] = 1;
+    previous[0] = 1;
+    let mut errors = 0;
+    let mut shift = 1;
+    let mut previous_discrepancy = 1;
+
+    for n in 0..FEC_PARITY_SIZE {
+        let discrepancy = (1..=errors)
+            .fold(syndromes[n], |d, i| d ^ mul(locator[i], syndromes[n - i]));
+
+        if discrepancy == 0 {
+            shift += 1;
+            continue;
+        }
+
+        let scale = div(discrepancy, previous_discrepancy);
+        let saved = locator;
+
+        for i in shift..=FEC_PARITY_SIZE {
+            locator[i] ^= mul(scale, previous[i - shift]);
+        }
+
+        if 2 * errors <= n {
+            errors = n + 1 - errors;
+            previous = saved;
+            previous_discrepancy = discrepancy;
+            shift = 1;
+        } else {
+            shift += 1;
+        }
+    }
+
+    if errors > FEC_MAX_ERRORS {
+        return None;
+    }
+
+    // Error evaluator polynomial: syndromes * locator mod x^16.
+    let mut evaluator = [0u8; FEC_PARITY_SIZE];
+
+    for (i, value) in evaluator.iter_mut().enumerate() {
+        *value = (0..=i.min(errors))
+            .fold(0, |sum, j| sum ^ mul(locator[j], syndromes[i - j]));
+    }
+
+    // Chien search and Forney algorithm.
+    let mut found = 0;
+
+    for (index, byte) in block.iter_mut().enumerate() {
+        let power = size - 1 - index;
+        let inverse = alpha(255 - power % 255);
+
+        let mut value = 0;
+        let mut derivative = 0;
+        let mut x = 1;
+
+        for (i, coefficient) in locator[..=errors].iter().enumerate() {
+            value ^= mul(*coefficient, x);
+
+            if i % 2 == 1 {
+                derivative ^= mul(*coefficient, div(x, inverse));
+            }
+
+            x = mul(x, inverse);
+        }
+
+        if value != 0 {
+            continue;
+        }
+
+        if derivative == 0 {
+            return None;
+        }
+
+        let mut omega = 0;
+        let mut x = 1;
+
+        for coefficient in &evaluator {
+            omega ^= mul(*coefficient, x);
+            x = mul(x, inverse);
+        }
+
+        *byte ^= mul(alpha(power), div(omega, derivative));
+        found += 1;
+    }
+
+    (found == errors).then_some(found)
+}
+
+/// Correct errors of raw IDTP-F (FEC mode) frame in place. Trailer is
+/// restored as well.
+///
+/// # Parameters
+/// - `bytes` - given raw IDTP-F frame. Frame size must not be corrupted
+///   (e.g. it is known from datagram size).
+///
+/// # Returns
+/// - Number of corrected bytes - in case of success.
+/// - `Err` - otherwise, frame is left unchanged.
+///
+/// # Errors
+/// - Will return `Err` if frame size is out of range or does not match any
+///   message size, frame has more errors than can be corrected or
+///   corrected frame is not in FEC mode.
+pub fn correct(bytes: &mut [u8]) -> Result<usize, IdtpError> {
+    if bytes.len() < IDTP_PACKET_MIN_SIZE {
+        return Err(IdtpError::FrameTooShort);
+    }
+
+    if bytes.len() > IDTP_PACKET_MAX_SIZE {
+        return Err(IdtpError::FrameTooLarge);
+    }
+
+    let coded_size = bytes.len() - IDTP_TRAILER_SIZE;
+    let message_size =
+        message_size(coded_size).ok_or(IdtpError::PayloadSizeMismatch)?;
+
+    if message_size < IDTP_HEADER_SIZE {
+        return Err(IdtpError::PayloadSizeMismatch);
+    }
+
+    let mut work = [0u8; IDTP_PACKET_MAX_SIZE];
+    work[..bytes.len()].copy_from_slice(bytes);
+    let (message, rest) = work.split_at_mut(message_size);
+    let (parity, trailer) = rest.split_at_mut(coded_size - message_size);
+    let mut corrected = 0;
+
+    let blocks = message.chunks_mut(FEC_BLOCK_DATA_SIZE);
+
+    for (data, parity) in blocks.zip(parity.chunks_mut(FEC_PARITY_SIZE)) {
+        let mut block = [0u8; FEC_BLOCK_SIZE];
+        let size = data.len() + FEC_PARITY_SIZE;
+        block[..data.len()].copy_from_slice(data);
+        block[data.len()..size].copy_from_slice(parity);
+
+        corrected += correct_block(&mut block[..size])
+            .ok_or(IdtpError::Uncorrectable)?;
+
+        data.copy_from_slice(&block[..data.len()]);
+        parity.copy_from_slice(&block[data.len()..size]);
+    }
+
+    for (byte, expected) in trailer.iter_mut().zip(IDTP_TRAILER) {
+        if *byte != *expected {
+            *byte = *expected;
+            corrected += 1;
+        }
+    }
+
+    if Mode::from(work[7]) != Mode::Fec {
+        return Err(IdtpError::InvalidPayload);
+    }
+
+    bytes.copy_from_slice(&work[..bytes.len()]);
+    Ok(corrected)
+}
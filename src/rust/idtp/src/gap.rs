@@ -0,0 +1,310 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Detection of lost samples and filling of gaps with synthetic samples.
+//!
+//! Downstream integrators (e.g. strapdown navigation) expect evenly spaced
+//! samples. `GapFiller` takes standard samples of single device with their
+//! position on extended timeline (see `timeline` module): missing sequence
+//! numbers between two samples are replaced with synthetic samples placed
+//! evenly between their timestamps. Vector fields are interpolated linearly,
+//! quaternions with SLERP, or the last sample is held. Synthetic samples are
+//! flagged, so consumers may ignore them. Gaps are detected by `sequence`
+//! only, their timestamps are checked to be increasing and optionally not
+//! too far apart (e.g. after device pause), otherwise gap is left unfilled.
+//! Samples should be released in `sequence` order (see `reorder` module)
+//! before filling.
+
+use crate::payload::{Imu6, Imu9, Quaternion, StandardPayload, Vector3};
+use crate::timeline::Unwrapped;
+
+/// Default maximum number of missing samples to fill.
+pub const GAP_DEFAULT_MAX_LENGTH: u32 = 10;
+
+/// Method of computing synthetic samples.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FillMethod {
+    /// Linear interpolation of vector fields and SLERP of quaternions
+    /// between samples around the gap.
+    #[default]
+    Interpolate,
+    /// Repeat the last sample before the gap.
+    HoldLast,
+}
+
+/// Gap filling configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GapConfig {
+    /// Method of computing synthetic samples.
+    pub method: FillMethod,
+    /// Maximum number of missing samples to fill. Longer gaps are left
+    /// unfilled, `0` disables filling.
+    pub max_length: u32,
+    /// Maximum timestamp distance between samples around filled gap (in
+    /// device timestamp units). Gaps spanning longer time are left
+    /// unfilled, `None` disables the check.
+    pub max_span: Option<u64>,
+}
+
+impl Default for GapConfig {
+    /// Construct default gap filling configuration.
+    ///
+    /// # Returns
+    /// - Interpolation of gaps up to `GAP_DEFAULT_MAX_LENGTH` samples
+    ///   without timestamp span limit.
+    fn default() -> Self {
+        Self {
+            method: FillMethod::Interpolate,
+            max_length: GAP_DEFAULT_MAX_LENGTH,
+            max_span: None,
+        }
+    }
+}
+
+/// Sample emitted by `GapFiller`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sample {
+    /// Extended sequence number.
+    pub sequence: u64,
+    /// Extended timestamp.
+    pub timestamp: u64,
+    /// Sample values.
+    pub payload: StandardPayload,
+    /// Whether sample was computed in place of lost one.
+    pub synthetic: bool,
+}
+
+/// Result of adding sample to `GapFiller`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fill {
+    /// Sample directly follows the previous one (or is the first one).
+    Contiguous,
+    /// Given number of missing samples was filled with synthetic samples.
+    Filled(u64),
+    /// Given number of missing samples was not filled: gap is longer than
+    /// configured maximum, its timestamps do not increase or span longer
+    /// than configured maximum, or payload type changed.
+    Unfilled(u64),
+    /// Sample is not newer than the previous one and was dropped.
+    Late,
+}
+
+/// Gap filler of single device stream.
+#[derive(Debug, Clone)]
+pub struct GapFiller {
+    /// Gap filling configuration.
+    config: GapConfig,
+    /// The last emitted real sample.
+    last: Option<Sample>,
+}
+
+impl GapFiller {
+    /// Construct new `GapFiller` struct.
+    ///
+    /// # Parameters
+    /// - `config` - given gap filling configuration.
+    ///
+    /// # Returns
+    /// - New `GapFiller` struct.
+    pub const fn new(config: GapConfig) -> Self {
+        Self { config, last: None }
+    }
+
+    /// Get gap filling configuration.
+    ///
+    /// # Returns
+    /// - Gap filling configuration.
+    pub fn config(&self) -> &GapConfig {
+        &self.config
+    }
+
+    /// Forget the last sample (e.g. after stream restart).
+    pub fn reset(&mut self) {
+        self.last = None;
+    }
+
+    /// Add received sample. Synthetic samples filling the gap before it are
+    /// emitted first, followed by the sample itself.
+    ///
+    /// # Parameters
+    /// - `position` - given sample position on extended timeline.
+    /// - `payload` - given sample values.
+    /// - `f` - given function to call for every emitted sample.
+    ///
+    /// # Returns
+    /// - Gap detected before the sample.
+    pub fn push<F: FnMut(Sample)>(
+        &mut self,
+        position: &Unwrapped,
+        payload: StandardPayload,
+        mut f: F,
+    ) -> Fill {
+        let sample = Sample {
+            sequence: position.sequence,
+            timestamp: position.timestamp,
+            payload,
+            synthetic: false,
+        };
+
+        let Some(last) = self.last else {
+            self.last = Some(sample);
+            f(sample);
+            return Fill::Contiguous;
+        };
+
+        if sample.sequence <= last.sequence {
+            return Fill::Late;
+        }
+
+        let missing = sample.sequence - last.sequence - 1;
+        let same_type = payload.payload_type() == last.payload.payload_type();
+        let span = sample.timestamp.checked_sub(last.timestamp);
+        let in_span = span.is_some_and(|span| {
+            span > 0 && self.config.max_span.is_none_or(|max| span <= max)
+        });
+
+        let fill = if missing == 0 {
+            Fill::Contiguous
+        } else if missing > u64::from(self.config.max_length)
+            || !same_type
+            || !in_span
+        {
+            Fill::Unfilled(missing)
+        } else {
+            self.fill(&last, &sample, missing, &mut f);
+            Fill::Filled(missing)
+        };
+
+        self.last = Some(sample);
+        f(sample);
+        fill
+    }
+
+    /// Emit synthetic samples between two real samples.
+    fn fill<F: FnMut(Sample)>(
+        &self,
+        first: &Sample,
+        second: &Sample,
+        missing: u64,
+        f: &mut F,
+    ) {
+        let span = second.timestamp.saturating_sub(first.timestamp);
+        let steps = missing + 1;
+
+        for step in 1..steps {
+            let offset =
+                u128::from(span) * u128::from(step) / u128::from(steps);
+
+            let payload = match self.config.method {
+                FillMethod::Interpolate => {
+                    let t = step as f32 / steps as f32;
+                    interpolate(&first.payload, &second.payload, t)
+                }
+                FillMethod::HoldLast => first.payload,
+            };
+
+            f(Sample {
+                sequence: first.sequence + step,
+                timestamp: first.timestamp + offset as u64,
+                payload,
+                synthetic: true,
+            });
+        }
+    }
+}
+
+impl Default for GapFiller {
+    /// Construct new `GapFiller` struct with default configuration.
+    ///
+    /// # Returns
+    /// - New `GapFiller` struct.
+    fn default() -> Self {
+        Self::new(GapConfig::default())
+    }
+}
+
+/// Interpolate between two samples of the same payload type.
+fn interpolate(
+    a: &StandardPayload,
+    b: &StandardPayload,
+    t: f32,
+) -> StandardPayload {
+    match (a, b) {
+        (StandardPayload::Imu6(a), StandardPayload::Imu6(b)) => {
+            StandardPayload::Imu6(Imu6 {
+                acc: lerp(&a.acc, &b.acc, t),
+                gyr: lerp(&a.gyr, &b.gyr, t),
+            })
+        }
+        (StandardPayload::Imu9(a), StandardPayload::Imu9(b)) => {
+            StandardPayload::Imu9(Imu9 {
+                acc: lerp(&a.acc, &b.acc, t),
+                gyr: lerp(&a.gyr, &b.gyr, t),
+                mag: lerp(&a.mag, &b.mag, t),
+            })
+        }
+        (
+            StandardPayload::Accelerometer(a),
+            StandardPayload::Accelerometer(b),
+        ) => StandardPayload::Accelerometer(lerp(a, b, t)),
+        (StandardPayload::Gyroscope(a), StandardPayload::Gyroscope(b)) => {
+            StandardPayload::Gyroscope(lerp(a, b, t))
+        }
+        (
+            StandardPayload::Magnetometer(a),
+            StandardPayload::Magnetometer(b),
+        ) => StandardPayload::Magnetometer(lerp(a, b, t)),
+        (StandardPayload::Quaternion(a), StandardPayload::Quaternion(b)) => {
+            StandardPayload::Quaternion(slerp(a, b, t))
+        }
+        _ => *a,
+    }
+}
+
+/// Linear interpolation of three-axis samples.
+fn lerp(a: &Vector3, b: &Vector3, t: f32) -> Vector3 {
+    let [ax, ay, az] = a.to_array();
+    let [bx, by, bz] = b.to_array();
+
+    Vector3::new(ax + (bx - ax) * t, ay + (by - ay) * t, az + (bz - az) * t)
+}
+
+/// Spherical linear interpolation of orientations along the shortest path.
+fn slerp(a: &Quaternion, b: &Quaternion, t: f32) -> Quaternion {
+    let mut b = *b;
+    let mut dot = a.w * b.w + a.x * b.x + a.y * b.y + a.z * b.z;
+
+    // `q` and `-q` represent the same orientation.
+    if dot < 0.0 {
+        b = Quaternion::new(-b.w, -b.x, -b.y, -b.z);
+        dot = -dot;
+    }
+
+    // Nearly identical orientations: linear interpolation is precise and
+    // avoids division by sine of small angle.
+    let (ka, kb) = if dot > 0.9995 {
+        (1.0 - t, t)
+    } else {
+        let theta = libm::acosf(dot);
+        let sin = libm::sinf(theta);
+        (
+            libm::sinf((1.0 - t) * theta) / sin,
+            libm::sinf(t * theta) / sin,
+        )
+    };
+
+    let q = Quaternion::new(
+        ka * a.w + kb * b.w,
+        ka * a.x + kb * b.x,
+        ka * a.y + kb * b.y,
+        ka * a.z + kb * b.z,
+    );
+
+    let norm = libm::sqrtf(q.w * q.w + q.x * q.x + q.y * q.y + q.z * q.z);
+
+    if norm > 0.0 {
+        Quaternion::new(q.w / norm, q.x / norm, q.y / norm, q.z / norm)
+    } else {
+        *a
+    }
+}
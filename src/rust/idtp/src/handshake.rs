@@ -0,0 +1,630 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Secure mode session key establishment.
+//!
+//! Handshake is carried by Normal mode frames with control payload types and
+//! is always initiated by the host:
+//!
+//! 1. `KeyRequest` (host -> device) - new key identifier and host ephemeral
+//!    X25519 public key.
+//! 2. `KeyResponse` (device -> host) - device ephemeral X25519 public key and
+//!    device confirmation value.
+//! 3. `KeyFinished` (host -> device) - host confirmation value.
+//!
+//! Both sides derive AES-128-GCM key and confirmation values from X25519
+//! shared secret with HKDF-SHA256. Pre-shared device secret is used as HKDF
+//! salt, so handshake with party which does not know it fails confirmation.
+//! Empty pre-shared secret gives unauthenticated key exchange.
+//!
+//! Device switches to the new key only after valid `KeyFinished`, host keeps
+//! the previous key until the device starts using the new one. Confirmation
+//! values are bound to ephemeral keys of both sides, so replayed handshake
+//! messages never complete the handshake.
+//!
+//! Ephemeral secrets are passed as 32 random bytes, caller is responsible for
+//! getting them from cryptographically secure random number generator.
+
+use crate::{
+    IdtpError, IdtpFrame, IdtpHeader, Mode,
+    payload::{
+        PAYLOAD_TYPE_KEY_FINISHED, PAYLOAD_TYPE_KEY_REQUEST,
+        PAYLOAD_TYPE_KEY_RESPONSE, Payload,
+    },
+    secure::{IDTP_KEY_SIZE, SecureKey},
+};
+use hkdf::Hkdf;
+use sha2::Sha256;
+use x25519_dalek::{PublicKey, StaticSecret};
+
+/// Size of X25519 public key in bytes.
+pub const PUBLIC_KEY_SIZE: usize = 32;
+
+/// Size of handshake confirmation value in bytes.
+pub const CONFIRM_SIZE: usize = 16;
+
+/// HKDF info prefix.
+const INFO_LABEL: &[u8] = b"IDTP-E handshake";
+
+/// Session key request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyRequest {
+    /// Identifier of the new key.
+    pub key_id: u8,
+    /// Host ephemeral X25519 public key.
+    pub public_key: [u8; PUBLIC_KEY_SIZE],
+}
+
+/// Session key response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyResponse {
+    /// Identifier of the new key.
+    pub key_id: u8,
+    /// Device ephemeral X25519 public key.
+    pub public_key: [u8; PUBLIC_KEY_SIZE],
+    /// Device confirmation value.
+    pub confirm: [u8; CONFIRM_SIZE],
+}
+
+/// Session key confirmation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyFinished {
+    /// Identifier of the new key.
+    pub key_id: u8,
+    /// Host confirmation value.
+    pub confirm: [u8; CONFIRM_SIZE],
+}
+
+impl Payload for KeyRequest {
+    const PAYLOAD_TYPE: u8 = PAYLOAD_TYPE_KEY_REQUEST;
+    const SIZE: usize = 1 + PUBLIC_KEY_SIZE;
+
+    fn encode(&self, buffer: &mut [u8]) -> Result<usize, IdtpError> {
+        if buffer.len() < Self::SIZE {
+            return Err(IdtpError::BufferTooSmall);
+        }
+
+        buffer[0] = self.key_id;
+        buffer[1..Self::SIZE].copy_from_slice(&self.public_key);
+        Ok(Self::SIZE)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, IdtpError> {
+        if bytes.len() != Self::SIZE {
+            return Err(IdtpError::InvalidPayload);
+        }
+
+        let mut public_key = [0u8; PUBLIC_KEY_SIZE];
+        public_key.copy_from_slice(&bytes[1..]);
+
+        Ok(Self {
+            key_id: bytes[0],
+            public_key,
+        })
+    }
+}
+
+impl Payload for KeyResponse {
+    const PAYLOAD_TYPE: u8 = PAYLOAD_TYPE_KEY_RESPONSE;
+    const SIZE: usize = 1 + PUBLIC_KEY_SIZE + CONFIRM_SIZE;
+
+    fn encode(&self, buffer: &mut [u8]) -> Result<usize, IdtpError> {
+        if buffer.len() < Self::SIZE {
+            return Err(IdtpError::BufferTooSmall);
+        }
+
+        buffer[0] = self.key_id;
+        buffer[1..=PUBLIC_KEY_SIZE].copy_from_slice(&self.public_key);
+        buffer[1 + PUBLIC_KEY_SIZE..Self::SIZE].copy_from_slice(&self.confirm);
+        Ok(Self::SIZE)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, IdtpError> {
+        if bytes.len() != Self::SIZE {
+            return Err(IdtpError::InvalidPayload);
+        }
+
+        let mut public_key = [0u8; PUBLIC_KEY_SIZE];
+        let mut confirm = [0u8; CONFIRM_SIZE];
+        public_key.copy_from_slice(&bytes[1..=PUBLIC_KEY_SIZE]);
+        confirm.copy_from_slice(&bytes[1 + PUBLIC_KEY_SIZE..]);
+
+        Ok(Self {
+            key_id: bytes[0],
+            public_key,
+            confirm,
+        })
+    }
+}
+
+impl Payload for KeyFinished {
+    const PAYLOAD_TYPE: u8 = PAYLOAD_TYPE_KEY_FINISHED;
+    const SIZE: usize = 1 + CONFIRM_SIZE;
+
+    fn encode(&self, buffer: &mut [u8]) -> Result<usize, IdtpError> {
+        if buffer.len() < Self::SIZE {
+            return Err(IdtpError::BufferTooSmall);
+        }
+
+        buffer[0] = self.key_id;
+        buffer[1..Self::SIZE].copy_from_slice(&self.confirm);
+        Ok(Self::SIZE)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, IdtpError> {
+        if bytes.len() != Self::SIZE {
+            return Err(IdtpError::InvalidPayload);
+        }
+
+        let mut confirm = [0u8; CONFIRM_SIZE];
+        confirm.copy_from_slice(&bytes[1..]);
+
+        Ok(Self {
+            key_id: bytes[0],
+            confirm,
+        })
+    }
+}
+
+/// Session key rotation policy. Limits are inclusive, `None` means no limit.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RotationPolicy {
+    /// Maximum number of sequence numbers to use with one key.
+    pub max_frames: Option<u32>,
+    /// Maximum key lifetime in header `timestamp` units.
+    pub max_age: Option<u32>,
+}
+
+impl RotationPolicy {
+    /// Check whether key must be rotated.
+    ///
+    /// # Parameters
+    /// - `first` - given header of the first frame sent with the key.
+    /// - `header` - given header of the current frame.
+    ///
+    /// # Returns
+    /// - `true`  - if any of limits is reached.
+    /// - `false` - otherwise.
+    pub fn is_due(&self, first: &IdtpHeader, header: &IdtpHeader) -> bool {
+        let frames = header.sequence.wrapping_sub(first.sequence);
+        let age = header.timestamp.wrapping_sub(first.timestamp);
+
+        self.max_frames.is_some_and(|max| frames >= max)
+            || self.max_age.is_some_and(|max| age >= max)
+    }
+}
+
+/// Keys derived by handshake.
+struct Derived {
+    /// New session key.
+    key: SecureKey,
+    /// Device confirmation value.
+    device_confirm: [u8; CONFIRM_SIZE],
+    /// Host confirmation value.
+    host_confirm: [u8; CONFIRM_SIZE],
+}
+
+/// Derive session key and confirmation values.
+///
+/// # Parameters
+/// - `psk` - given pre-shared device secret.
+/// - `device_id` - given IMU device identifier.
+/// - `key_id` - given new key identifier.
+/// - `secret` - given own ephemeral secret.
+/// - `peer` - given peer ephemeral public key.
+/// - `host_public` - given host ephemeral public key.
+/// - `device_public` - given device ephemeral public key.
+///
+/// # Returns
+/// - Derived values - in case of success.
+/// - `Err` - otherwise.
+///
+/// # Errors
+/// - Will return `Err` if peer public key is of low order.
+fn derive(
+    psk: &[u8],
+    device_id: u16,
+    key_id: u8,
+    secret: &StaticSecret,
+    peer: &[u8; PUBLIC_KEY_SIZE],
+    host_public: &[u8; PUBLIC_KEY_SIZE],
+    device_public: &[u8; PUBLIC_KEY_SIZE],
+) -> Result<Derived, IdtpError> {
+    let shared = secret.diffie_hellman(&PublicKey::from(*peer));
+
+    if !shared.was_contributory() {
+        return Err(IdtpError::AuthenticationFailed);
+    }
+
+    let mut info = [0u8; INFO_LABEL.len() + 3 + 2 * PUBLIC_KEY_SIZE];
+    let (label, rest) = info.split_at_mut(INFO_LABEL.len());
+    label.copy_from_slice(INFO_LABEL);
+    rest[0..2].copy_from_slice(&device_id.to_be_bytes());
+    rest[2] = key_id;
+    rest[3..=PUBLIC_KEY_SIZE + 2].copy_from_slice(host_public);
+    rest[PUBLIC_KEY_SIZE + 3..].copy_from_slice(device_public);
+
+    let mut okm = [0u8; IDTP_KEY_SIZE + 2 * CONFIRM_SIZE];
+    Hkdf::<Sha256>::new(Some(psk), shared.as_bytes())
+        .expand(&info, &mut okm)
+        // Fails only for outputs longer than 255 hash lengths.
+        .expect("HKDF output is too long");
+
+    let mut key = [0u8; IDTP_KEY_SIZE];
+    let mut device_confirm = [0u8; CONFIRM_SIZE];
+    let mut host_confirm = [0u8; CONFIRM_SIZE];
+    key.copy_from_slice(&okm[..IDTP_KEY_SIZE]);
+    device_confirm.copy_from_slice(&okm[IDTP_KEY_SIZE..][..CONFIRM_SIZE]);
+    host_confirm.copy_from_slice(&okm[IDTP_KEY_SIZE + CONFIRM_SIZE..]);
+
+    let derived = Derived {
+        key: SecureKey::new(key_id, &key),
+        device_confirm,
+        host_confirm,
+    };
+
+    okm.fill(0);
+    key.fill(0);
+    Ok(derived)
+}
+
+/// Compare confirmation values in constant time.
+fn confirm_eq(a: &[u8; CONFIRM_SIZE], b: &[u8; CONFIRM_SIZE]) -> bool {
+    a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// Handshake state which waits for `KeyFinished`.
+struct DevicePending {
+    /// New session key.
+    key: SecureKey,
+    /// Expected host confirmation value.
+    host_confirm: [u8; CONFIRM_SIZE],
+}
+
+/// Device-side handshake state machine.
+pub struct DeviceHandshake<'a> {
+    /// IMU device identifier.
+    device_id: u16,
+    /// Pre-shared device secret.
+    psk: &'a [u8],
+    /// Key rotation policy.
+    policy: RotationPolicy,
+    /// Current session key.
+    key: Option<SecureKey>,
+    /// Header of the first frame sealed with current session key.
+    first: Option<IdtpHeader>,
+    /// Handshake in progress.
+    pending: Option<DevicePending>,
+}
+
+impl<'a> DeviceHandshake<'a> {
+    /// Construct new `DeviceHandshake` struct.
+    ///
+    /// # Parameters
+    /// - `device_id` - given IMU device identifier.
+    /// - `psk` - given pre-shared device secret.
+    /// - `policy` - given key rotation policy.
+    ///
+    /// # Returns
+    /// - New `DeviceHandshake` struct.
+    pub fn new(device_id: u16, psk: &'a [u8], policy: RotationPolicy) -> Self {
+        Self {
+            device_id,
+            psk,
+            policy,
+            key: None,
+            first: None,
+            pending: None,
+        }
+    }
+
+    /// Get current session key.
+    ///
+    /// # Returns
+    /// - Current session key - if established.
+    /// - `None` - otherwise.
+    pub fn key(&self) -> Option<&SecureKey> {
+        self.key.as_ref()
+    }
+
+    /// Handle session key request and start handshake. Previous handshake in
+    /// progress is abandoned.
+    ///
+    /// # Parameters
+    /// - `request` - given session key request.
+    /// - `secret` - given 32 random bytes for device ephemeral secret.
+    ///
+    /// # Returns
+    /// - Session key response to send to host - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if request reuses identifier of current key or
+    ///   host public key is of low order.
+    pub fn handle_request(
+        &mut self,
+        request: &KeyRequest,
+        secret: [u8; 32],
+    ) -> Result<KeyResponse, IdtpError> {
+        if self
+            .key
+            .as_ref()
+            .is_some_and(|key| key.id() == request.key_id)
+        {
+            return Err(IdtpError::AuthenticationFailed);
+        }
+
+        let secret = StaticSecret::from(secret);
+        let public_key = PublicKey::from(&secret).to_bytes();
+
+        let derived = derive(
+            self.psk,
+            self.device_id,
+            request.key_id,
+            &secret,
+            &request.public_key,
+            &request.public_key,
+            &public_key,
+        )?;
+
+        self.pending = Some(DevicePending {
+            key: derived.key,
+            host_confirm: derived.host_confirm,
+        });
+
+        Ok(KeyResponse {
+            key_id: request.key_id,
+            public_key,
+            confirm: derived.device_confirm,
+        })
+    }
+
+    /// Handle session key confirmation and switch to the new key.
+    ///
+    /// # Parameters
+    /// - `finished` - given session key confirmation.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise, handshake in progress is kept.
+    ///
+    /// # Errors
+    /// - Will return `Err` if there is no matching handshake in progress or
+    ///   host confirmation value does not match.
+    pub fn handle_finished(
+        &mut self,
+        finished: &KeyFinished,
+    ) -> Result<(), IdtpError> {
+        let pending = self
+            .pending
+            .as_ref()
+            .filter(|pending| pending.key.id() == finished.key_id)
+            .ok_or(IdtpError::AuthenticationFailed)?;
+
+        if !confirm_eq(&pending.host_confirm, &finished.confirm) {
+            return Err(IdtpError::AuthenticationFailed);
+        }
+
+        self.key = self.pending.take().map(|pending| pending.key);
+        self.first = None;
+        Ok(())
+    }
+
+    /// Check whether current session key must be rotated.
+    ///
+    /// # Parameters
+    /// - `header` - given header of the next frame to send.
+    ///
+    /// # Returns
+    /// - `true`  - if key rotation policy limit is reached.
+    /// - `false` - otherwise.
+    pub fn is_rotation_due(&self, header: &IdtpHeader) -> bool {
+        self.first
+            .is_some_and(|first| self.policy.is_due(&first, header))
+    }
+
+    /// Encrypt frame with current session key (see `IdtpFrame::seal`).
+    ///
+    /// # Parameters
+    /// - `frame` - given frame to encrypt.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if session key is not established, key rotation
+    ///   policy limit is reached or frame `sequence` would reuse nonce.
+    /// - Will return `Err` if payload with authentication tag is too large.
+    pub fn seal(&mut self, frame: &mut IdtpFrame) -> Result<(), IdtpError> {
+        let header = frame.header();
+
+        if self.is_rotation_due(&header) {
+            return Err(IdtpError::KeyUnavailable);
+        }
+
+        let key = self.key.as_mut().ok_or(IdtpError::KeyUnavailable)?;
+        frame.seal(key)?;
+        self.first.get_or_insert(header);
+        Ok(())
+    }
+}
+
+/// Handshake state which waits for `KeyResponse`.
+struct HostPending {
+    /// Identifier of the new key.
+    key_id: u8,
+    /// Host ephemeral secret.
+    secret: StaticSecret,
+}
+
+/// Host-side handshake state machine for one device.
+pub struct HostHandshake<'a> {
+    /// IMU device identifier.
+    device_id: u16,
+    /// Pre-shared device secret.
+    psk: &'a [u8],
+    /// Key rotation policy.
+    policy: RotationPolicy,
+    /// Identifier of the next key.
+    next_key_id: u8,
+    /// Newest session key.
+    key: Option<SecureKey>,
+    /// Session key used before the newest one.
+    previous: Option<SecureKey>,
+    /// Header of the first frame received with the newest session key.
+    first: Option<IdtpHeader>,
+    /// Handshake in progress.
+    pending: Option<HostPending>,
+}
+
+impl<'a> HostHandshake<'a> {
+    /// Construct new `HostHandshake` struct.
+    ///
+    /// # Parameters
+    /// - `device_id` - given IMU device identifier.
+    /// - `psk` - given pre-shared device secret.
+    /// - `policy` - given key rotation policy.
+    ///
+    /// # Returns
+    /// - New `HostHandshake` struct.
+    pub fn new(device_id: u16, psk: &'a [u8], policy: RotationPolicy) -> Self {
+        Self {
+            device_id,
+            psk,
+            policy,
+            next_key_id: 0,
+            key: None,
+            previous: None,
+            first: None,
+            pending: None,
+        }
+    }
+
+    /// Get the newest session key.
+    ///
+    /// # Returns
+    /// - Newest session key - if established.
+    /// - `None` - otherwise.
+    pub fn key(&self) -> Option<&SecureKey> {
+        self.key.as_ref()
+    }
+
+    /// Start handshake for the new session key. Previous handshake in
+    /// progress is abandoned.
+    ///
+    /// # Parameters
+    /// - `secret` - given 32 random bytes for host ephemeral secret.
+    ///
+    /// # Returns
+    /// - Session key request to send to device.
+    pub fn start(&mut self, secret: [u8; 32]) -> KeyRequest {
+        let secret = StaticSecret::from(secret);
+        let key_id = self.next_key_id;
+
+        let request = KeyRequest {
+            key_id,
+            public_key: PublicKey::from(&secret).to_bytes(),
+        };
+
+        self.next_key_id = key_id.wrapping_add(1);
+        self.pending = Some(HostPending { key_id, secret });
+        request
+    }
+
+    /// Handle session key response. On success the new key is accepted along
+    /// with the previous one until device starts using it.
+    ///
+    /// # Parameters
+    /// - `response` - given session key response.
+    ///
+    /// # Returns
+    /// - Session key confirmation to send to device - in case of success.
+    /// - `Err` - otherwise, handshake in progress is kept.
+    ///
+    /// # Errors
+    /// - Will return `Err` if there is no matching handshake in progress or
+    ///   device confirmation value does not match.
+    pub fn handle_response(
+        &mut self,
+        response: &KeyResponse,
+    ) -> Result<KeyFinished, IdtpError> {
+        let pending = self
+            .pending
+            .as_ref()
+            .filter(|pending| pending.key_id == response.key_id)
+            .ok_or(IdtpError::AuthenticationFailed)?;
+
+        let derived = derive(
+            self.psk,
+            self.device_id,
+            pending.key_id,
+            &pending.secret,
+            &response.public_key,
+            &PublicKey::from(&pending.secret).to_bytes(),
+            &response.public_key,
+        )?;
+
+        if !confirm_eq(&derived.device_confirm, &response.confirm) {
+            return Err(IdtpError::AuthenticationFailed);
+        }
+
+        self.pending = None;
+        self.previous = self.key.replace(derived.key);
+        self.first = None;
+
+        Ok(KeyFinished {
+            key_id: response.key_id,
+            confirm: derived.host_confirm,
+        })
+    }
+
+    /// Check whether newest session key must be rotated.
+    ///
+    /// # Parameters
+    /// - `header` - given header of the last received frame.
+    ///
+    /// # Returns
+    /// - `true`  - if key rotation policy limit is reached.
+    /// - `false` - otherwise.
+    pub fn is_rotation_due(&self, header: &IdtpHeader) -> bool {
+        self.first
+            .is_some_and(|first| self.policy.is_due(&first, header))
+    }
+
+    /// Verify and decrypt frame with session key it refers to (see
+    /// `IdtpFrame::open`). Previous key is dropped once device starts using
+    /// the newest one.
+    ///
+    /// # Parameters
+    /// - `frame` - given frame to decrypt.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if frame is not in Secure mode, it refers to
+    ///   unknown key or authentication fails.
+    pub fn open(&mut self, frame: &mut IdtpFrame) -> Result<(), IdtpError> {
+        let header = frame.header();
+
+        if !matches!(header.mode, Mode::Secure) {
+            return Err(IdtpError::AuthenticationFailed);
+        }
+
+        let key_id = header.key_id();
+
+        if let Some(key) = self.key.as_ref().filter(|key| key.id() == key_id) {
+            frame.open(key)?;
+            self.previous = None;
+            self.first.get_or_insert(header);
+            return Ok(());
+        }
+
+        match self.previous.as_ref().filter(|key| key.id() == key_id) {
+            Some(key) => frame.open(key),
+            None => Err(IdtpError::AuthenticationFailed),
+        }
+    }
+}
@@ -3,6 +3,9 @@
 
 //! IDTP header related declarations.
 
+use crate::IdtpError;
+use core::{fmt, str::FromStr};
+
 /// Value to signal the start of a new IDTP packet.
 pub const IDTP_PREAMBLE: &[u8] = b"IDTP";
 
@@ -15,8 +18,9 @@ pub const IDTP_TRAILER: &[u8] = b"PTDI";
 /// Size of IDTP trailer in bytes.
 pub const IDTP_TRAILER_SIZE: usize = IDTP_TRAILER.len();
 
-/// IDTP version information struct.
-#[derive(Debug, Default, Clone, Copy)]
+/// IDTP version information struct. Versions are ordered by major, minor
+/// and patch numbers.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(C)]
 pub struct Version {
     /// Increments after incompatible API changes were made.
@@ -63,6 +67,72 @@ impl Version {
     pub fn as_bytes(&self) -> [u8; IDTP_VERSION_SIZE] {
         [self.major, self.minor, self.patch]
     }
+
+    /// Check whether frames of other version can be processed by
+    /// implementation of this version. Versions with the same major number
+    /// are compatible: newer minor versions only add functionality signalled
+    /// by values which older receivers ignore (e.g. reserved bits).
+    ///
+    /// # Parameters
+    /// - `other` - given version of received frame.
+    ///
+    /// # Returns
+    /// - `true`  - if versions are compatible.
+    /// - `false` - otherwise.
+    pub const fn is_compatible(&self, other: &Version) -> bool {
+        self.major == other.major
+    }
+}
+
+impl fmt::Display for Version {
+    /// Format IDTP version as `MAJOR.MINOR.PATCH`.
+    ///
+    /// # Parameters
+    /// - `f` - given formatter.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl FromStr for Version {
+    type Err = IdtpError;
+
+    /// Parse IDTP version in `MAJOR.MINOR.PATCH` format (e.g. `1.0.0`).
+    ///
+    /// # Parameters
+    /// - `string` - given version string.
+    ///
+    /// # Returns
+    /// - Parsed version - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if string is not three decimal numbers in range
+    ///   0 - 255 separated by dots.
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let mut numbers = string.split('.').map(|number| {
+            if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit())
+            {
+                return Err(IdtpError::InvalidVersion);
+            }
+
+            number.parse::<u8>().map_err(|_| IdtpError::InvalidVersion)
+        });
+
+        let mut next =
+            || numbers.next().unwrap_or(Err(IdtpError::InvalidVersion));
+        let version = Self::new(next()?, next()?, next()?);
+
+        if numbers.next().is_some() {
+            return Err(IdtpError::InvalidVersion);
+        }
+
+        Ok(version)
+    }
 }
 
 impl From<&[u8]> for Version {
@@ -83,7 +153,7 @@ impl From<&[u8]> for Version {
 }
 
 /// IDTP operating mode.
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
 pub enum Mode {
     /// IDTP-N (Normal mode) - operating mode with general protection.
@@ -106,6 +176,27 @@ pub enum Mode {
     ///
     /// Both `checksum` and `crc` fields of IDTP header are used.
     Safety = 0x01,
+    /// IDTP-E (Secure mode) - operating mode with payload encryption and
+    /// authentication.
+    ///
+    /// Payload is encrypted with AES-128-GCM and followed by authentication
+    /// tag, header is authenticated as associated data. Tampering with any
+    /// header or payload byte is detected by receiver holding the key.
+    ///
+    /// Only `checksum` field of IDTP header is used for error detection.
+    /// The `crc` field is unused and filled with zeros.
+    Secure = 0x02,
+    /// IDTP-F (FEC mode) - operating mode with forward error correction.
+    ///
+    /// Header and payload are protected by Reed–Solomon parity appended to
+    /// payload, so receiver corrects corrupted bytes instead of only
+    /// detecting them (see `fec` module). Useful on links where
+    /// retransmission is impossible.
+    ///
+    /// `checksum` field of IDTP header covers header and payload without
+    /// parity and detects frames corrupted beyond correction capability.
+    /// The `crc` field is unused and filled with zeros.
+    Fec = 0x03,
     /// Unknown mode value. No special handling required.
     Unknown = 0xff,
 }
@@ -122,11 +213,109 @@ impl From<u8> for Mode {
         match byte {
             0x00 => Mode::Normal,
             0x01 => Mode::Safety,
+            0x02 => Mode::Secure,
+            0x03 => Mode::Fec,
             _ => Mode::Unknown,
         }
     }
 }
 
+/// Unit of IDTP header `timestamp` field.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum TimestampUnit {
+    /// Milliseconds. Frames without timestamp unit bits use this unit.
+    #[default]
+    Milliseconds = 0x00,
+    /// Microseconds.
+    Microseconds = 0x01,
+    /// Nanoseconds.
+    Nanoseconds = 0x02,
+    /// Ticks of device clock. Tick rate is carried by timestamp extension.
+    Ticks = 0x03,
+}
+
+impl TimestampUnit {
+    /// Get duration of one timestamp unit.
+    ///
+    /// # Parameters
+    /// - `tick_rate` - given device clock rate in Hz (used for `Ticks` only).
+    ///
+    /// # Returns
+    /// - Duration of one unit in microseconds - if known.
+    /// - `None` - for `Ticks` with zero tick rate.
+    pub fn period_us(self, tick_rate: u32) -> Option<f64> {
+        match self {
+            TimestampUnit::Milliseconds => Some(1000.0),
+            TimestampUnit::Microseconds => Some(1.0),
+            TimestampUnit::Nanoseconds => Some(0.001),
+            TimestampUnit::Ticks if tick_rate == 0 => None,
+            TimestampUnit::Ticks => Some(1e6 / f64::from(tick_rate)),
+        }
+    }
+}
+
+impl From<u8> for TimestampUnit {
+    /// Convert timestamp unit bits to timestamp unit.
+    ///
+    /// # Parameters
+    /// - `byte` - given timestamp unit bits (two least significant bits).
+    ///
+    /// # Returns
+    /// - Timestamp unit.
+    fn from(byte: u8) -> Self {
+        match byte & 0x03 {
+            0x00 => TimestampUnit::Milliseconds,
+            0x01 => TimestampUnit::Microseconds,
+            0x02 => TimestampUnit::Nanoseconds,
+            _ => TimestampUnit::Ticks,
+        }
+    }
+}
+
+/// Timestamp extension carried at the start of payload of frames with
+/// `IDTP_FLAG_TIMESTAMP_EXT` header flag.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TimestampExtension {
+    /// High 32 bits of 64-bit timestamp (header `timestamp` holds low bits).
+    pub timestamp_high: u32,
+    /// Device clock rate in Hz for `TimestampUnit::Ticks`, zero otherwise.
+    pub tick_rate: u32,
+}
+
+impl TimestampExtension {
+    /// Convert timestamp extension to bytes with big-endian network byte
+    /// order.
+    ///
+    /// # Returns
+    /// - Big-endian byte representation of timestamp extension.
+    pub fn to_bytes(&self) -> [u8; IDTP_TIMESTAMP_EXT_SIZE] {
+        let mut buffer = [0u8; IDTP_TIMESTAMP_EXT_SIZE];
+        buffer[0..4].copy_from_slice(&self.timestamp_high.to_be_bytes());
+        buffer[4..8].copy_from_slice(&self.tick_rate.to_be_bytes());
+        buffer
+    }
+
+    /// Convert bytes with big-endian network byte order to timestamp
+    /// extension.
+    ///
+    /// # Parameters
+    /// - `bytes` - given big-endian byte representation.
+    ///
+    /// # Returns
+    /// - Timestamp extension.
+    pub fn from_bytes(bytes: &[u8; IDTP_TIMESTAMP_EXT_SIZE]) -> Self {
+        Self {
+            timestamp_high: u32::from_be_bytes([
+                bytes[0], bytes[1], bytes[2], bytes[3],
+            ]),
+            tick_rate: u32::from_be_bytes([
+                bytes[4], bytes[5], bytes[6], bytes[7],
+            ]),
+        }
+    }
+}
+
 /// IDTP header struct.
 #[derive(Debug, Default, Clone, Copy)]
 #[repr(C, packed)]
@@ -155,6 +344,34 @@ pub struct IdtpHeader {
     pub reserved: [u8; 3],
 }
 
+/// Index of flags byte in `reserved` field of IDTP header.
+pub const IDTP_FLAGS_INDEX: usize = 0;
+
+/// Index of key identifier in `reserved` field of IDTP header.
+pub const IDTP_KEY_ID_INDEX: usize = 1;
+
+/// Flag signalling that payload ends with frame authentication tag.
+pub const IDTP_FLAG_AUTH: u8 = 0x01;
+
+/// Flag signalling that extension block precedes payload.
+pub const IDTP_FLAG_EXTENSIONS: u8 = 0x02;
+
+/// Flag signalling that payload starts with timestamp extension.
+pub const IDTP_FLAG_TIMESTAMP_EXT: u8 = 0x04;
+
+/// Flag signalling that frame must be acknowledged by receiver (see
+/// `reliable` module).
+pub const IDTP_FLAG_RELIABLE: u8 = 0x08;
+
+/// Mask of timestamp unit bits in header flags.
+pub const IDTP_TIMESTAMP_UNIT_MASK: u8 = 0x30;
+
+/// Position of timestamp unit bits in header flags.
+pub const IDTP_TIMESTAMP_UNIT_SHIFT: u32 = 4;
+
+/// Size of timestamp extension in bytes.
+pub const IDTP_TIMESTAMP_EXT_SIZE: usize = 8;
+
 /// Size of IDTP header in bytes.
 pub const IDTP_HEADER_SIZE: usize = size_of::<IdtpHeader>();
 
@@ -170,6 +387,68 @@ impl IdtpHeader {
         header
     }
 
+    /// Get size of the whole IDTP frame described by this header.
+    ///
+    /// # Returns
+    /// - Size of header, payload and trailer in bytes.
+    pub fn frame_size(&self) -> usize {
+        (self.payload_size as usize)
+            .saturating_add(IDTP_HEADER_SIZE + IDTP_TRAILER_SIZE)
+    }
+
+    /// Get header flags (first byte of `reserved` field).
+    ///
+    /// # Returns
+    /// - Header flags.
+    pub fn flags(&self) -> u8 {
+        self.reserved[IDTP_FLAGS_INDEX]
+    }
+
+    /// Set header flags.
+    ///
+    /// # Parameters
+    /// - `flags` - given header flags to set.
+    pub fn set_flags(&mut self, flags: u8) {
+        self.reserved[IDTP_FLAGS_INDEX] = flags;
+    }
+
+    /// Get unit of `timestamp` field (bits 4-5 of header flags).
+    ///
+    /// # Returns
+    /// - Timestamp unit.
+    pub fn timestamp_unit(&self) -> TimestampUnit {
+        TimestampUnit::from(
+            (self.flags() & IDTP_TIMESTAMP_UNIT_MASK)
+                >> IDTP_TIMESTAMP_UNIT_SHIFT,
+        )
+    }
+
+    /// Set unit of `timestamp` field.
+    ///
+    /// # Parameters
+    /// - `unit` - given timestamp unit to set.
+    pub fn set_timestamp_unit(&mut self, unit: TimestampUnit) {
+        let flags = self.flags() & !IDTP_TIMESTAMP_UNIT_MASK;
+        self.set_flags(flags | ((unit as u8) << IDTP_TIMESTAMP_UNIT_SHIFT));
+    }
+
+    /// Get identifier of the key used for frame authentication (second
+    /// byte of `reserved` field).
+    ///
+    /// # Returns
+    /// - Key identifier.
+    pub fn key_id(&self) -> u8 {
+        self.reserved[IDTP_KEY_ID_INDEX]
+    }
+
+    /// Set identifier of the key used for frame authentication.
+    ///
+    /// # Parameters
+    /// - `key_id` - given key identifier to set.
+    pub fn set_key_id(&mut self, key_id: u8) {
+        self.reserved[IDTP_KEY_ID_INDEX] = key_id;
+    }
+
     /// Convert IDTP header to byte slice with big-endian network byte order.
     ///
     /// # Returns
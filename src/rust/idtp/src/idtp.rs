@@ -3,7 +3,23 @@
 
 //! Inertial Measurement Unit Data Transfer Protocol frame implementation.
 
-use crate::{IDTP_HEADER_SIZE, IDTP_TRAILER, IDTP_TRAILER_SIZE, IdtpHeader};
+use crate::{
+    IDTP_FLAG_AUTH, IDTP_FLAG_EXTENSIONS, IDTP_FLAG_TIMESTAMP_EXT,
+    IDTP_HEADER_SIZE, IDTP_PREAMBLE, IDTP_PREAMBLE_SIZE,
+    IDTP_TIMESTAMP_EXT_SIZE, IDTP_TRAILER, IDTP_TRAILER_SIZE, IDTP_VERSION,
+    IdtpError, IdtpHeader, Integrity, Mode, TimestampExtension, TimestampUnit,
+    checksum, covered_payload, extension::ExtensionBlock, fec, frame_crc,
+};
+use core::time::Duration;
+
+#[cfg(feature = "auth")]
+use crate::auth::{AuthKey, IDTP_AUTH_TAG_SIZE};
+
+#[cfg(feature = "secure")]
+use crate::{
+    covered_header,
+    secure::{IDTP_TAG_SIZE, SecureKey, nonce},
+};
 
 /// IDTP network packet max size in bytes. It includes size of IDTP header,
 /// payload and packet trailer.
@@ -85,6 +101,506 @@ impl IdtpFrame {
         self.payload_size
     }
 
+    /// Get size of raw IDTP network packet in bytes.
+    ///
+    /// # Returns
+    /// - Size of header, payload and trailer in bytes.
+    pub fn frame_size(&self) -> usize {
+        IDTP_PACKET_MIN_SIZE + self.payload_size
+    }
+
+    /// Fill header `payload_size`, `checksum` and `crc` fields according to
+    /// current payload and operating mode.
+    pub fn update_integrity(&mut self) {
+        self.header.payload_size = self.payload_size as u32;
+        self.header.crc = 0;
+
+        if let Mode::Safety = self.header.mode {
+            self.header.crc = frame_crc(&self.header, self.payload());
+        }
+
+        self.header.checksum = checksum(&self.header, self.payload());
+    }
+
+    /// Verify frame integrity according to its operating mode.
+    ///
+    /// # Returns
+    /// - Integrity verification result.
+    pub fn verify(&self) -> Integrity {
+        Integrity::verify(&self.header, self.payload())
+    }
+
+    /// Get extension block preceding payload data.
+    ///
+    /// # Returns
+    /// - Extension block (`ExtensionBlock::EMPTY` if header
+    ///   `IDTP_FLAG_EXTENSIONS` flag is not set) - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if extension block is malformed.
+    pub fn extensions(&self) -> Result<ExtensionBlock<'_>, IdtpError> {
+        if self.header.flags() & IDTP_FLAG_EXTENSIONS == 0 {
+            return Ok(ExtensionBlock::EMPTY);
+        }
+
+        ExtensionBlock::parse(self.payload())
+    }
+
+    /// Put extension block at the start of payload and set header
+    /// `IDTP_FLAG_EXTENSIONS` flag. Existing block is replaced. Must be
+    /// called before `update_integrity`, `sign` or `seal`.
+    ///
+    /// # Parameters
+    /// - `block` - given extension block (e.g. written by
+    ///   `ExtensionBuilder`).
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise, frame is left unchanged.
+    ///
+    /// # Errors
+    /// - Will return `Err` if given or existing extension block is malformed
+    ///   or payload with extension block exceeds `IDTP_PAYLOAD_MAX_SIZE`.
+    pub fn set_extensions(&mut self, block: &[u8]) -> Result<(), IdtpError> {
+        if ExtensionBlock::parse(block)?.size() != block.len() {
+            return Err(IdtpError::InvalidPayload);
+        }
+
+        let old_size = self.extensions()?.size();
+        let data_size = self.payload_size - old_size;
+
+        if block.len() + data_size > IDTP_PAYLOAD_MAX_SIZE {
+            return Err(IdtpError::FrameTooLarge);
+        }
+
+        self.payload
+            .copy_within(old_size..self.payload_size, block.len());
+        self.payload[..block.len()].copy_from_slice(block);
+        self.payload_size = block.len() + data_size;
+
+        let flags = self.header.flags();
+        self.header.set_flags(flags | IDTP_FLAG_EXTENSIONS);
+        Ok(())
+    }
+
+    /// Get payload data without extension block, timestamp extension and
+    /// Reed–Solomon parity.
+    ///
+    /// # Returns
+    /// - Payload data - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if extension block is malformed or header flag
+    ///   signals timestamp extension which does not fit into payload.
+    pub fn data(&self) -> Result<&[u8], IdtpError> {
+        let mut offset = self.extensions()?.size();
+
+        if self.header.flags() & IDTP_FLAG_TIMESTAMP_EXT != 0 {
+            offset += IDTP_TIMESTAMP_EXT_SIZE;
+        }
+
+        covered_payload(&self.header, self.payload())
+            .get(offset..)
+            .ok_or(IdtpError::InvalidPayload)
+    }
+
+    /// Get timestamp extension from the start of payload data (after
+    /// extension block).
+    ///
+    /// # Returns
+    /// - Timestamp extension - if header `IDTP_FLAG_TIMESTAMP_EXT` flag is
+    ///   set and payload is large enough.
+    /// - `None` - otherwise.
+    pub fn timestamp_extension(&self) -> Option<TimestampExtension> {
+        if self.header.flags() & IDTP_FLAG_TIMESTAMP_EXT == 0 {
+            return None;
+        }
+
+        let offset = self.extensions().ok()?.size();
+        let bytes = self.payload()[offset..]
+            .first_chunk::<IDTP_TIMESTAMP_EXT_SIZE>()?;
+        Some(TimestampExtension::from_bytes(bytes))
+    }
+
+    /// Put timestamp extension at the start of payload data (after extension
+    /// block) and set header `IDTP_FLAG_TIMESTAMP_EXT` flag. Existing
+    /// extension is replaced. Must be called before `update_integrity`, `sign`
+    /// or `seal`.
+    ///
+    /// # Parameters
+    /// - `extension` - given timestamp extension to set.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if extension block is malformed or payload with
+    ///   timestamp extension exceeds `IDTP_PAYLOAD_MAX_SIZE`.
+    pub fn set_timestamp_extension(
+        &mut self,
+        extension: &TimestampExtension,
+    ) -> Result<(), IdtpError> {
+        let offset = self.extensions()?.size();
+        let flags = self.header.flags();
+
+        if self.timestamp_extension().is_none() {
+            let size = self.payload_size;
+
+            if size + IDTP_TIMESTAMP_EXT_SIZE > IDTP_PAYLOAD_MAX_SIZE {
+                return Err(IdtpError::FrameTooLarge);
+            }
+
+            self.payload
+                .copy_within(offset..size, offset + IDTP_TIMESTAMP_EXT_SIZE);
+            self.payload_size = size + IDTP_TIMESTAMP_EXT_SIZE;
+            self.header.set_flags(flags | IDTP_FLAG_TIMESTAMP_EXT);
+        }
+
+        self.payload[offset..offset + IDTP_TIMESTAMP_EXT_SIZE]
+            .copy_from_slice(&extension.to_bytes());
+        Ok(())
+    }
+
+    /// Remove timestamp extension from payload. Header
+    /// `IDTP_FLAG_TIMESTAMP_EXT` flag is cleared and `payload_size` is set to
+    /// the size of payload without extension, so `checksum` and `crc` fields
+    /// no longer match.
+    ///
+    /// # Returns
+    /// - Removed timestamp extension - if frame has one.
+    /// - `None` - otherwise, frame is left unchanged.
+    pub fn take_timestamp_extension(&mut self) -> Option<TimestampExtension> {
+        let extension = self.timestamp_extension()?;
+        let offset = self.extensions().ok()?.size();
+        let size = self.payload_size - IDTP_TIMESTAMP_EXT_SIZE;
+
+        self.payload.copy_within(
+            offset + IDTP_TIMESTAMP_EXT_SIZE..self.payload_size,
+            offset,
+        );
+        self.payload[size..self.payload_size].fill(0);
+        self.payload_size = size;
+        self.header.payload_size = size as u32;
+
+        let flags = self.header.flags();
+        self.header.set_flags(flags & !IDTP_FLAG_TIMESTAMP_EXT);
+        Some(extension)
+    }
+
+    /// Get frame timestamp according to header timestamp unit and timestamp
+    /// extension (extension must still be present in payload).
+    ///
+    /// # Returns
+    /// - Time since device clock start - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if header flag signals timestamp extension which
+    ///   does not fit into payload or timestamp is counted in ticks without
+    ///   known tick rate.
+    pub fn timestamp(&self) -> Result<Duration, IdtpError> {
+        let has_extension = self.header.flags() & IDTP_FLAG_TIMESTAMP_EXT != 0;
+        let extension = self.timestamp_extension();
+
+        if has_extension && extension.is_none() {
+            return Err(IdtpError::InvalidPayload);
+        }
+
+        let extension = extension.unwrap_or_default();
+        let timestamp = (u64::from(extension.timestamp_high) << 32)
+            | u64::from(self.header.timestamp);
+
+        match self.header.timestamp_unit() {
+            TimestampUnit::Milliseconds => Ok(Duration::from_millis(timestamp)),
+            TimestampUnit::Microseconds => Ok(Duration::from_micros(timestamp)),
+            TimestampUnit::Nanoseconds => Ok(Duration::from_nanos(timestamp)),
+            TimestampUnit::Ticks => {
+                let rate = u64::from(extension.tick_rate);
+
+                if rate == 0 {
+                    return Err(IdtpError::InvalidPayload);
+                }
+
+                let nanos = u128::from(timestamp % rate) * 1_000_000_000
+                    / u128::from(rate);
+                Ok(Duration::new(timestamp / rate, nanos as u32))
+            }
+        }
+    }
+
+    /// Append Reed–Solomon parity over header and payload in FEC mode and
+    /// fill header `mode`, `payload_size`, `checksum` and `crc` fields. Must
+    /// be called after all other header fields are set and payload is
+    /// complete.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise, frame is left unchanged.
+    ///
+    /// # Errors
+    /// - Will return `Err` if payload with parity exceeds
+    ///   `IDTP_PAYLOAD_MAX_SIZE` or frame carries authentication tag (its
+    ///   `mode` and `payload_size` fields are authenticated).
+    pub fn protect(&mut self) -> Result<(), IdtpError> {
+        if self.header.flags() & IDTP_FLAG_AUTH != 0 {
+            return Err(IdtpError::InvalidPayload);
+        }
+
+        let size = self.payload_size;
+        let parity_size = fec::parity_size(IDTP_HEADER_SIZE + size);
+
+        if size + parity_size > IDTP_PAYLOAD_MAX_SIZE {
+            return Err(IdtpError::FrameTooLarge);
+        }
+
+        self.header.mode = Mode::Fec;
+        self.payload_size = size + parity_size;
+        self.update_integrity();
+
+        let mut message = [0u8; IDTP_PACKET_MAX_SIZE];
+        message[..IDTP_HEADER_SIZE].copy_from_slice(&self.header.as_bytes_be());
+        message[IDTP_HEADER_SIZE..IDTP_HEADER_SIZE + size]
+            .copy_from_slice(&self.payload[..size]);
+
+        fec::encode(
+            &message[..IDTP_HEADER_SIZE + size],
+            &mut self.payload[size..size + parity_size],
+        );
+        Ok(())
+    }
+
+    /// Encrypt payload in Secure mode and fill header `mode`, key
+    /// identifier, `payload_size`, `checksum` and `crc` fields. Header
+    /// `device_id` and `sequence` fields must be set beforehand, as they are
+    /// used to derive nonce. Frames sealed with the same key must have
+    /// increasing `sequence` (see `SecureKey::reserve`).
+    ///
+    /// # Parameters
+    /// - `key` - given key to encrypt payload with.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise, frame is left unchanged.
+    ///
+    /// # Errors
+    /// - Will return `Err` if payload with authentication tag exceeds
+    ///   `IDTP_PAYLOAD_MAX_SIZE`.
+    /// - Will return `Err` if `sequence` is not greater than sequence number
+    ///   of the last frame sealed with the key, as nonce would be reused
+    ///   (e.g. `sequence` wrapped around or restarted). Key must be rotated.
+    #[cfg(feature = "secure")]
+    pub fn seal(&mut self, key: &mut SecureKey) -> Result<(), IdtpError> {
+        let size = self.payload_size;
+
+        if size + IDTP_TAG_SIZE > IDTP_PAYLOAD_MAX_SIZE {
+            return Err(IdtpError::FrameTooLarge);
+        }
+
+        key.reserve(self.header.sequence)?;
+
+        self.header.mode = Mode::Secure;
+        self.header.set_key_id(key.id());
+        self.header.payload_size = (size + IDTP_TAG_SIZE) as u32;
+        self.header.crc = 0;
+
+        let nonce = nonce(self.header.device_id, self.header.sequence);
+        let aad = covered_header(&self.header);
+        let tag = key.encrypt(&nonce, &aad, &mut self.payload[..size]);
+
+        self.payload[size..size + IDTP_TAG_SIZE].copy_from_slice(&tag);
+        self.payload_size = size + IDTP_TAG_SIZE;
+        self.header.checksum = checksum(&self.header, self.payload());
+        Ok(())
+    }
+
+    /// Verify Secure mode frame authenticity and decrypt payload. Frame
+    /// payload and header `payload_size` are replaced by plaintext ones.
+    ///
+    /// # Parameters
+    /// - `key` - given key to decrypt payload with.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise, frame is left unchanged.
+    ///
+    /// # Errors
+    /// - Will return `Err` if frame is not in Secure mode, it was encrypted
+    ///   with another key or header or payload were altered.
+    #[cfg(feature = "secure")]
+    pub fn open(&mut self, key: &SecureKey) -> Result<(), IdtpError> {
+        let size = self
+            .payload_size
+            .checked_sub(IDTP_TAG_SIZE)
+            .ok_or(IdtpError::AuthenticationFailed)?;
+
+        if !matches!(self.header.mode, Mode::Secure)
+            || self.header.key_id() != key.id()
+        {
+            return Err(IdtpError::AuthenticationFailed);
+        }
+
+        let nonce = nonce(self.header.device_id, self.header.sequence);
+        let aad = covered_header(&self.header);
+        let mut tag = [0u8; IDTP_TAG_SIZE];
+        tag.copy_from_slice(&self.payload[size..self.payload_size]);
+
+        key.decrypt(&nonce, &aad, &mut self.payload[..size], &tag)?;
+
+        self.payload[size..self.payload_size].fill(0);
+        self.payload_size = size;
+        self.header.payload_size = size as u32;
+        Ok(())
+    }
+
+    /// Append frame authentication tag, set `IDTP_FLAG_AUTH` header flag and
+    /// fill header `payload_size`, `checksum` and `crc` fields. Must be
+    /// called after all other header fields are set.
+    ///
+    /// # Parameters
+    /// - `key` - given authentication key.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if payload with authentication tag exceeds
+    ///   `IDTP_PAYLOAD_MAX_SIZE`.
+    #[cfg(feature = "auth")]
+    pub fn sign(&mut self, key: &AuthKey) -> Result<(), IdtpError> {
+        let size = self.payload_size;
+
+        if size + IDTP_AUTH_TAG_SIZE > IDTP_PAYLOAD_MAX_SIZE {
+            return Err(IdtpError::FrameTooLarge);
+        }
+
+        let flags = self.header.flags();
+        self.header.set_flags(flags | IDTP_FLAG_AUTH);
+        self.header.payload_size = (size + IDTP_AUTH_TAG_SIZE) as u32;
+
+        let tag = key.tag(&self.header, &self.payload[..size]);
+        self.payload[size..size + IDTP_AUTH_TAG_SIZE].copy_from_slice(&tag);
+        self.payload_size = size + IDTP_AUTH_TAG_SIZE;
+        self.update_integrity();
+        Ok(())
+    }
+
+    /// Verify frame authentication tag and remove it from payload. Header
+    /// `IDTP_FLAG_AUTH` flag is cleared and `payload_size` is set to the size
+    /// of payload without tag, so `checksum` and `crc` fields no longer match.
+    ///
+    /// # Parameters
+    /// - `key` - given authentication key.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise, frame is left unchanged.
+    ///
+    /// # Errors
+    /// - Will return `Err` if frame has no authentication tag or tag does
+    ///   not match.
+    #[cfg(feature = "auth")]
+    pub fn authenticate(&mut self, key: &AuthKey) -> Result<(), IdtpError> {
+        let flags = self.header.flags();
+
+        if flags & IDTP_FLAG_AUTH == 0 {
+            return Err(IdtpError::AuthenticationFailed);
+        }
+
+        let size = self
+            .payload_size
+            .checked_sub(IDTP_AUTH_TAG_SIZE)
+            .ok_or(IdtpError::AuthenticationFailed)?;
+
+        let mut tag = [0u8; IDTP_AUTH_TAG_SIZE];
+        tag.copy_from_slice(&self.payload[size..self.payload_size]);
+        key.verify(&self.header, &self.payload[..size], &tag)?;
+
+        self.payload[size..self.payload_size].fill(0);
+        self.payload_size = size;
+        self.header.payload_size = size as u32;
+        self.header.set_flags(flags & !IDTP_FLAG_AUTH);
+        Ok(())
+    }
+
+    /// Parse raw IDTP network packet validating its structure and
+    /// authenticity (see `parse` and `authenticate`). Frames without
+    /// authentication tag are rejected.
+    ///
+    /// # Parameters
+    /// - `bytes` - given raw IDTP packet (big-endian byte order).
+    /// - `keys` - given function to look up authentication key for frame
+    ///   header (e.g. by `device_id`).
+    ///
+    /// # Returns
+    /// - IDTP frame struct without authentication tag - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if packet structure is invalid, there is no key
+    ///   for the frame or frame authentication fails.
+    #[cfg(feature = "auth")]
+    pub fn parse_authenticated<'a, F>(
+        bytes: &[u8],
+        keys: F,
+    ) -> Result<Self, IdtpError>
+    where
+        F: FnOnce(&IdtpHeader) -> Option<&'a AuthKey>,
+    {
+        let mut frame = Self::parse(bytes)?;
+        let key = keys(&frame.header).ok_or(IdtpError::AuthenticationFailed)?;
+        frame.authenticate(key)?;
+        Ok(frame)
+    }
+
+    /// Parse raw IDTP network packet validating its structure.
+    /// Integrity is not checked, use `verify` for that.
+    ///
+    /// # Parameters
+    /// - `bytes` - given raw IDTP packet (big-endian byte order).
+    ///
+    /// # Returns
+    /// - IDTP frame struct - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if packet size is out of range, preamble or
+    ///   trailer are invalid or `payload_size` does not match packet size.
+    /// - Will return `Err` if major protocol version differs from
+    ///   `IDTP_VERSION` (see `Version::is_compatible`).
+    pub fn parse(bytes: &[u8]) -> Result<Self, IdtpError> {
+        if bytes.len() < IDTP_PACKET_MIN_SIZE {
+            return Err(IdtpError::FrameTooShort);
+        }
+
+        if bytes.len() > IDTP_PACKET_MAX_SIZE {
+            return Err(IdtpError::FrameTooLarge);
+        }
+
+        if &bytes[0..IDTP_PREAMBLE_SIZE] != IDTP_PREAMBLE {
+            return Err(IdtpError::InvalidPreamble);
+        }
+
+        let header = IdtpHeader::from(&bytes[0..IDTP_HEADER_SIZE]);
+
+        if !IDTP_VERSION.is_compatible(&header.version) {
+            return Err(IdtpError::InvalidVersion);
+        }
+
+        if header.frame_size() != bytes.len() {
+            return Err(IdtpError::PayloadSizeMismatch);
+        }
+
+        if &bytes[bytes.len() - IDTP_TRAILER_SIZE..] != IDTP_TRAILER {
+            return Err(IdtpError::InvalidTrailer);
+        }
+
+        Ok(Self::from(bytes))
+    }
+
     /// Pack into raw IDTP network packet.
     ///
     /// # Parameters
@@ -131,7 +647,9 @@ impl Default for IdtpFrame {
 }
 
 impl From<&[u8]> for IdtpFrame {
-    /// Convert byte slice to IDTP frame.
+    /// Convert byte slice to IDTP frame. Structure is not validated, use
+    /// `parse` for that. Missing header bytes are treated as zeros and
+    /// payload is truncated to `IDTP_PAYLOAD_MAX_SIZE`.
     ///
     /// # Parameters
     /// - `bytes` - given byte slice to convert (big-endian byte order).
@@ -139,12 +657,24 @@ impl From<&[u8]> for IdtpFrame {
     /// # Returns
     /// - IDTP frame struct from byte slice.
     fn from(bytes: &[u8]) -> Self {
+        let mut header = [0u8; IDTP_HEADER_SIZE];
+        let header_size = bytes.len().min(IDTP_HEADER_SIZE);
+        header[..header_size].copy_from_slice(&bytes[..header_size]);
+
         let mut idtp = IdtpFrame::new();
-        idtp.header = IdtpHeader::from(&bytes[0..IDTP_HEADER_SIZE]);
-        idtp.payload_size = bytes.len() - IDTP_HEADER_SIZE - IDTP_TRAILER_SIZE;
+        idtp.header = IdtpHeader::from(&header[..]);
+        idtp.payload_size = bytes
+            .len()
+            .saturating_sub(IDTP_PACKET_MIN_SIZE)
+            .min(IDTP_PAYLOAD_MAX_SIZE);
+
+        if idtp.payload_size > 0 {
+            let copy_range =
+                IDTP_HEADER_SIZE..IDTP_HEADER_SIZE + idtp.payload_size;
+            idtp.payload[0..idtp.payload_size]
+                .copy_from_slice(&bytes[copy_range]);
+        }
 
-        let copy_range = IDTP_HEADER_SIZE..IDTP_HEADER_SIZE + idtp.payload_size;
-        idtp.payload[0..idtp.payload_size].copy_from_slice(&bytes[copy_range]);
         idtp
     }
 }
@@ -0,0 +1,298 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP frame integrity checks (checksum & CRC).
+
+use crate::{IDTP_HEADER_SIZE, IdtpHeader, Mode, fec};
+
+/// Offset of `mode` field in IDTP header.
+const MODE_OFFSET: usize = 7;
+
+/// Offset range of `checksum` field in IDTP header.
+const CHECKSUM_RANGE: core::ops::Range<usize> = 10..12;
+
+/// Offset range of `crc` field in IDTP header.
+const CRC_RANGE: core::ops::Range<usize> = 20..24;
+
+/// CRC-32 (Ethernet) generator polynomial in reflected form (`0x04C11DB7`).
+const CRC32_POLYNOMIAL: u32 = 0xEDB8_8320;
+
+/// CRC-32 lookup table.
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+/// Generate CRC-32 lookup table.
+///
+/// # Returns
+/// - CRC-32 lookup table.
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+
+    while i < 256 {
+        let mut value = i as u32;
+        let mut bit = 0;
+
+        while bit < 8 {
+            value = if value & 1 == 1 {
+                (value >> 1) ^ CRC32_POLYNOMIAL
+            } else {
+                value >> 1
+            };
+            bit += 1;
+        }
+
+        table[i] = value;
+        i += 1;
+    }
+
+    table
+}
+
+/// Incremental CRC-32 (Ethernet, `0x04C11DB7` polynomial) calculator.
+#[derive(Debug, Clone, Copy)]
+pub struct Crc32 {
+    /// Current CRC register value.
+    state: u32,
+}
+
+impl Crc32 {
+    /// Construct new `Crc32` struct.
+    ///
+    /// # Returns
+    /// - New `Crc32` struct.
+    pub const fn new() -> Self {
+        Self { state: 0xFFFF_FFFF }
+    }
+
+    /// Process bytes.
+    ///
+    /// # Parameters
+    /// - `bytes` - given bytes to process.
+    pub fn update(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            let index = (self.state ^ u32::from(*byte)) & 0xFF;
+            self.state = (self.state >> 8) ^ CRC32_TABLE[index as usize];
+        }
+    }
+
+    /// Get CRC-32 of processed bytes.
+    ///
+    /// # Returns
+    /// - CRC-32 value.
+    pub const fn finalize(&self) -> u32 {
+        !self.state
+    }
+}
+
+impl Default for Crc32 {
+    /// Construct new default `Crc32` struct.
+    ///
+    /// # Returns
+    /// - New default `Crc32` struct.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Calculate CRC-32 (Ethernet, `0x04C11DB7` polynomial).
+///
+/// # Parameters
+/// - `bytes` - given bytes to process.
+///
+/// # Returns
+/// - CRC-32 value.
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = Crc32::new();
+    crc.update(bytes);
+    crc.finalize()
+}
+
+/// Get big-endian header bytes with `checksum` and `crc` fields zeroed.
+///
+/// # Parameters
+/// - `header` - given IDTP header.
+///
+/// # Returns
+/// - Header bytes covered by integrity checks.
+#[cfg(any(feature = "auth", feature = "secure"))]
+pub(crate) fn covered_header(header: &IdtpHeader) -> [u8; IDTP_HEADER_SIZE] {
+    covered_bytes(&header.as_bytes_be())
+}
+
+/// Get raw header bytes with `checksum` and `crc` fields zeroed.
+fn covered_bytes(header: &[u8; IDTP_HEADER_SIZE]) -> [u8; IDTP_HEADER_SIZE] {
+    let mut bytes = *header;
+    bytes[CHECKSUM_RANGE].fill(0);
+    bytes[CRC_RANGE].fill(0);
+    bytes
+}
+
+/// Get payload bytes covered by checksum: payload without Reed–Solomon
+/// parity in FEC mode, the whole payload otherwise.
+///
+/// # Parameters
+/// - `header` - given IDTP header.
+/// - `payload` - given IDTP payload.
+///
+/// # Returns
+/// - Payload bytes covered by checksum.
+pub(crate) fn covered_payload<'a>(
+    header: &IdtpHeader,
+    payload: &'a [u8],
+) -> &'a [u8] {
+    covered_mode_payload(header.mode, payload)
+}
+
+/// Get payload bytes covered by checksum in given operating mode.
+fn covered_mode_payload(mode: Mode, payload: &[u8]) -> &[u8] {
+    if mode != Mode::Fec {
+        return payload;
+    }
+
+    fec::message_size(IDTP_HEADER_SIZE + payload.len())
+        .and_then(|size| size.checked_sub(IDTP_HEADER_SIZE))
+        .map_or(payload, |size| &payload[..size])
+}
+
+/// Calculate IDTP frame checksum - sum of header bytes (excluding `checksum`
+/// and `crc` fields) and payload bytes (excluding parity in FEC mode).
+///
+/// # Parameters
+/// - `header` - given IDTP header.
+/// - `payload` - given IDTP payload.
+///
+/// # Returns
+/// - IDTP frame checksum.
+pub fn checksum(header: &IdtpHeader, payload: &[u8]) -> u16 {
+    raw_checksum(&header.as_bytes_be(), payload)
+}
+
+/// Calculate IDTP frame checksum over raw header bytes, keeping field values
+/// which `IdtpHeader` can not represent (e.g. unknown `mode`).
+///
+/// # Parameters
+/// - `header` - given raw IDTP header bytes.
+/// - `payload` - given IDTP payload.
+///
+/// # Returns
+/// - IDTP frame checksum.
+pub fn raw_checksum(header: &[u8; IDTP_HEADER_SIZE], payload: &[u8]) -> u16 {
+    let mode = Mode::from(header[MODE_OFFSET]);
+
+    covered_bytes(header)
+        .iter()
+        .chain(covered_mode_payload(mode, payload))
+        .fold(0u16, |sum, byte| sum.wrapping_add(u16::from(*byte)))
+}
+
+/// Calculate IDTP frame CRC-32 over header (with `checksum` and `crc`
+/// fields filled with zeros) and payload bytes.
+///
+/// # Parameters
+/// - `header` - given IDTP header.
+/// - `payload` - given IDTP payload.
+///
+/// # Returns
+/// - IDTP frame CRC-32.
+pub fn frame_crc(header: &IdtpHeader, payload: &[u8]) -> u32 {
+    raw_frame_crc(&header.as_bytes_be(), payload)
+}
+
+/// Calculate IDTP frame CRC-32 over raw header bytes, keeping field values
+/// which `IdtpHeader` can not represent (e.g. unknown `mode`).
+///
+/// # Parameters
+/// - `header` - given raw IDTP header bytes.
+/// - `payload` - given IDTP payload.
+///
+/// # Returns
+/// - IDTP frame CRC-32.
+pub fn raw_frame_crc(header: &[u8; IDTP_HEADER_SIZE], payload: &[u8]) -> u32 {
+    let mut crc = Crc32::new();
+    crc.update(&covered_bytes(header));
+    crc.update(payload);
+    crc.finalize()
+}
+
+/// Result of single integrity check.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Check<T> {
+    /// Value calculated over the received frame contents.
+    pub expected: T,
+    /// Value carried by the frame header.
+    pub actual: T,
+}
+
+impl<T: PartialEq> Check<T> {
+    /// Check whether carried value matches calculated one.
+    ///
+    /// # Returns
+    /// - `true`  - if values match.
+    /// - `false` - otherwise.
+    pub fn is_ok(&self) -> bool {
+        self.expected == self.actual
+    }
+}
+
+/// Result of IDTP frame integrity verification.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Integrity {
+    /// Checksum verification result.
+    pub checksum: Check<u16>,
+    /// CRC verification result (`None` if CRC is not used by frame mode).
+    pub crc: Option<Check<u32>>,
+}
+
+impl Integrity {
+    /// Verify IDTP frame integrity according to its operating mode.
+    ///
+    /// # Parameters
+    /// - `header` - given IDTP header.
+    /// - `payload` - given IDTP payload.
+    ///
+    /// # Returns
+    /// - Integrity verification result.
+    pub fn verify(header: &IdtpHeader, payload: &[u8]) -> Self {
+        Self::verify_raw(&header.as_bytes_be(), payload)
+    }
+
+    /// Verify IDTP frame integrity over raw header bytes, keeping field
+    /// values which `IdtpHeader` can not represent (e.g. unknown `mode`).
+    ///
+    /// # Parameters
+    /// - `header` - given raw IDTP header bytes.
+    /// - `payload` - given IDTP payload.
+    ///
+    /// # Returns
+    /// - Integrity verification result.
+    pub fn verify_raw(header: &[u8; IDTP_HEADER_SIZE], payload: &[u8]) -> Self {
+        let crc = match Mode::from(header[MODE_OFFSET]) {
+            Mode::Safety => Some(Check {
+                expected: raw_frame_crc(header, payload),
+                actual: u32::from_be_bytes(
+                    header[CRC_RANGE].try_into().unwrap(),
+                ),
+            }),
+            _ => None,
+        };
+
+        let actual = header[CHECKSUM_RANGE].try_into().unwrap();
+
+        Self {
+            checksum: Check {
+                expected: raw_checksum(header, payload),
+                actual: u16::from_be_bytes(actual),
+            },
+            crc,
+        }
+    }
+
+    /// Check whether all performed checks passed.
+    ///
+    /// # Returns
+    /// - `true`  - if frame integrity is confirmed.
+    /// - `false` - otherwise.
+    pub fn is_ok(&self) -> bool {
+        self.checksum.is_ok() && self.crc.is_none_or(|crc| crc.is_ok())
+    }
+}
@@ -0,0 +1,39 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! I/O helpers shared by host-side file formats.
+
+use std::io::{self, Read};
+
+/// Maximum size of variable-length block read from file in bytes.
+pub(crate) const MAX_BLOCK_SIZE: usize = 1 << 24;
+
+/// Read exactly `buffer.len()` bytes unless input ends.
+///
+/// # Parameters
+/// - `reader` - given input to read from.
+/// - `buffer` - given buffer to fill.
+///
+/// # Returns
+/// - `true`  - if buffer was filled.
+/// - `false` - if input ended before buffer was filled.
+pub(crate) fn read_complete<R: Read>(
+    reader: &mut R,
+    buffer: &mut [u8],
+) -> io::Result<bool> {
+    match reader.read_exact(buffer) {
+        Ok(()) => Ok(true),
+        Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => Ok(false),
+        Err(error) => Err(error),
+    }
+}
+
+/// Construct I/O error caused by invalid data.
+pub(crate) fn invalid_data(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Construct I/O error caused by invalid input.
+pub(crate) fn invalid_input(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
@@ -5,13 +5,59 @@
 //! used for transferring IMU data. This protocol is suitable for usage in areas
 //! of robotics, unmanned vehicles, wearable devices and etc.
 //!
-//! This crate was designed for use on `embedded systems`.
+//! This crate was designed for use on `embedded systems`. Host-side
+//! functionality (e.g. recording of IDTP streams or pcap export) is available
+//! with `std` feature enabled. IDTP-E (Secure mode) payload encryption is
+//! available with `secure` feature enabled and session key establishment
+//! with `handshake` feature enabled. Frame authentication without encryption
+//! is available with `auth` feature enabled. Filling of lost samples is
+//! available with `gap` feature enabled.
 
 #![no_std]
 // Ignore #[must_use] suggestions from clippy.
 #![allow(clippy::must_use_candidate)]
 
+#[cfg(feature = "std")]
+extern crate std;
+
+mod deframer;
+mod error;
 mod header;
 mod idtp;
+mod integrity;
+#[cfg(feature = "std")]
+mod io_util;
+pub use deframer::*;
+pub use error::*;
 pub use header::*;
 pub use idtp::*;
+pub use integrity::*;
+
+pub mod command;
+pub mod compact;
+pub mod control;
+pub mod delta;
+pub mod extension;
+pub mod fec;
+pub mod payload;
+pub mod reliable;
+pub mod reorder;
+pub mod stats;
+pub mod sync;
+pub mod timeline;
+
+#[cfg(feature = "auth")]
+pub mod auth;
+#[cfg(feature = "gap")]
+pub mod gap;
+#[cfg(feature = "handshake")]
+pub mod handshake;
+#[cfg(feature = "secure")]
+pub mod secure;
+
+#[cfg(feature = "std")]
+pub mod client;
+#[cfg(feature = "std")]
+pub mod pcap;
+#[cfg(feature = "std")]
+pub mod recording;
@@ -0,0 +1,478 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Standard IDTP payload types.
+//!
+//! Payload types `0x00` - `0xBF` are vendor-specific. Range `0xC0` - `0xDF`
+//! is reserved for standard data payloads and range `0xE0` - `0xFF` is
+//! reserved for standard control payloads. All multi-byte values of standard
+//! payloads are transmitted in big-endian byte order.
+
+use crate::IdtpError;
+
+/// Accelerometer & gyroscope sample payload type.
+pub const PAYLOAD_TYPE_IMU6: u8 = 0xC0;
+
+/// Accelerometer, gyroscope & magnetometer sample payload type.
+pub const PAYLOAD_TYPE_IMU9: u8 = 0xC1;
+
+/// Accelerometer sample payload type.
+pub const PAYLOAD_TYPE_ACCELEROMETER: u8 = 0xC2;
+
+/// Gyroscope sample payload type.
+pub const PAYLOAD_TYPE_GYROSCOPE: u8 = 0xC3;
+
+/// Magnetometer sample payload type.
+pub const PAYLOAD_TYPE_MAGNETOMETER: u8 = 0xC4;
+
+/// Orientation quaternion payload type.
+pub const PAYLOAD_TYPE_QUATERNION: u8 = 0xC5;
+
+/// Delta compressed batch of standard data samples payload type (see
+/// `delta` module).
+pub const PAYLOAD_TYPE_DELTA: u8 = 0xC6;
+
+/// Hello (host capabilities) control payload type.
+pub const PAYLOAD_TYPE_HELLO: u8 = 0xE0;
+
+/// Hello reply (device capabilities) control payload type.
+pub const PAYLOAD_TYPE_HELLO_REPLY: u8 = 0xE1;
+
+/// Command request control payload type.
+pub const PAYLOAD_TYPE_COMMAND_REQUEST: u8 = 0xE2;
+
+/// Command response control payload type.
+pub const PAYLOAD_TYPE_COMMAND_RESPONSE: u8 = 0xE3;
+
+/// Reliable delivery acknowledgement control payload type.
+pub const PAYLOAD_TYPE_ACK: u8 = 0xE4;
+
+/// Secure mode session key request control payload type.
+pub const PAYLOAD_TYPE_KEY_REQUEST: u8 = 0xF0;
+
+/// Secure mode session key response control payload type.
+pub const PAYLOAD_TYPE_KEY_RESPONSE: u8 = 0xF1;
+
+/// Secure mode session key confirmation control payload type.
+pub const PAYLOAD_TYPE_KEY_FINISHED: u8 = 0xF2;
+
+/// Standard payload type description.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PayloadInfo {
+    /// Payload type value.
+    pub payload_type: u8,
+    /// Short payload type name.
+    pub name: &'static str,
+    /// Payload size in bytes (`None` if size is variable).
+    pub size: Option<usize>,
+}
+
+/// Registry of standard payload types.
+pub const PAYLOAD_REGISTRY: &[PayloadInfo] = &[
+    PayloadInfo {
+        payload_type: PAYLOAD_TYPE_IMU6,
+        name: "imu6",
+        size: Some(Imu6::SIZE),
+    },
+    PayloadInfo {
+        payload_type: PAYLOAD_TYPE_IMU9,
+        name: "imu9",
+        size: Some(Imu9::SIZE),
+    },
+    PayloadInfo {
+        payload_type: PAYLOAD_TYPE_ACCELEROMETER,
+        name: "accelerometer",
+        size: Some(Vector3::SIZE),
+    },
+    PayloadInfo {
+        payload_type: PAYLOAD_TYPE_GYROSCOPE,
+        name: "gyroscope",
+        size: Some(Vector3::SIZE),
+    },
+    PayloadInfo {
+        payload_type: PAYLOAD_TYPE_MAGNETOMETER,
+        name: "magnetometer",
+        size: Some(Vector3::SIZE),
+    },
+    PayloadInfo {
+        payload_type: PAYLOAD_TYPE_QUATERNION,
+        name: "quaternion",
+        size: Some(Quaternion::SIZE),
+    },
+    PayloadInfo {
+        payload_type: PAYLOAD_TYPE_DELTA,
+        name: "delta",
+        size: None,
+    },
+];
+
+/// Find standard payload type description.
+///
+/// # Parameters
+/// - `payload_type` - given payload type value.
+///
+/// # Returns
+/// - Payload type description - if payload type is standard.
+/// - `None` - otherwise.
+pub fn payload_info(payload_type: u8) -> Option<&'static PayloadInfo> {
+    PAYLOAD_REGISTRY
+        .iter()
+        .find(|info| info.payload_type == payload_type)
+}
+
+/// Payload with fixed binary layout.
+pub trait Payload: Sized {
+    /// Payload type value.
+    const PAYLOAD_TYPE: u8;
+    /// Payload size in bytes.
+    const SIZE: usize;
+
+    /// Serialize payload.
+    ///
+    /// # Parameters
+    /// - `buffer` - given buffer to store payload bytes.
+    ///
+    /// # Returns
+    /// - Number of bytes written - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if buffer is too small.
+    fn encode(&self, buffer: &mut [u8]) -> Result<usize, IdtpError>;
+
+    /// Deserialize payload.
+    ///
+    /// # Parameters
+    /// - `bytes` - given payload bytes.
+    ///
+    /// # Returns
+    /// - Payload - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if payload size does not match.
+    fn decode(bytes: &[u8]) -> Result<Self, IdtpError>;
+}
+
+/// Read big-endian floats from bytes.
+fn read_floats<const N: usize>(bytes: &[u8]) -> Result<[f32; N], IdtpError> {
+    if bytes.len() != N * 4 {
+        return Err(IdtpError::InvalidPayload);
+    }
+
+    let mut values = [0f32; N];
+
+    for (value, chunk) in values.iter_mut().zip(bytes.chunks_exact(4)) {
+        *value = f32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+    }
+
+    Ok(values)
+}
+
+/// Write big-endian floats into buffer.
+fn write_floats(values: &[f32], buffer: &mut [u8]) -> Result<usize, IdtpError> {
+    let size = values.len() * 4;
+
+    if buffer.len() < size {
+        return Err(IdtpError::BufferTooSmall);
+    }
+
+    for (value, chunk) in values.iter().zip(buffer.chunks_exact_mut(4)) {
+        chunk.copy_from_slice(&value.to_be_bytes());
+    }
+
+    Ok(size)
+}
+
+/// Three-axis sensor sample.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Vector3 {
+    /// Value along the X axis.
+    pub x: f32,
+    /// Value along the Y axis.
+    pub y: f32,
+    /// Value along the Z axis.
+    pub z: f32,
+}
+
+impl Vector3 {
+    /// Size of three-axis sample in bytes.
+    pub const SIZE: usize = 12;
+
+    /// Construct new `Vector3` struct.
+    ///
+    /// # Parameters
+    /// - `x` - given value along the X axis.
+    /// - `y` - given value along the Y axis.
+    /// - `z` - given value along the Z axis.
+    ///
+    /// # Returns
+    /// - New `Vector3` struct.
+    pub const fn new(x: f32, y: f32, z: f32) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Get sample values.
+    ///
+    /// # Returns
+    /// - Values along X, Y and Z axes.
+    pub const fn to_array(&self) -> [f32; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    /// Construct sample from values.
+    ///
+    /// # Parameters
+    /// - `values` - given values along X, Y and Z axes.
+    ///
+    /// # Returns
+    /// - New `Vector3` struct.
+    pub const fn from_array(values: [f32; 3]) -> Self {
+        Self::new(values[0], values[1], values[2])
+    }
+}
+
+/// Accelerometer & gyroscope sample.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Imu6 {
+    /// Acceleration (m/s^2).
+    pub acc: Vector3,
+    /// Angular velocity (rad/s).
+    pub gyr: Vector3,
+}
+
+impl Payload for Imu6 {
+    const PAYLOAD_TYPE: u8 = PAYLOAD_TYPE_IMU6;
+    const SIZE: usize = 2 * Vector3::SIZE;
+
+    fn encode(&self, buffer: &mut [u8]) -> Result<usize, IdtpError> {
+        let [ax, ay, az] = self.acc.to_array();
+        let [gx, gy, gz] = self.gyr.to_array();
+        write_floats(&[ax, ay, az, gx, gy, gz], buffer)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, IdtpError> {
+        let [ax, ay, az, gx, gy, gz] = read_floats::<6>(bytes)?;
+
+        Ok(Self {
+            acc: Vector3::new(ax, ay, az),
+            gyr: Vector3::new(gx, gy, gz),
+        })
+    }
+}
+
+/// Accelerometer, gyroscope & magnetometer sample.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Imu9 {
+    /// Acceleration (m/s^2).
+    pub acc: Vector3,
+    /// Angular velocity (rad/s).
+    pub gyr: Vector3,
+    /// Magnetic field (uT).
+    pub mag: Vector3,
+}
+
+impl Payload for Imu9 {
+    const PAYLOAD_TYPE: u8 = PAYLOAD_TYPE_IMU9;
+    const SIZE: usize = 3 * Vector3::SIZE;
+
+    fn encode(&self, buffer: &mut [u8]) -> Result<usize, IdtpError> {
+        let [ax, ay, az] = self.acc.to_array();
+        let [gx, gy, gz] = self.gyr.to_array();
+        let [mx, my, mz] = self.mag.to_array();
+        write_floats(&[ax, ay, az, gx, gy, gz, mx, my, mz], buffer)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, IdtpError> {
+        let [ax, ay, az, gx, gy, gz, mx, my, mz] = read_floats::<9>(bytes)?;
+
+        Ok(Self {
+            acc: Vector3::new(ax, ay, az),
+            gyr: Vector3::new(gx, gy, gz),
+            mag: Vector3::new(mx, my, mz),
+        })
+    }
+}
+
+/// Orientation quaternion.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quaternion {
+    /// Scalar component.
+    pub w: f32,
+    /// X component of vector part.
+    pub x: f32,
+    /// Y component of vector part.
+    pub y: f32,
+    /// Z component of vector part.
+    pub z: f32,
+}
+
+impl Default for Quaternion {
+    /// Construct identity quaternion.
+    ///
+    /// # Returns
+    /// - Identity quaternion.
+    fn default() -> Self {
+        Self::new(1.0, 0.0, 0.0, 0.0)
+    }
+}
+
+impl Quaternion {
+    /// Construct new `Quaternion` struct.
+    ///
+    /// # Parameters
+    /// - `w` - given scalar component.
+    /// - `x` - given X component of vector part.
+    /// - `y` - given Y component of vector part.
+    /// - `z` - given Z component of vector part.
+    ///
+    /// # Returns
+    /// - New `Quaternion` struct.
+    pub const fn new(w: f32, x: f32, y: f32, z: f32) -> Self {
+        Self { w, x, y, z }
+    }
+}
+
+impl Payload for Quaternion {
+    const PAYLOAD_TYPE: u8 = PAYLOAD_TYPE_QUATERNION;
+    const SIZE: usize = 16;
+
+    fn encode(&self, buffer: &mut [u8]) -> Result<usize, IdtpError> {
+        write_floats(&[self.w, self.x, self.y, self.z], buffer)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, IdtpError> {
+        let [w, x, y, z] = read_floats::<4>(bytes)?;
+        Ok(Self::new(w, x, y, z))
+    }
+}
+
+/// Decoded standard payload.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StandardPayload {
+    /// Accelerometer & gyroscope sample.
+    Imu6(Imu6),
+    /// Accelerometer, gyroscope & magnetometer sample.
+    Imu9(Imu9),
+    /// Accelerometer sample (m/s^2).
+    Accelerometer(Vector3),
+    /// Gyroscope sample (rad/s).
+    Gyroscope(Vector3),
+    /// Magnetometer sample (uT).
+    Magnetometer(Vector3),
+    /// Orientation quaternion.
+    Quaternion(Quaternion),
+}
+
+impl StandardPayload {
+    /// Decode standard payload.
+    ///
+    /// # Parameters
+    /// - `payload_type` - given payload type value.
+    /// - `bytes` - given payload bytes.
+    ///
+    /// # Returns
+    /// - Decoded payload - in case of success.
+    /// - `None` - if payload type is not a standard data payload.
+    /// - `Err` - if payload does not match payload type layout.
+    pub fn decode(
+        payload_type: u8,
+        bytes: &[u8],
+    ) -> Option<Result<Self, IdtpError>> {
+        let vector = |bytes| read_floats::<3>(bytes).map(Vector3::from_array);
+
+        let payload = match payload_type {
+            PAYLOAD_TYPE_IMU6 => Imu6::decode(bytes).map(Self::Imu6),
+            PAYLOAD_TYPE_IMU9 => Imu9::decode(bytes).map(Self::Imu9),
+            PAYLOAD_TYPE_ACCELEROMETER => {
+                vector(bytes).map(Self::Accelerometer)
+            }
+            PAYLOAD_TYPE_GYROSCOPE => vector(bytes).map(Self::Gyroscope),
+            PAYLOAD_TYPE_MAGNETOMETER => vector(bytes).map(Self::Magnetometer),
+            PAYLOAD_TYPE_QUATERNION => {
+                Quaternion::decode(bytes).map(Self::Quaternion)
+            }
+            _ => return None,
+        };
+
+        Some(payload)
+    }
+
+    /// Get payload type value.
+    ///
+    /// # Returns
+    /// - Payload type value.
+    pub fn payload_type(&self) -> u8 {
+        match self {
+            Self::Imu6(_) => PAYLOAD_TYPE_IMU6,
+            Self::Imu9(_) => PAYLOAD_TYPE_IMU9,
+            Self::Accelerometer(_) => PAYLOAD_TYPE_ACCELEROMETER,
+            Self::Gyroscope(_) => PAYLOAD_TYPE_GYROSCOPE,
+            Self::Magnetometer(_) => PAYLOAD_TYPE_MAGNETOMETER,
+            Self::Quaternion(_) => PAYLOAD_TYPE_QUATERNION,
+        }
+    }
+
+    /// Serialize payload.
+    ///
+    /// # Parameters
+    /// - `buffer` - given buffer to store payload bytes.
+    ///
+    /// # Returns
+    /// - Number of bytes written - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if buffer is too small.
+    pub fn encode(&self, buffer: &mut [u8]) -> Result<usize, IdtpError> {
+        match self {
+            Self::Imu6(payload) => payload.encode(buffer),
+            Self::Imu9(payload) => payload.encode(buffer),
+            Self::Accelerometer(vector)
+            | Self::Gyroscope(vector)
+            | Self::Magnetometer(vector) => {
+                write_floats(&vector.to_array(), buffer)
+            }
+            Self::Quaternion(payload) => payload.encode(buffer),
+        }
+    }
+
+    /// Call given function for every named payload field.
+    ///
+    /// # Parameters
+    /// - `f` - given function to call with field name and value.
+    pub fn for_each_field<F: FnMut(&'static str, f32)>(&self, mut f: F) {
+        let mut vector = |names: [&'static str; 3], vector: &Vector3| {
+            for (name, value) in names.into_iter().zip(vector.to_array()) {
+                f(name, value);
+            }
+        };
+
+        match self {
+            Self::Imu6(payload) => {
+                vector(["acc_x", "acc_y", "acc_z"], &payload.acc);
+                vector(["gyr_x", "gyr_y", "gyr_z"], &payload.gyr);
+            }
+            Self::Imu9(payload) => {
+                vector(["acc_x", "acc_y", "acc_z"], &payload.acc);
+                vector(["gyr_x", "gyr_y", "gyr_z"], &payload.gyr);
+                vector(["mag_x", "mag_y", "mag_z"], &payload.mag);
+            }
+            Self::Accelerometer(payload) => {
+                vector(["acc_x", "acc_y", "acc_z"], payload);
+            }
+            Self::Gyroscope(payload) => {
+                vector(["gyr_x", "gyr_y", "gyr_z"], payload);
+            }
+            Self::Magnetometer(payload) => {
+                vector(["mag_x", "mag_y", "mag_z"], payload);
+            }
+            Self::Quaternion(payload) => {
+                f("w", payload.w);
+                f("x", payload.x);
+                f("y", payload.y);
+                f("z", payload.z);
+            }
+        }
+    }
+}
@@ -0,0 +1,875 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Export and import of IDTP traffic in pcap/pcapng capture files.
+//!
+//! Captures are written in pcapng format. Each IDTP frame is either wrapped
+//! in synthetic Ethernet/IPv4/UDP headers or stored as is with one of the
+//! user-defined link types (`DLT_USER0` - `DLT_USER15`), which is suitable
+//! for raw serial captures. Both pcap and pcapng files can be read back.
+
+use crate::io_util::{
+    MAX_BLOCK_SIZE, invalid_data, invalid_input, read_complete,
+};
+use std::{
+    io::{self, Read, Write},
+    net::{Ipv4Addr, SocketAddrV4},
+    vec,
+    vec::Vec,
+};
+
+/// Ethernet link type.
+pub const LINKTYPE_ETHERNET: u16 = 1;
+
+/// Raw IP link type.
+pub const LINKTYPE_RAW: u16 = 101;
+
+/// Linux "cooked" capture link type.
+pub const LINKTYPE_LINUX_SLL: u16 = 113;
+
+/// First user-defined link type (`DLT_USER0`).
+pub const LINKTYPE_USER0: u16 = 147;
+
+/// Last user-defined link type (`DLT_USER15`).
+pub const LINKTYPE_USER15: u16 = 162;
+
+/// Raw IPv4 link type.
+pub const LINKTYPE_IPV4: u16 = 228;
+
+/// Raw IPv6 link type.
+pub const LINKTYPE_IPV6: u16 = 229;
+
+/// Linux "cooked" capture v2 link type.
+pub const LINKTYPE_LINUX_SLL2: u16 = 276;
+
+/// Maximum number of bytes captured per packet.
+const SNAPLEN: u32 = 65535;
+
+/// Maximum size of packet accepted by reader in bytes.
+const MAX_PACKET_SIZE: u32 = 262_144;
+
+/// Classic pcap magic number (microsecond timestamps).
+const PCAP_MAGIC_US: u32 = 0xa1b2_c3d4;
+
+/// Classic pcap magic number (nanosecond timestamps).
+const PCAP_MAGIC_NS: u32 = 0xa1b2_3c4d;
+
+/// pcapng Section Header Block type.
+const BLOCK_SECTION_HEADER: u32 = 0x0a0d_0d0a;
+
+/// pcapng Interface Description Block type.
+const BLOCK_INTERFACE: u32 = 0x0000_0001;
+
+/// pcapng (obsolete) Packet Block type.
+const BLOCK_PACKET: u32 = 0x0000_0002;
+
+/// pcapng Simple Packet Block type.
+const BLOCK_SIMPLE_PACKET: u32 = 0x0000_0003;
+
+/// pcapng Enhanced Packet Block type.
+const BLOCK_ENHANCED_PACKET: u32 = 0x0000_0006;
+
+/// pcapng byte-order magic.
+const BYTE_ORDER_MAGIC: u32 = 0x1a2b_3c4d;
+
+/// Ethernet header size in bytes.
+const ETHERNET_HEADER_SIZE: usize = 14;
+
+/// IPv4 header size (without options) in bytes.
+const IPV4_HEADER_SIZE: usize = 20;
+
+/// UDP header size in bytes.
+const UDP_HEADER_SIZE: usize = 8;
+
+/// IPv4 EtherType.
+const ETHERTYPE_IPV4: u16 = 0x0800;
+
+/// IPv6 EtherType.
+const ETHERTYPE_IPV6: u16 = 0x86dd;
+
+/// UDP IP protocol number.
+const IP_PROTOCOL_UDP: u8 = 17;
+
+/// Synthetic UDP flow used to wrap IDTP frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UdpFlow {
+    /// Source MAC address.
+    pub src_mac: [u8; 6],
+    /// Destination MAC address.
+    pub dst_mac: [u8; 6],
+    /// Source IPv4 address and UDP port.
+    pub src: SocketAddrV4,
+    /// Destination IPv4 address and UDP port.
+    pub dst: SocketAddrV4,
+}
+
+impl UdpFlow {
+    /// Construct new `UdpFlow` struct with locally administered MAC
+    /// addresses.
+    ///
+    /// # Parameters
+    /// - `src` - given source IPv4 address and UDP port.
+    /// - `dst` - given destination IPv4 address and UDP port.
+    ///
+    /// # Returns
+    /// - New `UdpFlow` struct.
+    pub const fn new(src: SocketAddrV4, dst: SocketAddrV4) -> Self {
+        Self {
+            src_mac: [0x02, 0x00, 0x00, 0x00, 0x00, 0x01],
+            dst_mac: [0x02, 0x00, 0x00, 0x00, 0x00, 0x02],
+            src,
+            dst,
+        }
+    }
+
+    /// Construct new `UdpFlow` struct for loopback traffic.
+    ///
+    /// # Parameters
+    /// - `port` - given UDP port used as both source and destination port.
+    ///
+    /// # Returns
+    /// - New `UdpFlow` struct.
+    pub const fn loopback(port: u16) -> Self {
+        Self::new(
+            SocketAddrV4::new(Ipv4Addr::LOCALHOST, port),
+            SocketAddrV4::new(Ipv4Addr::LOCALHOST, port),
+        )
+    }
+}
+
+/// The way IDTP frames are encapsulated in capture file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encapsulation {
+    /// Frame is wrapped in synthetic Ethernet/IPv4/UDP headers.
+    Udp(UdpFlow),
+    /// Frame is stored as is with user-defined link type
+    /// (`DLT_USER0` + given value, which must be in range 0..=15).
+    User(u8),
+}
+
+impl Encapsulation {
+    /// Get pcap link type of encapsulation.
+    ///
+    /// # Returns
+    /// - pcap link type.
+    pub fn link_type(&self) -> u16 {
+        match self {
+            Self::Udp(_) => LINKTYPE_ETHERNET,
+            Self::User(index) => LINKTYPE_USER0 + u16::from(*index),
+        }
+    }
+}
+
+/// IDTP frame extracted from capture file.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CapturedFrame {
+    /// Capture timestamp in microseconds since Unix epoch.
+    pub timestamp_us: u64,
+    /// Source UDP port (zero for raw link types).
+    pub src_port: u16,
+    /// Destination UDP port (zero for raw link types).
+    pub dst_port: u16,
+    /// Raw IDTP frame bytes.
+    pub data: Vec<u8>,
+}
+
+/// pcapng capture file writer.
+#[derive(Debug)]
+pub struct PcapngWriter<W: Write> {
+    /// Output to write capture into.
+    writer: W,
+    /// The way IDTP frames are encapsulated.
+    encapsulation: Encapsulation,
+    /// IPv4 identification field of the next packet.
+    ip_id: u16,
+}
+
+impl<W: Write> PcapngWriter<W> {
+    /// Construct new `PcapngWriter` struct and write capture file header.
+    ///
+    /// # Parameters
+    /// - `writer` - given output to write capture into.
+    /// - `encapsulation` - given way IDTP frames are encapsulated.
+    ///
+    /// # Returns
+    /// - New `PcapngWriter` struct - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if user-defined link type is out of range or
+    ///   header could not be written.
+    pub fn new(
+        mut writer: W,
+        encapsulation: Encapsulation,
+    ) -> io::Result<Self> {
+        if let Encapsulation::User(index) = encapsulation
+            && index > 15
+        {
+            return Err(invalid_input(
+                "User link type must be in range 0..=15",
+            ));
+        }
+
+        let mut section = Vec::with_capacity(28);
+        section.extend_from_slice(&BLOCK_SECTION_HEADER.to_le_bytes());
+        section.extend_from_slice(&28u32.to_le_bytes());
+        section.extend_from_slice(&BYTE_ORDER_MAGIC.to_le_bytes());
+        section.extend_from_slice(&1u16.to_le_bytes());
+        section.extend_from_slice(&0u16.to_le_bytes());
+        section.extend_from_slice(&(-1i64).to_le_bytes());
+        section.extend_from_slice(&28u32.to_le_bytes());
+
+        let mut interface = Vec::with_capacity(20);
+        interface.extend_from_slice(&BLOCK_INTERFACE.to_le_bytes());
+        interface.extend_from_slice(&20u32.to_le_bytes());
+        interface.extend_from_slice(&encapsulation.link_type().to_le_bytes());
+        interface.extend_from_slice(&0u16.to_le_bytes());
+        interface.extend_from_slice(&SNAPLEN.to_le_bytes());
+        interface.extend_from_slice(&20u32.to_le_bytes());
+
+        writer.write_all(&section)?;
+        writer.write_all(&interface)?;
+
+        Ok(Self {
+            writer,
+            encapsulation,
+            ip_id: 0,
+        })
+    }
+
+    /// Write IDTP frame as captured packet.
+    ///
+    /// # Parameters
+    /// - `timestamp_us` - given capture timestamp in microseconds since
+    ///   Unix epoch.
+    /// - `frame` - given raw IDTP frame bytes.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if frame is too large or packet could not be
+    ///   written.
+    pub fn write_frame(
+        &mut self,
+        timestamp_us: u64,
+        frame: &[u8],
+    ) -> io::Result<()> {
+        let packet = match self.encapsulation {
+            Encapsulation::Udp(flow) => {
+                self.ip_id = self.ip_id.wrapping_add(1);
+                udp_packet(&flow, self.ip_id, frame)?
+            }
+            Encapsulation::User(_) => frame.to_vec(),
+        };
+
+        let packet_size = u32::try_from(packet.len())
+            .ok()
+            .filter(|size| *size <= SNAPLEN)
+            .ok_or_else(|| invalid_input("Frame is too large to capture"))?;
+
+        let padding = (4 - packet.len() % 4) % 4;
+        let block_size = 32 + packet.len() as u32 + padding as u32;
+
+        let mut block = Vec::with_capacity(block_size as usize);
+        block.extend_from_slice(&BLOCK_ENHANCED_PACKET.to_le_bytes());
+        block.extend_from_slice(&block_size.to_le_bytes());
+        block.extend_from_slice(&0u32.to_le_bytes());
+        block.extend_from_slice(&((timestamp_us >> 32) as u32).to_le_bytes());
+        block.extend_from_slice(&(timestamp_us as u32).to_le_bytes());
+        block.extend_from_slice(&packet_size.to_le_bytes());
+        block.extend_from_slice(&packet_size.to_le_bytes());
+        block.extend_from_slice(&packet);
+        block.extend_from_slice(&[0u8; 3][..padding]);
+        block.extend_from_slice(&block_size.to_le_bytes());
+
+        self.writer.write_all(&block)
+    }
+
+    /// Flush buffered packets to the output.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if output could not be flushed.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Flush and get underlying output.
+    ///
+    /// # Returns
+    /// - Underlying output - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if output could not be flushed.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Interface description of capture file.
+#[derive(Debug, Clone, Copy)]
+struct Interface {
+    /// pcap link type.
+    link_type: u16,
+    /// Number of timestamp units per second.
+    units_per_second: u64,
+}
+
+/// Format of capture file being read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    /// Classic pcap format.
+    Pcap,
+    /// pcapng format.
+    Pcapng,
+}
+
+/// pcap/pcapng capture file reader which extracts IDTP frames.
+#[derive(Debug)]
+pub struct PcapReader<R: Read> {
+    /// Input to read capture from.
+    reader: R,
+    /// Capture file format.
+    format: Format,
+    /// Whether capture file is stored in big-endian byte order.
+    big_endian: bool,
+    /// Interfaces of the current section.
+    interfaces: Vec<Interface>,
+    /// UDP port to extract frames from (`None` - any port).
+    port: Option<u16>,
+}
+
+impl<R: Read> PcapReader<R> {
+    /// Construct new `PcapReader` struct and read capture file header.
+    ///
+    /// # Parameters
+    /// - `reader` - given input to read capture from.
+    ///
+    /// # Returns
+    /// - New `PcapReader` struct - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if input is not a pcap or pcapng capture.
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+
+        let mut capture = Self {
+            reader,
+            format: Format::Pcap,
+            big_endian: false,
+            interfaces: Vec::new(),
+            port: None,
+        };
+
+        if u32::from_le_bytes(magic) == BLOCK_SECTION_HEADER {
+            capture.format = Format::Pcapng;
+            capture.read_section_header()?;
+        } else {
+            capture.read_pcap_header(magic)?;
+        }
+
+        Ok(capture)
+    }
+
+    /// Extract IDTP frames only from UDP datagrams with given source or
+    /// destination port.
+    ///
+    /// # Parameters
+    /// - `port` - given UDP port (`None` - any port).
+    pub fn set_port(&mut self, port: Option<u16>) {
+        self.port = port;
+    }
+
+    /// Read next IDTP frame. Packets which do not carry IDTP frame
+    /// (e.g. non-UDP traffic) are skipped.
+    ///
+    /// # Returns
+    /// - Next IDTP frame - in case of success.
+    /// - `None` - if there are no more packets.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if capture file is malformed.
+    pub fn next_frame(&mut self) -> io::Result<Option<CapturedFrame>> {
+        loop {
+            let packet = match self.format {
+                Format::Pcap => self.read_pcap_packet()?,
+                Format::Pcapng => self.read_pcapng_packet()?,
+            };
+
+            let Some((link_type, timestamp_us, data)) = packet else {
+                return Ok(None);
+            };
+
+            if let Some(frame) = self.extract(link_type, timestamp_us, &data) {
+                return Ok(Some(frame));
+            }
+        }
+    }
+
+    /// Read classic pcap global header.
+    ///
+    /// # Parameters
+    /// - `magic` - given first four bytes of the capture file.
+    fn read_pcap_header(&mut self, magic: [u8; 4]) -> io::Result<()> {
+        let units_per_second = if u32::from_le_bytes(magic) == PCAP_MAGIC_US {
+            1_000_000
+        } else if u32::from_be_bytes(magic) == PCAP_MAGIC_US {
+            self.big_endian = true;
+            1_000_000
+        } else if u32::from_le_bytes(magic) == PCAP_MAGIC_NS {
+            1_000_000_000
+        } else if u32::from_be_bytes(magic) == PCAP_MAGIC_NS {
+            self.big_endian = true;
+            1_000_000_000
+        } else {
+            return Err(invalid_data("Not a pcap or pcapng capture"));
+        };
+
+        let mut header = [0u8; 20];
+        self.reader.read_exact(&mut header)?;
+
+        let link_type = self.u32(&header[16..20]) as u16;
+        self.interfaces.push(Interface {
+            link_type,
+            units_per_second,
+        });
+
+        Ok(())
+    }
+
+    /// Read classic pcap packet record.
+    fn read_pcap_packet(&mut self) -> io::Result<Option<(u16, u64, Vec<u8>)>> {
+        let mut header = [0u8; 16];
+
+        if !read_complete(&mut self.reader, &mut header)? {
+            return Ok(None);
+        }
+
+        let seconds = u64::from(self.u32(&header[0..4]));
+        let fraction = u64::from(self.u32(&header[4..8]));
+        let size = self.u32(&header[8..12]);
+
+        if size > MAX_PACKET_SIZE {
+            return Err(invalid_data("pcap packet is too large"));
+        }
+
+        let mut data = vec![0u8; size as usize];
+        self.reader.read_exact(&mut data)?;
+
+        let interface = self.interfaces[0];
+        let timestamp_us = seconds * 1_000_000
+            + fraction * 1_000_000 / interface.units_per_second;
+
+        Ok(Some((interface.link_type, timestamp_us, data)))
+    }
+
+    /// Read pcapng section header block body (block type is already read).
+    fn read_section_header(&mut self) -> io::Result<()> {
+        let mut header = [0u8; 8];
+        self.reader.read_exact(&mut header)?;
+
+        if u32::from_le_bytes(header[4..8].try_into().unwrap())
+            == BYTE_ORDER_MAGIC
+        {
+            self.big_endian = false;
+        } else if u32::from_be_bytes(header[4..8].try_into().unwrap())
+            == BYTE_ORDER_MAGIC
+        {
+            self.big_endian = true;
+        } else {
+            return Err(invalid_data("Invalid pcapng byte-order magic"));
+        }
+
+        let block_size = self.u32(&header[0..4]) as usize;
+
+        if block_size < 28
+            || !block_size.is_multiple_of(4)
+            || block_size > MAX_BLOCK_SIZE
+        {
+            return Err(invalid_data("Invalid pcapng section header size"));
+        }
+
+        let mut body = vec![0u8; block_size - 12];
+        self.reader.read_exact(&mut body)?;
+        self.interfaces.clear();
+
+        Ok(())
+    }
+
+    /// Read pcapng blocks until packet block is found.
+    fn read_pcapng_packet(
+        &mut self,
+    ) -> io::Result<Option<(u16, u64, Vec<u8>)>> {
+        loop {
+            let mut block_type = [0u8; 4];
+
+            if !read_complete(&mut self.reader, &mut block_type)? {
+                return Ok(None);
+            }
+
+            if u32::from_le_bytes(block_type) == BLOCK_SECTION_HEADER {
+                self.read_section_header()?;
+                continue;
+            }
+
+            let block_type = self.u32(&block_type);
+            let mut size = [0u8; 4];
+            self.reader.read_exact(&mut size)?;
+
+            let block_size = self.u32(&size) as usize;
+
+            if block_size < 12
+                || !block_size.is_multiple_of(4)
+                || block_size > MAX_BLOCK_SIZE
+            {
+                return Err(invalid_data("Invalid pcapng block size"));
+            }
+
+            let mut body = vec![0u8; block_size - 8];
+            self.reader.read_exact(&mut body)?;
+            let body = &body[..block_size - 12];
+
+            match block_type {
+                BLOCK_INTERFACE => self.read_interface(body)?,
+                BLOCK_ENHANCED_PACKET | BLOCK_PACKET => {
+                    return self.read_enhanced_packet(block_type, body);
+                }
+                BLOCK_SIMPLE_PACKET => {
+                    return self.read_simple_packet(body);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Parse pcapng interface description block body.
+    fn read_interface(&mut self, body: &[u8]) -> io::Result<()> {
+        if body.len() < 8 {
+            return Err(invalid_data("Invalid pcapng interface block"));
+        }
+
+        let link_type = self.u16(&body[0..2]);
+        let mut units_per_second = 1_000_000u64;
+        let mut options = &body[8..];
+
+        while options.len() >= 4 {
+            let code = self.u16(&options[0..2]);
+            let size = self.u16(&options[2..4]) as usize;
+            let padded = (size + 3) & !3;
+
+            if options.len() < 4 + padded {
+                break;
+            }
+
+            // Option "if_tsresol".
+            if code == 9 && size == 1 {
+                let resolution = options[4];
+                let exponent = u32::from(resolution & 0x7f);
+                units_per_second = if resolution & 0x80 == 0 {
+                    10u64.checked_pow(exponent).unwrap_or(u64::MAX)
+                } else {
+                    2u64.checked_pow(exponent).unwrap_or(u64::MAX)
+                };
+            }
+
+            if code == 0 {
+                break;
+            }
+
+            options = &options[4 + padded..];
+        }
+
+        self.interfaces.push(Interface {
+            link_type,
+            units_per_second,
+        });
+
+        Ok(())
+    }
+
+    /// Parse pcapng enhanced (or obsolete) packet block body.
+    fn read_enhanced_packet(
+        &self,
+        block_type: u32,
+        body: &[u8],
+    ) -> io::Result<Option<(u16, u64, Vec<u8>)>> {
+        if body.len() < 20 {
+            return Err(invalid_data("Invalid pcapng packet block"));
+        }
+
+        let interface_id = if block_type == BLOCK_PACKET {
+            u32::from(self.u16(&body[0..2]))
+        } else {
+            self.u32(&body[0..4])
+        };
+
+        let interface = self.interface(interface_id)?;
+        let timestamp = (u64::from(self.u32(&body[4..8])) << 32)
+            | u64::from(self.u32(&body[8..12]));
+        let size = self.u32(&body[12..16]) as usize;
+
+        if body.len() < 20 + size {
+            return Err(invalid_data("Invalid pcapng packet size"));
+        }
+
+        let timestamp_us = (u128::from(timestamp) * 1_000_000
+            / u128::from(interface.units_per_second))
+            as u64;
+
+        Ok(Some((
+            interface.link_type,
+            timestamp_us,
+            body[20..20 + size].to_vec(),
+        )))
+    }
+
+    /// Parse pcapng simple packet block body.
+    fn read_simple_packet(
+        &self,
+        body: &[u8],
+    ) -> io::Result<Option<(u16, u64, Vec<u8>)>> {
+        if body.len() < 4 {
+            return Err(invalid_data("Invalid pcapng simple packet block"));
+        }
+
+        let interface = self.interface(0)?;
+        let size = (self.u32(&body[0..4]) as usize).min(body.len() - 4);
+
+        Ok(Some((interface.link_type, 0, body[4..4 + size].to_vec())))
+    }
+
+    /// Get interface description by identifier.
+    fn interface(&self, interface_id: u32) -> io::Result<Interface> {
+        self.interfaces
+            .get(interface_id as usize)
+            .copied()
+            .ok_or_else(|| invalid_data("Unknown pcapng interface"))
+    }
+
+    /// Extract IDTP frame from captured packet.
+    fn extract(
+        &self,
+        link_type: u16,
+        timestamp_us: u64,
+        data: &[u8],
+    ) -> Option<CapturedFrame> {
+        if (LINKTYPE_USER0..=LINKTYPE_USER15).contains(&link_type) {
+            return Some(CapturedFrame {
+                timestamp_us,
+                data: data.to_vec(),
+                ..CapturedFrame::default()
+            });
+        }
+
+        let (ethertype, ip) = match link_type {
+            LINKTYPE_ETHERNET => ethernet_payload(data)?,
+            LINKTYPE_RAW => (ip_ethertype(data)?, data),
+            LINKTYPE_IPV4 => (ETHERTYPE_IPV4, data),
+            LINKTYPE_IPV6 => (ETHERTYPE_IPV6, data),
+            LINKTYPE_LINUX_SLL if data.len() >= 16 => {
+                (u16::from_be_bytes([data[14], data[15]]), &data[16..])
+            }
+            LINKTYPE_LINUX_SLL2 if data.len() >= 20 => {
+                (u16::from_be_bytes([data[0], data[1]]), &data[20..])
+            }
+            _ => return None,
+        };
+
+        let udp = match ethertype {
+            ETHERTYPE_IPV4 => ipv4_payload(ip)?,
+            ETHERTYPE_IPV6 => ipv6_payload(ip)?,
+            _ => return None,
+        };
+
+        if udp.len() < UDP_HEADER_SIZE {
+            return None;
+        }
+
+        let src_port = u16::from_be_bytes([udp[0], udp[1]]);
+        let dst_port = u16::from_be_bytes([udp[2], udp[3]]);
+        let size = u16::from_be_bytes([udp[4], udp[5]]) as usize;
+
+        if self
+            .port
+            .is_some_and(|port| port != src_port && port != dst_port)
+        {
+            return None;
+        }
+
+        let end = size.clamp(UDP_HEADER_SIZE, udp.len());
+
+        Some(CapturedFrame {
+            timestamp_us,
+            src_port,
+            dst_port,
+            data: udp[UDP_HEADER_SIZE..end].to_vec(),
+        })
+    }
+
+    /// Read 16-bit value in capture file byte order.
+    fn u16(&self, bytes: &[u8]) -> u16 {
+        let bytes = [bytes[0], bytes[1]];
+
+        if self.big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        }
+    }
+
+    /// Read 32-bit value in capture file byte order.
+    fn u32(&self, bytes: &[u8]) -> u32 {
+        let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+
+        if self.big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        }
+    }
+}
+
+impl<R: Read> Iterator for PcapReader<R> {
+    type Item = io::Result<CapturedFrame>;
+
+    /// Read next IDTP frame.
+    ///
+    /// # Returns
+    /// - Next IDTP frame or error - if there are frames left.
+    /// - `None` - otherwise.
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_frame().transpose()
+    }
+}
+
+/// Build Ethernet/IPv4/UDP packet carrying IDTP frame.
+///
+/// # Parameters
+/// - `flow` - given synthetic UDP flow.
+/// - `ip_id` - given IPv4 identification field.
+/// - `frame` - given raw IDTP frame bytes.
+///
+/// # Returns
+/// - Packet bytes - in case of success.
+/// - `Err` - if frame does not fit into single UDP datagram.
+fn udp_packet(flow: &UdpFlow, ip_id: u16, frame: &[u8]) -> io::Result<Vec<u8>> {
+    let udp_size = u16::try_from(UDP_HEADER_SIZE + frame.len())
+        .map_err(|_| invalid_input("Frame is too large for UDP datagram"))?;
+    let ip_size = udp_size
+        .checked_add(IPV4_HEADER_SIZE as u16)
+        .ok_or_else(|| invalid_input("Frame is too large for UDP datagram"))?;
+
+    let mut packet =
+        Vec::with_capacity(ETHERNET_HEADER_SIZE + ip_size as usize);
+    packet.extend_from_slice(&flow.dst_mac);
+    packet.extend_from_slice(&flow.src_mac);
+    packet.extend_from_slice(&ETHERTYPE_IPV4.to_be_bytes());
+
+    let mut ip = [0u8; IPV4_HEADER_SIZE];
+    ip[0] = 0x45;
+    ip[2..4].copy_from_slice(&ip_size.to_be_bytes());
+    ip[4..6].copy_from_slice(&ip_id.to_be_bytes());
+    // Don't fragment flag.
+    ip[6] = 0x40;
+    ip[8] = 64;
+    ip[9] = IP_PROTOCOL_UDP;
+    ip[12..16].copy_from_slice(&flow.src.ip().octets());
+    ip[16..20].copy_from_slice(&flow.dst.ip().octets());
+
+    let checksum = ipv4_checksum(&ip);
+    ip[10..12].copy_from_slice(&checksum.to_be_bytes());
+    packet.extend_from_slice(&ip);
+
+    // UDP checksum is optional for IPv4 and left zero.
+    packet.extend_from_slice(&flow.src.port().to_be_bytes());
+    packet.extend_from_slice(&flow.dst.port().to_be_bytes());
+    packet.extend_from_slice(&udp_size.to_be_bytes());
+    packet.extend_from_slice(&0u16.to_be_bytes());
+    packet.extend_from_slice(frame);
+
+    Ok(packet)
+}
+
+/// Calculate IPv4 header checksum.
+///
+/// # Parameters
+/// - `header` - given IPv4 header with zeroed checksum field.
+///
+/// # Returns
+/// - IPv4 header checksum.
+fn ipv4_checksum(header: &[u8]) -> u16 {
+    let mut sum = header
+        .chunks(2)
+        .map(|word| u32::from(u16::from_be_bytes([word[0], word[1]])))
+        .sum::<u32>();
+
+    while sum > 0xffff {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+
+    !(sum as u16)
+}
+
+/// Get EtherType and payload of Ethernet frame (VLAN tags are skipped).
+fn ethernet_payload(data: &[u8]) -> Option<(u16, &[u8])> {
+    let mut offset = 12;
+
+    loop {
+        let ethertype = data.get(offset..offset + 2)?;
+        let ethertype = u16::from_be_bytes([ethertype[0], ethertype[1]]);
+
+        match ethertype {
+            0x8100 | 0x88a8 => offset += 4,
+            _ => return Some((ethertype, &data[offset + 2..])),
+        }
+    }
+}
+
+/// Get EtherType of raw IP packet by IP version.
+fn ip_ethertype(data: &[u8]) -> Option<u16> {
+    match data.first()? >> 4 {
+        4 => Some(ETHERTYPE_IPV4),
+        6 => Some(ETHERTYPE_IPV6),
+        _ => None,
+    }
+}
+
+/// Get UDP datagram carried by IPv4 packet.
+fn ipv4_payload(data: &[u8]) -> Option<&[u8]> {
+    if data.len() < IPV4_HEADER_SIZE || data[0] >> 4 != 4 {
+        return None;
+    }
+
+    let header_size = usize::from(data[0] & 0x0f) * 4;
+    let total_size = u16::from_be_bytes([data[2], data[3]]) as usize;
+    let fragment = u16::from_be_bytes([data[6], data[7]]);
+
+    // Fragmented datagrams are not reassembled.
+    if data[9] != IP_PROTOCOL_UDP || fragment & 0x3fff != 0 {
+        return None;
+    }
+
+    let end = total_size.min(data.len());
+    data.get(header_size..end)
+}
+
+/// Get UDP datagram carried by IPv6 packet (extension headers are not
+/// supported).
+fn ipv6_payload(data: &[u8]) -> Option<&[u8]> {
+    if data.len() < 40 || data[0] >> 4 != 6 || data[6] != IP_PROTOCOL_UDP {
+        return None;
+    }
+
+    let size = u16::from_be_bytes([data[4], data[5]]) as usize;
+    data.get(40..(40 + size).min(data.len()))
+}
@@ -0,0 +1,875 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP recording file (`.idtp`) format implementation.
+//!
+//! Recording consists of file header with session metadata, sequence of
+//! records (host receive timestamp + raw IDTP frame) and optional footer
+//! index which is used for seeking by time. Footer is written only after
+//! recording was finished properly, so recordings left after a crash are
+//! still readable - the index is rebuilt and truncated tail is ignored.
+
+use crate::{
+    IDTP_PACKET_MAX_SIZE, IDTP_PACKET_MIN_SIZE, IdtpFrame,
+    io_util::{MAX_BLOCK_SIZE, invalid_data, invalid_input, read_complete},
+    timeline::{Unwrapped, Unwrapper},
+};
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    path::Path,
+    string::String,
+    time::{SystemTime, UNIX_EPOCH},
+    vec,
+    vec::Vec,
+};
+
+/// Value to signal the start of IDTP recording file.
+pub const RECORDING_MAGIC: &[u8; 8] = b"IDTPREC\0";
+
+/// Value to signal the end of IDTP recording footer index.
+pub const RECORDING_INDEX_MAGIC: &[u8; 8] = b"IDTPIDX\0";
+
+/// Current IDTP recording file format version.
+pub const RECORDING_FORMAT_VERSION: u16 = 1;
+
+/// Default number of records between two footer index entries.
+pub const RECORDING_INDEX_INTERVAL: usize = 64;
+
+/// Size of fixed part of recording file header in bytes.
+const FILE_HEADER_SIZE: usize = 24;
+
+/// Size of record header (tag, receive timestamp & frame length) in bytes.
+const RECORD_HEADER_SIZE: usize = 11;
+
+/// Size of footer index tail (index offset & magic) in bytes.
+const INDEX_TAIL_SIZE: usize = 16;
+
+/// Tag of record which contains raw IDTP frame.
+const TAG_FRAME: u8 = 0x01;
+
+/// Tag of footer index record.
+const TAG_INDEX: u8 = 0x02;
+
+/// Get current host time.
+///
+/// # Returns
+/// - Number of microseconds elapsed since Unix epoch.
+pub fn unix_time_us() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_micros() as u64)
+}
+
+/// IMU device description stored in the session metadata.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DeviceInfo {
+    /// IMU device identifier.
+    pub device_id: u16,
+    /// Human-readable device name.
+    pub name: String,
+}
+
+/// Recording session metadata.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Session {
+    /// Name of the host which recorded the session.
+    pub host: String,
+    /// Session start time in microseconds since Unix epoch.
+    pub start_time_us: u64,
+    /// Registry of IMU devices participating in the session.
+    pub devices: Vec<DeviceInfo>,
+    /// Arbitrary user-defined key-value properties.
+    pub properties: Vec<(String, String)>,
+}
+
+impl Session {
+    /// Construct new `Session` struct started at current host time.
+    ///
+    /// # Parameters
+    /// - `host` - given name of the recording host.
+    ///
+    /// # Returns
+    /// - New `Session` struct.
+    pub fn new(host: &str) -> Self {
+        Self {
+            host: host.into(),
+            start_time_us: unix_time_us(),
+            ..Self::default()
+        }
+    }
+
+    /// Add IMU device to the session device registry.
+    ///
+    /// # Parameters
+    /// - `device_id` - given IMU device identifier.
+    /// - `name` - given human-readable device name.
+    pub fn add_device(&mut self, device_id: u16, name: &str) {
+        self.devices.push(DeviceInfo {
+            device_id,
+            name: name.into(),
+        });
+    }
+
+    /// Find IMU device in the session device registry.
+    ///
+    /// # Parameters
+    /// - `device_id` - given IMU device identifier to find.
+    ///
+    /// # Returns
+    /// - Device description - if device is registered.
+    /// - `None` - otherwise.
+    pub fn device(&self, device_id: u16) -> Option<&DeviceInfo> {
+        self.devices.iter().find(|info| info.device_id == device_id)
+    }
+
+    /// Add user-defined property to the session metadata.
+    ///
+    /// # Parameters
+    /// - `key` - given property name.
+    /// - `value` - given property value.
+    pub fn set_property(&mut self, key: &str, value: &str) {
+        self.properties.push((key.into(), value.into()));
+    }
+
+    /// Find user-defined property value.
+    ///
+    /// # Parameters
+    /// - `key` - given property name to find.
+    ///
+    /// # Returns
+    /// - Property value - if property is set.
+    /// - `None` - otherwise.
+    pub fn property(&self, key: &str) -> Option<&str> {
+        self.properties
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Serialize session metadata.
+    ///
+    /// # Returns
+    /// - Session metadata bytes.
+    fn to_bytes(&self) -> io::Result<Vec<u8>> {
+        let mut buffer = Vec::new();
+        write_string(&mut buffer, &self.host)?;
+
+        buffer.extend_from_slice(&count_u16(self.devices.len())?.to_be_bytes());
+        for device in &self.devices {
+            buffer.extend_from_slice(&device.device_id.to_be_bytes());
+            write_string(&mut buffer, &device.name)?;
+        }
+
+        let count = count_u16(self.properties.len())?;
+        buffer.extend_from_slice(&count.to_be_bytes());
+        for (key, value) in &self.properties {
+            write_string(&mut buffer, key)?;
+            write_string(&mut buffer, value)?;
+        }
+
+        Ok(buffer)
+    }
+
+    /// Deserialize session metadata.
+    ///
+    /// # Parameters
+    /// - `start_time_us` - given session start time.
+    /// - `bytes` - given session metadata bytes.
+    ///
+    /// # Returns
+    /// - Session metadata from bytes.
+    fn from_bytes(start_time_us: u64, mut bytes: &[u8]) -> io::Result<Self> {
+        let mut session = Self {
+            host: read_string(&mut bytes)?,
+            start_time_us,
+            ..Self::default()
+        };
+
+        for _ in 0..read_u16(&mut bytes)? {
+            let device_id = read_u16(&mut bytes)?;
+            let name = read_string(&mut bytes)?;
+            session.devices.push(DeviceInfo { device_id, name });
+        }
+
+        for _ in 0..read_u16(&mut bytes)? {
+            let key = read_string(&mut bytes)?;
+            let value = read_string(&mut bytes)?;
+            session.properties.push((key, value));
+        }
+
+        Ok(session)
+    }
+}
+
+/// Single record of IDTP recording.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Record {
+    /// Host receive timestamp in microseconds since Unix epoch.
+    pub recv_time_us: u64,
+    /// Raw IDTP frame bytes as they were received.
+    pub data: Vec<u8>,
+}
+
+impl Record {
+    /// Convert record data to IDTP frame.
+    ///
+    /// # Returns
+    /// - IDTP frame struct from record data.
+    pub fn frame(&self) -> IdtpFrame {
+        IdtpFrame::from(&self.data[..])
+    }
+}
+
+/// Footer index entry.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct IndexEntry {
+    /// Host receive timestamp of indexed record.
+    recv_time_us: u64,
+    /// Offset of indexed record from the start of the file.
+    offset: u64,
+}
+
+/// IDTP recording writer.
+#[derive(Debug)]
+pub struct Recorder<W: Write> {
+    /// Output to write recording into.
+    writer: W,
+    /// Current offset from the start of the recording.
+    offset: u64,
+    /// Footer index entries collected so far.
+    index: Vec<IndexEntry>,
+    /// Number of records between two index entries.
+    index_interval: usize,
+    /// Number of records written.
+    count: usize,
+    /// Extended timelines of recorded devices.
+    unwrapper: Unwrapper,
+}
+
+impl Recorder<BufWriter<File>> {
+    /// Create new recording file.
+    ///
+    /// # Parameters
+    /// - `path` - given path of recording file to create.
+    /// - `session` - given session metadata to store.
+    ///
+    /// # Returns
+    /// - New `Recorder` struct - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if file could not be created or written.
+    pub fn create<P: AsRef<Path>>(
+        path: P,
+        session: &Session,
+    ) -> io::Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?), session)
+    }
+}
+
+impl<W: Write> Recorder<W> {
+    /// Construct new `Recorder` struct and write recording file header.
+    ///
+    /// # Parameters
+    /// - `writer` - given output to write recording into.
+    /// - `session` - given session metadata to store.
+    ///
+    /// # Returns
+    /// - New `Recorder` struct - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if file header could not be written.
+    pub fn new(mut writer: W, session: &Session) -> io::Result<Self> {
+        let metadata = session.to_bytes()?;
+        let metadata_size = u32::try_from(metadata.len())
+            .map_err(|_| invalid_input("Session metadata is too large"))?;
+
+        let mut header = [0u8; FILE_HEADER_SIZE];
+        header[0..8].copy_from_slice(RECORDING_MAGIC);
+        header[8..10].copy_from_slice(&RECORDING_FORMAT_VERSION.to_be_bytes());
+        header[12..20].copy_from_slice(&session.start_time_us.to_be_bytes());
+        header[20..24].copy_from_slice(&metadata_size.to_be_bytes());
+
+        writer.write_all(&header)?;
+        writer.write_all(&metadata)?;
+
+        Ok(Self {
+            writer,
+            offset: (FILE_HEADER_SIZE + metadata.len()) as u64,
+            index: Vec::new(),
+            index_interval: RECORDING_INDEX_INTERVAL,
+            count: 0,
+            unwrapper: Unwrapper::new(),
+        })
+    }
+
+    /// Set number of records between two footer index entries.
+    ///
+    /// # Parameters
+    /// - `interval` - given number of records (zero is treated as one).
+    pub fn set_index_interval(&mut self, interval: usize) {
+        self.index_interval = interval.max(1);
+    }
+
+    /// Get reference to the underlying output.
+    ///
+    /// # Returns
+    /// - Underlying output.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Get number of records written.
+    ///
+    /// # Returns
+    /// - Number of records written.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Get extended timelines of recorded devices, e.g. to detect device
+    /// reboots during recording.
+    ///
+    /// # Returns
+    /// - Extended timelines of devices of valid recorded frames.
+    pub fn unwrapper(&self) -> &Unwrapper {
+        &self.unwrapper
+    }
+
+    /// Write raw IDTP frame record.
+    ///
+    /// # Parameters
+    /// - `recv_time_us` - given host receive timestamp in microseconds
+    ///   since Unix epoch.
+    /// - `frame` - given raw IDTP frame bytes (possibly malformed).
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if frame is too large or record could not be
+    ///   written.
+    pub fn record(
+        &mut self,
+        recv_time_us: u64,
+        frame: &[u8],
+    ) -> io::Result<()> {
+        let frame_size = u16::try_from(frame.len())
+            .map_err(|_| invalid_input("Frame is too large to record"))?;
+
+        if self.count.is_multiple_of(self.index_interval) {
+            self.index.push(IndexEntry {
+                recv_time_us,
+                offset: self.offset,
+            });
+        }
+
+        let mut header = [0u8; RECORD_HEADER_SIZE];
+        header[0] = TAG_FRAME;
+        header[1..9].copy_from_slice(&recv_time_us.to_be_bytes());
+        header[9..11].copy_from_slice(&frame_size.to_be_bytes());
+
+        self.writer.write_all(&header)?;
+        self.writer.write_all(frame)?;
+        self.offset += (RECORD_HEADER_SIZE + frame.len()) as u64;
+        self.count += 1;
+
+        if let Ok(frame) = IdtpFrame::parse(frame) {
+            self.unwrapper.update(&frame.header());
+        }
+
+        Ok(())
+    }
+
+    /// Write raw IDTP frame record received right now.
+    ///
+    /// # Parameters
+    /// - `frame` - given raw IDTP frame bytes (possibly malformed).
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if frame is too large or record could not be
+    ///   written.
+    pub fn record_now(&mut self, frame: &[u8]) -> io::Result<()> {
+        self.record(unix_time_us(), frame)
+    }
+
+    /// Pack IDTP frame and write it as a record.
+    ///
+    /// # Parameters
+    /// - `recv_time_us` - given host receive timestamp in microseconds
+    ///   since Unix epoch.
+    /// - `frame` - given IDTP frame to record.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if frame could not be packed or written.
+    pub fn record_frame(
+        &mut self,
+        recv_time_us: u64,
+        frame: &IdtpFrame,
+    ) -> io::Result<()> {
+        let mut buffer = [0u8; IDTP_PACKET_MAX_SIZE];
+        let size = IDTP_PACKET_MIN_SIZE + frame.payload_size();
+
+        frame.pack(&mut buffer).map_err(invalid_input)?;
+        self.record(recv_time_us, &buffer[..size])
+    }
+
+    /// Flush buffered records to the output.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if output could not be flushed.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Write footer index and finish recording.
+    ///
+    /// # Returns
+    /// - Underlying output - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if footer could not be written.
+    pub fn finish(mut self) -> io::Result<W> {
+        let count = u32::try_from(self.index.len())
+            .map_err(|_| invalid_input("Footer index is too large"))?;
+
+        let mut footer = vec![TAG_INDEX];
+        footer.extend_from_slice(&count.to_be_bytes());
+
+        for entry in &self.index {
+            footer.extend_from_slice(&entry.recv_time_us.to_be_bytes());
+            footer.extend_from_slice(&entry.offset.to_be_bytes());
+        }
+
+        footer.extend_from_slice(&self.offset.to_be_bytes());
+        footer.extend_from_slice(RECORDING_INDEX_MAGIC);
+
+        self.writer.write_all(&footer)?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// IDTP recording reader.
+#[derive(Debug)]
+pub struct Player<R: Read + Seek> {
+    /// Input to read recording from.
+    reader: R,
+    /// Recording session metadata.
+    session: Session,
+    /// Footer index entries (stored or rebuilt).
+    index: Vec<IndexEntry>,
+    /// Offset of the first record.
+    data_start: u64,
+    /// Offset right after the last complete record.
+    data_end: u64,
+    /// Current read offset.
+    position: u64,
+    /// Whether recording ends with incomplete or unreadable tail.
+    truncated: bool,
+    /// Whether footer index was missing and had to be rebuilt.
+    index_rebuilt: bool,
+    /// Extended timelines of devices since rewind or seek.
+    unwrapper: Unwrapper,
+}
+
+impl Player<BufReader<File>> {
+    /// Open recording file.
+    ///
+    /// # Parameters
+    /// - `path` - given path of recording file to open.
+    ///
+    /// # Returns
+    /// - New `Player` struct - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if file could not be opened or it is not a valid
+    ///   IDTP recording.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read + Seek> Player<R> {
+    /// Construct new `Player` struct and read session metadata.
+    ///
+    /// # Parameters
+    /// - `reader` - given input to read recording from.
+    ///
+    /// # Returns
+    /// - New `Player` struct - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if input is not a valid IDTP recording.
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut header = [0u8; FILE_HEADER_SIZE];
+        reader.seek(SeekFrom::Start(0))?;
+        reader.read_exact(&mut header)?;
+
+        if &header[0..8] != RECORDING_MAGIC {
+            return Err(invalid_data("Not an IDTP recording"));
+        }
+
+        let version = u16::from_be_bytes([header[8], header[9]]);
+
+        if version != RECORDING_FORMAT_VERSION {
+            return Err(invalid_data("Unsupported IDTP recording version"));
+        }
+
+        let start_time_us =
+            u64::from_be_bytes(header[12..20].try_into().unwrap());
+        let metadata_size =
+            u32::from_be_bytes(header[20..24].try_into().unwrap());
+
+        if metadata_size as usize > MAX_BLOCK_SIZE {
+            return Err(invalid_data("Session metadata is too large"));
+        }
+
+        let mut metadata = vec![0u8; metadata_size as usize];
+        reader.read_exact(&mut metadata)?;
+
+        let session = Session::from_bytes(start_time_us, &metadata)?;
+        let data_start = (FILE_HEADER_SIZE + metadata.len()) as u64;
+
+        let mut player = Self {
+            reader,
+            session,
+            index: Vec::new(),
+            data_start,
+            data_end: data_start,
+            position: data_start,
+            truncated: false,
+            index_rebuilt: false,
+            unwrapper: Unwrapper::new(),
+        };
+
+        if !player.read_index()? {
+            player.rebuild_index()?;
+        }
+
+        player.rewind()?;
+        Ok(player)
+    }
+
+    /// Get recording session metadata.
+    ///
+    /// # Returns
+    /// - Session metadata.
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+
+    /// Check whether recording ends with incomplete or unreadable tail,
+    /// e.g. after recorder crash.
+    ///
+    /// # Returns
+    /// - `true`  - if tail of the recording was ignored.
+    /// - `false` - otherwise.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Check whether footer index was missing and had to be rebuilt.
+    ///
+    /// # Returns
+    /// - `true`  - if recording was not finished properly.
+    /// - `false` - otherwise.
+    pub fn is_index_rebuilt(&self) -> bool {
+        self.index_rebuilt
+    }
+
+    /// Move to the first record.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if input could not be seeked.
+    pub fn rewind(&mut self) -> io::Result<()> {
+        self.unwrapper.reset();
+        self.seek_offset(self.data_start)
+    }
+
+    /// Move to the first record received at or after given time.
+    ///
+    /// # Parameters
+    /// - `recv_time_us` - given host receive timestamp in microseconds
+    ///   since Unix epoch.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if input could not be read.
+    pub fn seek(&mut self, recv_time_us: u64) -> io::Result<()> {
+        let position = self
+            .index
+            .partition_point(|entry| entry.recv_time_us < recv_time_us);
+
+        let offset = match position {
+            0 => self.data_start,
+            _ => self.index[position - 1].offset,
+        };
+
+        self.unwrapper.reset();
+        self.seek_offset(offset)?;
+
+        loop {
+            let offset = self.position;
+
+            match self.next_record()? {
+                Some(record) if record.recv_time_us < recv_time_us => {}
+                _ => return self.seek_offset(offset),
+            }
+        }
+    }
+
+    /// Read next record.
+    ///
+    /// # Returns
+    /// - Next record - in case of success.
+    /// - `None` - if there are no more records.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if input could not be read.
+    pub fn next_record(&mut self) -> io::Result<Option<Record>> {
+        if self.position >= self.data_end {
+            return Ok(None);
+        }
+
+        match self.read_record()? {
+            Some(record) => Ok(Some(record)),
+            None => Err(invalid_data("Unexpected end of IDTP recording")),
+        }
+    }
+
+    /// Read next record and extend sequence number and timestamp of its
+    /// frame. Timelines start over after `rewind` or `seek`.
+    ///
+    /// # Returns
+    /// - Next record with frame position on extended timeline of its device
+    ///   (`None` for malformed frames) - in case of success.
+    /// - `None` - if there are no more records.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if input could not be read.
+    pub fn next_unwrapped(
+        &mut self,
+    ) -> io::Result<Option<(Record, Option<Unwrapped>)>> {
+        let Some(record) = self.next_record()? else {
+            return Ok(None);
+        };
+
+        let unwrapped = IdtpFrame::parse(&record.data)
+            .ok()
+            .map(|frame| self.unwrapper.update(&frame.header()));
+
+        Ok(Some((record, unwrapped)))
+    }
+
+    /// Read footer index if it is present.
+    ///
+    /// # Returns
+    /// - `true`  - if footer index was read.
+    /// - `false` - if footer index is missing or damaged.
+    fn read_index(&mut self) -> io::Result<bool> {
+        let file_size = self.reader.seek(SeekFrom::End(0))?;
+
+        if file_size < self.data_start + (1 + 4 + INDEX_TAIL_SIZE) as u64 {
+            return Ok(false);
+        }
+
+        let mut tail = [0u8; INDEX_TAIL_SIZE];
+        self.reader
+            .seek(SeekFrom::Start(file_size - INDEX_TAIL_SIZE as u64))?;
+        self.reader.read_exact(&mut tail)?;
+
+        if &tail[8..16] != RECORDING_INDEX_MAGIC {
+            return Ok(false);
+        }
+
+        let index_offset = u64::from_be_bytes(tail[0..8].try_into().unwrap());
+
+        if index_offset < self.data_start || index_offset >= file_size {
+            return Ok(false);
+        }
+
+        let mut index_header = [0u8; 5];
+        self.reader.seek(SeekFrom::Start(index_offset))?;
+        self.reader.read_exact(&mut index_header)?;
+
+        let count = u32::from_be_bytes(index_header[1..5].try_into().unwrap());
+        let index_size = 5 + 16 * u64::from(count) + INDEX_TAIL_SIZE as u64;
+
+        if index_header[0] != TAG_INDEX
+            || index_offset + index_size != file_size
+        {
+            return Ok(false);
+        }
+
+        let mut entries = vec![0u8; 16 * count as usize];
+        self.reader.read_exact(&mut entries)?;
+
+        self.index = entries
+            .chunks_exact(16)
+            .map(|entry| IndexEntry {
+                recv_time_us: u64::from_be_bytes(
+                    entry[0..8].try_into().unwrap(),
+                ),
+                offset: u64::from_be_bytes(entry[8..16].try_into().unwrap()),
+            })
+            .collect();
+        self.data_end = index_offset;
+
+        Ok(true)
+    }
+
+    /// Scan all records, rebuild index and find the end of complete records.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn rebuild_index(&mut self) -> io::Result<()> {
+        self.index_rebuilt = true;
+        self.data_end = u64::MAX;
+        self.seek_offset(self.data_start)?;
+
+        let mut count = 0usize;
+
+        loop {
+            let offset = self.position;
+
+            match self.read_record()? {
+                Some(record) => {
+                    if count.is_multiple_of(RECORDING_INDEX_INTERVAL) {
+                        self.index.push(IndexEntry {
+                            recv_time_us: record.recv_time_us,
+                            offset,
+                        });
+                    }
+                    count += 1;
+                }
+                None => {
+                    let file_size = self.reader.seek(SeekFrom::End(0))?;
+                    self.truncated = offset != file_size;
+                    self.data_end = offset;
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    /// Read record at current offset.
+    ///
+    /// # Returns
+    /// - Record - in case of success.
+    /// - `None` - if record is incomplete or it is not a frame record.
+    fn read_record(&mut self) -> io::Result<Option<Record>> {
+        let mut header = [0u8; RECORD_HEADER_SIZE];
+
+        if !read_complete(&mut self.reader, &mut header)?
+            || header[0] != TAG_FRAME
+        {
+            return Ok(None);
+        }
+
+        let recv_time_us = u64::from_be_bytes(header[1..9].try_into().unwrap());
+        let frame_size = u16::from_be_bytes([header[9], header[10]]) as usize;
+        let mut data = vec![0u8; frame_size];
+
+        if !read_complete(&mut self.reader, &mut data)? {
+            return Ok(None);
+        }
+
+        self.position += (RECORD_HEADER_SIZE + frame_size) as u64;
+        Ok(Some(Record { recv_time_us, data }))
+    }
+
+    /// Move to the given offset.
+    ///
+    /// # Parameters
+    /// - `offset` - given offset from the start of the recording.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn seek_offset(&mut self, offset: u64) -> io::Result<()> {
+        self.position = self.reader.seek(SeekFrom::Start(offset))?;
+        Ok(())
+    }
+}
+
+impl<R: Read + Seek> Iterator for Player<R> {
+    type Item = io::Result<Record>;
+
+    /// Read next record.
+    ///
+    /// # Returns
+    /// - Next record or error - if there are records left.
+    /// - `None` - otherwise.
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_record().transpose()
+    }
+}
+
+/// Convert collection length to 16-bit counter.
+fn count_u16(count: usize) -> io::Result<u16> {
+    u16::try_from(count).map_err(|_| invalid_input("Too many session entries"))
+}
+
+/// Write length-prefixed UTF-8 string.
+fn write_string(buffer: &mut Vec<u8>, string: &str) -> io::Result<()> {
+    let size = u16::try_from(string.len())
+        .map_err(|_| invalid_input("Session string is too long"))?;
+
+    buffer.extend_from_slice(&size.to_be_bytes());
+    buffer.extend_from_slice(string.as_bytes());
+    Ok(())
+}
+
+/// Read big-endian 16-bit value and advance byte slice.
+fn read_u16(bytes: &mut &[u8]) -> io::Result<u16> {
+    let (value, rest) = bytes
+        .split_first_chunk::<2>()
+        .ok_or_else(|| invalid_data("Session metadata is truncated"))?;
+
+    *bytes = rest;
+    Ok(u16::from_be_bytes(*value))
+}
+
+/// Read length-prefixed UTF-8 string and advance byte slice.
+fn read_string(bytes: &mut &[u8]) -> io::Result<String> {
+    let size = read_u16(bytes)? as usize;
+
+    if bytes.len() < size {
+        return Err(invalid_data("Session metadata is truncated"));
+    }
+
+    let (string, rest) = bytes.split_at(size);
+    *bytes = rest;
+
+    String::from_utf8(string.into())
+        .map_err(|_| invalid_data("Session string is not valid UTF-8"))
+}
@@ -0,0 +1,449 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Reliable delivery of IDTP frames over lossy datagram links.
+//!
+//! Frames sent with `IDTP_FLAG_RELIABLE` header flag are kept by
+//! `ReliableSender` in bounded retransmit buffer keyed on header `sequence`
+//! until receiver acknowledges them. `ReliableReceiver` answers every such
+//! frame with `Ack` control payload: acknowledged sequence number and bitmap
+//! of the preceding sequence numbers received, so single `Ack` confirms
+//! several frames and its gaps act as selective NACK. Frames which are not
+//! acknowledged in time are retransmitted until retry limit is reached and
+//! then reported to application as expired.
+//!
+//! Unreliable frames (e.g. IMU data) may share `sequence` counter with
+//! reliable ones, as only frames kept by sender are matched against `Ack`.
+
+use crate::{
+    IDTP_FLAG_RELIABLE, IdtpError, IdtpFrame, IdtpHeader,
+    payload::{PAYLOAD_TYPE_ACK, Payload},
+};
+
+/// Default time to wait for acknowledgement in microseconds.
+pub const RELIABLE_DEFAULT_TIMEOUT_US: u64 = 100_000;
+
+/// Default number of retransmissions before frame expires.
+pub const RELIABLE_DEFAULT_RETRIES: u32 = 5;
+
+/// Default number of frames kept by `ReliableSender`.
+pub const RELIABLE_DEFAULT_WINDOW: usize = 16;
+
+/// Number of preceding sequence numbers covered by `Ack` bitmap.
+pub const ACK_BITMAP_SIZE: u32 = u32::BITS;
+
+/// Number of sequence numbers tracked by `ReliableReceiver` for duplicate
+/// detection.
+pub const RELIABLE_HISTORY_SIZE: u32 = u64::BITS;
+
+/// Acknowledgement of reliable frames.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Ack {
+    /// Sequence number of acknowledged frame.
+    pub sequence: u32,
+    /// Received preceding sequence numbers: bit `i` is set if frame with
+    /// sequence number `sequence - 1 - i` was received.
+    pub bitmap: u32,
+}
+
+impl Ack {
+    /// Check whether frame with sequence number is acknowledged.
+    ///
+    /// # Parameters
+    /// - `sequence` - given frame sequence number.
+    ///
+    /// # Returns
+    /// - `true` - if frame is acknowledged.
+    /// - `false` - otherwise.
+    pub const fn acknowledges(&self, sequence: u32) -> bool {
+        match self.sequence.wrapping_sub(sequence) {
+            0 => true,
+            distance if distance <= ACK_BITMAP_SIZE => {
+                self.bitmap & (1 << (distance - 1)) != 0
+            }
+            _ => false,
+        }
+    }
+
+    /// Check whether frame with sequence number is reported missing: it
+    /// precedes acknowledged frame within bitmap range, but its bit is not
+    /// set.
+    ///
+    /// # Parameters
+    /// - `sequence` - given frame sequence number.
+    ///
+    /// # Returns
+    /// - `true` - if frame is reported missing.
+    /// - `false` - otherwise.
+    pub const fn reports_missing(&self, sequence: u32) -> bool {
+        let distance = self.sequence.wrapping_sub(sequence);
+        distance != 0
+            && distance <= ACK_BITMAP_SIZE
+            && !self.acknowledges(sequence)
+    }
+}
+
+impl Payload for Ack {
+    const PAYLOAD_TYPE: u8 = PAYLOAD_TYPE_ACK;
+    const SIZE: usize = 8;
+
+    fn encode(&self, buffer: &mut [u8]) -> Result<usize, IdtpError> {
+        if buffer.len() < Self::SIZE {
+            return Err(IdtpError::BufferTooSmall);
+        }
+
+        buffer[0..4].copy_from_slice(&self.sequence.to_be_bytes());
+        buffer[4..8].copy_from_slice(&self.bitmap.to_be_bytes());
+        Ok(Self::SIZE)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, IdtpError> {
+        if bytes.len() != Self::SIZE {
+            return Err(IdtpError::InvalidPayload);
+        }
+
+        Ok(Self {
+            sequence: u32::from_be_bytes([
+                bytes[0], bytes[1], bytes[2], bytes[3],
+            ]),
+            bitmap: u32::from_be_bytes([
+                bytes[4], bytes[5], bytes[6], bytes[7],
+            ]),
+        })
+    }
+}
+
+/// Retransmission parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReliableConfig {
+    /// Time to wait for acknowledgement before retransmission in
+    /// microseconds.
+    pub timeout_us: u64,
+    /// Number of retransmissions before frame expires.
+    pub max_retries: u32,
+}
+
+impl Default for ReliableConfig {
+    /// Construct default retransmission parameters.
+    ///
+    /// # Returns
+    /// - New `ReliableConfig` struct.
+    fn default() -> Self {
+        Self {
+            timeout_us: RELIABLE_DEFAULT_TIMEOUT_US,
+            max_retries: RELIABLE_DEFAULT_RETRIES,
+        }
+    }
+}
+
+/// Frame kept in retransmit buffer.
+#[derive(Debug, Clone, Copy)]
+struct Pending {
+    /// Frame to retransmit.
+    frame: IdtpFrame,
+    /// Time of the next retransmission in microseconds.
+    due_us: u64,
+    /// Number of retransmissions done.
+    retries: u32,
+    /// Whether frame was already retransmitted because receiver reported it
+    /// missing (later reports may predate that retransmission, so they are
+    /// left to timeout).
+    nacked: bool,
+}
+
+/// Action required from application by `ReliableSender`.
+#[derive(Debug)]
+pub enum SenderEvent<'a> {
+    /// Frame must be sent again.
+    Retransmit(&'a IdtpFrame),
+    /// Frame with given header was not acknowledged after all
+    /// retransmissions and was dropped from retransmit buffer.
+    Expired(IdtpHeader),
+}
+
+/// Sender side of reliable delivery keeping at most `N` unacknowledged
+/// frames.
+#[derive(Debug, Clone)]
+pub struct ReliableSender<const N: usize = RELIABLE_DEFAULT_WINDOW> {
+    /// Retransmission parameters.
+    config: ReliableConfig,
+    /// Retransmit buffer.
+    pending: [Option<Pending>; N],
+}
+
+impl<const N: usize> ReliableSender<N> {
+    /// Construct new `ReliableSender` struct.
+    ///
+    /// # Parameters
+    /// - `config` - given retransmission parameters.
+    ///
+    /// # Returns
+    /// - New `ReliableSender` struct.
+    pub const fn new(config: ReliableConfig) -> Self {
+        const {
+            assert!(N > 0, "ReliableSender must keep at least one frame");
+        };
+
+        Self {
+            config,
+            pending: [None; N],
+        }
+    }
+
+    /// Get number of unacknowledged frames.
+    ///
+    /// # Returns
+    /// - Number of frames in retransmit buffer.
+    pub fn pending(&self) -> usize {
+        self.pending.iter().flatten().count()
+    }
+
+    /// Check whether retransmit buffer is full.
+    ///
+    /// # Returns
+    /// - `true` - if no more frames can be sent until acknowledgement.
+    /// - `false` - otherwise.
+    pub fn is_full(&self) -> bool {
+        self.pending.iter().all(Option::is_some)
+    }
+
+    /// Keep frame which is being sent until it is acknowledged. Frame must
+    /// be complete (integrity fields filled) and carry `IDTP_FLAG_RELIABLE`
+    /// header flag and unique `sequence`.
+    ///
+    /// # Parameters
+    /// - `frame` - given frame which is being sent.
+    /// - `now_us` - given current time in microseconds.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if frame has no `IDTP_FLAG_RELIABLE` flag, frame
+    ///   with the same `sequence` is already kept or retransmit buffer is
+    ///   full.
+    pub fn send(
+        &mut self,
+        frame: &IdtpFrame,
+        now_us: u64,
+    ) -> Result<(), IdtpError> {
+        let header = frame.header();
+
+        if header.flags() & IDTP_FLAG_RELIABLE == 0
+            || self.find(header.sequence).is_some()
+        {
+            return Err(IdtpError::InvalidPayload);
+        }
+
+        let slot = self
+            .pending
+            .iter_mut()
+            .find(|slot| slot.is_none())
+            .ok_or(IdtpError::BufferTooSmall)?;
+
+        *slot = Some(Pending {
+            frame: *frame,
+            due_us: now_us.saturating_add(self.config.timeout_us),
+            retries: 0,
+            nacked: false,
+        });
+        Ok(())
+    }
+
+    /// Process acknowledgement: release acknowledged frames and schedule
+    /// immediate retransmission of frames reported missing for the first
+    /// time.
+    ///
+    /// # Parameters
+    /// - `ack` - given acknowledgement.
+    /// - `now_us` - given current time in microseconds.
+    ///
+    /// # Returns
+    /// - Number of released frames.
+    pub fn acknowledge(&mut self, ack: &Ack, now_us: u64) -> usize {
+        let mut released = 0;
+
+        for slot in &mut self.pending {
+            let Some(pending) = slot else {
+                continue;
+            };
+
+            let sequence = pending.frame.header().sequence;
+
+            if ack.acknowledges(sequence) {
+                *slot = None;
+                released += 1;
+            } else if ack.reports_missing(sequence) && !pending.nacked {
+                pending.nacked = true;
+                pending.due_us = pending.due_us.min(now_us);
+            }
+        }
+
+        released
+    }
+
+    /// Get time of the next retransmission.
+    ///
+    /// # Returns
+    /// - Time in microseconds - if there are unacknowledged frames.
+    /// - `None` - otherwise.
+    pub fn next_due(&self) -> Option<u64> {
+        self.pending.iter().flatten().map(|p| p.due_us).min()
+    }
+
+    /// Take next action whose time has come: retransmission of frame which
+    /// was not acknowledged in time or report of expired frame. Must be
+    /// called until it returns `None`.
+    ///
+    /// # Parameters
+    /// - `now_us` - given current time in microseconds.
+    ///
+    /// # Returns
+    /// - Required action - if any.
+    /// - `None` - otherwise.
+    pub fn poll(&mut self, now_us: u64) -> Option<SenderEvent<'_>> {
+        let index = self
+            .pending
+            .iter()
+            .enumerate()
+            .filter_map(|(index, slot)| {
+                slot.as_ref().map(|p| (index, p.due_us))
+            })
+            .filter(|(_, due_us)| *due_us <= now_us)
+            .min_by_key(|(_, due_us)| *due_us)
+            .map(|(index, _)| index)?;
+
+        let slot = &mut self.pending[index];
+        let max_retries = self.config.max_retries;
+
+        if slot.as_ref().is_some_and(|p| p.retries >= max_retries) {
+            return slot.take().map(|p| SenderEvent::Expired(p.frame.header()));
+        }
+
+        let pending = slot.as_mut()?;
+
+        pending.retries += 1;
+        pending.due_us = now_us.saturating_add(self.config.timeout_us);
+        Some(SenderEvent::Retransmit(&pending.frame))
+    }
+
+    /// Find kept frame.
+    fn find(&self, sequence: u32) -> Option<&Pending> {
+        self.pending
+            .iter()
+            .flatten()
+            .find(|p| p.frame.header().sequence == sequence)
+    }
+}
+
+impl<const N: usize> Default for ReliableSender<N> {
+    /// Construct new `ReliableSender` struct with default parameters.
+    ///
+    /// # Returns
+    /// - New `ReliableSender` struct.
+    fn default() -> Self {
+        Self::new(ReliableConfig::default())
+    }
+}
+
+/// Result of reliable frame reception.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reception {
+    /// Frame is received for the first time and must be acknowledged.
+    New(Ack),
+    /// Frame was already received (e.g. acknowledgement was lost) and must
+    /// be acknowledged again, but not processed.
+    Duplicate(Ack),
+    /// Frame is too old to tell whether it was received and must be
+    /// dropped without acknowledgement.
+    Stale,
+}
+
+/// Receiver side of reliable delivery.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ReliableReceiver {
+    /// Whether any frame was received.
+    started: bool,
+    /// The newest received sequence number.
+    newest: u32,
+    /// Received sequence numbers: bit `i` is set if frame with sequence
+    /// number `newest - i` was received.
+    history: u64,
+}
+
+impl ReliableReceiver {
+    /// Construct new `ReliableReceiver` struct.
+    ///
+    /// # Returns
+    /// - New `ReliableReceiver` struct.
+    pub const fn new() -> Self {
+        Self {
+            started: false,
+            newest: 0,
+            history: 0,
+        }
+    }
+
+    /// Forget received frames (e.g. after sender reboot).
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Register frame carrying `IDTP_FLAG_RELIABLE` header flag.
+    ///
+    /// # Parameters
+    /// - `sequence` - given frame sequence number.
+    ///
+    /// # Returns
+    /// - Reception result with acknowledgement to send back.
+    pub fn receive(&mut self, sequence: u32) -> Reception {
+        if !self.started {
+            self.started = true;
+            self.newest = sequence;
+            self.history = 1;
+            return Reception::New(self.ack(sequence));
+        }
+
+        let ahead = sequence.wrapping_sub(self.newest) as i32;
+
+        if ahead > 0 {
+            self.history = match ahead.unsigned_abs() {
+                shift if shift < RELIABLE_HISTORY_SIZE => self.history << shift,
+                _ => 0,
+            } | 1;
+            self.newest = sequence;
+            return Reception::New(self.ack(sequence));
+        }
+
+        let behind = ahead.unsigned_abs();
+
+        if behind >= RELIABLE_HISTORY_SIZE {
+            return Reception::Stale;
+        }
+
+        let bit = 1u64 << behind;
+        let duplicate = self.history & bit != 0;
+        self.history |= bit;
+
+        if duplicate {
+            Reception::Duplicate(self.ack(sequence))
+        } else {
+            Reception::New(self.ack(sequence))
+        }
+    }
+
+    /// Build acknowledgement of received frame.
+    fn ack(&self, sequence: u32) -> Ack {
+        let behind = self.newest.wrapping_sub(sequence) + 1;
+        let bitmap = match behind {
+            shift if shift < RELIABLE_HISTORY_SIZE => self.history >> shift,
+            _ => 0,
+        };
+
+        Ack {
+            sequence,
+            bitmap: bitmap as u32,
+        }
+    }
+}
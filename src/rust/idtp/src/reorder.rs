@@ -0,0 +1,262 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Jitter/reorder buffer releasing frames of single device in `sequence`
+//! order.
+//!
+//! Datagram links (e.g. Wi-Fi) deliver frames out of order, while consumers
+//! such as fusion filters expect time order. `ReorderBuffer` holds frames
+//! arriving ahead of the next expected `sequence` for at most configured
+//! latency: when it passes (or buffer is full) the gap is skipped, and frames
+//! arriving after their place was released are reported late. Buffer works
+//! on any `Copy` item (e.g. `IdtpFrame` on host or decoded sample on
+//! firmware), so one buffer is used per `device_id`.
+
+use crate::IdtpError;
+
+/// Default number of frames held by `ReorderBuffer`.
+pub const REORDER_DEFAULT_CAPACITY: usize = 16;
+
+/// Default maximum time to hold frame in microseconds.
+pub const REORDER_DEFAULT_LATENCY_US: u64 = 20_000;
+
+/// Result of adding frame to `ReorderBuffer`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arrival {
+    /// Frame is held until its turn.
+    Buffered,
+    /// Frame arrived after its place in sequence was released and was
+    /// dropped.
+    Late,
+    /// Frame with the same sequence number is already held, frame was
+    /// dropped.
+    Duplicate,
+}
+
+/// Frame released by `ReorderBuffer`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Released<T> {
+    /// Frame sequence number.
+    pub sequence: u32,
+    /// Released frame.
+    pub item: T,
+    /// Number of missing sequence numbers skipped right before this frame.
+    pub skipped: u32,
+}
+
+/// Held frame.
+#[derive(Debug, Clone, Copy)]
+struct Slot<T> {
+    /// Frame sequence number.
+    sequence: u32,
+    /// Arrival time in microseconds.
+    arrival_us: u64,
+    /// Held frame.
+    item: T,
+}
+
+/// Buffer releasing frames of single device strictly by `sequence`, holding
+/// at most `N` frames.
+#[derive(Debug, Clone)]
+pub struct ReorderBuffer<T: Copy, const N: usize = REORDER_DEFAULT_CAPACITY> {
+    /// Maximum time to hold frame in microseconds.
+    latency_us: u64,
+    /// Held frames.
+    slots: [Option<Slot<T>>; N],
+    /// Whether any frame was added.
+    started: bool,
+    /// Sequence number of the next frame to release.
+    next: u32,
+}
+
+impl<T: Copy, const N: usize> ReorderBuffer<T, N> {
+    /// Construct new `ReorderBuffer` struct.
+    ///
+    /// # Parameters
+    /// - `latency_us` - given maximum time to hold frame in microseconds.
+    ///
+    /// # Returns
+    /// - New `ReorderBuffer` struct.
+    pub const fn new(latency_us: u64) -> Self {
+        const {
+            assert!(N > 0, "ReorderBuffer must hold at least one frame");
+        };
+
+        Self {
+            latency_us,
+            slots: [None; N],
+            started: false,
+            next: 0,
+        }
+    }
+
+    /// Get number of held frames.
+    ///
+    /// # Returns
+    /// - Number of held frames.
+    pub fn len(&self) -> usize {
+        self.slots.iter().flatten().count()
+    }
+
+    /// Check whether buffer holds no frames.
+    ///
+    /// # Returns
+    /// - `true` - if buffer is empty.
+    /// - `false` - otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.slots.iter().all(Option::is_none)
+    }
+
+    /// Get sequence number of the next frame to release.
+    ///
+    /// # Returns
+    /// - Sequence number - if any frame was added.
+    /// - `None` - otherwise.
+    pub fn next_sequence(&self) -> Option<u32> {
+        self.started.then_some(self.next)
+    }
+
+    /// Drop held frames and start over with the next added frame (e.g.
+    /// after device reboot).
+    pub fn reset(&mut self) {
+        self.slots = [None; N];
+        self.started = false;
+    }
+
+    /// Add received frame. The first frame sets sequence number to start
+    /// releasing from.
+    ///
+    /// # Parameters
+    /// - `sequence` - given frame sequence number.
+    /// - `item` - given frame.
+    /// - `now_us` - given current time in microseconds.
+    ///
+    /// # Returns
+    /// - Arrival result - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if buffer is full (frames were not taken with
+    ///   `pop` after the previous addition).
+    pub fn push(
+        &mut self,
+        sequence: u32,
+        item: T,
+        now_us: u64,
+    ) -> Result<Arrival, IdtpError> {
+        if !self.started {
+            self.started = true;
+            self.next = sequence;
+        }
+
+        if (sequence.wrapping_sub(self.next) as i32) < 0 {
+            return Ok(Arrival::Late);
+        }
+
+        if self.slots.iter().flatten().any(|s| s.sequence == sequence) {
+            return Ok(Arrival::Duplicate);
+        }
+
+        let slot = self
+            .slots
+            .iter_mut()
+            .find(|slot| slot.is_none())
+            .ok_or(IdtpError::BufferTooSmall)?;
+
+        *slot = Some(Slot {
+            sequence,
+            arrival_us: now_us,
+            item,
+        });
+        Ok(Arrival::Buffered)
+    }
+
+    /// Get time when the next frame is due for release.
+    ///
+    /// # Returns
+    /// - Time in microseconds - if buffer holds frames.
+    /// - `None` - otherwise.
+    pub fn next_due(&self) -> Option<u64> {
+        self.slots
+            .iter()
+            .flatten()
+            .map(|s| {
+                if s.sequence == self.next {
+                    s.arrival_us
+                } else {
+                    s.arrival_us.saturating_add(self.latency_us)
+                }
+            })
+            .min()
+    }
+
+    /// Take next frame in sequence order. Missing frames are skipped once
+    /// any held frame waited for `latency_us` or buffer is full. Must be
+    /// called until it returns `None` after every `push`.
+    ///
+    /// # Parameters
+    /// - `now_us` - given current time in microseconds.
+    ///
+    /// # Returns
+    /// - Released frame - if any.
+    /// - `None` - otherwise.
+    pub fn pop(&mut self, now_us: u64) -> Option<Released<T>> {
+        let skip = self.slots.iter().all(Option::is_some)
+            || self.slots.iter().flatten().any(|s| {
+                now_us.saturating_sub(s.arrival_us) >= self.latency_us
+            });
+
+        self.release(skip)
+    }
+
+    /// Take all held frames in sequence order regardless of their time
+    /// (e.g. at the end of stream).
+    ///
+    /// # Returns
+    /// - Released frame - if any.
+    /// - `None` - if buffer is empty.
+    pub fn flush(&mut self) -> Option<Released<T>> {
+        self.release(true)
+    }
+
+    /// Release held frame with the lowest sequence number if it is the next
+    /// one or gaps may be skipped.
+    fn release(&mut self, skip: bool) -> Option<Released<T>> {
+        let index = self.lowest()?;
+        let slot = self.slots[index]?;
+        let skipped = slot.sequence.wrapping_sub(self.next);
+
+        if skipped != 0 && !skip {
+            return None;
+        }
+
+        self.slots[index] = None;
+        self.next = slot.sequence.wrapping_add(1);
+
+        Some(Released {
+            sequence: slot.sequence,
+            item: slot.item,
+            skipped,
+        })
+    }
+
+    /// Find held frame with the lowest sequence number.
+    fn lowest(&self) -> Option<usize> {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(index, slot)| slot.as_ref().map(|s| (index, s)))
+            .min_by_key(|(_, s)| s.sequence.wrapping_sub(self.next))
+            .map(|(index, _)| index)
+    }
+}
+
+impl<T: Copy, const N: usize> Default for ReorderBuffer<T, N> {
+    /// Construct new `ReorderBuffer` struct with default latency.
+    ///
+    /// # Returns
+    /// - New `ReorderBuffer` struct.
+    fn default() -> Self {
+        Self::new(REORDER_DEFAULT_LATENCY_US)
+    }
+}
@@ -0,0 +1,178 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP-E (Secure mode) payload encryption and authentication with
+//! AES-128-GCM.
+//!
+//! Payload section of Secure mode frame holds ciphertext followed by
+//! `IDTP_TAG_SIZE` bytes authentication tag, both are counted in header
+//! `payload_size`, so frame structure stays valid for receivers without the
+//! key. Tag does not fit into `reserved` field and trailer must stay intact
+//! for deframing (see specification §5.2). Header bytes (with `checksum` and
+//! `crc` fields zeroed) are used as associated data. Nonce is derived from
+//! `device_id` and `sequence` fields (see `nonce`) and key identifier is
+//! carried in the `reserved` field (see `IdtpHeader::key_id`).
+//!
+//! Nonce must never repeat for the same key: sender must use new key before
+//! `sequence` wraps around or restarts (e.g. after reboot). `SecureKey`
+//! refuses to seal frames whose `sequence` is not greater than the last
+//! sealed one. This is tracked in memory only, so keys stored across reboots
+//! must not be used again.
+
+use crate::IdtpError;
+use aes_gcm::{
+    Aes128Gcm, Nonce, Tag,
+    aead::{AeadInPlace, KeyInit},
+};
+use core::fmt;
+
+/// Size of Secure mode key in bytes.
+pub const IDTP_KEY_SIZE: usize = 16;
+
+/// Size of Secure mode nonce in bytes.
+pub const IDTP_NONCE_SIZE: usize = 12;
+
+/// Size of Secure mode authentication tag in bytes.
+pub const IDTP_TAG_SIZE: usize = 16;
+
+/// Derive Secure mode nonce: `device_id` (big-endian), six zero bytes and
+/// `sequence` (big-endian).
+///
+/// # Parameters
+/// - `device_id` - given IMU device identifier.
+/// - `sequence` - given frame sequence number.
+///
+/// # Returns
+/// - Nonce for frame encryption.
+pub fn nonce(device_id: u16, sequence: u32) -> [u8; IDTP_NONCE_SIZE] {
+    let mut nonce = [0u8; IDTP_NONCE_SIZE];
+    nonce[0..2].copy_from_slice(&device_id.to_be_bytes());
+    nonce[8..12].copy_from_slice(&sequence.to_be_bytes());
+    nonce
+}
+
+/// AES-128-GCM key with its identifier.
+#[derive(Clone)]
+pub struct SecureKey {
+    /// Key identifier carried by frames encrypted with this key.
+    id: u8,
+    /// AES-128-GCM cipher instance.
+    cipher: Aes128Gcm,
+    /// Sequence number of the last frame sealed with this key.
+    sealed: Option<u32>,
+}
+
+impl SecureKey {
+    /// Construct new `SecureKey` struct.
+    ///
+    /// # Parameters
+    /// - `id` - given key identifier.
+    /// - `key` - given raw key bytes.
+    ///
+    /// # Returns
+    /// - New `SecureKey` struct.
+    pub fn new(id: u8, key: &[u8; IDTP_KEY_SIZE]) -> Self {
+        Self {
+            id,
+            cipher: Aes128Gcm::new(key.into()),
+            sealed: None,
+        }
+    }
+
+    /// Get key identifier.
+    ///
+    /// # Returns
+    /// - Key identifier.
+    pub fn id(&self) -> u8 {
+        self.id
+    }
+
+    /// Reserve frame sequence number for encryption, so its nonce is never
+    /// used again with this key.
+    ///
+    /// # Parameters
+    /// - `sequence` - given sequence number of frame to encrypt.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Will return `Err` if `sequence` is not greater than sequence number
+    ///   of the last frame sealed with this key (key must be rotated).
+    pub fn reserve(&mut self, sequence: u32) -> Result<(), IdtpError> {
+        if self.sealed.is_some_and(|sealed| sequence <= sealed) {
+            return Err(IdtpError::KeyUnavailable);
+        }
+
+        self.sealed = Some(sequence);
+        Ok(())
+    }
+
+    /// Encrypt bytes in place. Nonce uniqueness is not checked.
+    ///
+    /// # Parameters
+    /// - `nonce` - given unique nonce.
+    /// - `aad` - given associated data to authenticate.
+    /// - `buffer` - given plaintext to replace with ciphertext.
+    ///
+    /// # Returns
+    /// - Authentication tag.
+    pub fn encrypt(
+        &self,
+        nonce: &[u8; IDTP_NONCE_SIZE],
+        aad: &[u8],
+        buffer: &mut [u8],
+    ) -> [u8; IDTP_TAG_SIZE] {
+        self.cipher
+            .encrypt_in_place_detached(Nonce::from_slice(nonce), aad, buffer)
+            .map(Into::into)
+            // Fails only for buffers longer than 64 GiB.
+            .expect("AES-GCM plaintext is too long")
+    }
+
+    /// Verify authentication tag and decrypt bytes in place.
+    ///
+    /// # Parameters
+    /// - `nonce` - given nonce used for encryption.
+    /// - `aad` - given associated data to authenticate.
+    /// - `buffer` - given ciphertext to replace with plaintext.
+    /// - `tag` - given authentication tag.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise, buffer is left unchanged.
+    ///
+    /// # Errors
+    /// - Will return `Err` if authentication tag does not match.
+    pub fn decrypt(
+        &self,
+        nonce: &[u8; IDTP_NONCE_SIZE],
+        aad: &[u8],
+        buffer: &mut [u8],
+        tag: &[u8; IDTP_TAG_SIZE],
+    ) -> Result<(), IdtpError> {
+        self.cipher
+            .decrypt_in_place_detached(
+                Nonce::from_slice(nonce),
+                aad,
+                buffer,
+                Tag::from_slice(tag),
+            )
+            .map_err(|_| IdtpError::AuthenticationFailed)
+    }
+}
+
+impl fmt::Debug for SecureKey {
+    /// Format key without exposing key material.
+    ///
+    /// # Parameters
+    /// - `f` - given formatter.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecureKey").field("id", &self.id).finish()
+    }
+}
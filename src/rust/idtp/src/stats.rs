@@ -0,0 +1,427 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Link and stream statistics of single device.
+//!
+//! `StreamStats` accumulates counters of frames received from one
+//! `device_id`: integrity failures, preamble resyncs, sequence gaps,
+//! duplicates and reordered frames, as well as effective frame rate and
+//! inter-arrival jitter. Sequence numbers are extended across wraps and
+//! device reboots by `timeline::Timeline`. Jitter of a frame is the
+//! difference between its inter-arrival time and the previous one (IPDV),
+//! its distribution is kept in fixed log-scale histogram, so no allocation
+//! is needed.
+
+use crate::{IdtpHeader, Integrity, timeline::Timeline};
+use core::fmt;
+
+/// Number of sequence numbers behind the newest one tracked to tell
+/// duplicates from reordered frames. Older late frames are counted as
+/// reordered.
+pub const STATS_HISTORY_SIZE: u32 = 64;
+
+/// Number of sub-buckets per power of two in jitter histogram.
+const SUB_BUCKETS: usize = 4;
+
+/// Number of buckets in jitter histogram (up to ~2^32 microseconds).
+const BUCKETS: usize = SUB_BUCKETS * 31;
+
+/// Statistics collected by `StreamStats`.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct StatsSnapshot {
+    /// Number of recorded frames.
+    pub frames: u64,
+    /// Total size of recorded frames in bytes.
+    pub bytes: u64,
+    /// Number of frames with checksum mismatch.
+    pub checksum_failures: u64,
+    /// Number of frames with CRC mismatch.
+    pub crc_failures: u64,
+    /// Number of preamble resyncs.
+    pub resyncs: u64,
+    /// Number of sequence gaps.
+    pub gaps: u64,
+    /// Number of frames missing in sequence gaps which did not arrive
+    /// later.
+    pub lost: u64,
+    /// Number of frames received more than once.
+    pub duplicates: u64,
+    /// Number of frames arriving after frames with higher sequence.
+    pub reordered: u64,
+    /// Effective rate of unique frames in Hz (`None` without arrival times
+    /// of at least two frames).
+    pub frame_rate_hz: Option<f64>,
+    /// Mean inter-arrival jitter in microseconds (`None` without arrival
+    /// times of at least three frames).
+    pub jitter_mean_us: Option<f64>,
+    /// 99th percentile of inter-arrival jitter in microseconds (upper bound
+    /// of histogram bucket, at most 25% above the exact value).
+    pub jitter_p99_us: Option<u64>,
+}
+
+/// Optional statistics value formatted as number or `n/a`.
+struct Value<T>(Option<T>, &'static str);
+
+impl<T: fmt::Display> fmt::Display for Value<T> {
+    /// Format value with precision of formatter and given unit.
+    ///
+    /// # Parameters
+    /// - `f` - given formatter.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.0, f.precision()) {
+            (Some(value), Some(precision)) => {
+                write!(f, "{value:.precision$} {}", self.1)
+            }
+            (Some(value), None) => write!(f, "{value} {}", self.1),
+            (None, _) => f.write_str("n/a"),
+        }
+    }
+}
+
+impl fmt::Display for StatsSnapshot {
+    /// Format statistics as human-readable summary.
+    ///
+    /// # Parameters
+    /// - `f` - given formatter.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "frames: {} ({} bytes), frame rate: {:.1}, checksum failures: {}, \
+             crc failures: {}, resyncs: {}, gaps: {} ({} lost), \
+             duplicates: {}, reordered: {}, jitter: mean {:.1}, p99 {}",
+            self.frames,
+            self.bytes,
+            Value(self.frame_rate_hz, "Hz"),
+            self.checksum_failures,
+            self.crc_failures,
+            self.resyncs,
+            self.gaps,
+            self.lost,
+            self.duplicates,
+            self.reordered,
+            Value(self.jitter_mean_us, "us"),
+            Value(self.jitter_p99_us, "us"),
+        )
+    }
+}
+
+/// Log-scale histogram of microsecond values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Histogram {
+    /// Number of values in every bucket.
+    buckets: [u64; BUCKETS],
+    /// Number of values.
+    count: u64,
+    /// Sum of values.
+    sum: u64,
+}
+
+impl Histogram {
+    /// Construct new empty `Histogram` struct.
+    const fn new() -> Self {
+        Self {
+            buckets: [0; BUCKETS],
+            count: 0,
+            sum: 0,
+        }
+    }
+
+    /// Add value.
+    fn record(&mut self, value: u64) {
+        self.buckets[Self::bucket(value)] += 1;
+        self.count += 1;
+        self.sum = self.sum.saturating_add(value);
+    }
+
+    /// Get mean value.
+    fn mean(&self) -> Option<f64> {
+        if self.count == 0 {
+            return None;
+        }
+
+        Some(self.sum as f64 / self.count as f64)
+    }
+
+    /// Get upper bound of bucket holding given percentile.
+    fn percentile(&self, percent: u64) -> Option<u64> {
+        if self.count == 0 {
+            return None;
+        }
+
+        let rank = (self.count * percent).div_ceil(100).max(1);
+        let mut seen = 0;
+
+        for (index, count) in self.buckets.iter().enumerate() {
+            seen += count;
+
+            if seen >= rank {
+                return Some(Self::upper_bound(index));
+            }
+        }
+
+        Some(Self::upper_bound(BUCKETS - 1))
+    }
+
+    /// Get bucket index of value: values below `SUB_BUCKETS` have own
+    /// buckets, every next power of two is split into `SUB_BUCKETS`.
+    fn bucket(value: u64) -> usize {
+        if value < SUB_BUCKETS as u64 {
+            return value as usize;
+        }
+
+        let exponent = value.ilog2() as usize;
+        let shift = exponent - SUB_BUCKETS.ilog2() as usize;
+        let mantissa = (value >> shift) as usize - SUB_BUCKETS;
+        (SUB_BUCKETS + shift * SUB_BUCKETS + mantissa).min(BUCKETS - 1)
+    }
+
+    /// Get the largest value of bucket.
+    fn upper_bound(index: usize) -> u64 {
+        if index < SUB_BUCKETS {
+            return index as u64;
+        }
+
+        let shift = (index - SUB_BUCKETS) / SUB_BUCKETS;
+        let mantissa =
+            ((index - SUB_BUCKETS) % SUB_BUCKETS + SUB_BUCKETS + 1) as u64;
+        (mantissa << shift) - 1
+    }
+}
+
+/// Statistics accumulator of frames received from single device.
+#[derive(Debug, Clone)]
+pub struct StreamStats {
+    /// Counters without derived values.
+    counters: StatsSnapshot,
+    /// Extended timeline of the device.
+    timeline: Timeline,
+    /// Whether any frame passed integrity checks.
+    started: bool,
+    /// The highest received extended sequence number.
+    highest: u64,
+    /// Bit `i` is set if frame `highest - i` was received.
+    history: u64,
+    /// Number of unique frames with arrival time.
+    unique: u64,
+    /// Arrival time of the first unique frame in microseconds.
+    first_us: u64,
+    /// Arrival time of the last unique frame in microseconds.
+    last_us: u64,
+    /// Inter-arrival time of the last unique frame in microseconds.
+    interval_us: Option<u64>,
+    /// Inter-arrival jitter histogram.
+    jitter: Histogram,
+}
+
+impl StreamStats {
+    /// Construct new `StreamStats` struct.
+    ///
+    /// # Returns
+    /// - New `StreamStats` struct.
+    pub const fn new() -> Self {
+        Self {
+            counters: StatsSnapshot {
+                frames: 0,
+                bytes: 0,
+                checksum_failures: 0,
+                crc_failures: 0,
+                resyncs: 0,
+                gaps: 0,
+                lost: 0,
+                duplicates: 0,
+                reordered: 0,
+                frame_rate_hz: None,
+                jitter_mean_us: None,
+                jitter_p99_us: None,
+            },
+            timeline: Timeline::new(),
+            started: false,
+            highest: 0,
+            history: 0,
+            unique: 0,
+            first_us: 0,
+            last_us: 0,
+            interval_us: None,
+            jitter: Histogram::new(),
+        }
+    }
+
+    /// Clear all statistics.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Add preamble resyncs of the link the device is received over (e.g.
+    /// increase of `Deframer::resyncs`).
+    ///
+    /// # Parameters
+    /// - `count` - given number of resyncs.
+    pub fn record_resyncs(&mut self, count: u64) {
+        self.counters.resyncs += count;
+    }
+
+    /// Add received frame. Sequence and timing of frames which failed
+    /// integrity checks are not analyzed.
+    ///
+    /// # Parameters
+    /// - `header` - given frame header.
+    /// - `integrity` - given frame integrity verification result.
+    /// - `now_us` - given frame arrival time in microseconds (`None` if
+    ///   unknown, timing is not analyzed then).
+    pub fn record(
+        &mut self,
+        header: &IdtpHeader,
+        integrity: &Integrity,
+        now_us: Option<u64>,
+    ) {
+        let counters = &mut self.counters;
+        counters.frames += 1;
+        counters.bytes += header.frame_size() as u64;
+
+        if !integrity.checksum.is_ok() {
+            counters.checksum_failures += 1;
+        }
+
+        if integrity.crc.is_some_and(|crc| !crc.is_ok()) {
+            counters.crc_failures += 1;
+        }
+
+        if integrity.is_ok()
+            && self.track(header)
+            && let Some(now_us) = now_us
+        {
+            self.arrive(now_us);
+        }
+    }
+
+    /// Get current statistics.
+    ///
+    /// # Returns
+    /// - Statistics snapshot.
+    pub fn snapshot(&self) -> StatsSnapshot {
+        let span_us = self.last_us.saturating_sub(self.first_us);
+        let frame_rate_hz = (span_us > 0)
+            .then(|| (self.unique - 1) as f64 * 1e6 / span_us as f64);
+
+        StatsSnapshot {
+            frame_rate_hz,
+            jitter_mean_us: self.jitter.mean(),
+            jitter_p99_us: self.jitter.percentile(99),
+            ..self.counters
+        }
+    }
+
+    /// Update sequence history.
+    ///
+    /// # Returns
+    /// - `true`  - if frame was received for the first time.
+    /// - `false` - otherwise.
+    fn track(&mut self, header: &IdtpHeader) -> bool {
+        if !self.started {
+            self.timeline = Timeline::with_unit(header.timestamp_unit(), 0);
+        }
+
+        let sequence = self
+            .timeline
+            .update(header.sequence, header.timestamp)
+            .sequence;
+
+        if !self.started {
+            self.started = true;
+            self.highest = sequence;
+            self.history = 1;
+            return true;
+        }
+
+        let counters = &mut self.counters;
+
+        if sequence > self.highest {
+            let ahead = sequence - self.highest;
+
+            if ahead > 1 {
+                counters.gaps += 1;
+                counters.lost += ahead - 1;
+            }
+
+            self.history = u32::try_from(ahead)
+                .ok()
+                .and_then(|ahead| self.history.checked_shl(ahead))
+                .unwrap_or(0)
+                | 1;
+            self.highest = sequence;
+            return true;
+        }
+
+        let behind = self.highest - sequence;
+
+        if behind >= u64::from(STATS_HISTORY_SIZE) {
+            counters.reordered += 1;
+            counters.lost = counters.lost.saturating_sub(1);
+            return true;
+        }
+
+        let bit = 1u64 << behind;
+
+        if self.history & bit != 0 {
+            counters.duplicates += 1;
+            return false;
+        }
+
+        self.history |= bit;
+        counters.reordered += 1;
+        counters.lost = counters.lost.saturating_sub(1);
+        true
+    }
+
+    /// Update timing of unique frames.
+    fn arrive(&mut self, now_us: u64) {
+        self.unique += 1;
+
+        if self.unique == 1 {
+            self.first_us = now_us;
+            self.last_us = now_us;
+            return;
+        }
+
+        let interval_us = now_us.saturating_sub(self.last_us);
+
+        if let Some(previous) = self.interval_us {
+            self.jitter.record(interval_us.abs_diff(previous));
+        }
+
+        self.interval_us = Some(interval_us);
+        self.last_us = self.last_us.max(now_us);
+    }
+}
+
+impl Default for StreamStats {
+    /// Construct new default `StreamStats` struct.
+    ///
+    /// # Returns
+    /// - New default `StreamStats` struct.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for StreamStats {
+    /// Format current statistics as human-readable summary.
+    ///
+    /// # Parameters
+    /// - `f` - given formatter.
+    ///
+    /// # Returns
+    /// - `Ok`  - in case of success.
+    /// - `Err` - otherwise.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.snapshot().fmt(f)
+    }
+}
@@ -0,0 +1,590 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Host-device clock synchronization and timestamp translation.
+//!
+//! Header `timestamp` is a free-running device clock counted in its
+//! timestamp unit (milliseconds by default, see `TimestampUnit`), so
+//! relation to host clock (in microseconds) is estimated as an offset and a
+//! drift. `SyncEstimator` fits them from ping exchanges over the command
+//! channel (host send time `t1`, device receive time `t2`, device send time
+//! `t3` and host receive time `t4`), `PassiveEstimator` fits them from frame
+//! receive times only. Both produce `TimeMapper` which converts device
+//! timestamps to host time with confidence bounds.
+//!
+//! Device timestamps are extended to 64-bit timeline (see `timeline`
+//! module), so consecutive observations MUST be less than 2^31 timestamp
+//! units apart.
+
+use crate::timeline::unwrap_u32;
+
+/// Duration of device timestamp unit in microseconds assumed by default
+/// (milliseconds).
+pub const SYNC_DEFAULT_PERIOD_US: f64 = 1000.0;
+
+/// Drift tolerance assumed when drift can not be estimated (in ppm).
+pub const SYNC_DEFAULT_DRIFT_TOLERANCE_PPM: f64 = 100.0;
+
+/// Result of single ping exchange.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SyncSample {
+    /// Host time when ping request was sent (microseconds).
+    pub t1: u64,
+    /// Device timestamp when ping request was received.
+    pub t2: u32,
+    /// Device timestamp when ping response was sent.
+    pub t3: u32,
+    /// Host time when ping response was received (microseconds).
+    pub t4: u64,
+    /// Duration of device timestamp unit in microseconds (see
+    /// `TimestampUnit::period_us`).
+    pub period_us: f64,
+}
+
+impl SyncSample {
+    /// Get network round trip delay without device processing time.
+    ///
+    /// # Returns
+    /// - Round trip delay in microseconds.
+    pub fn round_trip(&self) -> u64 {
+        let processing = self.processing_us() as u64;
+        self.t4.saturating_sub(self.t1).saturating_sub(processing)
+    }
+
+    /// Get clock offset measured by exchange (see `TimeMapper::offset_us`).
+    ///
+    /// # Returns
+    /// - Host minus device time at exchange in microseconds.
+    pub fn offset_us(&self) -> i64 {
+        let point = self.point(u64::from(self.t2));
+        let device_us = f64::from(self.t2) * self.period_us + point.device_us;
+        point.host as i64 - device_us as i64
+    }
+
+    /// Get device processing time in microseconds.
+    fn processing_us(&self) -> f64 {
+        f64::from(self.t3.wrapping_sub(self.t2)) * self.period_us
+    }
+
+    /// Convert exchange to fitting point: midpoint of device processing
+    /// matches midpoint of host round trip within half of network delay and
+    /// half of timestamp unit.
+    fn point(&self, t2: u64) -> Point {
+        let round_trip = self.t4.saturating_sub(self.t1);
+        let resolution = self.period_us / 2.0;
+
+        Point {
+            device: t2,
+            device_us: self.processing_us() / 2.0 + resolution,
+            host: self.t1 + round_trip / 2,
+            uncertainty: self.round_trip() as f64 / 2.0 + resolution,
+        }
+    }
+}
+
+impl Default for SyncSample {
+    /// Construct new default `SyncSample` struct with millisecond device
+    /// timestamps.
+    ///
+    /// # Returns
+    /// - New default `SyncSample` struct.
+    fn default() -> Self {
+        Self {
+            t1: 0,
+            t2: 0,
+            t3: 0,
+            t4: 0,
+            period_us: SYNC_DEFAULT_PERIOD_US,
+        }
+    }
+}
+
+/// Device timestamp converted to host time.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MappedTime {
+    /// Estimated host time in microseconds.
+    pub time_us: u64,
+    /// Maximum estimation error in microseconds.
+    pub uncertainty_us: u64,
+}
+
+impl MappedTime {
+    /// Get the earliest possible host time.
+    ///
+    /// # Returns
+    /// - Host time in microseconds.
+    pub fn earliest(&self) -> u64 {
+        self.time_us.saturating_sub(self.uncertainty_us)
+    }
+
+    /// Get the latest possible host time.
+    ///
+    /// # Returns
+    /// - Host time in microseconds.
+    pub fn latest(&self) -> u64 {
+        self.time_us.saturating_add(self.uncertainty_us)
+    }
+}
+
+/// Linear mapping of device timestamps to host time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeMapper {
+    /// Extended device timestamp of reference point.
+    device: u64,
+    /// Host time of reference point in microseconds.
+    host: u64,
+    /// Host time at reference device timestamp relative to `host`.
+    intercept: f64,
+    /// Host microseconds per device microsecond.
+    rate: f64,
+    /// Duration of device timestamp unit in microseconds.
+    period_us: f64,
+    /// Estimation error inside fitting window in microseconds.
+    uncertainty: f64,
+    /// Estimation error of `rate`.
+    rate_error: f64,
+    /// Fitting window bounds relative to `device` in microseconds.
+    window: (f64, f64),
+}
+
+impl TimeMapper {
+    /// Convert device timestamp to host time.
+    ///
+    /// # Parameters
+    /// - `timestamp` - given header timestamp value.
+    ///
+    /// # Returns
+    /// - Host time with confidence bounds, which grow when timestamp is
+    ///   outside of fitting window.
+    pub fn to_host(&self, timestamp: u32) -> MappedTime {
+        self.to_host_unwrapped(unwrap_u32(self.device, timestamp))
+    }
+
+    /// Convert extended device timestamp to host time.
+    ///
+    /// # Parameters
+    /// - `timestamp` - given extended timestamp on the same timeline as
+    ///   timestamps used for estimation.
+    ///
+    /// # Returns
+    /// - Host time with confidence bounds, which grow when timestamp is
+    ///   outside of fitting window.
+    pub fn to_host_unwrapped(&self, timestamp: u64) -> MappedTime {
+        let x = relative(timestamp, self.device, self.period_us);
+        let time = self.host as f64 + self.intercept + self.rate * x;
+
+        let distance = if x < self.window.0 {
+            self.window.0 - x
+        } else {
+            (x - self.window.1).max(0.0)
+        };
+
+        MappedTime {
+            time_us: time.max(0.0) as u64,
+            uncertainty_us: (self.uncertainty + self.rate_error * distance)
+                as u64,
+        }
+    }
+
+    /// Convert host time to device timestamp.
+    ///
+    /// # Parameters
+    /// - `time_us` - given host time in microseconds.
+    ///
+    /// # Returns
+    /// - Header timestamp value.
+    pub fn to_device(&self, time_us: u64) -> u32 {
+        self.to_device_unwrapped(time_us) as u32
+    }
+
+    /// Convert host time to extended device timestamp.
+    ///
+    /// # Parameters
+    /// - `time_us` - given host time in microseconds.
+    ///
+    /// # Returns
+    /// - Extended timestamp.
+    pub fn to_device_unwrapped(&self, time_us: u64) -> u64 {
+        let y = (time_us as i64 - self.host as i64) as f64 - self.intercept;
+        let x = (y / self.rate / self.period_us) as i64;
+        self.device.saturating_add_signed(x)
+    }
+
+    /// Get clock offset at reference point.
+    ///
+    /// # Returns
+    /// - Host minus device time in microseconds.
+    pub fn offset_us(&self) -> i64 {
+        let device_us = self.device as f64 * self.period_us;
+        self.host as i64 + self.intercept as i64 - device_us as i64
+    }
+
+    /// Get device clock drift relative to host clock.
+    ///
+    /// # Returns
+    /// - Drift in ppm (positive if device clock is slow).
+    pub fn drift_ppm(&self) -> f64 {
+        (self.rate - 1.0) * 1e6
+    }
+}
+
+/// Clock observation used for fitting.
+#[derive(Debug, Default, Clone, Copy)]
+struct Point {
+    /// Extended device timestamp.
+    device: u64,
+    /// Additional device time in microseconds.
+    device_us: f64,
+    /// Host time in microseconds.
+    host: u64,
+    /// Maximum error of host time in microseconds.
+    uncertainty: f64,
+}
+
+/// Get device time relative to reference in microseconds.
+fn relative(timestamp: u64, reference: u64, period_us: f64) -> f64 {
+    (timestamp as i64 - reference as i64) as f64 * period_us
+}
+
+/// Fixed-size window of the latest points.
+#[derive(Debug, Clone)]
+struct Window<const N: usize> {
+    /// Ring buffer of points.
+    points: [Point; N],
+    /// Number of stored points.
+    len: usize,
+    /// Index of the next point to overwrite.
+    next: usize,
+    /// Extended device timestamp of the latest point.
+    latest: Option<u64>,
+    /// Duration of device timestamp unit in microseconds.
+    period_us: f64,
+}
+
+impl<const N: usize> Window<N> {
+    /// Construct new empty window.
+    const fn new() -> Self {
+        Self {
+            points: [Point {
+                device: 0,
+                device_us: 0.0,
+                host: 0,
+                uncertainty: 0.0,
+            }; N],
+            len: 0,
+            next: 0,
+            latest: None,
+            period_us: SYNC_DEFAULT_PERIOD_US,
+        }
+    }
+
+    /// Drop all points and set timestamp unit of the next ones.
+    fn reset(&mut self, period_us: f64) {
+        self.len = 0;
+        self.next = 0;
+        self.latest = None;
+        self.period_us = period_us;
+    }
+
+    /// Extend device timestamp relative to the latest point.
+    fn unwrap(&self, timestamp: u32) -> u64 {
+        self.latest.map_or(u64::from(timestamp), |latest| {
+            unwrap_u32(latest, timestamp)
+        })
+    }
+
+    /// Add point replacing the oldest one if window is full.
+    fn push(&mut self, point: Point) {
+        if N == 0 {
+            return;
+        }
+
+        self.latest = Some(point.device);
+        self.points[self.next] = point;
+        self.next = (self.next + 1) % N;
+        self.len = (self.len + 1).min(N);
+    }
+
+    /// Get points starting from the oldest one.
+    fn iter(&self) -> impl Iterator<Item = &Point> {
+        let start = if self.len < N { 0 } else { self.next };
+        (0..self.len).map(move |i| &self.points[(start + i) % N])
+    }
+
+    /// Fit line through points accepted by filter with least squares.
+    ///
+    /// Returns mapper with zero uncertainty and residuals of accepted points
+    /// stored in `residuals`.
+    fn fit<F>(
+        &self,
+        accept: F,
+        residuals: &mut [f64; N],
+    ) -> Option<(TimeMapper, usize)>
+    where
+        F: Fn(&Point) -> bool,
+    {
+        let reference = *self.iter().find(|point| accept(point))?;
+        let coordinates = |point: &Point| {
+            let x = relative(point.device, reference.device, self.period_us)
+                + point.device_us;
+            let y = (point.host as i64 - reference.host as i64) as f64;
+            (x, y)
+        };
+
+        let (mut count, mut sum_x, mut sum_y) = (0usize, 0.0, 0.0);
+        let (mut min_x, mut max_x) = (f64::MAX, f64::MIN);
+
+        for (x, y) in self.iter().filter(|p| accept(p)).map(coordinates) {
+            count += 1;
+            sum_x += x;
+            sum_y += y;
+            min_x = min_x.min(x);
+            max_x = max_x.max(x);
+        }
+
+        let (mean_x, mean_y) = (sum_x / count as f64, sum_y / count as f64);
+        let (mut sxx, mut sxy) = (0.0, 0.0);
+
+        for (x, y) in self.iter().filter(|p| accept(p)).map(coordinates) {
+            sxx += (x - mean_x) * (x - mean_x);
+            sxy += (x - mean_x) * (y - mean_y);
+        }
+
+        let span = max_x - min_x;
+        let (rate, rate_error) = if span > 0.0 {
+            (sxy / sxx, 0.0)
+        } else {
+            (1.0, SYNC_DEFAULT_DRIFT_TOLERANCE_PPM / 1e6)
+        };
+        let intercept = mean_y - rate * mean_x;
+
+        for (residual, (x, y)) in residuals
+            .iter_mut()
+            .zip(self.iter().filter(|p| accept(p)).map(coordinates))
+        {
+            *residual = y - (intercept + rate * x);
+        }
+
+        let mapper = TimeMapper {
+            device: reference.device,
+            host: reference.host,
+            intercept,
+            rate,
+            period_us: self.period_us,
+            uncertainty: 0.0,
+            rate_error,
+            window: (min_x, max_x),
+        };
+
+        Some((mapper, count))
+    }
+}
+
+/// Set estimation error of drift from error inside fitting window.
+fn set_rate_error(mapper: &mut TimeMapper) {
+    let span = mapper.window.1 - mapper.window.0;
+
+    if span > 0.0 {
+        mapper.rate_error = 2.0 * mapper.uncertainty / span;
+    }
+}
+
+/// Clock offset and drift estimator based on ping exchanges.
+///
+/// Exchanges delayed by the network are less precise, so only exchanges
+/// with round trip not exceeding twice the minimal one in the window are
+/// used for fitting.
+#[derive(Debug, Clone)]
+pub struct SyncEstimator<const N: usize> {
+    /// Window of the latest exchanges.
+    window: Window<N>,
+}
+
+impl<const N: usize> SyncEstimator<N> {
+    /// Construct new `SyncEstimator` struct.
+    ///
+    /// # Returns
+    /// - New `SyncEstimator` struct.
+    pub const fn new() -> Self {
+        Self {
+            window: Window::new(),
+        }
+    }
+
+    /// Get number of exchanges in the window.
+    ///
+    /// # Returns
+    /// - Number of exchanges.
+    pub fn len(&self) -> usize {
+        self.window.len
+    }
+
+    /// Check whether there are no exchanges in the window.
+    ///
+    /// # Returns
+    /// - `true`  - if window is empty.
+    /// - `false` - otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.window.len == 0
+    }
+
+    /// Add ping exchange result. Exchange with timestamp unit different
+    /// from the previous ones restarts estimation.
+    ///
+    /// # Parameters
+    /// - `sample` - given ping exchange result.
+    pub fn add(&mut self, sample: &SyncSample) {
+        if sample.period_us != self.window.period_us {
+            self.window.reset(sample.period_us);
+        }
+
+        self.window
+            .push(sample.point(self.window.unwrap(sample.t2)));
+    }
+
+    /// Estimate clock mapping.
+    ///
+    /// # Returns
+    /// - Clock mapping - if window is not empty.
+    /// - `None` - otherwise.
+    pub fn mapper(&self) -> Option<TimeMapper> {
+        let best = self
+            .window
+            .iter()
+            .map(|point| point.uncertainty)
+            .min_by(f64::total_cmp)?;
+        let limit = 2.0 * best;
+        let accept = |point: &Point| point.uncertainty <= limit;
+
+        let mut residuals = [0f64; N];
+        let (mut mapper, _) = self.window.fit(accept, &mut residuals)?;
+
+        // Host time of each accepted exchange is within its uncertainty, so
+        // fitted line is within residual and uncertainty of each of them.
+        mapper.uncertainty = self
+            .window
+            .iter()
+            .filter(|point| accept(point))
+            .zip(residuals)
+            .map(|(point, residual)| residual.abs() + point.uncertainty)
+            .fold(0.0, f64::max);
+
+        set_rate_error(&mut mapper);
+        Some(mapper)
+    }
+}
+
+impl<const N: usize> Default for SyncEstimator<N> {
+    /// Construct new `SyncEstimator` struct.
+    ///
+    /// # Returns
+    /// - New `SyncEstimator` struct.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Clock offset and drift estimator based on frame receive times.
+///
+/// Frame arrives after its `timestamp` with transport latency, which is never
+/// below some minimum. Drift is fitted over all frames in the window, offset
+/// is taken from the lower envelope (frames with minimal latency) minus known
+/// minimal latency. Confidence bound is the median latency above the minimum.
+#[derive(Debug, Clone)]
+pub struct PassiveEstimator<const N: usize> {
+    /// Window of the latest frame arrivals.
+    window: Window<N>,
+    /// Minimal transport latency in microseconds.
+    latency_us: u64,
+}
+
+impl<const N: usize> PassiveEstimator<N> {
+    /// Construct new `PassiveEstimator` struct for millisecond timestamps.
+    ///
+    /// # Parameters
+    /// - `latency_us` - given minimal transport latency in microseconds
+    ///   (`0` if unknown).
+    ///
+    /// # Returns
+    /// - New `PassiveEstimator` struct.
+    pub const fn new(latency_us: u64) -> Self {
+        Self {
+            window: Window::new(),
+            latency_us,
+        }
+    }
+
+    /// Set timestamp unit of the next frames and drop frames in the window.
+    ///
+    /// # Parameters
+    /// - `period_us` - given duration of timestamp unit in microseconds (see
+    ///   `TimestampUnit::period_us`).
+    pub fn set_period_us(&mut self, period_us: f64) {
+        self.window.reset(period_us);
+    }
+
+    /// Get number of frames in the window.
+    ///
+    /// # Returns
+    /// - Number of frames.
+    pub fn len(&self) -> usize {
+        self.window.len
+    }
+
+    /// Check whether there are no frames in the window.
+    ///
+    /// # Returns
+    /// - `true`  - if window is empty.
+    /// - `false` - otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.window.len == 0
+    }
+
+    /// Add frame arrival.
+    ///
+    /// # Parameters
+    /// - `timestamp` - given frame header timestamp.
+    /// - `received_us` - given host receive time in microseconds.
+    pub fn add(&mut self, timestamp: u32, received_us: u64) {
+        self.add_unwrapped(self.window.unwrap(timestamp), received_us);
+    }
+
+    /// Add frame arrival with extended timestamp.
+    ///
+    /// # Parameters
+    /// - `timestamp` - given extended frame timestamp (e.g. from
+    ///   `timeline::Unwrapper`).
+    /// - `received_us` - given host receive time in microseconds.
+    pub fn add_unwrapped(&mut self, timestamp: u64, received_us: u64) {
+        self.window.push(Point {
+            device: timestamp,
+            device_us: 0.0,
+            host: received_us,
+            uncertainty: 0.0,
+        });
+    }
+
+    /// Estimate clock mapping.
+    ///
+    /// # Returns
+    /// - Clock mapping - if window holds at least two frames.
+    /// - `None` - otherwise.
+    pub fn mapper(&self) -> Option<TimeMapper> {
+        if self.window.len < 2 {
+            return None;
+        }
+
+        let mut residuals = [0f64; N];
+        let (mut mapper, count) = self.window.fit(|_| true, &mut residuals)?;
+        let residuals = &mut residuals[..count];
+        let minimum = residuals.iter().copied().fold(f64::MAX, f64::min);
+
+        mapper.intercept += minimum - self.latency_us as f64;
+        residuals.sort_unstable_by(f64::total_cmp);
+        mapper.uncertainty =
+            residuals[count / 2] - minimum + self.window.period_us;
+
+        set_rate_error(&mut mapper);
+        Some(mapper)
+    }
+}
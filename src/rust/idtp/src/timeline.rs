@@ -0,0 +1,357 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Extension of 32-bit header `sequence` and `timestamp` to 64-bit
+//! timelines.
+//!
+//! Millisecond `timestamp` wraps after ~49.7 days and `sequence` wraps at
+//! high frame rates, so both are extended to `u64` values which keep
+//! growing across wraps. Device reboot (sequence reset to a small value
+//! together with a time jump) starts a new epoch: extended values continue
+//! right after the last ones of previous epoch, so timelines stay monotonic.
+//! Time jumps are detected in timestamp unit of the device (see
+//! `TimestampUnit`).
+
+use crate::{IdtpHeader, TimestampUnit};
+
+/// Maximum sequence distance of late (reordered or duplicated) frames.
+pub const UNWRAP_REORDER_WINDOW: u32 = 1024;
+
+/// Maximum time gap in milliseconds between frames of the same epoch when
+/// sequence restarts (converted to timestamp unit of the device).
+pub const UNWRAP_MAX_GAP_MS: u32 = 60_000;
+
+/// Default number of devices tracked by `Unwrapper`.
+pub const UNWRAP_DEFAULT_DEVICES: usize = 16;
+
+/// Extend 32-bit value to 64-bit value nearest to the reference.
+///
+/// # Parameters
+/// - `reference` - given 64-bit value of nearby point of timeline.
+/// - `value` - given 32-bit value to extend.
+///
+/// # Returns
+/// - 64-bit value which ends with `value` and is at most 2^31 away from
+///   `reference` (never below zero).
+pub const fn unwrap_u32(reference: u64, value: u32) -> u64 {
+    let distance = value.wrapping_sub(reference as u32) as i32 as i64;
+
+    match reference.checked_add_signed(distance) {
+        Some(extended) => extended,
+        None => value as u64,
+    }
+}
+
+/// Frame position on extended timeline.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Unwrapped {
+    /// Number of device reboots observed before this frame.
+    pub epoch: u32,
+    /// Extended sequence number.
+    pub sequence: u64,
+    /// Extended timestamp.
+    pub timestamp: u64,
+    /// Whether this frame is the first one after device reboot.
+    pub reboot: bool,
+}
+
+/// Extended timeline of single device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timeline {
+    /// Maximum timestamp gap of the same epoch in timestamp units.
+    max_gap: u32,
+    /// Whether any frame was observed.
+    started: bool,
+    /// Current epoch.
+    epoch: u32,
+    /// Raw sequence number of the newest frame.
+    raw_sequence: u32,
+    /// Raw timestamp of the newest frame.
+    raw_timestamp: u32,
+    /// Extended sequence number of the newest frame.
+    sequence: u64,
+    /// Extended timestamp of the newest frame.
+    timestamp: u64,
+}
+
+impl Timeline {
+    /// Construct new `Timeline` struct for millisecond timestamps.
+    ///
+    /// # Returns
+    /// - New `Timeline` struct.
+    pub const fn new() -> Self {
+        Self {
+            max_gap: UNWRAP_MAX_GAP_MS,
+            started: false,
+            epoch: 0,
+            raw_sequence: 0,
+            raw_timestamp: 0,
+            sequence: 0,
+            timestamp: 0,
+        }
+    }
+
+    /// Construct new `Timeline` struct for timestamps in given unit. Time
+    /// jumps of device ticks with unknown rate and gaps which do not fit
+    /// into half of 32-bit timestamp range are detected only when timestamp
+    /// goes back.
+    ///
+    /// # Parameters
+    /// - `unit` - given timestamp unit.
+    /// - `tick_rate` - given device clock rate in Hz (used for `Ticks`
+    ///   only, `0` if unknown).
+    ///
+    /// # Returns
+    /// - New `Timeline` struct.
+    pub fn with_unit(unit: TimestampUnit, tick_rate: u32) -> Self {
+        let max_gap = unit.period_us(tick_rate).map_or(i32::MAX as u32, |p| {
+            let max_gap = f64::from(UNWRAP_MAX_GAP_MS) * 1000.0 / p;
+            max_gap.min(f64::from(i32::MAX)) as u32
+        });
+
+        Self {
+            max_gap,
+            ..Self::new()
+        }
+    }
+
+    /// Get current epoch.
+    ///
+    /// # Returns
+    /// - Number of device reboots observed.
+    pub fn epoch(&self) -> u32 {
+        self.epoch
+    }
+
+    /// Get extended sequence number of the newest frame.
+    ///
+    /// # Returns
+    /// - Extended sequence number.
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Get extended timestamp of the newest frame.
+    ///
+    /// # Returns
+    /// - Extended timestamp.
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    /// Extend frame sequence number and timestamp.
+    ///
+    /// Late frames (at most `UNWRAP_REORDER_WINDOW` behind the newest one)
+    /// are placed before the newest frame and do not move the timeline.
+    /// Sequence restart without time jump continues the same epoch.
+    ///
+    /// # Parameters
+    /// - `sequence` - given header sequence number.
+    /// - `timestamp` - given header timestamp.
+    ///
+    /// # Returns
+    /// - Frame position on extended timeline.
+    pub fn update(&mut self, sequence: u32, timestamp: u32) -> Unwrapped {
+        if !self.started {
+            *self = Self {
+                max_gap: self.max_gap,
+                started: true,
+                epoch: 0,
+                raw_sequence: sequence,
+                raw_timestamp: timestamp,
+                sequence: u64::from(sequence),
+                timestamp: u64::from(timestamp),
+            };
+            return self.position(false);
+        }
+
+        let sequence_step = sequence.wrapping_sub(self.raw_sequence) as i32;
+        let time_step = timestamp.wrapping_sub(self.raw_timestamp) as i32;
+        let window = UNWRAP_REORDER_WINDOW as i32;
+
+        let restart =
+            sequence_step < -window && sequence < UNWRAP_REORDER_WINDOW;
+        let time_jump = time_step < 0 || time_step > self.max_gap as i32;
+
+        if restart && time_jump {
+            self.epoch += 1;
+            self.advance(sequence, timestamp, 1, 1);
+            return self.position(true);
+        }
+
+        if restart {
+            let time_step = time_step as u64;
+            self.advance(sequence, timestamp, 1, time_step);
+            return self.position(false);
+        }
+
+        if sequence_step <= 0 {
+            // Late frame.
+            return Unwrapped {
+                epoch: self.epoch,
+                sequence: self
+                    .sequence
+                    .saturating_add_signed(sequence_step.into()),
+                timestamp: self
+                    .timestamp
+                    .saturating_add_signed(time_step.into()),
+                reboot: false,
+            };
+        }
+
+        let time_step = time_step.max(0) as u64;
+        self.advance(sequence, timestamp, sequence_step as u64, time_step);
+        self.position(false)
+    }
+
+    /// Move timeline to the newest frame.
+    fn advance(
+        &mut self,
+        sequence: u32,
+        timestamp: u32,
+        sequence_step: u64,
+        time_step: u64,
+    ) {
+        self.raw_sequence = sequence;
+        self.sequence += sequence_step;
+
+        // Timestamp of the newest frame may be older than the previous one.
+        if time_step > 0 {
+            self.raw_timestamp = timestamp;
+            self.timestamp += time_step;
+        }
+    }
+
+    /// Get position of the newest frame.
+    fn position(&self, reboot: bool) -> Unwrapped {
+        Unwrapped {
+            epoch: self.epoch,
+            sequence: self.sequence,
+            timestamp: self.timestamp,
+            reboot,
+        }
+    }
+}
+
+impl Default for Timeline {
+    /// Construct new default `Timeline` struct for millisecond timestamps.
+    ///
+    /// # Returns
+    /// - New default `Timeline` struct.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Extended timelines of multiple devices identified by `device_id`.
+///
+/// At most `N` devices are tracked, timeline of the least recently seen
+/// device is dropped when new device appears. Timeline of each device uses
+/// timestamp unit of its first frame.
+#[derive(Debug, Clone)]
+pub struct Unwrapper<const N: usize = UNWRAP_DEFAULT_DEVICES> {
+    /// Tracked devices: `device_id`, timeline and time of the last update.
+    devices: [(u16, Timeline, u64); N],
+    /// Number of tracked devices.
+    len: usize,
+    /// Number of updates.
+    updates: u64,
+    /// Device clock rate in Hz for `TimestampUnit::Ticks`.
+    tick_rate: u32,
+}
+
+impl<const N: usize> Unwrapper<N> {
+    /// Construct new `Unwrapper` struct.
+    ///
+    /// # Returns
+    /// - New `Unwrapper` struct.
+    pub const fn new() -> Self {
+        const { assert!(N > 0, "Unwrapper must track at least one device") };
+
+        Self {
+            devices: [(0, Timeline::new(), 0); N],
+            len: 0,
+            updates: 0,
+            tick_rate: 0,
+        }
+    }
+
+    /// Get timeline of device.
+    ///
+    /// # Parameters
+    /// - `device_id` - given device identifier.
+    ///
+    /// # Returns
+    /// - Device timeline - if device is tracked.
+    /// - `None` - otherwise.
+    pub fn timeline(&self, device_id: u16) -> Option<&Timeline> {
+        self.devices[..self.len]
+            .iter()
+            .find(|(id, ..)| *id == device_id)
+            .map(|(_, timeline, _)| timeline)
+    }
+
+    /// Set device clock rate used for timelines of devices with
+    /// `TimestampUnit::Ticks` timestamps which appear later.
+    ///
+    /// # Parameters
+    /// - `tick_rate` - given device clock rate in Hz (`0` if unknown).
+    pub fn set_tick_rate(&mut self, tick_rate: u32) {
+        self.tick_rate = tick_rate;
+    }
+
+    /// Forget all devices.
+    pub fn reset(&mut self) {
+        self.len = 0;
+    }
+
+    /// Extend frame sequence number and timestamp on its device timeline.
+    ///
+    /// # Parameters
+    /// - `header` - given frame header.
+    ///
+    /// # Returns
+    /// - Frame position on extended timeline of its device.
+    pub fn update(&mut self, header: &IdtpHeader) -> Unwrapped {
+        let device_id = header.device_id;
+        self.updates += 1;
+
+        let found = self.devices[..self.len]
+            .iter()
+            .position(|(id, ..)| *id == device_id);
+
+        let index = match found {
+            Some(index) => index,
+            None if self.len < N => {
+                self.len += 1;
+                self.len - 1
+            }
+            None => self
+                .devices
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, (.., used))| *used)
+                .map_or(0, |(index, _)| index),
+        };
+
+        if found.is_none() {
+            let timeline =
+                Timeline::with_unit(header.timestamp_unit(), self.tick_rate);
+            self.devices[index] = (device_id, timeline, 0);
+        }
+
+        let (_, timeline, used) = &mut self.devices[index];
+        *used = self.updates;
+        timeline.update(header.sequence, header.timestamp)
+    }
+}
+
+impl<const N: usize> Default for Unwrapper<N> {
+    /// Construct new `Unwrapper` struct.
+    ///
+    /// # Returns
+    /// - New `Unwrapper` struct.
+    fn default() -> Self {
+        Self::new()
+    }
+}
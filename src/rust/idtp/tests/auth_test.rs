@@ -0,0 +1,137 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP frame authentication tests.
+
+extern crate idtp;
+
+#[cfg(test)]
+mod tests {
+    use idtp::auth::*;
+    use idtp::*;
+
+    const PAYLOAD: &[u8] = b"regulatory log";
+
+    fn test_frame(mode: Mode) -> IdtpFrame {
+        let mut header = IdtpHeader::new();
+        header.mode = mode;
+        header.device_id = 7;
+        header.timestamp = 123_456;
+        header.sequence = 99;
+        header.payload_type = 0xC0;
+
+        let mut frame = IdtpFrame::new();
+        frame.set_header(&header);
+        frame.set_payload(PAYLOAD);
+        frame
+    }
+
+    fn pack(frame: &IdtpFrame) -> Vec<u8> {
+        let mut buffer = vec![0u8; frame.frame_size()];
+        frame.pack(&mut buffer).unwrap();
+        buffer
+    }
+
+    #[test]
+    fn test_tag_vector() {
+        let key = AuthKey::new(&[0x0b; 32]);
+        let mut frame = test_frame(Mode::Safety);
+        frame.sign(&key).unwrap();
+
+        // HMAC-SHA256 over header with zeroed checksum and crc and payload,
+        // truncated to 16 bytes.
+        let expected = [
+            0x78, 0x3d, 0x73, 0x6e, 0x37, 0x5d, 0x13, 0x3e, 0xbe, 0x5a, 0xba,
+            0xb9, 0xd9, 0x15, 0x52, 0x79,
+        ];
+        assert_eq!(&frame.payload()[PAYLOAD.len()..], expected);
+        assert_eq!(frame.header().flags(), IDTP_FLAG_AUTH);
+    }
+
+    #[test]
+    fn test_sign_parse_authenticated() {
+        let key = AuthKey::new(b"device 7 key");
+
+        for mode in [Mode::Normal, Mode::Safety] {
+            let mut frame = test_frame(mode);
+            frame.sign(&key).unwrap();
+            let bytes = pack(&frame);
+
+            // Payload stays readable and frame is valid for old receivers.
+            let old = IdtpFrame::parse(&bytes).unwrap();
+            assert!(old.verify().is_ok());
+            assert_eq!(&old.payload()[..PAYLOAD.len()], PAYLOAD);
+            assert_eq!(old.payload_size(), PAYLOAD.len() + IDTP_AUTH_TAG_SIZE);
+
+            let frame = IdtpFrame::parse_authenticated(&bytes, |header| {
+                (header.device_id == 7).then_some(&key)
+            })
+            .unwrap();
+            assert_eq!(frame.payload(), PAYLOAD);
+            assert_eq!(frame.header().flags(), 0);
+            assert_eq!({ frame.header().payload_size }, PAYLOAD.len() as u32);
+        }
+    }
+
+    #[test]
+    fn test_sign_too_large() {
+        let mut frame = IdtpFrame::new();
+        frame.set_payload(&[0u8; IDTP_PAYLOAD_MAX_SIZE - 15]);
+        let key = AuthKey::new(b"key");
+        assert_eq!(frame.sign(&key), Err(IdtpError::FrameTooLarge));
+    }
+
+    #[test]
+    fn test_parse_authenticated_rejects_spoofing() {
+        let key = AuthKey::new(b"device 7 key");
+        let keys =
+            |header: &IdtpHeader| (header.device_id == 7).then_some(&key);
+
+        let mut frame = test_frame(Mode::Normal);
+        frame.sign(&key).unwrap();
+        let bytes = pack(&frame);
+
+        for offset in 4..bytes.len() - IDTP_TRAILER_SIZE {
+            // Integrity fields are not authenticated, major version and
+            // `payload_size` are checked by `parse`.
+            if matches!(offset, 4 | 10..12 | 20..28) {
+                continue;
+            }
+
+            let mut tampered = bytes.clone();
+            tampered[offset] ^= 0x80;
+            assert_eq!(
+                IdtpFrame::parse_authenticated(&tampered, keys).unwrap_err(),
+                IdtpError::AuthenticationFailed,
+                "offset {offset}"
+            );
+        }
+
+        // Frame signed with another key.
+        let mut frame = test_frame(Mode::Normal);
+        frame.sign(&AuthKey::new(b"attacker key")).unwrap();
+        assert_eq!(
+            IdtpFrame::parse_authenticated(&pack(&frame), keys).unwrap_err(),
+            IdtpError::AuthenticationFailed
+        );
+
+        // Frame without authentication tag.
+        let mut frame = test_frame(Mode::Normal);
+        frame.update_integrity();
+        assert_eq!(
+            IdtpFrame::parse_authenticated(&pack(&frame), keys).unwrap_err(),
+            IdtpError::AuthenticationFailed
+        );
+
+        // Unknown device.
+        let mut frame = test_frame(Mode::Normal);
+        let mut header = frame.header();
+        header.device_id = 8;
+        frame.set_header(&header);
+        frame.sign(&key).unwrap();
+        assert_eq!(
+            IdtpFrame::parse_authenticated(&pack(&frame), keys).unwrap_err(),
+            IdtpError::AuthenticationFailed
+        );
+    }
+}
@@ -0,0 +1,367 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP command channel tests.
+
+extern crate idtp;
+
+#[cfg(test)]
+mod tests {
+    use idtp::client::*;
+    use idtp::command::*;
+    use idtp::payload::{PAYLOAD_TYPE_COMMAND_REQUEST, Payload};
+    use idtp::*;
+    use std::{collections::VecDeque, io, time::Duration};
+
+    const DEVICE_ID: u16 = 0x0007;
+
+    /// Device firmware which supports a subset of commands.
+    struct Firmware {
+        rate: u16,
+        accelerometer_range: u16,
+        mode: Mode,
+    }
+
+    impl CommandHandler for Firmware {
+        fn device_info(&mut self) -> Result<DeviceInfo, CommandError> {
+            Ok(DeviceInfo {
+                firmware_version: Version {
+                    major: 1,
+                    minor: 2,
+                    patch: 3,
+                },
+                hardware_revision: 4,
+                serial_number: 0xDEAD_BEEF,
+            })
+        }
+
+        fn output_data_rate(&mut self) -> Result<u16, CommandError> {
+            Ok(self.rate)
+        }
+
+        fn set_output_data_rate(
+            &mut self,
+            rate: u16,
+        ) -> Result<(), CommandError> {
+            if !(1..=1000).contains(&rate) {
+                return Err(CommandError::InvalidArgument);
+            }
+
+            self.rate = rate;
+            Ok(())
+        }
+
+        fn sensor_range(
+            &mut self,
+            sensor: Sensor,
+        ) -> Result<u16, CommandError> {
+            match sensor {
+                Sensor::Accelerometer => Ok(self.accelerometer_range),
+                _ => Err(CommandError::Unsupported),
+            }
+        }
+
+        fn set_sensor_range(
+            &mut self,
+            sensor: Sensor,
+            range: u16,
+        ) -> Result<(), CommandError> {
+            match sensor {
+                Sensor::Accelerometer => {
+                    self.accelerometer_range = range;
+                    Ok(())
+                }
+                _ => Err(CommandError::Unsupported),
+            }
+        }
+
+        fn mode(&mut self) -> Result<Mode, CommandError> {
+            Ok(self.mode)
+        }
+
+        fn set_mode(&mut self, mode: Mode) -> Result<(), CommandError> {
+            self.mode = mode;
+            Ok(())
+        }
+
+        fn timestamp(&mut self) -> Result<u32, CommandError> {
+            Ok(1234)
+        }
+    }
+
+    /// In-memory transport with device firmware on the other side.
+    struct Device {
+        firmware: Firmware,
+        /// Frames sent by device.
+        uplink: VecDeque<Vec<u8>>,
+        /// Number of requests to lose.
+        drop_requests: usize,
+        /// Number of received requests.
+        requests: usize,
+        /// Whether device sends IMU sample before each response.
+        streaming: bool,
+    }
+
+    impl Device {
+        fn new() -> Self {
+            Self {
+                firmware: Firmware {
+                    rate: 100,
+                    accelerometer_range: 4,
+                    mode: Mode::Normal,
+                },
+                uplink: VecDeque::new(),
+                drop_requests: 0,
+                requests: 0,
+                streaming: false,
+            }
+        }
+
+        fn push(&mut self, payload_type: u8, payload: &[u8]) {
+            let mut header = IdtpHeader::new();
+            header.device_id = DEVICE_ID;
+            header.payload_type = payload_type;
+
+            let mut frame = IdtpFrame::new();
+            frame.set_header(&header);
+            frame.set_payload(payload);
+            frame.update_integrity();
+
+            let mut bytes = vec![0u8; frame.frame_size()];
+            frame.pack(&mut bytes).unwrap();
+            self.uplink.push_back(bytes);
+        }
+    }
+
+    impl Transport for Device {
+        fn send(&mut self, bytes: &[u8]) -> io::Result<()> {
+            let frame = IdtpFrame::parse(bytes).unwrap();
+            assert!(frame.verify().is_ok());
+            assert_eq!(
+                frame.header().payload_type,
+                PAYLOAD_TYPE_COMMAND_REQUEST
+            );
+            self.requests += 1;
+
+            if self.drop_requests > 0 {
+                self.drop_requests -= 1;
+                return Ok(());
+            }
+
+            if self.streaming {
+                self.push(0xC0, &[0u8; 24]);
+            }
+
+            if let Some(mut response) = self.firmware.dispatch(frame.payload())
+            {
+                response.set_sent(1236);
+                let mut payload = [0u8; CommandResponse::SIZE];
+                response.encode(&mut payload).unwrap();
+                self.push(CommandResponse::PAYLOAD_TYPE, &payload);
+            }
+
+            Ok(())
+        }
+
+        fn receive(&mut self, _: Duration) -> io::Result<Option<Vec<u8>>> {
+            Ok(self.uplink.pop_front())
+        }
+    }
+
+    fn client(device: Device) -> Client<Device> {
+        let mut client = Client::new(device, DEVICE_ID);
+        client.set_timeout(Duration::from_millis(10));
+        client
+    }
+
+    #[test]
+    fn test_message_encoding() {
+        let request = CommandRequest {
+            request_id: 0x0102,
+            command: Command::SetSensorRange(Sensor::Gyroscope, 2000),
+        };
+        let mut buffer = [0u8; CommandRequest::SIZE];
+        assert_eq!(request.encode(&mut buffer), Ok(7));
+        assert_eq!(buffer, [0x01, 0x02, 0x05, 0x01, 0x07, 0xD0, 0x00]);
+        assert_eq!(CommandRequest::decode(&buffer), Ok(request));
+
+        // Unknown sensor.
+        buffer[3] = 0x09;
+        assert_eq!(
+            CommandRequest::decode(&buffer),
+            Err(IdtpError::InvalidPayload)
+        );
+
+        let response = CommandResponse {
+            request_id: 9,
+            command: COMMAND_GET_OUTPUT_DATA_RATE,
+            result: Ok(Reply::OutputDataRate(400)),
+        };
+        let mut buffer = [0u8; CommandResponse::SIZE];
+        assert_eq!(response.encode(&mut buffer), Ok(12));
+        assert_eq!(buffer[..6], [0x00, 0x09, 0x02, 0x00, 0x01, 0x90]);
+        assert_eq!(CommandResponse::decode(&buffer), Ok(response));
+        assert_eq!(
+            CommandResponse::decode(&buffer[..11]),
+            Err(IdtpError::InvalidPayload)
+        );
+
+        let nack = CommandResponse {
+            request_id: 9,
+            command: COMMAND_SET_MODE,
+            result: Err(CommandError::Busy),
+        };
+        nack.encode(&mut buffer).unwrap();
+        assert_eq!(buffer[3], 0x04);
+        assert_eq!(CommandResponse::decode(&buffer), Ok(nack));
+        assert_eq!(
+            nack.encode(&mut buffer[..4]),
+            Err(IdtpError::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    fn test_dispatch_rejects_malformed_requests() {
+        let mut firmware = Device::new().firmware;
+
+        // Unknown command is answered with NACK.
+        let response = firmware.dispatch(&[0, 5, 0x7F, 0, 0, 0, 0]).unwrap();
+        assert_eq!(response.request_id, 5);
+        assert_eq!(response.command, 0x7F);
+        assert_eq!(response.result, Err(CommandError::UnknownCommand));
+
+        // Invalid operating mode.
+        let response = firmware.dispatch(&[0, 6, 0x07, 0xAA, 0, 0, 0]).unwrap();
+        assert_eq!(response.result, Err(CommandError::InvalidArgument));
+
+        // Request without complete header can not be answered.
+        assert_eq!(firmware.dispatch(&[0, 7, 0x01]), None);
+    }
+
+    #[test]
+    fn test_client_commands() {
+        let mut client = client(Device::new());
+
+        let info = client.device_info().unwrap();
+        assert_eq!(info.firmware_version.to_string(), "1.2.3");
+        assert_eq!(info.hardware_revision, 4);
+        assert_eq!(info.serial_number, 0xDEAD_BEEF);
+
+        assert_eq!(client.output_data_rate().unwrap(), 100);
+        client.set_output_data_rate(400).unwrap();
+        assert_eq!(client.output_data_rate().unwrap(), 400);
+
+        client.set_sensor_range(Sensor::Accelerometer, 16).unwrap();
+        assert_eq!(client.sensor_range(Sensor::Accelerometer).unwrap(), 16);
+
+        client.set_mode(Mode::Safety).unwrap();
+        assert_eq!(client.mode().unwrap(), Mode::Safety);
+        assert_eq!(client.transport().firmware.mode, Mode::Safety);
+    }
+
+    #[test]
+    fn test_client_nack() {
+        let mut client = client(Device::new());
+
+        assert!(matches!(
+            client.set_output_data_rate(5000),
+            Err(ClientError::Nack(CommandError::InvalidArgument))
+        ));
+        assert!(matches!(
+            client.sensor_range(Sensor::Magnetometer),
+            Err(ClientError::Nack(CommandError::Unsupported))
+        ));
+
+        // Handler is not implemented by firmware.
+        assert!(matches!(
+            client.set_time(1000),
+            Err(ClientError::Nack(CommandError::Unsupported))
+        ));
+        assert_eq!(client.transport().requests, 3);
+    }
+
+    #[test]
+    fn test_client_retransmits_lost_requests() {
+        let mut device = Device::new();
+        device.drop_requests = 2;
+        let mut client = client(device);
+
+        assert_eq!(client.output_data_rate().unwrap(), 100);
+        assert_eq!(client.transport().requests, 3);
+
+        client.transport().drop_requests = 3;
+        assert!(matches!(client.mode(), Err(ClientError::Timeout)));
+
+        client.set_retries(0);
+        client.transport().drop_requests = 1;
+        assert!(matches!(client.mode(), Err(ClientError::Timeout)));
+        assert_eq!(client.mode().unwrap(), Mode::Normal);
+    }
+
+    #[test]
+    fn test_client_keeps_other_frames() {
+        let mut device = Device::new();
+        device.streaming = true;
+        let mut client = client(device);
+
+        // Late response to previous request is dropped.
+        let stale = CommandResponse {
+            request_id: 0xFFFF,
+            command: COMMAND_GET_MODE,
+            result: Ok(Reply::Mode(Mode::Secure)),
+        };
+        let mut payload = [0u8; CommandResponse::SIZE];
+        stale.encode(&mut payload).unwrap();
+        client
+            .transport()
+            .push(CommandResponse::PAYLOAD_TYPE, &payload);
+
+        assert_eq!(client.mode().unwrap(), Mode::Normal);
+        assert_eq!(client.output_data_rate().unwrap(), 100);
+
+        for _ in 0..2 {
+            let frame = IdtpFrame::parse(&client.take_frame().unwrap());
+            assert_eq!(frame.unwrap().header().payload_type, 0xC0);
+        }
+        assert_eq!(client.take_frame(), None);
+        assert_eq!(client.dropped_frames(), 0);
+
+        // Only the newest frames are kept when queue is full.
+        client.set_queue_size(1);
+        assert_eq!(client.mode().unwrap(), Mode::Normal);
+        assert_eq!(client.output_data_rate().unwrap(), 100);
+        assert!(client.take_frame().is_some());
+        assert_eq!(client.take_frame(), None);
+        assert_eq!(client.dropped_frames(), 1);
+
+        client.set_queue_size(0);
+        assert_eq!(client.mode().unwrap(), Mode::Normal);
+        assert_eq!(client.take_frame(), None);
+        assert_eq!(client.dropped_frames(), 2);
+    }
+
+    #[test]
+    fn test_client_sync() {
+        let mut device = Device::new();
+        device.drop_requests = 1;
+        let mut client = client(device);
+
+        let sample = client.sync().unwrap();
+        assert_eq!((sample.t2, sample.t3), (1234, 1236));
+        assert!(sample.t1 <= sample.t4);
+        assert_eq!(client.transport().requests, 2);
+
+        // Dispatcher fills both device times with reception time.
+        let response = Device::new().firmware.handle(&CommandRequest {
+            request_id: 1,
+            command: Command::Ping,
+        });
+        assert_eq!(
+            response.result,
+            Ok(Reply::Pong {
+                received: 1234,
+                sent: 1234
+            })
+        );
+    }
+}
@@ -0,0 +1,161 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Compact IDTP frame tests.
+
+extern crate idtp;
+
+#[cfg(test)]
+mod tests {
+    use idtp::compact::*;
+    use idtp::*;
+
+    fn full_frame(timestamp: u32, sequence: u32, payload: &[u8]) -> IdtpFrame {
+        let mut header = IdtpHeader::new();
+        header.mode = Mode::Safety;
+        header.device_id = 0x0102;
+        header.timestamp = timestamp;
+        header.sequence = sequence;
+        header.payload_type = 0xC0;
+        header.set_flags(IDTP_FLAG_TIMESTAMP_EXT);
+        header.set_key_id(3);
+
+        let mut frame = IdtpFrame::new();
+        frame.set_header(&header);
+        frame.set_payload(payload);
+        frame.update_integrity();
+        frame
+    }
+
+    fn pack(frame: &IdtpFrame) -> Vec<u8> {
+        let mut buffer = vec![0u8; frame.frame_size()];
+        frame.pack(&mut buffer).unwrap();
+        buffer
+    }
+
+    #[test]
+    fn test_crc16() {
+        // CRC-16/CCITT-FALSE check value.
+        assert_eq!(crc16(b"123456789"), 0x29B1);
+        assert_eq!(crc16(&[]), 0xFFFF);
+    }
+
+    #[test]
+    fn test_compact_round_trip() {
+        let first = full_frame(1_000_000, 0x0001_FFF0, &[1u8; 24]);
+        let mut sender = CompactContext::new(&first.header());
+        let mut receiver = CompactContext::new(&first.header());
+
+        // Sequence crosses 16-bit boundary.
+        for i in 1..40u32 {
+            let frame =
+                full_frame(1_000_000 + i * 5, 0x0001_FFF0 + i, &[i as u8; 24]);
+            let compact = sender.compress(&frame).unwrap();
+
+            let mut buffer = [0u8; COMPACT_FRAME_MAX_SIZE];
+            let size = compact.pack(&mut buffer).unwrap();
+            // 60% smaller than full frame with 24 bytes payload.
+            assert_eq!(size, 36);
+            assert_eq!(buffer[0], COMPACT_SYNC);
+
+            let parsed = CompactFrame::parse(&buffer[..size]).unwrap();
+            assert!(parsed.verify().is_ok());
+            assert_eq!(parsed.header().timestamp_delta, (i * 5) as u16);
+
+            let expanded = receiver.expand(&parsed);
+            assert!(expanded.verify().is_ok());
+            assert_eq!(pack(&expanded), pack(&frame));
+        }
+
+        assert_eq!(receiver, sender);
+    }
+
+    #[test]
+    fn test_compact_not_representable() {
+        let first = full_frame(1000, 10, &[]);
+        let mut context = CompactContext::new(&first.header());
+
+        // Timestamp delta overflows.
+        let frame = full_frame(1000 + 65_536, 11, &[]);
+        assert!(context.compress(&frame).is_none());
+        // Timestamp before base.
+        let frame = full_frame(999, 11, &[]);
+        assert!(context.compress(&frame).is_none());
+        // Payload does not fit.
+        let frame = full_frame(1000, 11, &[0u8; 256]);
+        assert!(context.compress(&frame).is_none());
+        // Sequence jump is ambiguous.
+        let frame = full_frame(1000, 10 + 40_000, &[]);
+        assert!(context.compress(&frame).is_none());
+        assert_eq!(context.sequence, 10);
+
+        // Key identifier differs from context.
+        let mut frame = full_frame(1000, 11, &[]);
+        let mut header = frame.header();
+        header.set_key_id(4);
+        frame.set_header(&header);
+        assert!(context.compress(&frame).is_none());
+
+        // Unknown mode has no compact encoding.
+        header.mode = Mode::Unknown;
+        frame.set_header(&header);
+        let mut context = CompactContext::new(&header);
+        assert!(context.compress(&frame).is_none());
+
+        // Full frame establishes new context.
+        header.mode = Mode::Safety;
+        frame.set_header(&header);
+        let mut context = CompactContext::new(&header);
+        assert!(context.compress(&frame).is_some());
+    }
+
+    #[test]
+    fn test_compact_header_modes() {
+        for (mode, bits) in [
+            (Mode::Normal, 0x00),
+            (Mode::Safety, 0x40),
+            (Mode::Secure, 0x80),
+            (Mode::Fec, 0xC0),
+        ] {
+            let header = CompactHeader {
+                mode,
+                ..CompactHeader::default()
+            };
+            let bytes = header.as_bytes_be();
+            assert_eq!(bytes[1], bits);
+            assert_eq!(CompactHeader::from_bytes_be(&bytes).mode, mode);
+        }
+    }
+
+    #[test]
+    fn test_compact_parse_errors() {
+        let first = full_frame(0, 0, &[7u8; 4]);
+        let mut context = CompactContext::new(&first.header());
+        let compact = context.compress(&first).unwrap();
+
+        let mut buffer = [0u8; COMPACT_FRAME_MAX_SIZE];
+        let size = compact.pack(&mut buffer).unwrap();
+        assert_eq!(
+            compact.pack(&mut buffer[..size - 1]),
+            Err(IdtpError::BufferTooSmall)
+        );
+
+        assert_eq!(
+            CompactFrame::parse(&buffer[..11]).unwrap_err(),
+            IdtpError::FrameTooShort
+        );
+        assert_eq!(
+            CompactFrame::parse(&buffer[..size - 1]).unwrap_err(),
+            IdtpError::PayloadSizeMismatch
+        );
+        assert_eq!(
+            CompactFrame::parse(&pack(&first)).unwrap_err(),
+            IdtpError::InvalidPreamble
+        );
+
+        // Corrupted header byte is detected by CRC-16.
+        buffer[3] ^= 0x10;
+        let parsed = CompactFrame::parse(&buffer[..size]).unwrap();
+        assert!(!parsed.verify().is_ok());
+    }
+}
@@ -0,0 +1,107 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Standard IDTP control payloads tests.
+
+extern crate idtp;
+
+#[cfg(test)]
+mod tests {
+    use idtp::control::*;
+    use idtp::payload::Payload;
+    use idtp::*;
+
+    fn device() -> Capabilities {
+        Capabilities {
+            version: Version::new(1, 3, 1),
+            modes: Capabilities::mode_bit(Mode::Normal)
+                | Capabilities::mode_bit(Mode::Safety),
+            flags: 0,
+            max_frame_size: 256,
+        }
+    }
+
+    #[test]
+    fn test_local_capabilities() {
+        let local = Capabilities::local();
+
+        assert_eq!(local.version, IDTP_VERSION);
+        assert!(local.supports_mode(Mode::Normal));
+        assert!(local.supports_mode(Mode::Safety));
+        assert!(local.supports_mode(Mode::Secure));
+        assert!(local.supports_mode(Mode::Fec));
+        assert!(!local.supports_mode(Mode::Unknown));
+        assert_eq!(
+            local.flags,
+            IDTP_FLAG_AUTH
+                | IDTP_FLAG_EXTENSIONS
+                | IDTP_FLAG_TIMESTAMP_EXT
+                | IDTP_FLAG_RELIABLE
+        );
+        assert_eq!(usize::from(local.max_frame_size), IDTP_PACKET_MAX_SIZE);
+        assert_eq!(Capabilities::mode_bit(Mode::Secure), 0x04);
+        assert_eq!(Capabilities::mode_bit(Mode::Unknown), 0);
+    }
+
+    #[test]
+    fn test_hello_encoding() {
+        let hello = Hello(Capabilities::local());
+        let mut buffer = [0u8; Hello::SIZE];
+
+        assert_eq!(hello.encode(&mut buffer), Ok(7));
+        assert_eq!(buffer, [1, 0, 0, 0x0F, 0x0F, 0x04, 0x00]);
+        assert_eq!(Hello::decode(&buffer), Ok(hello));
+        assert_eq!(
+            HelloReply::decode(&buffer),
+            Ok(HelloReply(Capabilities::local()))
+        );
+
+        assert_eq!(Hello::decode(&buffer[..6]), Err(IdtpError::InvalidPayload));
+        assert_eq!(
+            hello.encode(&mut buffer[..6]),
+            Err(IdtpError::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    fn test_hello_exchange() {
+        let host = Capabilities::local();
+        let mut buffer = [0u8; IDTP_PAYLOAD_MAX_SIZE];
+
+        // Host -> device.
+        let size = Hello(host).encode(&mut buffer).unwrap();
+        let Hello(received) = Hello::decode(&buffer[..size]).unwrap();
+        let device_agreed = device().negotiate(&received).unwrap();
+
+        // Device -> host.
+        let size = HelloReply(device()).encode(&mut buffer).unwrap();
+        let HelloReply(received) = HelloReply::decode(&buffer[..size]).unwrap();
+        let host_agreed = host.negotiate(&received).unwrap();
+
+        assert_eq!(host_agreed, device_agreed);
+        assert_eq!(host_agreed.version, IDTP_VERSION);
+        assert!(host_agreed.supports_mode(Mode::Safety));
+        assert!(!host_agreed.supports_mode(Mode::Secure));
+        assert_eq!(host_agreed.flags, 0);
+        assert_eq!(host_agreed.max_frame_size, 256);
+    }
+
+    #[test]
+    fn test_negotiate_failures() {
+        let host = Capabilities::local();
+
+        let mut peer = device();
+        peer.version = Version::new(2, 0, 0);
+        assert_eq!(host.negotiate(&peer), Err(IdtpError::InvalidVersion));
+
+        let mut peer = device();
+        peer.max_frame_size = (IDTP_PACKET_MIN_SIZE - 1) as u16;
+        assert_eq!(host.negotiate(&peer), Err(IdtpError::InvalidPayload));
+
+        // Normal mode is always available.
+        let mut peer = device();
+        peer.modes = 0;
+        let agreed = host.negotiate(&peer).unwrap();
+        assert_eq!(agreed.modes, Capabilities::mode_bit(Mode::Normal));
+    }
+}
@@ -0,0 +1,109 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP streaming deframer tests.
+
+extern crate idtp;
+
+#[cfg(test)]
+mod tests {
+    use idtp::*;
+
+    fn test_frame(sequence: u32, payload: &[u8]) -> Vec<u8> {
+        let mut header = IdtpHeader::new();
+        header.sequence = sequence;
+
+        let mut frame = IdtpFrame::new();
+        frame.set_header(&header);
+        frame.set_payload(payload);
+        frame.update_integrity();
+
+        let mut buffer = vec![0u8; frame.frame_size()];
+        frame.pack(&mut buffer).unwrap();
+        buffer
+    }
+
+    #[test]
+    fn test_deframer_byte_by_byte() {
+        let frame = test_frame(1, &[1, 2, 3, 4]);
+        let mut deframer = Deframer::new();
+        let mut frames = Vec::new();
+
+        for byte in &frame {
+            deframer.push(&[*byte]);
+
+            while let Some(raw) = deframer.next_frame() {
+                frames.push(raw.to_vec());
+            }
+        }
+
+        assert_eq!(frames, vec![frame]);
+        assert_eq!(deframer.resyncs(), 0);
+        assert_eq!(deframer.buffered(), 0);
+    }
+
+    #[test]
+    fn test_deframer_resync_after_garbage() {
+        let frames = [
+            test_frame(1, &[0xAA; 10]),
+            test_frame(2, &[]),
+            test_frame(3, b"IDTP inside payload"),
+        ];
+
+        let mut stream = b"IDnoiseIDTP".to_vec();
+        stream.extend_from_slice(&frames[0]);
+        stream.extend_from_slice(&frames[1]);
+        stream.extend_from_slice(b"garbage");
+        stream.extend_from_slice(&frames[2]);
+
+        let mut deframer = Deframer::new();
+        let mut received = Vec::new();
+
+        for chunk in stream.chunks(7) {
+            deframer.feed(chunk, |raw| received.push(raw.to_vec()));
+        }
+
+        assert_eq!(received, frames.to_vec());
+        assert_eq!(deframer.resyncs(), 2);
+        assert_eq!(deframer.discarded(), 11 + 7);
+    }
+
+    #[test]
+    fn test_deframer_skips_invalid_trailer_and_size() {
+        let valid = test_frame(5, &[9; 3]);
+        let mut broken_trailer = test_frame(4, &[7; 3]);
+        let size = broken_trailer.len();
+        broken_trailer[size - 1] = 0;
+
+        let mut huge_size = test_frame(3, &[]);
+        huge_size[24..28].copy_from_slice(&u32::MAX.to_be_bytes());
+
+        let mut stream = huge_size;
+        stream.extend_from_slice(&broken_trailer);
+        stream.extend_from_slice(&valid);
+
+        let mut deframer = Deframer::new();
+        let mut received = Vec::new();
+        deframer.feed(&stream, |raw| received.push(raw.to_vec()));
+
+        assert_eq!(received, vec![valid]);
+        assert_eq!(deframer.resyncs(), 1);
+    }
+
+    #[test]
+    fn test_deframer_skips_incompatible_version() {
+        let valid = test_frame(2, &[1; 4]);
+        let mut future = test_frame(1, &[2; 4]);
+        future[4] = 2;
+
+        let mut stream = future.clone();
+        stream.extend_from_slice(&valid);
+
+        let mut deframer = Deframer::new();
+        let mut received = Vec::new();
+        deframer.feed(&stream, |raw| received.push(raw.to_vec()));
+
+        assert_eq!(received, vec![valid]);
+        assert_eq!(deframer.discarded(), future.len() as u64);
+    }
+}
@@ -0,0 +1,212 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Delta compression of standard payload streams tests.
+
+extern crate idtp;
+
+#[cfg(test)]
+mod tests {
+    use idtp::delta::*;
+    use idtp::payload::*;
+    use idtp::*;
+
+    /// Standard gravity (m/s^2).
+    const G: f32 = 9.806_65;
+
+    fn sample(i: usize) -> StandardPayload {
+        let t = i as f32 * 0.01;
+
+        StandardPayload::Imu6(Imu6 {
+            acc: Vector3::new(0.02 * t.sin(), -0.01 * t, G + 0.003 * t.cos()),
+            gyr: Vector3::new(0.1 * t.cos(), 0.002, -0.05 * t.sin()),
+        })
+    }
+
+    fn samples(start: usize, count: usize) -> Vec<StandardPayload> {
+        (start..start + count).map(sample).collect()
+    }
+
+    fn decode(
+        decoder: &mut DeltaDecoder,
+        bytes: &[u8],
+    ) -> Result<Option<Vec<StandardPayload>>, IdtpError> {
+        let mut decoded = Vec::new();
+        let count = decoder.decode(bytes, |sample| decoded.push(sample))?;
+        Ok(count.map(|count| {
+            assert_eq!(count, decoded.len());
+            decoded
+        }))
+    }
+
+    #[test]
+    fn test_lossless_round_trip() {
+        let config = DeltaConfig::lossless(PAYLOAD_TYPE_IMU6);
+        let mut encoder = DeltaEncoder::new(config).unwrap();
+        let mut decoder = DeltaDecoder::new();
+        let mut buffer = [0u8; IDTP_PAYLOAD_MAX_SIZE];
+
+        for frame in 0..(2 * DELTA_KEYFRAME_INTERVAL as usize) {
+            let batch = samples(frame * 10, 10);
+            let size = encoder.encode(&batch, &mut buffer).unwrap();
+            let keyframe = frame % DELTA_KEYFRAME_INTERVAL as usize == 0;
+
+            assert_eq!(buffer[0], PAYLOAD_TYPE_IMU6);
+            assert_eq!(buffer[1] & DELTA_FLAG_KEYFRAME != 0, keyframe);
+            assert!(size < batch.len() * Imu6::SIZE);
+
+            let decoded = decode(&mut decoder, &buffer[..size]).unwrap();
+            assert_eq!(decoded, Some(batch));
+        }
+    }
+
+    #[test]
+    fn test_quantized_round_trip() {
+        // 1 mg and 0.01 dps.
+        let resolutions = [0.001 * G, 0.01f32.to_radians(), 0.0];
+        let config = DeltaConfig {
+            payload_type: PAYLOAD_TYPE_IMU6,
+            resolutions,
+            keyframe_interval: 0,
+        };
+
+        let mut encoder = DeltaEncoder::new(config).unwrap();
+        let mut decoder = DeltaDecoder::new();
+        let mut buffer = [0u8; IDTP_PAYLOAD_MAX_SIZE];
+        let mut total = 0;
+
+        for frame in 0..20 {
+            let batch = samples(frame * 40, 40);
+            let size = encoder.encode(&batch, &mut buffer).unwrap();
+            assert_eq!(buffer[1] & DELTA_FLAG_KEYFRAME != 0, frame == 0);
+            total += size;
+
+            let decoded = decode(&mut decoder, &buffer[..size]).unwrap();
+
+            for (original, decoded) in batch.iter().zip(decoded.unwrap()) {
+                let mut values = Vec::new();
+                original.for_each_field(|_, value| values.push(value));
+                decoded.for_each_field(|_, value| values.push(value));
+
+                // Error is bounded by half of resolution.
+                for i in 0..6 {
+                    let bound = resolutions[i / 3] / 2.0 + 1e-6;
+                    assert!((values[i] - values[i + 6]).abs() <= bound);
+                }
+            }
+        }
+
+        // At least 3 times smaller than raw samples.
+        assert!(total * 3 < 20 * 40 * Imu6::SIZE);
+    }
+
+    #[test]
+    fn test_loss_recovery() {
+        let config = DeltaConfig {
+            keyframe_interval: 4,
+            ..DeltaConfig::lossless(PAYLOAD_TYPE_IMU6)
+        };
+        let mut encoder = DeltaEncoder::new(config).unwrap();
+        let mut frames = Vec::new();
+
+        for frame in 0..10 {
+            let mut buffer = [0u8; IDTP_PAYLOAD_MAX_SIZE];
+            let size = encoder.encode(&samples(frame, 1), &mut buffer).unwrap();
+            frames.push(buffer[..size].to_vec());
+        }
+
+        // Receiver joins mid-stream and waits for keyframe.
+        let mut decoder = DeltaDecoder::new();
+        assert_eq!(decode(&mut decoder, &frames[2]), Ok(None));
+        assert!(!decoder.is_synced());
+        assert_eq!(decode(&mut decoder, &frames[4]), Ok(Some(samples(4, 1))));
+        assert!(decoder.is_synced());
+
+        // Frame 6 is lost: frame 7 is skipped until keyframe 8.
+        assert_eq!(decode(&mut decoder, &frames[5]), Ok(Some(samples(5, 1))));
+        assert_eq!(decode(&mut decoder, &frames[7]), Ok(None));
+        assert!(!decoder.is_synced());
+        assert_eq!(decode(&mut decoder, &frames[8]), Ok(Some(samples(8, 1))));
+        assert_eq!(decode(&mut decoder, &frames[9]), Ok(Some(samples(9, 1))));
+
+        // Duplicated frame breaks the chain as well.
+        assert_eq!(decode(&mut decoder, &frames[9]), Ok(None));
+
+        // Keyframe on request (e.g. after receiver reported loss).
+        let mut buffer = [0u8; IDTP_PAYLOAD_MAX_SIZE];
+        encoder.request_keyframe();
+        let size = encoder.encode(&samples(10, 1), &mut buffer).unwrap();
+        assert_eq!(buffer[1], DELTA_FLAG_KEYFRAME);
+        assert_eq!(
+            decode(&mut decoder, &buffer[..size]),
+            Ok(Some(samples(10, 1)))
+        );
+    }
+
+    #[test]
+    fn test_delta_errors() {
+        assert!(DeltaEncoder::new(DeltaConfig::lossless(0x01)).is_err());
+        assert!(
+            DeltaEncoder::new(DeltaConfig {
+                resolutions: [-1.0, 0.0, 0.0],
+                ..DeltaConfig::lossless(PAYLOAD_TYPE_IMU6)
+            })
+            .is_err()
+        );
+
+        let config = DeltaConfig::lossless(PAYLOAD_TYPE_IMU6);
+        let mut encoder = DeltaEncoder::new(config).unwrap();
+        let mut buffer = [0u8; IDTP_PAYLOAD_MAX_SIZE];
+
+        assert_eq!(
+            encoder.encode(&[], &mut buffer),
+            Err(IdtpError::InvalidPayload)
+        );
+        let quaternion = StandardPayload::Quaternion(Quaternion::default());
+        assert_eq!(
+            encoder.encode(&[quaternion], &mut buffer),
+            Err(IdtpError::InvalidPayload)
+        );
+        assert_eq!(
+            encoder.encode(&samples(0, 2), &mut buffer[..20]),
+            Err(IdtpError::BufferTooSmall)
+        );
+
+        // Failed encoding keeps encoder state: the first frame is keyframe.
+        let size = encoder.encode(&samples(0, 2), &mut buffer).unwrap();
+        assert_eq!(buffer[1], DELTA_FLAG_KEYFRAME);
+        let frame = buffer[..size].to_vec();
+
+        let mut decoder = DeltaDecoder::new();
+        let malformed: [&[u8]; 5] = [
+            // Truncated header.
+            &frame[..4],
+            // Unknown payload type.
+            &[0x01, 1, 0, 0, 1],
+            // No samples.
+            &[PAYLOAD_TYPE_IMU6, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+            // Truncated values.
+            &frame[..size - 1],
+            // Trailing bytes.
+            &[&frame[..], &[0]].concat(),
+        ];
+
+        for bytes in malformed {
+            assert_eq!(
+                decoder.decode(bytes, |_| panic!("sample of malformed frame")),
+                Err(IdtpError::InvalidPayload)
+            );
+        }
+
+        // Varint exceeding u32 range.
+        let mut bytes = frame[..DELTA_HEADER_SIZE + 8].to_vec();
+        bytes.extend([0xFF, 0xFF, 0xFF, 0xFF, 0x7F]);
+        assert_eq!(
+            decoder.decode(&bytes, |_| ()),
+            Err(IdtpError::InvalidPayload)
+        );
+
+        assert!(!decoder.is_synced());
+        assert_eq!(decode(&mut decoder, &frame), Ok(Some(samples(0, 2))));
+    }
+}
@@ -0,0 +1,172 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP extension block tests.
+
+extern crate idtp;
+
+#[cfg(test)]
+mod tests {
+    use idtp::extension::*;
+    use idtp::*;
+
+    const PAYLOAD: &[u8] = b"imu sample";
+
+    fn test_block(buffer: &mut [u8]) -> usize {
+        let mut builder = ExtensionBuilder::new(buffer);
+        builder
+            .push(EXTENSION_TYPE_TEMPERATURE, &2150i16.to_be_bytes())
+            .unwrap();
+        builder.push(0x80, &[]).unwrap();
+        builder
+            .push(EXTENSION_TYPE_FRAGMENT, &[0, 1, 0, 3])
+            .unwrap();
+        builder.finish().unwrap()
+    }
+
+    #[test]
+    fn test_extension_builder() {
+        let mut buffer = [0u8; 32];
+        let size = test_block(&mut buffer);
+        assert_eq!(size, 14);
+        assert_eq!(
+            buffer[..size],
+            [0, 14, 0x02, 2, 0x08, 0x66, 0x80, 0, 0x03, 4, 0, 1, 0, 3]
+        );
+
+        let block = ExtensionBlock::parse(&buffer[..size]).unwrap();
+        assert_eq!(block.size(), 14);
+        let types: Vec<u8> = block.iter().map(|tlv| tlv.tlv_type).collect();
+        assert_eq!(types, [0x02, 0x80, 0x03]);
+        assert_eq!(block.get(0x80), Some(&[][..]));
+        assert_eq!(block.get(EXTENSION_TYPE_FRAGMENT), Some(&[0, 1, 0, 3][..]));
+        assert_eq!(block.get(EXTENSION_TYPE_KEY_ID), None);
+
+        // Empty block consists of size field only.
+        let size = ExtensionBuilder::new(&mut buffer).finish().unwrap();
+        let block = ExtensionBlock::parse(&buffer[..size]).unwrap();
+        assert!(block.is_empty());
+        assert_eq!(block.size(), 2);
+    }
+
+    #[test]
+    fn test_extension_builder_errors() {
+        let mut buffer = [0u8; 8];
+        let mut builder = ExtensionBuilder::new(&mut buffer);
+        assert_eq!(
+            builder.push(0x01, &[0u8; 256]),
+            Err(IdtpError::InvalidPayload)
+        );
+        builder.push(0x01, &[1, 2]).unwrap();
+        assert_eq!(builder.push(0x02, &[1]), Err(IdtpError::BufferTooSmall));
+        assert_eq!(builder.finish(), Ok(6));
+
+        assert_eq!(
+            ExtensionBuilder::new(&mut [0u8; 1]).finish(),
+            Err(IdtpError::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    fn test_extension_block_malformed() {
+        // Block size exceeds bytes.
+        assert_eq!(
+            ExtensionBlock::parse(&[0, 6, 0x01, 1, 0]),
+            Err(IdtpError::InvalidPayload)
+        );
+        // Block size smaller than size field.
+        assert_eq!(
+            ExtensionBlock::parse(&[0, 1, 0]),
+            Err(IdtpError::InvalidPayload)
+        );
+        // TLV value crosses block end.
+        assert_eq!(
+            ExtensionBlock::parse(&[0, 5, 0x01, 2, 0, 0]),
+            Err(IdtpError::InvalidPayload)
+        );
+        // Truncated TLV header.
+        assert_eq!(
+            ExtensionBlock::parse(&[0, 3, 0x01]),
+            Err(IdtpError::InvalidPayload)
+        );
+        assert_eq!(ExtensionBlock::parse(&[0]), Err(IdtpError::InvalidPayload));
+    }
+
+    #[test]
+    fn test_frame_extensions() {
+        let mut header = IdtpHeader::new();
+        header.mode = Mode::Safety;
+        header.set_timestamp_unit(TimestampUnit::Microseconds);
+
+        let mut frame = IdtpFrame::new();
+        frame.set_header(&header);
+        frame.set_payload(PAYLOAD);
+        assert!(frame.extensions().unwrap().is_empty());
+        assert_eq!(frame.data(), Ok(PAYLOAD));
+
+        let timestamp = TimestampExtension {
+            timestamp_high: 1,
+            tick_rate: 0,
+        };
+        frame.set_timestamp_extension(&timestamp).unwrap();
+
+        let mut buffer = [0u8; 32];
+        let size = test_block(&mut buffer);
+        frame.set_extensions(&buffer[..size]).unwrap();
+        frame.update_integrity();
+        assert_eq!(frame.payload_size(), 14 + 8 + PAYLOAD.len());
+
+        let mut bytes = vec![0u8; frame.frame_size()];
+        frame.pack(&mut bytes).unwrap();
+        let frame = IdtpFrame::parse(&bytes).unwrap();
+        assert!(frame.verify().is_ok());
+        assert_eq!(
+            frame.header().flags(),
+            IDTP_FLAG_EXTENSIONS | IDTP_FLAG_TIMESTAMP_EXT | 0x10
+        );
+
+        // Extension block precedes timestamp extension and data.
+        let block = frame.extensions().unwrap();
+        assert_eq!(
+            block.get(EXTENSION_TYPE_TEMPERATURE),
+            Some(&[0x08, 0x66][..])
+        );
+        assert_eq!(frame.timestamp_extension(), Some(timestamp));
+        assert_eq!(frame.data(), Ok(PAYLOAD));
+
+        // Replacing block keeps the rest of payload.
+        let mut frame = frame;
+        let size = ExtensionBuilder::new(&mut buffer).finish().unwrap();
+        frame.set_extensions(&buffer[..size]).unwrap();
+        assert_eq!(frame.payload_size(), 2 + 8 + PAYLOAD.len());
+        assert_eq!(frame.take_timestamp_extension(), Some(timestamp));
+        assert_eq!(frame.payload()[2..], *PAYLOAD);
+        assert_eq!(frame.data(), Ok(PAYLOAD));
+    }
+
+    #[test]
+    fn test_frame_extensions_errors() {
+        let mut frame = IdtpFrame::new();
+        frame.set_payload(&[0u8; IDTP_PAYLOAD_MAX_SIZE - 2]);
+
+        // Block size field does not match given bytes.
+        assert_eq!(
+            frame.set_extensions(&[0, 2, 0]),
+            Err(IdtpError::InvalidPayload)
+        );
+        assert_eq!(
+            frame.set_extensions(&[0, 4, 0x01, 0]),
+            Err(IdtpError::FrameTooLarge)
+        );
+        assert_eq!(frame.payload_size(), IDTP_PAYLOAD_MAX_SIZE - 2);
+        assert_eq!(frame.header().flags(), 0);
+
+        // Flag without valid block.
+        let mut header = IdtpHeader::new();
+        header.set_flags(IDTP_FLAG_EXTENSIONS);
+        frame.set_header(&header);
+        frame.set_payload(&[0, 9, 0x01]);
+        assert_eq!(frame.extensions(), Err(IdtpError::InvalidPayload));
+        assert_eq!(frame.data(), Err(IdtpError::InvalidPayload));
+    }
+}
@@ -0,0 +1,151 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP-F (FEC mode) tests.
+
+extern crate idtp;
+
+#[cfg(test)]
+mod tests {
+    use idtp::fec::*;
+    use idtp::*;
+
+    fn protected_frame(payload: &[u8]) -> Vec<u8> {
+        let mut header = IdtpHeader::new();
+        header.device_id = 0x0A0B;
+        header.sequence = 42;
+        header.payload_type = 0x01;
+
+        let mut frame = IdtpFrame::new();
+        frame.set_header(&header);
+        frame.set_payload(payload);
+        frame.protect().unwrap();
+
+        let mut bytes = vec![0u8; frame.frame_size()];
+        frame.pack(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_sizes() {
+        assert_eq!(parity_size(1), FEC_PARITY_SIZE);
+        assert_eq!(parity_size(FEC_BLOCK_DATA_SIZE), FEC_PARITY_SIZE);
+        assert_eq!(parity_size(FEC_BLOCK_DATA_SIZE + 1), 2 * FEC_PARITY_SIZE);
+
+        assert_eq!(message_size(FEC_PARITY_SIZE + 1), Some(1));
+        assert_eq!(message_size(FEC_BLOCK_SIZE), Some(FEC_BLOCK_DATA_SIZE));
+        assert_eq!(
+            message_size(FEC_BLOCK_SIZE + 1 + FEC_PARITY_SIZE),
+            Some(240)
+        );
+        // Parity only or partial parity of the last block.
+        assert_eq!(message_size(FEC_PARITY_SIZE), None);
+        assert_eq!(message_size(FEC_BLOCK_SIZE + FEC_PARITY_SIZE), None);
+    }
+
+    #[test]
+    fn test_protect_round_trip() {
+        let payload = b"accelerometer sample";
+        let bytes = protected_frame(payload);
+        assert_eq!(
+            bytes.len(),
+            IDTP_PACKET_MIN_SIZE + payload.len() + FEC_PARITY_SIZE
+        );
+
+        let frame = IdtpFrame::parse(&bytes).unwrap();
+        assert_eq!(frame.header().mode, Mode::Fec);
+        assert!(frame.verify().is_ok());
+        assert_eq!(frame.data(), Ok(&payload[..]));
+
+        // Clean frame needs no correction.
+        let mut received = bytes.clone();
+        assert_eq!(correct(&mut received), Ok(0));
+        assert_eq!(received, bytes);
+    }
+
+    #[test]
+    fn test_correct_errors() {
+        let payload: Vec<u8> = (0..64).collect();
+        let bytes = protected_frame(&payload);
+
+        // Corrupt up to FEC_MAX_ERRORS bytes across header, data, parity and
+        // trailer.
+        let mut received = bytes.clone();
+        let positions = [0, 7, 12, 30, 60, 80, 100, bytes.len() - 1];
+
+        for (i, position) in positions.iter().enumerate() {
+            received[*position] ^= 0x11 * (i as u8 + 1);
+        }
+
+        assert!(IdtpFrame::parse(&received).is_err());
+        assert_eq!(correct(&mut received), Ok(positions.len()));
+        assert_eq!(received, bytes);
+
+        let frame = IdtpFrame::parse(&received).unwrap();
+        assert!(frame.verify().is_ok());
+        assert_eq!(frame.data(), Ok(&payload[..]));
+    }
+
+    #[test]
+    fn test_correct_multiple_blocks() {
+        let payload: Vec<u8> = (0..600).map(|i| (i * 7) as u8).collect();
+        let bytes = protected_frame(&payload);
+        let message = IDTP_HEADER_SIZE + payload.len();
+        assert_eq!(
+            bytes.len(),
+            IDTP_PACKET_MIN_SIZE + payload.len() + parity_size(message)
+        );
+
+        // Burst of FEC_MAX_ERRORS bytes in every block.
+        let mut received = bytes.clone();
+        let blocks = message.div_ceil(FEC_BLOCK_DATA_SIZE);
+
+        for block in 0..blocks {
+            let start = block * FEC_BLOCK_DATA_SIZE + 20;
+            received[start..start + FEC_MAX_ERRORS].fill(0xFF);
+        }
+
+        let corrected = correct(&mut received).unwrap();
+        assert!(corrected > 0 && corrected <= blocks * FEC_MAX_ERRORS);
+        assert_eq!(received, bytes);
+        assert_eq!(
+            IdtpFrame::parse(&received).unwrap().data(),
+            Ok(&payload[..])
+        );
+    }
+
+    #[test]
+    fn test_uncorrectable() {
+        let bytes = protected_frame(&[0x5A; 32]);
+
+        let mut received = bytes.clone();
+        received[10..10 + FEC_MAX_ERRORS + 1]
+            .iter_mut()
+            .for_each(|byte| *byte = !*byte);
+        let corrupted = received.clone();
+
+        assert_eq!(correct(&mut received), Err(IdtpError::Uncorrectable));
+        assert_eq!(received, corrupted);
+
+        // Size leaves partial parity of the last block.
+        let mut received = vec![0u8; FEC_BLOCK_SIZE + 1 + IDTP_TRAILER_SIZE];
+        assert_eq!(correct(&mut received), Err(IdtpError::PayloadSizeMismatch));
+        assert_eq!(
+            correct(&mut received[..IDTP_PACKET_MIN_SIZE - 1]),
+            Err(IdtpError::FrameTooShort)
+        );
+
+        // Payload with parity does not fit.
+        let mut frame = IdtpFrame::new();
+        frame.set_payload(&[0u8; IDTP_PAYLOAD_MAX_SIZE]);
+        assert_eq!(frame.protect(), Err(IdtpError::FrameTooLarge));
+        assert_eq!(frame.header().mode, Mode::Normal);
+
+        // Authenticated header fields can not be changed.
+        let mut header = IdtpHeader::new();
+        header.set_flags(IDTP_FLAG_AUTH);
+        frame.set_header(&header);
+        frame.set_payload(&[0u8; 32]);
+        assert_eq!(frame.protect(), Err(IdtpError::InvalidPayload));
+    }
+}
@@ -0,0 +1,218 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Gap detection and filling tests.
+
+extern crate idtp;
+
+#[cfg(test)]
+mod tests {
+    use idtp::gap::*;
+    use idtp::payload::*;
+    use idtp::timeline::*;
+
+    fn position(sequence: u64, timestamp: u64) -> Unwrapped {
+        Unwrapped {
+            sequence,
+            timestamp,
+            ..Unwrapped::default()
+        }
+    }
+
+    fn accelerometer(x: f32) -> StandardPayload {
+        StandardPayload::Accelerometer(Vector3::new(x, -x, 9.8))
+    }
+
+    fn push(
+        filler: &mut GapFiller,
+        position: &Unwrapped,
+        payload: StandardPayload,
+    ) -> (Fill, Vec<Sample>) {
+        let mut samples = Vec::new();
+        let fill = filler.push(position, payload, |s| samples.push(s));
+        (fill, samples)
+    }
+
+    #[test]
+    fn test_linear_interpolation() {
+        let mut filler = GapFiller::default();
+
+        let (fill, samples) =
+            push(&mut filler, &position(7, 100), accelerometer(0.0));
+        assert_eq!(fill, Fill::Contiguous);
+        assert_eq!(samples.len(), 1);
+
+        // Samples 8, 9 and 10 are lost.
+        let (fill, samples) =
+            push(&mut filler, &position(11, 140), accelerometer(4.0));
+        assert_eq!(fill, Fill::Filled(3));
+
+        let expected = [
+            (8, 110, 1.0, true),
+            (9, 120, 2.0, true),
+            (10, 130, 3.0, true),
+            (11, 140, 4.0, false),
+        ];
+
+        assert_eq!(samples.len(), expected.len());
+
+        for (sample, (sequence, timestamp, x, synthetic)) in
+            samples.iter().zip(expected)
+        {
+            assert_eq!(sample.sequence, sequence);
+            assert_eq!(sample.timestamp, timestamp);
+            assert_eq!(sample.payload, accelerometer(x));
+            assert_eq!(sample.synthetic, synthetic);
+        }
+
+        let (fill, samples) =
+            push(&mut filler, &position(12, 150), accelerometer(5.0));
+        assert_eq!(fill, Fill::Contiguous);
+        assert_eq!(samples.len(), 1);
+
+        // Every field of combined samples is interpolated.
+        let imu = |x: f32| {
+            let v = Vector3::new(x, 2.0 * x, 3.0 * x);
+            StandardPayload::Imu9(Imu9 {
+                acc: v,
+                gyr: v,
+                mag: v,
+            })
+        };
+
+        let mut filler = GapFiller::default();
+        push(&mut filler, &position(0, 0), imu(0.0));
+        let (_, samples) = push(&mut filler, &position(2, 20), imu(2.0));
+        assert_eq!(samples[0].payload, imu(1.0));
+    }
+
+    #[test]
+    fn test_quaternion_slerp() {
+        let half = core::f32::consts::FRAC_PI_4;
+        let rotation = |angle: f32| {
+            // Rotation about Z axis.
+            Quaternion::new((angle / 2.0).cos(), 0.0, 0.0, (angle / 2.0).sin())
+        };
+
+        let mut filler = GapFiller::default();
+        let start = StandardPayload::Quaternion(rotation(0.0));
+        push(&mut filler, &position(0, 0), start);
+
+        // End orientation given with opposite sign: the shortest path is
+        // still taken.
+        let end = rotation(3.0 * half);
+        let end = Quaternion::new(-end.w, -end.x, -end.y, -end.z);
+        let (fill, samples) = push(
+            &mut filler,
+            &position(3, 30),
+            StandardPayload::Quaternion(end),
+        );
+        assert_eq!(fill, Fill::Filled(2));
+
+        for (sample, angle) in samples.iter().zip([half, 2.0 * half]) {
+            let StandardPayload::Quaternion(q) = sample.payload else {
+                panic!("quaternion expected");
+            };
+
+            let expected = rotation(angle);
+            assert!(sample.synthetic);
+            assert!((q.w - expected.w).abs() < 1e-5);
+            assert!((q.z - expected.z).abs() < 1e-5);
+            assert!(q.x.abs() < 1e-6 && q.y.abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_hold_last_and_limits() {
+        let config = GapConfig {
+            method: FillMethod::HoldLast,
+            max_length: 2,
+            max_span: None,
+        };
+        let mut filler = GapFiller::new(config);
+        assert_eq!(filler.config(), &config);
+
+        push(&mut filler, &position(0, 0), accelerometer(1.0));
+        let (fill, samples) =
+            push(&mut filler, &position(3, 3), accelerometer(4.0));
+        assert_eq!(fill, Fill::Filled(2));
+        assert_eq!(samples[0].payload, accelerometer(1.0));
+        assert_eq!(samples[1].payload, accelerometer(1.0));
+        assert_eq!(samples[2].payload, accelerometer(4.0));
+
+        // Gap longer than maximum is left unfilled.
+        let (fill, samples) =
+            push(&mut filler, &position(7, 7), accelerometer(5.0));
+        assert_eq!(fill, Fill::Unfilled(3));
+        assert_eq!(samples.len(), 1);
+        assert!(!samples[0].synthetic);
+
+        // Late samples are dropped.
+        let (fill, samples) =
+            push(&mut filler, &position(6, 6), accelerometer(5.0));
+        assert_eq!(fill, Fill::Late);
+        assert!(samples.is_empty());
+
+        // Samples of different payload types are not mixed.
+        let gyroscope = StandardPayload::Gyroscope(Vector3::default());
+        let (fill, samples) = push(&mut filler, &position(9, 9), gyroscope);
+        assert_eq!(fill, Fill::Unfilled(1));
+        assert_eq!(samples.len(), 1);
+
+        filler.reset();
+        let (fill, _) = push(&mut filler, &position(0, 0), gyroscope);
+        assert_eq!(fill, Fill::Contiguous);
+    }
+
+    #[test]
+    fn test_gap_timestamps() {
+        let mut filler = GapFiller::new(GapConfig {
+            max_span: Some(50),
+            ..GapConfig::default()
+        });
+
+        push(&mut filler, &position(0, 100), accelerometer(0.0));
+        let (fill, _) =
+            push(&mut filler, &position(2, 150), accelerometer(2.0));
+        assert_eq!(fill, Fill::Filled(1));
+
+        // Device paused between samples.
+        let (fill, samples) =
+            push(&mut filler, &position(4, 201), accelerometer(4.0));
+        assert_eq!(fill, Fill::Unfilled(1));
+        assert_eq!(samples.len(), 1);
+
+        // Timestamp does not increase.
+        let (fill, _) =
+            push(&mut filler, &position(6, 201), accelerometer(6.0));
+        assert_eq!(fill, Fill::Unfilled(1));
+        let (fill, _) =
+            push(&mut filler, &position(8, 190), accelerometer(8.0));
+        assert_eq!(fill, Fill::Unfilled(1));
+    }
+
+    #[test]
+    fn test_gap_across_sequence_wrap() {
+        let mut timeline = Timeline::new();
+        let mut filler = GapFiller::default();
+        let mut sequences = Vec::new();
+
+        for (sequence, timestamp) in [(u32::MAX - 1, 10), (1, 40), (2, 50)] {
+            let position = timeline.update(sequence, timestamp);
+            filler.push(&position, accelerometer(0.0), |sample| {
+                sequences.push((sample.sequence as u32, sample.synthetic));
+            });
+        }
+
+        assert_eq!(
+            sequences,
+            [
+                (u32::MAX - 1, false),
+                (u32::MAX, true),
+                (0, true),
+                (1, false),
+                (2, false)
+            ]
+        );
+    }
+}
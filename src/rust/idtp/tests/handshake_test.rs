@@ -0,0 +1,309 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Secure mode session key establishment tests.
+
+extern crate idtp;
+
+#[cfg(test)]
+mod tests {
+    use idtp::handshake::*;
+    use idtp::payload::Payload;
+    use idtp::*;
+    use std::collections::VecDeque;
+
+    const DEVICE_ID: u16 = 0x0042;
+    const PSK: &[u8] = b"pre-shared device secret";
+
+    /// In-memory transport carrying packed frames in one direction.
+    #[derive(Default)]
+    struct Link {
+        frames: VecDeque<Vec<u8>>,
+    }
+
+    impl Link {
+        fn send(&mut self, frame: &IdtpFrame) -> Vec<u8> {
+            let mut bytes = vec![0u8; frame.frame_size()];
+            frame.pack(&mut bytes).unwrap();
+            self.frames.push_back(bytes.clone());
+            bytes
+        }
+
+        fn send_message<P: Payload>(&mut self, message: &P) -> Vec<u8> {
+            let mut payload = [0u8; IDTP_PAYLOAD_MAX_SIZE];
+            let size = message.encode(&mut payload).unwrap();
+
+            let mut header = IdtpHeader::new();
+            header.device_id = DEVICE_ID;
+            header.payload_type = P::PAYLOAD_TYPE;
+
+            let mut frame = IdtpFrame::new();
+            frame.set_header(&header);
+            frame.set_payload(&payload[..size]);
+            frame.update_integrity();
+            self.send(&frame)
+        }
+
+        fn receive(&mut self) -> IdtpFrame {
+            let frame = IdtpFrame::parse(&self.frames.pop_front().unwrap());
+            let frame = frame.unwrap();
+            assert!(frame.verify().is_ok());
+            frame
+        }
+
+        fn receive_message<P: Payload>(&mut self) -> P {
+            let frame = self.receive();
+            assert_eq!(frame.header().payload_type, P::PAYLOAD_TYPE);
+            P::decode(frame.payload()).unwrap()
+        }
+    }
+
+    /// Host and device connected by in-memory links.
+    struct Session<'a> {
+        host: HostHandshake<'a>,
+        device: DeviceHandshake<'a>,
+        uplink: Link,
+        downlink: Link,
+        sequence: u32,
+    }
+
+    impl<'a> Session<'a> {
+        fn new(host_psk: &'a [u8], policy: RotationPolicy) -> Self {
+            Self {
+                host: HostHandshake::new(DEVICE_ID, host_psk, policy),
+                device: DeviceHandshake::new(DEVICE_ID, PSK, policy),
+                uplink: Link::default(),
+                downlink: Link::default(),
+                sequence: 0,
+            }
+        }
+
+        /// Run handshake and return raw handshake frames.
+        fn handshake(&mut self, seed: u8) -> Result<[Vec<u8>; 3], IdtpError> {
+            let request = self.host.start([seed; 32]);
+            let request_bytes = self.downlink.send_message(&request);
+
+            let request: KeyRequest = self.downlink.receive_message();
+            let response = self.device.handle_request(&request, [!seed; 32])?;
+            let response_bytes = self.uplink.send_message(&response);
+
+            let response: KeyResponse = self.uplink.receive_message();
+            let finished = self.host.handle_response(&response)?;
+            let finished_bytes = self.downlink.send_message(&finished);
+
+            let finished: KeyFinished = self.downlink.receive_message();
+            self.device.handle_finished(&finished)?;
+
+            Ok([request_bytes, response_bytes, finished_bytes])
+        }
+
+        fn sample(&mut self, value: u8) -> Result<(), IdtpError> {
+            let mut header = IdtpHeader::new();
+            header.device_id = DEVICE_ID;
+            header.sequence = self.sequence;
+            header.timestamp = self.sequence * 10;
+            self.sequence += 1;
+
+            let mut frame = IdtpFrame::new();
+            frame.set_header(&header);
+            frame.set_payload(&[value; 12]);
+            self.device.seal(&mut frame)?;
+            self.uplink.send(&frame);
+
+            let mut frame = self.uplink.receive();
+            self.host.open(&mut frame)?;
+            assert_eq!(frame.payload(), &[value; 12]);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_message_encoding() {
+        let response = KeyResponse {
+            key_id: 5,
+            public_key: [0xAB; PUBLIC_KEY_SIZE],
+            confirm: [0xCD; CONFIRM_SIZE],
+        };
+
+        let mut buffer = [0u8; KeyResponse::SIZE];
+        assert_eq!(response.encode(&mut buffer), Ok(49));
+        assert_eq!(buffer[0], 5);
+        assert_eq!(KeyResponse::decode(&buffer), Ok(response));
+        assert_eq!(
+            KeyResponse::decode(&buffer[..48]),
+            Err(IdtpError::InvalidPayload)
+        );
+        assert_eq!(
+            response.encode(&mut buffer[..10]),
+            Err(IdtpError::BufferTooSmall)
+        );
+
+        let finished = KeyFinished {
+            key_id: 1,
+            confirm: [7; CONFIRM_SIZE],
+        };
+        let mut buffer = [0u8; KeyFinished::SIZE];
+        finished.encode(&mut buffer).unwrap();
+        assert_eq!(KeyFinished::decode(&buffer), Ok(finished));
+    }
+
+    #[test]
+    fn test_handshake_end_to_end() {
+        let mut session = Session::new(PSK, RotationPolicy::default());
+        assert_eq!(session.sample(1), Err(IdtpError::KeyUnavailable));
+
+        session.handshake(1).unwrap();
+        assert_eq!(session.device.key().unwrap().id(), 0);
+        assert_eq!(session.host.key().unwrap().id(), 0);
+
+        for value in 0..10 {
+            session.sample(value).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_handshake_wrong_psk() {
+        let mut session = Session::new(b"guess", RotationPolicy::default());
+
+        assert_eq!(session.handshake(1), Err(IdtpError::AuthenticationFailed));
+        assert!(session.host.key().is_none());
+        assert!(session.device.key().is_none());
+    }
+
+    #[test]
+    fn test_key_rotation() {
+        let policy = RotationPolicy {
+            max_frames: Some(5),
+            max_age: None,
+        };
+        let mut session = Session::new(PSK, policy);
+        session.handshake(1).unwrap();
+
+        for value in 0..5 {
+            session.sample(value).unwrap();
+        }
+
+        // Device refuses to exceed the policy limit.
+        let mut header = IdtpHeader::new();
+        header.sequence = session.sequence;
+        assert!(session.host.is_rotation_due(&header));
+        assert_eq!(session.sample(5), Err(IdtpError::KeyUnavailable));
+
+        session.handshake(2).unwrap();
+        assert_eq!(session.device.key().unwrap().id(), 1);
+        session.sample(6).unwrap();
+
+        // Rotation by time.
+        let policy = RotationPolicy {
+            max_frames: None,
+            max_age: Some(100),
+        };
+        let mut first = IdtpHeader::new();
+        first.timestamp = u32::MAX - 10;
+        header.timestamp = 88;
+        assert!(!policy.is_due(&first, &header));
+        header.timestamp = 89;
+        assert!(policy.is_due(&first, &header));
+    }
+
+    #[test]
+    fn test_host_accepts_previous_key_during_rotation() {
+        let mut session = Session::new(PSK, RotationPolicy::default());
+        session.handshake(1).unwrap();
+        session.sample(1).unwrap();
+
+        // Frame sealed with the old key is still in flight.
+        let mut header = IdtpHeader::new();
+        header.device_id = DEVICE_ID;
+        header.sequence = 100;
+        let mut old = IdtpFrame::new();
+        old.set_header(&header);
+        old.set_payload(&[9; 4]);
+        session.device.seal(&mut old).unwrap();
+
+        session.handshake(2).unwrap();
+        let mut frame = old;
+        session.host.open(&mut frame).unwrap();
+
+        // Previous key is dropped once frame with the new key arrives.
+        session.sample(2).unwrap();
+        let mut frame = old;
+        assert_eq!(
+            session.host.open(&mut frame),
+            Err(IdtpError::AuthenticationFailed)
+        );
+    }
+
+    #[test]
+    fn test_replayed_handshake_messages() {
+        let mut session = Session::new(PSK, RotationPolicy::default());
+        let [old_request, old_response, old_finished] =
+            session.handshake(1).unwrap();
+        session.handshake(2).unwrap();
+        session.sample(1).unwrap();
+
+        let decode = |bytes: &[u8]| IdtpFrame::parse(bytes).unwrap();
+
+        // Replayed request starts handshake nobody can finish.
+        let request = KeyRequest::decode(decode(&old_request).payload());
+        let response = session
+            .device
+            .handle_request(&request.unwrap(), [3; 32])
+            .unwrap();
+
+        let finished = KeyFinished::decode(decode(&old_finished).payload());
+        assert_eq!(
+            session.device.handle_finished(&finished.unwrap()),
+            Err(IdtpError::AuthenticationFailed)
+        );
+
+        // Host has no matching handshake in progress.
+        let replayed = KeyResponse::decode(decode(&old_response).payload());
+        assert_eq!(
+            session.host.handle_response(&replayed.unwrap()),
+            Err(IdtpError::AuthenticationFailed)
+        );
+        assert_eq!(
+            session.host.handle_response(&response),
+            Err(IdtpError::AuthenticationFailed)
+        );
+
+        // Session keeps working with the current key.
+        assert_eq!(session.device.key().unwrap().id(), 1);
+        session.sample(2).unwrap();
+
+        // Replayed response does not match new handshake either.
+        let request = session.host.start([4; 32]);
+        let mut replayed = KeyResponse::decode(decode(&old_response).payload());
+        replayed.as_mut().unwrap().key_id = request.key_id;
+        assert_eq!(
+            session.host.handle_response(&replayed.unwrap()),
+            Err(IdtpError::AuthenticationFailed)
+        );
+    }
+
+    #[test]
+    fn test_device_rejects_current_key_id() {
+        let mut session = Session::new(PSK, RotationPolicy::default());
+        session.handshake(1).unwrap();
+
+        let request = KeyRequest {
+            key_id: 0,
+            public_key: [9; PUBLIC_KEY_SIZE],
+        };
+        assert_eq!(
+            session.device.handle_request(&request, [1; 32]),
+            Err(IdtpError::AuthenticationFailed)
+        );
+
+        // Low order public key.
+        let request = KeyRequest {
+            key_id: 7,
+            public_key: [0; PUBLIC_KEY_SIZE],
+        };
+        assert_eq!(
+            session.device.handle_request(&request, [1; 32]),
+            Err(IdtpError::AuthenticationFailed)
+        );
+    }
+}
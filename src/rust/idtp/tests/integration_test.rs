@@ -29,6 +29,30 @@ mod tests {
         assert_eq!(bytes, [0xff, 0xff, 0xff]);
     }
 
+    #[test]
+    fn test_idtp_version_order_and_format() {
+        assert!(Version::new(1, 0, 0) < Version::new(1, 0, 1));
+        assert!(Version::new(1, 0, 9) < Version::new(1, 1, 0));
+        assert!(Version::new(1, 9, 9) < Version::new(2, 0, 0));
+        assert_eq!(Version::new(1, 2, 3), Version::new(1, 2, 3));
+
+        assert_eq!(IDTP_VERSION.to_string(), "1.0.0");
+        assert_eq!("1.0.0".parse(), Ok(IDTP_VERSION));
+        assert_eq!("255.2.10".parse(), Ok(Version::new(255, 2, 10)));
+
+        for string in ["", "1", "1.0", "1.0.0.0", "1..0", "1.0.256", "+1.0.0"] {
+            assert_eq!(
+                string.parse::<Version>(),
+                Err(IdtpError::InvalidVersion),
+                "{string}"
+            );
+        }
+
+        assert!(IDTP_VERSION.is_compatible(&Version::new(1, 7, 3)));
+        assert!(!IDTP_VERSION.is_compatible(&Version::new(2, 0, 0)));
+        assert!(!IDTP_VERSION.is_compatible(&Version::new(0, 9, 0)));
+    }
+
     #[test]
     fn test_sizes() {
         assert_eq!(IDTP_PREAMBLE_SIZE, 4);
@@ -192,4 +216,136 @@ mod tests {
         let result = idtp.pack(&mut small_buffer);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(b""), 0);
+    }
+
+    #[test]
+    fn test_integrity_normal_and_safety_modes() {
+        let mut idtp = IdtpFrame::new();
+        idtp.set_payload(&[1, 2, 3, 4]);
+        idtp.update_integrity();
+
+        let header = idtp.header();
+        let expected: u16 =
+            [0x49, 0x44, 0x54, 0x50, 1, 4, 1, 2, 3, 4].iter().sum();
+        assert_eq!({ header.checksum }, expected);
+        assert_eq!({ header.crc }, 0);
+        assert!(idtp.verify().is_ok());
+        assert!(idtp.verify().crc.is_none());
+
+        let mut header = idtp.header();
+        header.mode = Mode::Safety;
+        idtp.set_header(&header);
+        idtp.update_integrity();
+
+        let integrity = idtp.verify();
+        assert!(integrity.is_ok());
+        assert_ne!(integrity.crc.unwrap().actual, 0);
+
+        idtp.set_payload(&[1, 2, 3, 5]);
+        let integrity = idtp.verify();
+        assert!(!integrity.is_ok());
+        assert!(!integrity.checksum.is_ok());
+        assert!(!integrity.crc.unwrap().is_ok());
+
+        // Raw header bytes keep unknown mode value.
+        let mut bytes = idtp.header().as_bytes_be();
+        assert_eq!(Integrity::verify_raw(&bytes, idtp.payload()), integrity);
+        bytes[7] = 0x07;
+        let expected: u16 = [0x49, 0x44, 0x54, 0x50, 1, 0x07, 4, 1, 2, 3, 5]
+            .iter()
+            .sum();
+        assert_eq!(raw_checksum(&bytes, &[1, 2, 3, 5]), expected);
+        assert!(Integrity::verify_raw(&bytes, &[1, 2, 3, 5]).crc.is_none());
+    }
+
+    #[test]
+    fn test_idtp_frame_parse() {
+        let mut idtp = IdtpFrame::new();
+        idtp.set_payload(&[0xAB; 6]);
+        idtp.update_integrity();
+
+        let mut buffer = vec![0u8; idtp.frame_size()];
+        idtp.pack(&mut buffer).unwrap();
+
+        let parsed = IdtpFrame::parse(&buffer).unwrap();
+        assert_eq!(parsed.payload(), &[0xAB; 6]);
+        assert!(parsed.verify().is_ok());
+
+        assert_eq!(
+            IdtpFrame::parse(&buffer[..IDTP_PACKET_MIN_SIZE - 1]).err(),
+            Some(IdtpError::FrameTooShort)
+        );
+        assert_eq!(
+            IdtpFrame::parse(&buffer[..buffer.len() - 1]).err(),
+            Some(IdtpError::PayloadSizeMismatch)
+        );
+        assert_eq!(
+            IdtpFrame::parse(&[0u8; IDTP_PACKET_MAX_SIZE + 1]).err(),
+            Some(IdtpError::FrameTooLarge)
+        );
+
+        let mut broken = buffer.clone();
+        broken[0] = b'X';
+        assert_eq!(
+            IdtpFrame::parse(&broken).err(),
+            Some(IdtpError::InvalidPreamble)
+        );
+
+        let mut broken = buffer.clone();
+        *broken.last_mut().unwrap() = b'X';
+        assert_eq!(
+            IdtpFrame::parse(&broken).err(),
+            Some(IdtpError::InvalidTrailer)
+        );
+
+        let mut broken = buffer.clone();
+        broken[4] = 2;
+        assert_eq!(
+            IdtpFrame::parse(&broken).err(),
+            Some(IdtpError::InvalidVersion)
+        );
+    }
+
+    #[test]
+    fn test_idtp_frame_parse_newer_minor_version() {
+        let mut header = IdtpHeader::new();
+        header.version = Version::new(1, 4, 2);
+        header.set_flags(0xFE);
+        header.reserved[2] = 0xAA;
+
+        let mut idtp = IdtpFrame::new();
+        idtp.set_header(&header);
+        idtp.set_payload(&[1, 2, 3]);
+        idtp.update_integrity();
+
+        let mut buffer = vec![0u8; idtp.frame_size()];
+        idtp.pack(&mut buffer).unwrap();
+
+        // Unknown reserved bits of newer minor version are ignored.
+        let parsed = IdtpFrame::parse(&buffer).unwrap();
+        assert_eq!(parsed.header().version, Version::new(1, 4, 2));
+        assert_eq!(parsed.payload(), &[1, 2, 3]);
+        assert!(parsed.verify().is_ok());
+    }
+
+    #[test]
+    fn test_idtp_frame_from_malformed_bytes() {
+        // Regression: input shorter than header and trailer used to
+        // underflow payload size, longer input overflowed payload buffer.
+        for size in [0, 1, IDTP_HEADER_SIZE, IDTP_PACKET_MIN_SIZE - 1] {
+            let idtp = IdtpFrame::from(&vec![0x49; size][..]);
+            assert_eq!(idtp.payload_size(), 0);
+        }
+
+        let idtp = IdtpFrame::from(IDTP_PREAMBLE);
+        assert_eq!(idtp.header().preamble, *IDTP_PREAMBLE);
+
+        let idtp = IdtpFrame::from(&[0xAB; IDTP_PACKET_MAX_SIZE + 100][..]);
+        assert_eq!(idtp.payload_size(), IDTP_PAYLOAD_MAX_SIZE);
+    }
 }
@@ -0,0 +1,65 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Standard IDTP payload tests.
+
+extern crate idtp;
+
+#[cfg(test)]
+mod tests {
+    use idtp::{payload::*, *};
+
+    #[test]
+    fn test_imu6_encoding() {
+        let sample = Imu6 {
+            acc: Vector3::new(1.0, -2.0, 9.81),
+            gyr: Vector3::new(0.1, 0.2, 0.3),
+        };
+
+        let mut buffer = [0u8; Imu6::SIZE];
+        assert_eq!(sample.encode(&mut buffer), Ok(24));
+        assert_eq!(&buffer[0..4], &1.0f32.to_be_bytes());
+        assert_eq!(Imu6::decode(&buffer), Ok(sample));
+
+        assert_eq!(
+            sample.encode(&mut [0u8; 23]),
+            Err(IdtpError::BufferTooSmall)
+        );
+        assert_eq!(Imu6::decode(&buffer[1..]), Err(IdtpError::InvalidPayload));
+    }
+
+    #[test]
+    fn test_standard_payload_registry() {
+        let quaternion = Quaternion::new(0.5, 0.5, 0.5, 0.5);
+        let mut buffer = [0u8; Quaternion::SIZE];
+        quaternion.encode(&mut buffer).unwrap();
+
+        let decoded = StandardPayload::decode(PAYLOAD_TYPE_QUATERNION, &buffer);
+        assert_eq!(decoded, Some(Ok(StandardPayload::Quaternion(quaternion))));
+
+        let mut names = Vec::new();
+        decoded
+            .unwrap()
+            .unwrap()
+            .for_each_field(|name, _| names.push(name));
+        assert_eq!(names, ["w", "x", "y", "z"]);
+
+        assert_eq!(StandardPayload::decode(0x00, &buffer), None);
+        assert_eq!(payload_info(PAYLOAD_TYPE_IMU9).unwrap().size, Some(36));
+        assert!(payload_info(0x01).is_none());
+
+        // Variable size payloads are not decoded as standard payload.
+        let delta = payload_info(PAYLOAD_TYPE_DELTA).unwrap();
+        assert_eq!(delta.size, None);
+        assert_eq!(StandardPayload::decode(PAYLOAD_TYPE_DELTA, &buffer), None);
+
+        for info in PAYLOAD_REGISTRY.iter().filter(|i| i.size.is_some()) {
+            let bytes = vec![0u8; info.size.unwrap()];
+            let payload = StandardPayload::decode(info.payload_type, &bytes);
+            assert_eq!(
+                payload.unwrap().unwrap().payload_type(),
+                info.payload_type
+            );
+        }
+    }
+}
@@ -0,0 +1,213 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP pcap/pcapng export and import tests.
+
+extern crate idtp;
+
+#[cfg(test)]
+mod tests {
+    use idtp::{pcap::*, *};
+    use std::{
+        io::Cursor,
+        net::{Ipv4Addr, SocketAddrV4},
+    };
+
+    fn test_frame(sequence: u32, payload: &[u8]) -> Vec<u8> {
+        let mut header = IdtpHeader::new();
+        header.sequence = sequence;
+        header.payload_size = payload.len() as u32;
+
+        let mut frame = IdtpFrame::new();
+        frame.set_header(&header);
+        frame.set_payload(payload);
+
+        let mut buffer = vec![0u8; IDTP_PACKET_MIN_SIZE + payload.len()];
+        frame.pack(&mut buffer).unwrap();
+        buffer
+    }
+
+    /// Build Ethernet/IPv4/UDP packet by hand.
+    fn udp_packet(
+        protocol: u8,
+        src_port: u16,
+        dst_port: u16,
+        data: &[u8],
+    ) -> Vec<u8> {
+        let mut packet = vec![0u8; 12];
+        packet.extend_from_slice(&[0x08, 0x00]);
+        packet.extend_from_slice(&ipv4_packet(
+            protocol, src_port, dst_port, data,
+        ));
+        packet
+    }
+
+    /// Build IPv4/UDP packet by hand.
+    fn ipv4_packet(
+        protocol: u8,
+        src_port: u16,
+        dst_port: u16,
+        data: &[u8],
+    ) -> Vec<u8> {
+        let total_size = (20 + 8 + data.len()) as u16;
+        let mut packet = vec![0x45, 0x00];
+        packet.extend_from_slice(&total_size.to_be_bytes());
+        packet.extend_from_slice(&[0, 0, 0, 0, 64, protocol, 0, 0]);
+        packet.extend_from_slice(&[10, 0, 0, 1, 10, 0, 0, 2]);
+        packet.extend_from_slice(&src_port.to_be_bytes());
+        packet.extend_from_slice(&dst_port.to_be_bytes());
+        packet.extend_from_slice(&((8 + data.len()) as u16).to_be_bytes());
+        packet.extend_from_slice(&[0, 0]);
+        packet.extend_from_slice(data);
+        packet
+    }
+
+    #[test]
+    fn test_pcapng_udp_round_trip() {
+        let flow = UdpFlow::new(
+            SocketAddrV4::new(Ipv4Addr::new(192, 168, 1, 10), 6000),
+            SocketAddrV4::new(Ipv4Addr::new(192, 168, 1, 1), 7000),
+        );
+
+        let mut writer = PcapngWriter::new(
+            Cursor::new(Vec::new()),
+            Encapsulation::Udp(flow),
+        )
+        .unwrap();
+
+        let frames: Vec<Vec<u8>> = (0..5)
+            .map(|i| test_frame(i, &vec![i as u8; i as usize]))
+            .collect();
+
+        for (i, frame) in frames.iter().enumerate() {
+            writer
+                .write_frame(1_700_000_000_000_000 + i as u64, frame)
+                .unwrap();
+        }
+
+        let bytes = writer.into_inner().unwrap().into_inner();
+        assert_eq!(&bytes[0..4], &[0x0a, 0x0d, 0x0d, 0x0a]);
+
+        let reader = PcapReader::new(Cursor::new(bytes)).unwrap();
+        let captured: Vec<CapturedFrame> = reader.map(Result::unwrap).collect();
+
+        assert_eq!(captured.len(), frames.len());
+
+        for (i, frame) in captured.iter().enumerate() {
+            assert_eq!(frame.timestamp_us, 1_700_000_000_000_000 + i as u64);
+            assert_eq!(frame.src_port, 6000);
+            assert_eq!(frame.dst_port, 7000);
+            assert_eq!(frame.data, frames[i]);
+        }
+    }
+
+    #[test]
+    fn test_pcapng_user_link_type_round_trip() {
+        let mut writer =
+            PcapngWriter::new(Cursor::new(Vec::new()), Encapsulation::User(3))
+                .unwrap();
+
+        let frame = test_frame(7, &[1, 2, 3]);
+        writer.write_frame(42, &frame).unwrap();
+        writer.write_frame(43, b"raw serial noise").unwrap();
+
+        let bytes = writer.into_inner().unwrap().into_inner();
+        let mut reader = PcapReader::new(Cursor::new(bytes)).unwrap();
+
+        let captured = reader.next_frame().unwrap().unwrap();
+        assert_eq!(captured.timestamp_us, 42);
+        assert_eq!(captured.data, frame);
+
+        let captured = reader.next_frame().unwrap().unwrap();
+        assert_eq!(captured.data, b"raw serial noise");
+        assert!(reader.next_frame().unwrap().is_none());
+
+        let result =
+            PcapngWriter::new(Cursor::new(Vec::new()), Encapsulation::User(16));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_pcap_little_endian_with_port_filter() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&0xa1b2_c3d4u32.to_le_bytes());
+        bytes.extend_from_slice(&2u16.to_le_bytes());
+        bytes.extend_from_slice(&4u16.to_le_bytes());
+        bytes.extend_from_slice(&[0u8; 8]);
+        bytes.extend_from_slice(&65535u32.to_le_bytes());
+        bytes.extend_from_slice(&LINKTYPE_ETHERNET.to_le_bytes());
+        bytes.extend_from_slice(&[0, 0]);
+
+        let frame = test_frame(1, &[0xAA; 24]);
+        let packets = [
+            udp_packet(17, 5000, 9999, b"other traffic"),
+            udp_packet(6, 5000, 5005, b"tcp segment"),
+            udp_packet(17, 40000, 5005, &frame),
+        ];
+
+        for (i, packet) in packets.iter().enumerate() {
+            bytes.extend_from_slice(&(10u32 + i as u32).to_le_bytes());
+            bytes.extend_from_slice(&500u32.to_le_bytes());
+            bytes.extend_from_slice(&(packet.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(&(packet.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(packet);
+        }
+
+        let mut reader = PcapReader::new(Cursor::new(bytes)).unwrap();
+        reader.set_port(Some(5005));
+
+        let captured: Vec<CapturedFrame> = reader.map(Result::unwrap).collect();
+        assert_eq!(captured.len(), 1);
+        assert_eq!(captured[0].timestamp_us, 12_000_500);
+        assert_eq!(captured[0].src_port, 40000);
+        assert_eq!(captured[0].data, frame);
+
+        let header = IdtpFrame::from(&captured[0].data[..]).header();
+        assert_eq!({ header.sequence }, 1);
+    }
+
+    #[test]
+    fn test_pcap_big_endian_nanosecond_raw_ip() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&0xa1b2_3c4du32.to_be_bytes());
+        bytes.extend_from_slice(&2u16.to_be_bytes());
+        bytes.extend_from_slice(&4u16.to_be_bytes());
+        bytes.extend_from_slice(&[0u8; 8]);
+        bytes.extend_from_slice(&65535u32.to_be_bytes());
+        bytes.extend_from_slice(&u32::from(LINKTYPE_RAW).to_be_bytes());
+
+        let frame = test_frame(2, &[]);
+        let packet = ipv4_packet(17, 5005, 5005, &frame);
+
+        bytes.extend_from_slice(&3u32.to_be_bytes());
+        bytes.extend_from_slice(&123_456_789u32.to_be_bytes());
+        bytes.extend_from_slice(&(packet.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(&(packet.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(&packet);
+
+        let mut reader = PcapReader::new(Cursor::new(bytes)).unwrap();
+        let captured = reader.next_frame().unwrap().unwrap();
+
+        assert_eq!(captured.timestamp_us, 3_123_456);
+        assert_eq!(captured.data, frame);
+        assert!(reader.next_frame().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_pcap_invalid_input() {
+        let result = PcapReader::new(Cursor::new(b"IDTP this is no capture"));
+        assert!(result.is_err());
+
+        let result = PcapReader::new(Cursor::new(b"\xd4"));
+        assert!(result.is_err());
+
+        // Section header block size is checked before allocation.
+        let mut bytes = vec![0x0a, 0x0d, 0x0d, 0x0a];
+        bytes.extend_from_slice(&0xFFFF_FFF0u32.to_le_bytes());
+        bytes.extend_from_slice(&0x1a2b_3c4du32.to_le_bytes());
+        bytes.resize(64, 0);
+
+        let error = PcapReader::new(Cursor::new(bytes)).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    }
+}
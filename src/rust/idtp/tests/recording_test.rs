@@ -0,0 +1,199 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP recording file format tests.
+
+extern crate idtp;
+
+#[cfg(test)]
+mod tests {
+    use idtp::{recording::*, *};
+    use std::io::Cursor;
+
+    fn test_session() -> Session {
+        let mut session = Session::new("bench-host");
+        session.start_time_us = 1_000_000;
+        session.add_device(0xABCD, "left-wrist");
+        session.add_device(0x0001, "chest");
+        session.set_property("operator", "alice");
+        session
+    }
+
+    fn test_frame(sequence: u32) -> Vec<u8> {
+        let mut header = IdtpHeader::new();
+        header.device_id = 0xABCD;
+        header.sequence = sequence;
+        header.payload_size = 4;
+
+        let mut frame = IdtpFrame::new();
+        frame.set_header(&header);
+        frame.set_payload(&sequence.to_be_bytes());
+
+        let mut buffer = vec![0u8; IDTP_PACKET_MIN_SIZE + 4];
+        frame.pack(&mut buffer).unwrap();
+        buffer
+    }
+
+    fn record_frames(count: u32, interval: usize) -> Vec<u8> {
+        let mut recorder =
+            Recorder::new(Cursor::new(Vec::new()), &test_session()).unwrap();
+        recorder.set_index_interval(interval);
+
+        for sequence in 0..count {
+            let time = 1_000_000 + u64::from(sequence) * 1_000;
+            recorder.record(time, &test_frame(sequence)).unwrap();
+        }
+
+        assert_eq!(recorder.count(), count as usize);
+        recorder.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_recording_round_trip() {
+        let bytes = record_frames(100, 8);
+        let player = Player::new(Cursor::new(bytes)).unwrap();
+
+        assert_eq!(player.session(), &test_session());
+        assert_eq!(player.session().device(0x0001).unwrap().name, "chest");
+        assert_eq!(player.session().property("operator"), Some("alice"));
+        assert!(!player.is_truncated());
+        assert!(!player.is_index_rebuilt());
+
+        let records: Vec<Record> = player.map(Result::unwrap).collect();
+        assert_eq!(records.len(), 100);
+
+        for (sequence, record) in records.iter().enumerate() {
+            assert_eq!(
+                record.recv_time_us,
+                1_000_000 + sequence as u64 * 1_000
+            );
+            assert_eq!(record.data, test_frame(sequence as u32));
+            let header = record.frame().header();
+            assert_eq!({ header.sequence }, sequence as u32);
+        }
+    }
+
+    #[test]
+    fn test_recording_seek_by_time() {
+        let bytes = record_frames(100, 8);
+        let mut player = Player::new(Cursor::new(bytes)).unwrap();
+
+        player.seek(1_042_500).unwrap();
+        let record = player.next_record().unwrap().unwrap();
+        assert_eq!(record.recv_time_us, 1_043_000);
+
+        player.seek(0).unwrap();
+        let record = player.next_record().unwrap().unwrap();
+        assert_eq!(record.recv_time_us, 1_000_000);
+
+        player.seek(u64::MAX).unwrap();
+        assert!(player.next_record().unwrap().is_none());
+
+        player.rewind().unwrap();
+        assert_eq!(player.count(), 100);
+    }
+
+    #[test]
+    fn test_recording_without_footer() {
+        let mut recorder =
+            Recorder::new(Cursor::new(Vec::new()), &test_session()).unwrap();
+
+        for sequence in 0..10 {
+            recorder
+                .record(u64::from(sequence), &test_frame(sequence))
+                .unwrap();
+        }
+
+        // Simulate crash: footer index is never written.
+        recorder.flush().unwrap();
+        let bytes = recorder.get_ref().get_ref().clone();
+
+        let mut player = Player::new(Cursor::new(bytes)).unwrap();
+        assert!(player.is_index_rebuilt());
+        assert!(!player.is_truncated());
+
+        player.seek(5).unwrap();
+        let record = player.next_record().unwrap().unwrap();
+        assert_eq!(record.recv_time_us, 5);
+        assert_eq!(player.count(), 4);
+    }
+
+    #[test]
+    fn test_recording_with_truncated_tail() {
+        let bytes = record_frames(10, 4);
+        let footer_size = 1 + 4 + 3 * 16 + 16;
+        let truncated = &bytes[..bytes.len() - footer_size - 7];
+
+        let player = Player::new(Cursor::new(truncated.to_vec())).unwrap();
+        assert!(player.is_index_rebuilt());
+        assert!(player.is_truncated());
+
+        let records: Vec<Record> = player.map(Result::unwrap).collect();
+        assert_eq!(records.len(), 9);
+        assert_eq!(records[8].data, test_frame(8));
+    }
+
+    #[test]
+    fn test_recording_frame_and_invalid_input() {
+        let mut recorder =
+            Recorder::new(Cursor::new(Vec::new()), &Session::default())
+                .unwrap();
+
+        let mut frame = IdtpFrame::new();
+        frame.set_payload(&[1, 2, 3]);
+        recorder.record_frame(42, &frame).unwrap();
+        assert!(recorder.record(43, &vec![0u8; 70_000]).is_err());
+
+        let bytes = recorder.finish().unwrap().into_inner();
+        let mut player = Player::new(Cursor::new(bytes)).unwrap();
+        let record = player.next_record().unwrap().unwrap();
+
+        assert_eq!(record.recv_time_us, 42);
+        assert_eq!(record.frame().payload(), &[1, 2, 3]);
+        assert!(player.next_record().unwrap().is_none());
+
+        let result =
+            Player::new(Cursor::new(b"not a recording at all".to_vec()));
+        assert!(result.is_err());
+
+        // Metadata size is checked before allocation.
+        let mut bytes = record_frames(1, 4);
+        bytes[20..24].copy_from_slice(&u32::MAX.to_be_bytes());
+        let error = Player::new(Cursor::new(bytes)).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_recording_unwrapped_timeline() {
+        let mut recorder =
+            Recorder::new(Cursor::new(Vec::new()), &test_session()).unwrap();
+
+        for sequence in [u32::MAX - 1, u32::MAX, 0, 1] {
+            recorder.record(1_000_000, &test_frame(sequence)).unwrap();
+        }
+        recorder.record(1_000_000, b"garbage").unwrap();
+
+        let timeline = recorder.unwrapper().timeline(0xABCD).unwrap();
+        assert_eq!(timeline.sequence(), u64::from(u32::MAX) + 2);
+
+        let bytes = recorder.finish().unwrap().into_inner();
+        let mut player = Player::new(Cursor::new(bytes)).unwrap();
+
+        for expected in 0..4 {
+            let (_, unwrapped) = player.next_unwrapped().unwrap().unwrap();
+            let unwrapped = unwrapped.unwrap();
+            assert_eq!(unwrapped.sequence, u64::from(u32::MAX) - 1 + expected);
+            assert_eq!(unwrapped.epoch, 0);
+        }
+
+        let (record, unwrapped) = player.next_unwrapped().unwrap().unwrap();
+        assert_eq!(record.data, b"garbage");
+        assert!(unwrapped.is_none());
+        assert!(player.next_unwrapped().unwrap().is_none());
+
+        // Timelines start over after rewind.
+        player.rewind().unwrap();
+        let (_, unwrapped) = player.next_unwrapped().unwrap().unwrap();
+        assert_eq!(unwrapped.unwrap().sequence, u64::from(u32::MAX - 1));
+    }
+}
@@ -0,0 +1,196 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Reliable delivery tests.
+
+extern crate idtp;
+
+#[cfg(test)]
+mod tests {
+    use idtp::payload::Payload;
+    use idtp::reliable::*;
+    use idtp::*;
+
+    const CONFIG: ReliableConfig = ReliableConfig {
+        timeout_us: 1000,
+        max_retries: 2,
+    };
+
+    fn reliable_frame(sequence: u32) -> IdtpFrame {
+        let mut header = IdtpHeader::new();
+        header.sequence = sequence;
+        header.set_flags(IDTP_FLAG_RELIABLE);
+
+        let mut frame = IdtpFrame::new();
+        frame.set_header(&header);
+        frame.set_payload(&sequence.to_be_bytes());
+        frame.update_integrity();
+        frame
+    }
+
+    fn retransmitted(sender: &mut ReliableSender<4>, now_us: u64) -> Vec<u32> {
+        let mut sequences = Vec::new();
+
+        while let Some(event) = sender.poll(now_us) {
+            match event {
+                SenderEvent::Retransmit(frame) => {
+                    sequences.push(frame.header().sequence);
+                }
+                SenderEvent::Expired(_) => panic!("unexpected expiry"),
+            }
+        }
+
+        sequences
+    }
+
+    #[test]
+    fn test_ack_encoding() {
+        let ack = Ack {
+            sequence: 100,
+            bitmap: 0b101,
+        };
+
+        let mut buffer = [0u8; Ack::SIZE];
+        assert_eq!(ack.encode(&mut buffer), Ok(8));
+        assert_eq!(buffer, [0, 0, 0, 100, 0, 0, 0, 5]);
+        assert_eq!(Ack::decode(&buffer), Ok(ack));
+        assert_eq!(Ack::decode(&buffer[..7]), Err(IdtpError::InvalidPayload));
+
+        assert!(ack.acknowledges(100));
+        assert!(ack.acknowledges(99) && ack.acknowledges(97));
+        assert!(!ack.acknowledges(98) && ack.reports_missing(98));
+        assert!(!ack.acknowledges(101) && !ack.reports_missing(101));
+        assert!(ack.reports_missing(100 - ACK_BITMAP_SIZE));
+        assert!(!ack.reports_missing(99 - ACK_BITMAP_SIZE));
+
+        // Bitmap covers sequence wrap.
+        let ack = Ack {
+            sequence: 1,
+            bitmap: 0b11,
+        };
+        assert!(ack.acknowledges(0) && ack.acknowledges(u32::MAX));
+    }
+
+    #[test]
+    fn test_receiver_duplicates() {
+        let mut receiver = ReliableReceiver::new();
+
+        let Reception::New(ack) = receiver.receive(10) else {
+            panic!("first frame is new");
+        };
+        assert_eq!(
+            ack,
+            Ack {
+                sequence: 10,
+                bitmap: 0
+            }
+        );
+
+        // Frames 11 and 12 are lost, 14 overtakes 13.
+        assert_eq!(
+            receiver.receive(14),
+            Reception::New(Ack {
+                sequence: 14,
+                bitmap: 0b1000,
+            })
+        );
+        assert_eq!(
+            receiver.receive(13),
+            Reception::New(Ack {
+                sequence: 13,
+                bitmap: 0b100,
+            })
+        );
+        assert_eq!(
+            receiver.receive(14),
+            Reception::Duplicate(Ack {
+                sequence: 14,
+                bitmap: 0b1001,
+            })
+        );
+
+        // Late retransmission within history is still accepted once.
+        assert!(matches!(receiver.receive(11), Reception::New(_)));
+        assert!(matches!(receiver.receive(11), Reception::Duplicate(_)));
+
+        // Frames beyond history can not be deduplicated.
+        receiver.receive(14 + RELIABLE_HISTORY_SIZE);
+        assert_eq!(receiver.receive(14), Reception::Stale);
+        assert!(matches!(receiver.receive(15), Reception::New(_)));
+
+        receiver.reset();
+        assert!(matches!(receiver.receive(14), Reception::New(_)));
+    }
+
+    #[test]
+    fn test_sender_retransmission() {
+        let mut sender = ReliableSender::<4>::new(CONFIG);
+
+        for sequence in 0..4 {
+            sender.send(&reliable_frame(sequence), 0).unwrap();
+        }
+
+        assert!(sender.is_full());
+        assert_eq!(
+            sender.send(&reliable_frame(4), 0),
+            Err(IdtpError::BufferTooSmall)
+        );
+        assert_eq!(sender.next_due(), Some(1000));
+        assert!(sender.poll(999).is_none());
+
+        // Frame 2 acknowledged, frames 0 and 1 reported missing.
+        let ack = Ack {
+            sequence: 2,
+            bitmap: 0,
+        };
+        assert_eq!(sender.acknowledge(&ack, 500), 1);
+        assert_eq!(sender.pending(), 3);
+        assert_eq!(retransmitted(&mut sender, 500), [0, 1]);
+
+        // Repeated NACK does not trigger another retransmission.
+        sender.acknowledge(&ack, 600);
+        assert!(sender.poll(600).is_none());
+
+        // Timeout of frame 3.
+        assert_eq!(retransmitted(&mut sender, 1000), [3]);
+
+        // One ACK releases several frames, frame 0 is still missing.
+        let ack = Ack {
+            sequence: 3,
+            bitmap: 0b011,
+        };
+        assert_eq!(sender.acknowledge(&ack, 1100), 2);
+        assert_eq!(sender.pending(), 1);
+        assert!(sender.poll(1100).is_none());
+        sender.send(&reliable_frame(4), 1100).unwrap();
+        assert_eq!(retransmitted(&mut sender, 1500), [0]);
+    }
+
+    #[test]
+    fn test_sender_expiry() {
+        let mut sender = ReliableSender::<4>::new(CONFIG);
+        sender.send(&reliable_frame(7), 0).unwrap();
+
+        assert_eq!(retransmitted(&mut sender, 1000), [7]);
+        assert_eq!(retransmitted(&mut sender, 2000), [7]);
+
+        let Some(SenderEvent::Expired(header)) = sender.poll(3000) else {
+            panic!("frame expires after retry limit");
+        };
+        assert_eq!({ header.sequence }, 7);
+        assert_eq!(sender.pending(), 0);
+        assert_eq!(sender.next_due(), None);
+
+        // Only reliable frames with unique sequence are kept.
+        let mut frame = reliable_frame(8);
+        let mut header = frame.header();
+        header.set_flags(0);
+        frame.set_header(&header);
+        assert_eq!(sender.send(&frame, 0), Err(IdtpError::InvalidPayload));
+        sender.send(&reliable_frame(8), 0).unwrap();
+        assert_eq!(
+            sender.send(&reliable_frame(8), 0),
+            Err(IdtpError::InvalidPayload)
+        );
+    }
+}
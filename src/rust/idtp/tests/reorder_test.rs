@@ -0,0 +1,136 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Jitter/reorder buffer tests.
+
+extern crate idtp;
+
+#[cfg(test)]
+mod tests {
+    use idtp::reorder::*;
+    use idtp::*;
+
+    fn drain<T: Copy, const N: usize>(
+        buffer: &mut ReorderBuffer<T, N>,
+        now_us: u64,
+    ) -> Vec<(u32, u32)> {
+        let mut released = Vec::new();
+
+        while let Some(frame) = buffer.pop(now_us) {
+            released.push((frame.sequence, frame.skipped));
+        }
+
+        released
+    }
+
+    #[test]
+    fn test_release_in_order() {
+        let mut buffer = ReorderBuffer::<u32, 8>::new(1000);
+        assert_eq!(buffer.next_sequence(), None);
+
+        for (time, sequence) in [10, 12, 11, 14, 13].into_iter().enumerate() {
+            let arrival = buffer.push(sequence, sequence * 2, time as u64);
+            assert_eq!(arrival, Ok(Arrival::Buffered));
+        }
+
+        assert_eq!(
+            drain(&mut buffer, 5),
+            [(10, 0), (11, 0), (12, 0), (13, 0), (14, 0)]
+        );
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.next_sequence(), Some(15));
+
+        // Items are kept with their sequence numbers.
+        buffer.push(15, 30, 6).unwrap();
+        assert_eq!(buffer.pop(6).map(|frame| frame.item), Some(30));
+
+        // Sequence wrap.
+        let mut buffer = ReorderBuffer::<u32, 4>::new(1000);
+        buffer.push(u32::MAX, 0, 0).unwrap();
+        buffer.push(1, 0, 0).unwrap();
+        buffer.push(0, 0, 0).unwrap();
+        assert_eq!(drain(&mut buffer, 0), [(u32::MAX, 0), (0, 0), (1, 0)]);
+    }
+
+    #[test]
+    fn test_skip_gap_after_latency() {
+        let mut buffer = ReorderBuffer::<(), 8>::new(1000);
+        buffer.push(0, (), 0).unwrap();
+        assert_eq!(drain(&mut buffer, 0), [(0, 0)]);
+
+        // Frames 1 and 2 are lost.
+        buffer.push(3, (), 100).unwrap();
+        buffer.push(4, (), 200).unwrap();
+        assert_eq!(buffer.next_due(), Some(1100));
+        assert!(drain(&mut buffer, 1099).is_empty());
+        assert_eq!(drain(&mut buffer, 1100), [(3, 2), (4, 0)]);
+
+        // Lost frames arriving after their place was released are late.
+        assert_eq!(buffer.push(1, (), 1200), Ok(Arrival::Late));
+        assert_eq!(buffer.push(4, (), 1200), Ok(Arrival::Late));
+
+        buffer.push(6, (), 1300).unwrap();
+        assert_eq!(buffer.push(6, (), 1400), Ok(Arrival::Duplicate));
+        assert_eq!(buffer.len(), 1);
+        assert_eq!(
+            buffer.flush().map(|f| (f.sequence, f.skipped)),
+            Some((6, 1))
+        );
+        assert_eq!(buffer.flush(), None);
+    }
+
+    #[test]
+    fn test_skip_gap_when_full() {
+        let mut buffer = ReorderBuffer::<u8, 4>::new(u64::MAX);
+        buffer.push(0, 0, 0).unwrap();
+        drain(&mut buffer, 0);
+
+        for sequence in 2..5 {
+            buffer.push(sequence, 0, 0).unwrap();
+            assert!(drain(&mut buffer, 0).is_empty());
+        }
+
+        // The last free slot is taken: gap is skipped to make room.
+        buffer.push(5, 0, 0).unwrap();
+        assert_eq!(drain(&mut buffer, 0), [(2, 1), (3, 0), (4, 0), (5, 0)]);
+
+        // Buffer not drained after filling up rejects frames.
+        for sequence in [7, 8, 9, 10] {
+            buffer.push(sequence, 0, 0).unwrap();
+        }
+        assert_eq!(buffer.push(11, 0, 0), Err(IdtpError::BufferTooSmall));
+
+        buffer.reset();
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.push(1, 0, 0), Ok(Arrival::Buffered));
+        assert_eq!(drain(&mut buffer, 0), [(1, 0)]);
+    }
+
+    #[test]
+    fn test_reorder_frames() {
+        let mut buffer = ReorderBuffer::<IdtpFrame>::default();
+
+        for sequence in [2u32, 0, 1] {
+            let mut header = IdtpHeader::new();
+            header.sequence = sequence;
+
+            let mut frame = IdtpFrame::new();
+            frame.set_header(&header);
+            buffer.push(sequence, frame, 0).unwrap();
+        }
+
+        let mut sequences = Vec::new();
+
+        while let Some(released) = buffer.pop(0) {
+            assert_eq!({ released.item.header().sequence }, released.sequence);
+            sequences.push(released.sequence);
+        }
+
+        // The first frame sets the start: frames 0 and 1 are late.
+        assert_eq!(sequences, [2]);
+        assert_eq!(
+            buffer.push(3, IdtpFrame::new(), REORDER_DEFAULT_LATENCY_US),
+            Ok(Arrival::Buffered)
+        );
+    }
+}
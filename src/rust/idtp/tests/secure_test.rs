@@ -0,0 +1,230 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP-E (Secure mode) tests.
+
+extern crate idtp;
+
+#[cfg(test)]
+mod tests {
+    use idtp::secure::*;
+    use idtp::*;
+
+    fn hex(string: &str) -> Vec<u8> {
+        (0..string.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&string[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    /// AES-128 test cases 1-4 from "The Galois/Counter Mode of Operation
+    /// (GCM)" by McGrew and Viega: key, IV, plaintext, AAD, ciphertext, tag.
+    const GCM_VECTORS: [[&str; 6]; 4] = [
+        [
+            "00000000000000000000000000000000",
+            "000000000000000000000000",
+            "",
+            "",
+            "",
+            "58e2fccefa7e3061367f1d57a4e7455a",
+        ],
+        [
+            "00000000000000000000000000000000",
+            "000000000000000000000000",
+            "00000000000000000000000000000000",
+            "",
+            "0388dace60b6a392f328c2b971b2fe78",
+            "ab6e47d42cec13bdf53a67b21257bddf",
+        ],
+        [
+            "feffe9928665731c6d6a8f9467308308",
+            "cafebabefacedbaddecaf888",
+            "d9313225f88406e5a55909c5aff5269a\
+             86a7a9531534f7da2e4c303d8a318a72\
+             1c3c0c95956809532fcf0e2449a6b525\
+             b16aedf5aa0de657ba637b391aafd255",
+            "",
+            "42831ec2217774244b7221b784d0d49c\
+             e3aa212f2c02a4e035c17e2329aca12e\
+             21d514b25466931c7d8f6a5aac84aa05\
+             1ba30b396a0aac973d58e091473f5985",
+            "4d5c2af327cd64a62cf35abd2ba6fab4",
+        ],
+        [
+            "feffe9928665731c6d6a8f9467308308",
+            "cafebabefacedbaddecaf888",
+            "d9313225f88406e5a55909c5aff5269a\
+             86a7a9531534f7da2e4c303d8a318a72\
+             1c3c0c95956809532fcf0e2449a6b525\
+             b16aedf5aa0de657ba637b39",
+            "feedfacedeadbeeffeedfacedeadbeefabaddad2",
+            "42831ec2217774244b7221b784d0d49c\
+             e3aa212f2c02a4e035c17e2329aca12e\
+             21d514b25466931c7d8f6a5aac84aa05\
+             1ba30b396a0aac973d58e091",
+            "5bc94fbc3221a5db94fae95ae7121a47",
+        ],
+    ];
+
+    fn test_key(id: u8) -> SecureKey {
+        SecureKey::new(id, &[id; IDTP_KEY_SIZE])
+    }
+
+    fn test_frame(payload: &[u8]) -> IdtpFrame {
+        let mut header = IdtpHeader::new();
+        header.device_id = 0x1234;
+        header.sequence = 42;
+        header.timestamp = 1000;
+        header.payload_type = 0x01;
+
+        let mut frame = IdtpFrame::new();
+        frame.set_header(&header);
+        frame.set_payload(payload);
+        frame
+    }
+
+    fn pack(frame: &IdtpFrame) -> Vec<u8> {
+        let mut buffer = vec![0u8; frame.frame_size()];
+        frame.pack(&mut buffer).unwrap();
+        buffer
+    }
+
+    #[test]
+    fn test_gcm_vectors() {
+        for [key, iv, plaintext, aad, ciphertext, tag] in GCM_VECTORS {
+            let key = SecureKey::new(0, &hex(key).try_into().unwrap());
+            let nonce = hex(iv).try_into().unwrap();
+            let mut buffer = hex(plaintext);
+
+            let actual = key.encrypt(&nonce, &hex(aad), &mut buffer);
+            assert_eq!(buffer, hex(ciphertext));
+            assert_eq!(actual.to_vec(), hex(tag));
+
+            key.decrypt(&nonce, &hex(aad), &mut buffer, &actual)
+                .unwrap();
+            assert_eq!(buffer, hex(plaintext));
+        }
+    }
+
+    #[test]
+    fn test_nonce() {
+        assert_eq!(
+            nonce(0x1234, 0xdeadbeef),
+            [0x12, 0x34, 0, 0, 0, 0, 0, 0, 0xde, 0xad, 0xbe, 0xef]
+        );
+    }
+
+    #[test]
+    fn test_seal_open() {
+        let payload = b"secret IMU measurements";
+        let mut key = test_key(7);
+
+        let mut frame = test_frame(payload);
+        frame.seal(&mut key).unwrap();
+
+        let header = frame.header();
+        assert!(matches!(header.mode, Mode::Secure));
+        assert_eq!(header.key_id(), 7);
+        assert_eq!(frame.payload_size(), payload.len() + IDTP_TAG_SIZE);
+        assert_ne!(&frame.payload()[..payload.len()], payload);
+
+        // Sealed frame stays valid for receivers without the key.
+        let bytes = pack(&frame);
+        let mut received = IdtpFrame::parse(&bytes).unwrap();
+        assert!(received.verify().is_ok());
+
+        received.open(&key).unwrap();
+        assert_eq!(received.payload(), payload);
+        assert_eq!({ received.header().payload_size }, payload.len() as u32);
+    }
+
+    #[test]
+    fn test_seal_too_large() {
+        let mut frame = test_frame(&[0u8; IDTP_PAYLOAD_MAX_SIZE]);
+        assert_eq!(frame.seal(&mut test_key(1)), Err(IdtpError::FrameTooLarge));
+
+        let max = IDTP_PAYLOAD_MAX_SIZE - IDTP_TAG_SIZE;
+        let mut frame = test_frame(&vec![0u8; max]);
+        frame.seal(&mut test_key(1)).unwrap();
+        assert_eq!(frame.payload_size(), IDTP_PAYLOAD_MAX_SIZE);
+
+        frame.open(&test_key(1)).unwrap();
+        assert_eq!(frame.payload_size(), max);
+    }
+
+    #[test]
+    fn test_seal_rejects_nonce_reuse() {
+        let mut key = test_key(5);
+        let mut frame = test_frame(&[1, 2, 3, 4]);
+        frame.seal(&mut key).unwrap();
+
+        // Sequence restarted (e.g. after reboot) or was not incremented.
+        for sequence in [0, 42] {
+            let mut frame = test_frame(&[1, 2, 3, 4]);
+            let mut header = frame.header();
+            header.sequence = sequence;
+            frame.set_header(&header);
+
+            let plain = pack(&frame);
+            assert_eq!(frame.seal(&mut key), Err(IdtpError::KeyUnavailable));
+            assert_eq!(pack(&frame), plain);
+        }
+
+        let mut frame = test_frame(&[1, 2, 3, 4]);
+        let mut header = frame.header();
+        header.sequence = 43;
+        frame.set_header(&header);
+        frame.seal(&mut key).unwrap();
+
+        // New key starts with no sealed frames.
+        let mut frame = test_frame(&[1, 2, 3, 4]);
+        frame.seal(&mut test_key(6)).unwrap();
+    }
+
+    #[test]
+    fn test_open_rejects_tampering() {
+        let mut key = test_key(3);
+        let mut frame = test_frame(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        frame.seal(&mut key).unwrap();
+        let bytes = pack(&frame);
+
+        // Header fields (offset 12..16 is timestamp), ciphertext and tag.
+        for offset in [8, 14, 17, 28, 32, 39, 40, 55] {
+            let mut tampered = bytes.clone();
+            tampered[offset] ^= 0x01;
+
+            let mut received = IdtpFrame::parse(&tampered).unwrap();
+            assert_eq!(
+                received.open(&key),
+                Err(IdtpError::AuthenticationFailed),
+                "offset {offset}"
+            );
+        }
+
+        let mut received = IdtpFrame::parse(&bytes).unwrap();
+        let wrong = SecureKey::new(3, &[0xff; IDTP_KEY_SIZE]);
+        assert_eq!(received.open(&wrong), Err(IdtpError::AuthenticationFailed));
+        assert_eq!(
+            received.open(&test_key(4)),
+            Err(IdtpError::AuthenticationFailed)
+        );
+
+        // Failed attempts leave frame unchanged.
+        assert_eq!(pack(&received), bytes);
+        received.open(&key).unwrap();
+    }
+
+    #[test]
+    fn test_open_rejects_plain_frames() {
+        let key = test_key(0);
+        let mut frame = test_frame(&[0u8; 32]);
+        frame.update_integrity();
+        assert_eq!(frame.open(&key), Err(IdtpError::AuthenticationFailed));
+
+        let mut frame = test_frame(&[]);
+        let mut header = frame.header();
+        header.mode = Mode::Secure;
+        frame.set_header(&header);
+        assert_eq!(frame.open(&key), Err(IdtpError::AuthenticationFailed));
+    }
+}
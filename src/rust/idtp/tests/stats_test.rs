@@ -0,0 +1,155 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Link and stream statistics tests.
+
+extern crate idtp;
+
+#[cfg(test)]
+mod tests {
+    use idtp::stats::*;
+    use idtp::*;
+
+    fn frame(sequence: u32, mode: Mode) -> IdtpFrame {
+        let mut header = IdtpHeader::new();
+        header.sequence = sequence;
+        header.mode = mode;
+
+        let mut frame = IdtpFrame::new();
+        frame.set_header(&header);
+        frame.set_payload(&[0u8; 8]);
+        frame.update_integrity();
+        frame
+    }
+
+    fn record(stats: &mut StreamStats, frame: &IdtpFrame, now_us: u64) {
+        stats.record(&frame.header(), &frame.verify(), Some(now_us));
+    }
+
+    #[test]
+    fn test_sequence_statistics() {
+        let mut stats = StreamStats::new();
+        let size = frame(0, Mode::Normal).frame_size() as u64;
+
+        // Frames 3 and 4 are lost, 2 overtakes 1 and 6 is duplicated.
+        for (time, sequence) in [0, 2, 1, 5, 6, 6].into_iter().enumerate() {
+            let time = time as u64 * 1000;
+            record(&mut stats, &frame(sequence, Mode::Normal), time);
+        }
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.frames, 6);
+        assert_eq!(snapshot.bytes, 6 * size);
+        assert_eq!(snapshot.gaps, 2);
+        assert_eq!(snapshot.lost, 2);
+        assert_eq!(snapshot.reordered, 1);
+        assert_eq!(snapshot.duplicates, 1);
+        assert_eq!(snapshot.checksum_failures, 0);
+        assert_eq!(snapshot.crc_failures, 0);
+
+        // 5 unique frames arrived within 4 ms.
+        assert!((snapshot.frame_rate_hz.unwrap() - 1000.0).abs() < 1e-9);
+        assert_eq!(snapshot.jitter_p99_us, Some(0));
+
+        // Sequence wrap is not a gap, stream restart is not reordering.
+        let mut stats = StreamStats::new();
+
+        for sequence in [u32::MAX - 1, u32::MAX, 0, 1, 5000, 0, 1] {
+            record(&mut stats, &frame(sequence, Mode::Normal), 0);
+        }
+
+        let snapshot = stats.snapshot();
+        assert_eq!((snapshot.gaps, snapshot.lost), (1, 4998));
+        assert_eq!((snapshot.reordered, snapshot.duplicates), (0, 0));
+        assert_eq!(snapshot.frame_rate_hz, None);
+
+        // Late frame beyond history is counted as reordered.
+        let mut stats = StreamStats::new();
+
+        for sequence in [0, 100, 30] {
+            record(&mut stats, &frame(sequence, Mode::Normal), 0);
+        }
+
+        let snapshot = stats.snapshot();
+        assert_eq!((snapshot.lost, snapshot.reordered), (98, 1));
+    }
+
+    #[test]
+    fn test_integrity_failures() {
+        let mut stats = StreamStats::default();
+        stats.record_resyncs(2);
+
+        let mut corrupted = frame(0, Mode::Safety);
+        corrupted.set_payload(&[1u8; 8]);
+        record(&mut stats, &corrupted, 0);
+
+        let mut corrupted = frame(1, Mode::Normal);
+        let mut header = corrupted.header();
+        header.checksum ^= 1;
+        corrupted.set_header(&header);
+        record(&mut stats, &corrupted, 0);
+
+        // Corrupted frames do not affect sequence analysis.
+        record(&mut stats, &frame(5, Mode::Safety), 0);
+        record(&mut stats, &frame(6, Mode::Safety), 0);
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.frames, 4);
+        assert_eq!(snapshot.checksum_failures, 2);
+        assert_eq!(snapshot.crc_failures, 1);
+        assert_eq!(snapshot.resyncs, 2);
+        assert_eq!(snapshot.gaps, 0);
+
+        stats.reset();
+        assert_eq!(stats.snapshot(), StatsSnapshot::default());
+    }
+
+    #[test]
+    fn test_jitter() {
+        let mut stats = StreamStats::new();
+        let mut now_us = 0;
+
+        // 10 ms period: every 10th interval is 3 ms longer and the next one
+        // 3 ms shorter.
+        for sequence in 0..=200 {
+            let delay = if sequence % 10 == 5 { 3000 } else { 0 };
+            record(&mut stats, &frame(sequence, Mode::Normal), now_us + delay);
+            now_us += 10_000;
+        }
+
+        let snapshot = stats.snapshot();
+        assert!((snapshot.frame_rate_hz.unwrap() - 100.0).abs() < 1e-9);
+
+        // Every delayed frame gives jitter values of 3, 6 and 3 ms: 20 of
+        // 199 values are 6 ms, so p99 falls into their bucket.
+        let jitter_mean_us = snapshot.jitter_mean_us.unwrap();
+        let jitter_p99_us = snapshot.jitter_p99_us.unwrap();
+        assert!((jitter_mean_us - 240_000.0 / 199.0).abs() < 1e-6);
+        assert!((6000..=6000 * 5 / 4).contains(&jitter_p99_us));
+
+        let summary = stats.to_string();
+        assert!(summary.starts_with("frames: 201 ("));
+        assert!(summary.contains("frame rate: 100.0 Hz"));
+        assert!(summary.contains("gaps: 0 (0 lost)"));
+        assert!(summary.ends_with(&format!("p99 {jitter_p99_us} us")));
+    }
+
+    #[test]
+    fn test_without_arrival_times() {
+        let mut stats = StreamStats::new();
+
+        for sequence in [0, 1, 3] {
+            let frame = frame(sequence, Mode::Normal);
+            stats.record(&frame.header(), &frame.verify(), None);
+        }
+
+        let snapshot = stats.snapshot();
+        assert_eq!((snapshot.frames, snapshot.lost), (3, 1));
+        assert_eq!(snapshot.frame_rate_hz, None);
+        assert_eq!(snapshot.jitter_mean_us, None);
+
+        let summary = stats.to_string();
+        assert!(summary.contains("frame rate: n/a"));
+        assert!(summary.ends_with("jitter: mean n/a, p99 n/a"));
+    }
+}
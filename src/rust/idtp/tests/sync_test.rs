@@ -0,0 +1,234 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Clock synchronization tests against simulated clocks.
+
+extern crate idtp;
+
+#[cfg(test)]
+mod tests {
+    use idtp::sync::*;
+
+    /// Host time of simulation start in microseconds.
+    const HOST_START: u64 = 1_700_000_000_000_000;
+
+    /// Small deterministic pseudo-random number generator (SplitMix64).
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = self.0;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^ (z >> 31)
+        }
+
+        fn below(&mut self, limit: u64) -> u64 {
+            self.next() % limit
+        }
+    }
+
+    /// Device clock with constant offset and drift.
+    struct DeviceClock {
+        /// Device time at simulation start in microseconds.
+        start_us: f64,
+        /// Device microseconds per host microsecond minus one.
+        skew: f64,
+    }
+
+    impl DeviceClock {
+        /// Device clock which wraps around 30 seconds after start and runs
+        /// 50 ppm slow.
+        fn new() -> Self {
+            Self {
+                start_us: f64::from(u32::MAX - 30_000) * 1000.0,
+                skew: -50e-6,
+            }
+        }
+
+        fn device_us(&self, host: u64) -> f64 {
+            self.start_us + (host - HOST_START) as f64 * (1.0 + self.skew)
+        }
+
+        fn timestamp(&self, host: u64) -> u32 {
+            ((self.device_us(host) / 1000.0) as u64 % (1 << 32)) as u32
+        }
+
+        /// Host time when device clock showed the middle of `timestamp`
+        /// (`timestamp` is counted from simulation start without wrapping).
+        fn host(&self, timestamp: u64) -> f64 {
+            let device_us = timestamp as f64 * 1000.0 + 500.0;
+            HOST_START as f64 + (device_us - self.start_us) / (1.0 + self.skew)
+        }
+    }
+
+    /// Check mapping of timestamps from simulation start up to `end_ms`
+    /// milliseconds after it.
+    fn check_mapper(
+        clock: &DeviceClock,
+        mapper: &TimeMapper,
+        end_ms: u64,
+        max_uncertainty: u64,
+    ) {
+        let first = (clock.start_us / 1000.0) as u64;
+
+        for timestamp in (first..first + end_ms).step_by(997) {
+            let mapped = mapper.to_host(timestamp as u32);
+            let actual = clock.host(timestamp);
+            let error = (mapped.time_us as f64 - actual).abs();
+
+            assert!(
+                error <= mapped.uncertainty_us as f64,
+                "timestamp {timestamp}: error {error}, {mapped:?}"
+            );
+            assert!(mapped.uncertainty_us <= max_uncertainty, "{mapped:?}");
+        }
+    }
+
+    #[test]
+    fn test_sync_sample() {
+        let sample = SyncSample {
+            t1: 10_000,
+            t2: 500,
+            t3: 502,
+            t4: 15_000,
+            period_us: SYNC_DEFAULT_PERIOD_US,
+        };
+
+        assert_eq!(sample.round_trip(), 3000);
+        // Host midpoint 12500 us matches device midpoint 501.5 ms.
+        assert_eq!(sample.offset_us(), 12_500 - 501_500);
+
+        // The same exchange with microsecond device timestamps.
+        let sample = SyncSample {
+            t2: 500_000,
+            t3: 502_000,
+            period_us: 1.0,
+            ..sample
+        };
+        assert_eq!(sample.round_trip(), 3000);
+        assert_eq!(sample.offset_us(), 12_500 - 501_000);
+
+        // Exchange in another timestamp unit restarts estimation.
+        let mut estimator = SyncEstimator::<4>::new();
+        estimator.add(&sample);
+        estimator.add(&SyncSample::default());
+        assert_eq!(estimator.len(), 1);
+    }
+
+    #[test]
+    fn test_sync_estimator() {
+        let clock = DeviceClock::new();
+        let mut rng = Rng(1);
+        let mut estimator = SyncEstimator::<128>::new();
+        assert!(estimator.mapper().is_none());
+
+        // Ping every 5 seconds for 10 minutes with jittery delays and some
+        // exchanges delayed by queueing.
+        for i in 0..120 {
+            let t1 = HOST_START + i * 5_000_000 + rng.below(1000);
+            let received = t1 + 200 + rng.below(2000);
+            let sent = received + 300 + rng.below(2000);
+            let mut t4 = sent + 200 + rng.below(2000);
+
+            if i % 10 == 3 {
+                t4 += 20_000;
+            }
+
+            estimator.add(&SyncSample {
+                t1,
+                t2: clock.timestamp(received),
+                t3: clock.timestamp(sent),
+                t4,
+                period_us: SYNC_DEFAULT_PERIOD_US,
+            });
+        }
+
+        assert_eq!(estimator.len(), 120);
+        let mapper = estimator.mapper().unwrap();
+        assert!((mapper.drift_ppm() - 50.0).abs() < 2.0, "{mapper:?}");
+
+        // Inside window and extrapolated for one more minute.
+        check_mapper(&clock, &mapper, 660_000, 3000);
+
+        // Extrapolation for an hour is less confident.
+        let first = (clock.start_us / 1000.0) as u64;
+        let late = first + 4_200_000;
+        let mapped = mapper.to_host(late as u32);
+        assert!((mapped.time_us as f64 - clock.host(late)).abs() < 10_000.0);
+        assert!(mapped.uncertainty_us > 10_000);
+
+        // Inverse conversion.
+        let timestamp = clock.timestamp(HOST_START + 100_000_000);
+        let host = mapper.to_host(timestamp).time_us;
+        assert!(mapper.to_device(host).abs_diff(timestamp) <= 1);
+    }
+
+    #[test]
+    fn test_sync_estimator_single_exchange() {
+        let clock = DeviceClock::new();
+        let mut estimator = SyncEstimator::<4>::default();
+
+        let t1 = HOST_START + 1000;
+        estimator.add(&SyncSample {
+            t1,
+            t2: clock.timestamp(t1 + 400),
+            t3: clock.timestamp(t1 + 400),
+            t4: t1 + 800,
+            period_us: SYNC_DEFAULT_PERIOD_US,
+        });
+
+        // Drift is unknown, so default tolerance bounds extrapolation.
+        let mapper = estimator.mapper().unwrap();
+        assert_eq!(mapper.drift_ppm(), 0.0);
+        check_mapper(&clock, &mapper, 60_000, 7000);
+    }
+
+    #[test]
+    fn test_passive_estimator() {
+        let clock = DeviceClock::new();
+        let mut rng = Rng(2);
+        let mut estimator = PassiveEstimator::<1024>::new(1500);
+
+        // Frame every 100 ms for 2 minutes (window keeps the latest 1024),
+        // latency is 1.5 ms plus up to 3 ms of jitter.
+        for i in 0..1200 {
+            let sampled = HOST_START + i * 100_000 + rng.below(100);
+            estimator.add(
+                clock.timestamp(sampled),
+                sampled + 1500 + rng.below(3000),
+            );
+        }
+
+        assert_eq!(estimator.len(), 1024);
+        let mapper = estimator.mapper().unwrap();
+        assert!((mapper.drift_ppm() - 50.0).abs() < 5.0, "{mapper:?}");
+        // First 18 seconds are outside of window.
+        check_mapper(&clock, &mapper, 130_000, 5000);
+        assert!(
+            mapper
+                .to_host(clock.timestamp(HOST_START + 60_000_000))
+                .uncertainty_us
+                <= 4000
+        );
+
+        let mut estimator = PassiveEstimator::<8>::new(0);
+        estimator.add(1, HOST_START);
+        assert!(estimator.mapper().is_none());
+
+        // Nanosecond timestamps of clock without drift.
+        estimator.set_period_us(0.001);
+        assert!(estimator.is_empty());
+        estimator.add(0, HOST_START);
+        estimator.add(2_000_000_000, HOST_START + 2_000_000);
+
+        let mapper = estimator.mapper().unwrap();
+        assert_eq!(mapper.drift_ppm(), 0.0);
+        assert_eq!(
+            mapper.to_host(1_000_000_000).time_us,
+            HOST_START + 1_000_000
+        );
+        assert_eq!(mapper.to_device(HOST_START + 500_000), 500_000_000);
+    }
+}
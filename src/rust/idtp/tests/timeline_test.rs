@@ -0,0 +1,149 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Sequence number and timestamp unwrapping tests.
+
+extern crate idtp;
+
+#[cfg(test)]
+mod tests {
+    use idtp::timeline::*;
+    use idtp::*;
+
+    fn header(device_id: u16, sequence: u32, timestamp: u32) -> IdtpHeader {
+        let mut header = IdtpHeader::new();
+        header.device_id = device_id;
+        header.sequence = sequence;
+        header.timestamp = timestamp;
+        header
+    }
+
+    #[test]
+    fn test_unwrap_u32() {
+        assert_eq!(unwrap_u32(0, 5), 5);
+        assert_eq!(unwrap_u32(0x1_0000_0005, 2), 0x1_0000_0002);
+        assert_eq!(unwrap_u32(0xFFFF_FFF0, 3), 0x1_0000_0003);
+        assert_eq!(unwrap_u32(0x1_0000_0003, 0xFFFF_FFF0), 0xFFFF_FFF0);
+
+        // Timeline never goes below zero.
+        assert_eq!(unwrap_u32(5, u32::MAX), u64::from(u32::MAX));
+    }
+
+    #[test]
+    fn test_timeline_wraps() {
+        let mut timeline = Timeline::new();
+        let start = u32::MAX - 2;
+
+        for i in 0..6u32 {
+            let unwrapped = timeline
+                .update(start.wrapping_add(i), start.wrapping_add(i * 10));
+            assert_eq!(unwrapped.sequence, u64::from(start) + u64::from(i));
+            assert_eq!(
+                unwrapped.timestamp,
+                u64::from(start) + u64::from(i) * 10
+            );
+            assert_eq!(unwrapped.epoch, 0);
+            assert!(!unwrapped.reboot);
+        }
+
+        assert_eq!(timeline.sequence(), u64::from(u32::MAX) + 3);
+
+        // Late frame from before the wrap.
+        let late = timeline.update(u32::MAX, start.wrapping_add(20));
+        assert_eq!(late.sequence, u64::from(u32::MAX));
+        assert_eq!(late.timestamp, u64::from(start) + 20);
+        assert_eq!(timeline.sequence(), u64::from(u32::MAX) + 3);
+
+        // Gap in sequence numbers.
+        let unwrapped = timeline.update(100, 2000);
+        assert_eq!(unwrapped.sequence, u64::from(u32::MAX) + 101);
+    }
+
+    #[test]
+    fn test_timeline_reboot() {
+        let mut timeline = Timeline::new();
+
+        for sequence in 5000..5010 {
+            timeline.update(sequence, sequence * 10);
+        }
+
+        // Sequence and time restart.
+        let unwrapped = timeline.update(0, 3);
+        assert!(unwrapped.reboot);
+        assert_eq!(unwrapped.epoch, 1);
+        assert_eq!(unwrapped.sequence, 5010);
+        assert_eq!(unwrapped.timestamp, 50_091);
+
+        let unwrapped = timeline.update(1, 13);
+        assert!(!unwrapped.reboot);
+        assert_eq!(unwrapped.epoch, 1);
+        assert_eq!((unwrapped.sequence, unwrapped.timestamp), (5011, 50_101));
+
+        // Sequence restart with continuous time is not a reboot.
+        for sequence in 2..3000 {
+            timeline.update(sequence, 13 + (sequence - 1) * 10);
+        }
+        let unwrapped = timeline.update(0, 30_000);
+        assert!(!unwrapped.reboot);
+        assert_eq!(unwrapped.epoch, 1);
+        assert_eq!(unwrapped.sequence, 5010 + 3000);
+        assert_eq!(unwrapped.timestamp, 50_091 + 30_000 - 3);
+
+        // Sequence restart with time jump forward is a reboot.
+        timeline.update(4000, 40_000);
+        let unwrapped = timeline.update(0, 40_000 + UNWRAP_MAX_GAP_MS + 1);
+        assert!(unwrapped.reboot);
+        assert_eq!(timeline.epoch(), 2);
+    }
+
+    #[test]
+    fn test_timeline_units() {
+        // Sequence restart 30 seconds later is not a reboot.
+        let mut timeline = Timeline::with_unit(TimestampUnit::Microseconds, 0);
+        timeline.update(4000, 40_000_000);
+        assert!(!timeline.update(0, 70_000_000).reboot);
+
+        // The same gap in 1 kHz ticks.
+        let mut timeline = Timeline::with_unit(TimestampUnit::Ticks, 1000);
+        timeline.update(4000, 40_000);
+        assert!(!timeline.update(0, 70_000).reboot);
+        timeline.update(4000, 80_000);
+        assert!(timeline.update(0, 80_000 + UNWRAP_MAX_GAP_MS + 1).reboot);
+
+        // Ticks with unknown rate: only time going back is a jump.
+        let mut timeline = Timeline::with_unit(TimestampUnit::Ticks, 0);
+        timeline.update(4000, 0);
+        assert!(!timeline.update(0, i32::MAX as u32).reboot);
+        timeline.update(4000, 10);
+        assert!(timeline.update(0, 5).reboot);
+
+        // Unwrapper uses timestamp unit of device frames.
+        let mut unwrapper = Unwrapper::<1>::new();
+        let mut microseconds = header(1, 4000, 40_000_000);
+        microseconds.set_timestamp_unit(TimestampUnit::Microseconds);
+        unwrapper.update(&microseconds);
+        microseconds.sequence = 0;
+        microseconds.timestamp = 70_000_000;
+        assert!(!unwrapper.update(&microseconds).reboot);
+    }
+
+    #[test]
+    fn test_unwrapper_devices() {
+        let mut unwrapper = Unwrapper::<2>::new();
+
+        unwrapper.update(&header(1, u32::MAX, 100));
+        unwrapper.update(&header(2, 10, 100));
+        let unwrapped = unwrapper.update(&header(1, 0, 110));
+        assert_eq!(unwrapped.sequence, u64::from(u32::MAX) + 1);
+        assert_eq!(unwrapper.timeline(2).unwrap().sequence(), 10);
+
+        // Third device replaces the least recently seen one.
+        unwrapper.update(&header(3, 7, 0));
+        assert!(unwrapper.timeline(2).is_none());
+        assert_eq!(unwrapper.timeline(1).unwrap().timestamp(), 110);
+
+        unwrapper.reset();
+        assert!(unwrapper.timeline(1).is_none());
+        assert_eq!(unwrapper.update(&header(1, 3, 4)).sequence, 3);
+    }
+}
@@ -0,0 +1,139 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP timestamp unit and extension tests.
+
+extern crate idtp;
+
+#[cfg(test)]
+mod tests {
+    use idtp::*;
+    use std::time::Duration;
+
+    const PAYLOAD: &[u8] = b"imu sample";
+
+    fn test_frame(unit: TimestampUnit, timestamp: u32) -> IdtpFrame {
+        let mut header = IdtpHeader::new();
+        header.mode = Mode::Safety;
+        header.timestamp = timestamp;
+        header.set_timestamp_unit(unit);
+
+        let mut frame = IdtpFrame::new();
+        frame.set_header(&header);
+        frame.set_payload(PAYLOAD);
+        frame
+    }
+
+    #[test]
+    fn test_timestamp_unit_flags() {
+        let mut header = IdtpHeader::new();
+        assert_eq!(header.timestamp_unit(), TimestampUnit::Milliseconds);
+
+        header.set_flags(IDTP_FLAG_AUTH);
+        header.set_timestamp_unit(TimestampUnit::Ticks);
+        assert_eq!(header.flags(), 0x31);
+        assert_eq!(header.timestamp_unit(), TimestampUnit::Ticks);
+
+        header.set_timestamp_unit(TimestampUnit::Microseconds);
+        assert_eq!(header.flags(), 0x11);
+        assert_eq!(header.timestamp_unit(), TimestampUnit::Microseconds);
+    }
+
+    #[test]
+    fn test_timestamp_units() {
+        // Zero reserved bytes keep millisecond meaning.
+        let frame = test_frame(TimestampUnit::Milliseconds, 1500);
+        assert_eq!(frame.timestamp(), Ok(Duration::from_millis(1500)));
+
+        let frame = test_frame(TimestampUnit::Microseconds, 1500);
+        assert_eq!(frame.timestamp(), Ok(Duration::from_micros(1500)));
+
+        let frame = test_frame(TimestampUnit::Nanoseconds, 1500);
+        assert_eq!(frame.timestamp(), Ok(Duration::from_nanos(1500)));
+
+        // Tick rate is unknown without extension.
+        let frame = test_frame(TimestampUnit::Ticks, 1500);
+        assert_eq!(frame.timestamp(), Err(IdtpError::InvalidPayload));
+    }
+
+    #[test]
+    fn test_timestamp_extension() {
+        let mut frame = test_frame(TimestampUnit::Ticks, 0x8000_0000);
+        assert_eq!(frame.timestamp_extension(), None);
+
+        let extension = TimestampExtension {
+            timestamp_high: 3,
+            tick_rate: 32_768,
+        };
+        frame.set_timestamp_extension(&extension).unwrap();
+        frame.update_integrity();
+        assert_eq!(frame.payload_size(), PAYLOAD.len() + 8);
+        assert_eq!(&frame.payload()[8..], PAYLOAD);
+
+        let mut buffer = vec![0u8; frame.frame_size()];
+        frame.pack(&mut buffer).unwrap();
+        assert_eq!(buffer[IDTP_HEADER_SIZE..][..8], extension.to_bytes());
+
+        let mut frame = IdtpFrame::parse(&buffer).unwrap();
+        assert!(frame.verify().is_ok());
+        assert_eq!(frame.timestamp_extension(), Some(extension));
+
+        // 0x3_8000_0000 ticks of 32768 Hz clock.
+        let ticks = 0x3_8000_0000u64;
+        let nanos = (ticks % 32_768) * 1_000_000_000 / 32_768;
+        let expected =
+            Duration::from_secs(ticks / 32_768) + Duration::from_nanos(nanos);
+        assert_eq!(frame.timestamp(), Ok(expected));
+
+        assert_eq!(frame.take_timestamp_extension(), Some(extension));
+        assert_eq!(frame.payload(), PAYLOAD);
+        assert_eq!(frame.header().flags() & IDTP_FLAG_TIMESTAMP_EXT, 0);
+        assert_eq!({ frame.header().payload_size }, PAYLOAD.len() as u32);
+        assert_eq!(frame.take_timestamp_extension(), None);
+    }
+
+    #[test]
+    fn test_timestamp_extension_64_bit() {
+        let mut frame = test_frame(TimestampUnit::Nanoseconds, 0x1234_5678);
+
+        let mut extension = TimestampExtension {
+            timestamp_high: 1,
+            tick_rate: 0,
+        };
+        frame.set_timestamp_extension(&extension).unwrap();
+
+        // Existing extension is replaced in place.
+        extension.timestamp_high = 0x10;
+        frame.set_timestamp_extension(&extension).unwrap();
+        assert_eq!(frame.payload_size(), PAYLOAD.len() + 8);
+        assert_eq!(frame.timestamp(), Ok(Duration::from_nanos(0x10_1234_5678)));
+    }
+
+    #[test]
+    fn test_timestamp_extension_errors() {
+        // Flag without extension bytes.
+        let mut header = IdtpHeader::new();
+        header.set_flags(IDTP_FLAG_TIMESTAMP_EXT);
+        let mut frame = IdtpFrame::new();
+        frame.set_header(&header);
+        frame.set_payload(&[0u8; 4]);
+        assert_eq!(frame.timestamp_extension(), None);
+        assert_eq!(frame.timestamp(), Err(IdtpError::InvalidPayload));
+
+        // Ticks with zero rate.
+        let mut frame = test_frame(TimestampUnit::Ticks, 1);
+        frame
+            .set_timestamp_extension(&TimestampExtension::default())
+            .unwrap();
+        assert_eq!(frame.timestamp(), Err(IdtpError::InvalidPayload));
+
+        // Payload is already full.
+        let mut frame = IdtpFrame::new();
+        frame.set_payload(&[0u8; IDTP_PAYLOAD_MAX_SIZE - 4]);
+        assert_eq!(
+            frame.set_timestamp_extension(&TimestampExtension::default()),
+            Err(IdtpError::FrameTooLarge)
+        );
+        assert_eq!(frame.payload_size(), IDTP_PAYLOAD_MAX_SIZE - 4);
+    }
+}